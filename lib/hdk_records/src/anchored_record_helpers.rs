@@ -24,7 +24,9 @@ use crate::{
     records::{
         create_record,
         read_record_entry_by_identity,
+        read_record_revisions_by_identity,
     },
+    metadata::RevisionHistoryPage,
     entries::{
         get_entry_by_action,
         update_entry,
@@ -120,6 +122,25 @@ pub fn read_anchored_record_entry<LT, T, R, B, A, I>(
     Ok((meta, A::new(dna_info()?.hash, id_string.as_ref().to_string()), entry_data))
 }
 
+/// Read a page of the full edit history of an anchored record, located via its `anchor index`.
+///
+/// @see hdk_records::records::read_record_revisions
+///
+pub fn read_anchored_record_revisions<LT, B, I>(
+    link_type: LT,
+    id_string: I,
+    after: Option<ActionHash>,
+    limit: Option<usize>,
+) -> RecordAPIResult<RevisionHistoryPage>
+    where LT: LinkTypeFilterExt,
+        I: AsRef<str>,
+        B: DnaAddressable<EntryHash>,
+{
+    let anchor_address = calculate_anchor_address(&id_string)?;
+    let identity_address = read_anchor_identity(link_type, &anchor_address)?;
+    read_record_revisions_by_identity::<B>(&identity_address, after, limit)
+}
+
 /// Creates a new record in the DHT and assigns it a manually specified `anchor index`
 /// that can be used like a primary key. The `create_payload` must also implement
 /// `UniquelyIdentifiable` in order to derive the unique `anchor index` value.
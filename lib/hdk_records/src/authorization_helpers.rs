@@ -0,0 +1,269 @@
+/**
+ * Helpers for validating permission to modify records.
+ *
+ * Integrity zomes use these to check that `Update` & `Delete` actions are authored by
+ * agents allowed to change the record. Which agents are allowed is decided by a
+ * `ModificationPolicy`, so that DNA builders can swap in their own rules (eg. role-based
+ * access) in place of the defaults provided here.
+ *
+ * :WARNING: policies are run inside validation callbacks and so must be deterministic.
+ * They may only read data via `must_get_*` methods and cannot make zome calls.
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk::prelude::*;
+
+/// Link tag identifying agent registrations (@see `register_agent_identity`)
+pub const AGENT_REGISTRATION_LINK_TAG: &'static [u8] = b"agent_registration";
+
+/// Implemented by record entry data to nominate agents who may modify a record
+/// in addition to its original author.
+///
+/// ValueFlows agents are referenced by `AgentAddress`, which is the identity of an `Agent` record
+/// in a separate DNA and so cannot be resolved to public keys during validation. Participants
+/// therefore match via agent registrations made in the record's own DNA (@see `register_agent_identity`).
+///
+pub trait RecordParticipants {
+    fn get_participants(&self) -> Vec<EntryHash>;
+}
+
+/// Information about a pending modification, as passed to `ModificationPolicy` checks
+///
+pub struct ModificationRequest<'a, T> {
+    /// The agent authoring the `Update` or `Delete` action
+    pub agent: &'a AgentPubKey,
+    /// The author of the `Create` action which started the record's revision history
+    pub original_author: &'a AgentPubKey,
+    /// The entry written by that `Create` action, which is the record's identity
+    pub identity: &'a EntryHash,
+    /// The action preceding the modification in the agent's source chain, from which any
+    /// other actions authored by the agent may be deterministically read
+    pub chain_top: &'a ActionHash,
+    /// Data of the revision being updated or deleted, if it is a record managed by this zome
+    pub current_entry: Option<&'a T>,
+    /// Data written by an `Update`, if it is a record managed by this zome. Always `None` for deletions.
+    pub new_entry: Option<&'a T>,
+}
+
+/// Rules determining which agents may update or delete records of type `T`.
+///
+/// Implement this trait and pass the implementing type to `declare_record_modification_validation!`
+/// (or the `validate_*_authorization` helpers) to customise permissions for a DNA.
+///
+pub trait ModificationPolicy<T> {
+    fn can_update(req: &ModificationRequest<T>) -> ExternResult<bool>;
+
+    fn can_delete(req: &ModificationRequest<T>) -> ExternResult<bool> {
+        Self::can_update(req)
+    }
+}
+
+/// Only the original author of a record may modify it.
+pub struct AuthorOnly;
+
+impl<T> ModificationPolicy<T> for AuthorOnly {
+    fn can_update(req: &ModificationRequest<T>) -> ExternResult<bool> {
+        Ok(req.agent == req.original_author)
+    }
+}
+
+/// The original author of a record, or any agent nominated via `RecordParticipants`, may modify it.
+///
+/// :WARNING: participants are matched via agent registrations, which are self-asserted (@see
+/// `register_agent_identity`). Any agent could register the identity of another and so gain permission
+/// to modify their records. This policy is therefore not used by default, and is only suitable for
+/// DNAs whose members are trusted not to make false registrations.
+///
+/// :TODO: require registrations to be signed by the author of the registered `Agent` record
+///
+pub struct AuthorOrParticipants;
+
+impl<T> ModificationPolicy<T> for AuthorOrParticipants
+    where T: RecordParticipants,
+{
+    fn can_update(req: &ModificationRequest<T>) -> ExternResult<bool> {
+        if req.agent == req.original_author {
+            return Ok(true);
+        }
+        let participants = match req.current_entry {
+            Some(entry) => entry.get_participants(),
+            None => return Ok(false),
+        };
+        Ok(read_registered_agent_identities(req.agent, req.chain_top)?
+            .iter()
+            .any(|agent_id| participants.contains(agent_id)))
+    }
+}
+
+/// Register the calling agent as the holder of the given `Agent` record identity within this DNA,
+/// so that they are recognised as a participant in records referencing that agent.
+///
+/// `link_type` must be a link type of the calling zome, and the zome's integrity validation should
+/// apply `validate_agent_registration` to links created with it.
+///
+/// :WARNING: registrations are asserted by the registering agent and cannot be checked against
+/// the agent DNA during validation. They are public and permanent, such that a false claim can be
+/// detected by comparison with `whois` queries, but should not be relied upon to grant access to
+/// records authored by other agents. @see `AuthorOrParticipants`
+///
+pub fn register_agent_identity<T, E>(agent_identity: &EntryHash, link_type: T) -> ExternResult<ActionHash>
+    where ScopedLinkType: TryFrom<T, Error = E>,
+        WasmError: From<E>,
+{
+    let agent = agent_info()?.agent_latest_pubkey;
+    create_link(agent, agent_identity.to_owned(), link_type, LinkTag::new(AGENT_REGISTRATION_LINK_TAG))
+}
+
+/// Ensure agent registration links are only created by the agent they register.
+pub fn validate_agent_registration(link: &CreateLink) -> ExternResult<ValidateCallbackResult> {
+    if link.tag.as_ref() != AGENT_REGISTRATION_LINK_TAG {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if link.base_address != AnyLinkableHash::from(link.author.to_owned()) {
+        return Ok(ValidateCallbackResult::Invalid(format!("Agent {} cannot register an identity for another agent", link.author)));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Read all `Agent` record identities registered by an agent, as of the given action in their source chain.
+///
+/// Uses `must_get_agent_activity`, and so is deterministic for use in validation.
+///
+pub fn read_registered_agent_identities(agent: &AgentPubKey, chain_top: &ActionHash) -> ExternResult<Vec<EntryHash>> {
+    let agent_base = AnyLinkableHash::from(agent.to_owned());
+    Ok(must_get_agent_activity(agent.to_owned(), ChainFilter::new(chain_top.to_owned()))?
+        .iter()
+        .filter_map(|activity| match activity.action.action() {
+            Action::CreateLink(CreateLink { base_address, target_address, tag, .. })
+                if base_address == &agent_base && tag.as_ref() == AGENT_REGISTRATION_LINK_TAG =>
+                Some(EntryHash::from(target_address.to_owned())),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Follow a record's revision history back to its initial `Create` action and return its author
+/// and the record's identity.
+pub fn get_record_origin(revision_id: &ActionHash) -> ExternResult<(AgentPubKey, EntryHash)> {
+    let mut action_hash = revision_id.to_owned();
+    loop {
+        let action = must_get_action(action_hash.to_owned())?;
+        match action.action() {
+            Action::Update(Update { original_action_address, .. }) => {
+                action_hash = original_action_address.to_owned();
+            },
+            Action::Create(Create { author, entry_hash, .. }) => return Ok((author.to_owned(), entry_hash.to_owned())),
+            _ => return Err(wasm_error!(WasmErrorInner::Guest(format!("Revision history of {} does not begin with a Create action", revision_id)))),
+        }
+    }
+}
+
+/// Validate an `Update` action against the rules of `ModificationPolicy` `P`.
+///
+/// `parse_entry` should decode entries of the zome's record type, and return `None` for any
+/// other entry types.
+///
+pub fn validate_update_authorization<P, T, F>(update: &Update, parse_entry: F) -> ExternResult<ValidateCallbackResult>
+    where P: ModificationPolicy<T>,
+        F: Fn(&Entry) -> Option<T>,
+{
+    let (original_author, identity) = get_record_origin(&update.original_action_address)?;
+    let current_entry = parse_entry(&must_get_entry(update.original_entry_address.to_owned())?.content);
+    let new_entry = parse_entry(&must_get_entry(update.entry_hash.to_owned())?.content);
+
+    let req = ModificationRequest {
+        agent: &update.author,
+        original_author: &original_author,
+        identity: &identity,
+        chain_top: &update.prev_action,
+        current_entry: current_entry.as_ref(),
+        new_entry: new_entry.as_ref(),
+    };
+
+    if P::can_update(&req)? {
+        Ok(ValidateCallbackResult::Valid)
+    } else {
+        Ok(ValidateCallbackResult::Invalid(format!("Agent {} is not permitted to update record {}", update.author, update.original_action_address)))
+    }
+}
+
+/// Validate a `Delete` action against the rules of `ModificationPolicy` `P`.
+///
+/// @see validate_update_authorization
+///
+pub fn validate_delete_authorization<P, T, F>(delete: &Delete, parse_entry: F) -> ExternResult<ValidateCallbackResult>
+    where P: ModificationPolicy<T>,
+        F: Fn(&Entry) -> Option<T>,
+{
+    let (original_author, identity) = get_record_origin(&delete.deletes_address)?;
+    let current_entry = parse_entry(&must_get_entry(delete.deletes_entry_address.to_owned())?.content);
+
+    let req = ModificationRequest {
+        agent: &delete.author,
+        original_author: &original_author,
+        identity: &identity,
+        chain_top: &delete.prev_action,
+        current_entry: current_entry.as_ref(),
+        new_entry: None,
+    };
+
+    if P::can_delete(&req)? {
+        Ok(ValidateCallbackResult::Valid)
+    } else {
+        Ok(ValidateCallbackResult::Invalid(format!("Agent {} is not permitted to delete record {}", delete.author, delete.deletes_address)))
+    }
+}
+
+/// Generate a `validate` callback for integrity zomes with no other validation rules, which
+/// checks permission to update & delete records and accepts all other operations.
+///
+/// `$storage` is the zome's `EntryStorage` type (@see `generate_record_entry!`) and `$policy`
+/// a `ModificationPolicy` for its entry data. Zomes with their own `validate` callback should
+/// call `validate_update_authorization` & `validate_delete_authorization` directly.
+///
+#[macro_export]
+macro_rules! declare_record_modification_validation {
+    ( $storage:ty, $policy:ty ) => {
+        #[hdk_extern]
+        fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+            match op {
+                Op::RegisterUpdate(RegisterUpdate { update, .. }) =>
+                    $crate::authorization::validate_update_authorization::<$policy, _, _>(
+                        &update.hashed.content,
+                        |entry| <$storage>::try_from(entry).ok().map(|s| $crate::record_interface::Identified::entry(&s)),
+                    ),
+                Op::RegisterDelete(RegisterDelete { delete, .. }) =>
+                    $crate::authorization::validate_delete_authorization::<$policy, _, _>(
+                        &delete.hashed.content,
+                        |entry| <$storage>::try_from(entry).ok().map(|s| $crate::record_interface::Identified::entry(&s)),
+                    ),
+                Op::RegisterCreateLink(RegisterCreateLink { create_link, .. }) =>
+                    $crate::authorization::validate_agent_registration(&create_link.hashed.content),
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        }
+    };
+}
+
+/// Generate an extern allowing agents to register their `Agent` record identity in the DNA.
+///
+/// `$link_type` is a link type of the zome reserved for agent registrations.
+/// @see `register_agent_identity`
+///
+#[macro_export]
+macro_rules! declare_agent_registration_zome_api {
+    ( $link_type:expr ) => {
+        #[hdk_extern]
+        fn register_my_agent($crate::authorization::RegisterAgentParams { agent_address }: $crate::authorization::RegisterAgentParams) -> ExternResult<ActionHash> {
+            $crate::authorization::register_agent_identity(&agent_address.1, $link_type)
+        }
+    };
+}
+
+/// Input parameters for `register_my_agent`, where `agent_address` is the `AgentAddress` of an `Agent` record
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterAgentParams {
+    pub agent_address: (DnaHash, EntryHash),
+}
@@ -18,7 +18,7 @@
  * @package HDK Graph Helpers
  * @since   2019-05-16
  */
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use hdk::prelude::*;
 use hdk_uuid_types::DnaAddressable;
 
@@ -28,6 +28,7 @@ use crate::{
 };
 use hdk_semantic_indexes_zome_rpc::{
     AppendAddress,
+    ByAddress,
 };
 
 //--------------------------------[ READ ]--------------------------------------
@@ -91,3 +92,70 @@ pub fn create_entry_identity<A, S, F, C>(
         },
     ).map_err(|e| { DataIntegrityError::LocalIndexNotConfigured(entry_def_id.to_string(), e.to_string()) })?)
 }
+
+//-------------------------------[ UPDATE ]-------------------------------------
+
+/// Positions the identifier of an entry at `time` within the additional time index
+/// `index_name` of the given `entry_type`, or removes it from the index if `time` is `None`.
+///
+/// Should be called whenever the record attribute the time index orders by is set or changed.
+///
+pub fn update_entry_time_index<A, S, I, F, C, Tz>(
+    zome_name_from_config: F,
+    entry_def_id: S,
+    index_name: I,
+    address: &A,
+    time: Option<DateTime<Tz>>,
+) -> RecordAPIResult<()>
+    where S: AsRef<str> + std::fmt::Display,
+        I: AsRef<str> + std::fmt::Display,
+        A: DnaAddressable<EntryHash>,
+        F: FnOnce(C) -> Option<String>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+        Tz: TimeZone,
+{
+    // @see hdk_semantic_indexes_zome_derive::index_zome
+    let append_fn_name = format!("record_{}_{}", entry_def_id, index_name);
+
+    // request repositioning / removal in companion zome
+    Ok(match time {
+        Some(time) => call_local_zome_method(
+            zome_name_from_config, append_fn_name,
+            AppendAddress {
+                address: address.to_owned(),
+                timestamp: time.with_timezone(&Utc),
+            },
+        ),
+        None => call_local_zome_method(
+            zome_name_from_config, format!("{}_cleared", append_fn_name),
+            ByAddress { address: address.to_owned() },
+        ),
+    }.map_err(|e| { DataIntegrityError::LocalIndexNotConfigured(entry_def_id.to_string(), e.to_string()) })?)
+}
+
+//-------------------------------[ DELETE ]-------------------------------------
+
+/// Marks the identifier of a deleted entry as removed, so that it is no longer returned
+/// from the global index for all entries of the given `entry_type`.
+///
+pub fn delete_entry_identity<A, S, F, C>(
+    zome_name_from_config: F,
+    entry_def_id: S,
+    address: &A,
+) -> RecordAPIResult<()>
+    where S: AsRef<str> + std::fmt::Display,
+        A: DnaAddressable<EntryHash>,
+        F: FnOnce(C) -> Option<String>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    // @see hdk_semantic_indexes_zome_derive::index_zome
+    let remove_fn_name = format!("record_deleted_{}", entry_def_id);
+
+    // request removal from index in companion zome
+    Ok(call_local_zome_method(
+        zome_name_from_config, remove_fn_name,
+        ByAddress { address: address.to_owned() },
+    ).map_err(|e| { DataIntegrityError::LocalIndexNotConfigured(entry_def_id.to_string(), e.to_string()) })?)
+}
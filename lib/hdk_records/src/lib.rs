@@ -26,6 +26,8 @@ mod record_helpers;
 mod anchored_record_helpers;
 mod rpc_helpers;
 mod metadata_helpers;
+mod authorization_helpers;
+mod outbox_helpers;
 
 // API interfaces
 
@@ -39,10 +41,12 @@ pub mod records { pub use crate::record_helpers::*; }
 pub mod records_anchored { pub use crate::anchored_record_helpers::*; }
 pub mod rpc { pub use crate::rpc_helpers::*; }
 pub mod metadata { pub use crate::metadata_helpers::*; }
+pub mod authorization { pub use crate::authorization_helpers::*; }
+pub mod outbox { pub use crate::outbox_helpers::*; }
 
 // externally-facing structs
 
-pub use metadata_helpers::{ RevisionMeta, RecordMeta };
+pub use metadata_helpers::{ RevisionMeta, RecordMeta, RevisionHistoryPage };
 
 // :TODO: these error types may just be duplicating enums from the HDK,
 // revisit this once result handling & serialisation have stabilised.
@@ -85,6 +89,20 @@ pub enum DataIntegrityError {
     LocalIndexNotConfigured(String, String),
     #[error("Mismatching units in arithmetic operation. Attempting to add or subtract {0:?} with {1:?}")]
     MismatchingUnits(Option<String>, Option<String>),
+    #[error("Numeric overflow in quantity arithmetic")]
+    QuantityOverflow,
+    #[error("Invalid unit conversion: {0}")]
+    InvalidUnitConversion(String),
+    #[error("Conversion factor for unit '{0}' must be non-zero")]
+    ZeroConversionFactor(String),
+    #[error("Invalid action: {0}")]
+    InvalidAction(String),
+    #[error("{0} records cannot be modified once created")]
+    ImmutableRecord(String),
+    #[error("Record {0} has already been corrected")]
+    AlreadyCorrected(String),
+    #[error("No recipe process produces resource {0}")]
+    RecipeNotFound(String),
 }
 
 pub type RecordAPIResult<T> = Result<T, DataIntegrityError>;
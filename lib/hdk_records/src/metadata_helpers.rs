@@ -1,6 +1,8 @@
+use std::cell::Cell;
+use std::collections::HashSet;
 use chrono::{ DateTime, Utc, NaiveDateTime };
 use hdk::prelude::*;
-use crate::{ RecordAPIResult, DataIntegrityError };
+use crate::{ RecordAPIResult, DataIntegrityError, SemanticIndexError };
 
 /// Metadata for a specific revision of a record, serializable for external transmission
 ///
@@ -17,35 +19,117 @@ pub struct RevisionMeta {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordMeta {
-    // pub original_revision: RevisionMeta,
+    pub original_revision: RevisionMeta,
     pub previous_revision: Option<RevisionMeta>,
-    // pub previous_revisions_count: u32,
-    // pub latest_revision: RevisionMeta,
-    // pub future_revisions_count: u32,
+    /// Omitted for records read in lists. @see with_summary_metadata
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_revisions_count: Option<u32>,
+    pub latest_revision: RevisionMeta,
+    /// Omitted for records read in lists. @see with_summary_metadata
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub future_revisions_count: Option<u32>,
     pub retrieved_revision: RevisionMeta,
+    /// Populated with every live branch head when the record's revision history has
+    /// diverged due to concurrent updates. Empty for non-conflicting records.
+    /// Conflicts are resolved by writing a merge update via `hdk_records::records::merge_record`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<RevisionMeta>,
 }
 
-/// Retrieve minimal revision metadata for a record needed by UIs to retrieve version history
+/// A page of revisions from the full edit history of a record, in ascending order of authoring time
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionHistoryPage {
+    pub revisions: Vec<RevisionMeta>,
+    pub has_next_page: bool,
+}
+
+thread_local! {
+    // set whilst reading records for display in a list, @see with_summary_metadata
+    static SUMMARY_READS: Cell<bool> = Cell::new(false);
+}
+
+/// Read records for display in a list, limiting the `RecordMeta` of any records read by `read`
+/// to that which can be determined without crawling their update trees.
+///
+/// Revision counts and conflicts are omitted, and the retrieved revision is reported as the latest.
+/// Clients needing them should read the record individually.
+///
+pub fn with_summary_metadata<T, F>(read: F) -> T
+    where F: FnOnce() -> T,
+{
+    SUMMARY_READS.with(|s| s.set(true));
+    let result = read();
+    SUMMARY_READS.with(|s| s.set(false));
+    result
+}
+
+/// Retrieve revision metadata for a record needed by UIs to retrieve version history
+///
+/// Rather than walking backwards through every prior revision, the original revision is located
+/// via the record's identity and only the live heads of its update tree are determined, with
+/// the position of the requested revision recorded whilst crawling towards them.
+///
+/// Unlike `read_revision_metadata_full`, the validation status of the requested revision is not checked.
+/// Within `with_summary_metadata`, the update tree is not crawled at all.
 ///
 pub fn read_revision_metadata_abbreviated(header: &SignedActionHashed) -> RecordAPIResult<RecordMeta>
 {
     let maybe_previous_element = get_previous_revision(header)?;
+    let first = get_original_revision_of(header)?;
+
+    if SUMMARY_READS.with(|s| s.get()) {
+        return Ok(RecordMeta {
+            original_revision: (&first).into(),
+            previous_revision: maybe_previous_element.map(|e| e.into()),
+            previous_revisions_count: None,
+            latest_revision: header.into(),
+            future_revisions_count: None,
+            retrieved_revision: header.into(),
+            conflicts: vec![],
+        });
+    }
+
+    // crawl forwards from original to determine branch heads; the most recent being considered latest
+    let tree = read_revision_tree(&first)?;
+    let previous_revisions_count = match tree.depth_of(&get_action_hash(header)) {
+        Some(depth) => depth,
+        // deletions are not part of the update tree, but follow the revision they delete
+        None => maybe_previous_element.as_ref()
+            .and_then(|previous| tree.depth_of(&get_action_hash(previous.signed_action())))
+            .map(|depth| depth + 1)
+            .unwrap_or(0),
+    };
+    let (latest, future_revisions_count) = match tree.heads.last() {
+        Some(head) => {
+            let head_depth = tree.depth_of(&get_action_hash(head)).unwrap_or(previous_revisions_count);
+            (head.to_owned(), head_depth.saturating_sub(previous_revisions_count))
+        },
+        // no live heads, record has been deleted
+        None => (header.to_owned(), 0),
+    };
 
     Ok(RecordMeta {
-        // original_revision: (&first).into(),
+        original_revision: (&first).into(),
         previous_revision: maybe_previous_element.map(|e| e.into()),
-        // previous_revisions_count,
-        // future_revisions_count: 0,
-        // latest_revision: e.clone().into(),
+        previous_revisions_count: Some(previous_revisions_count),
+        latest_revision: (&latest).into(),
+        future_revisions_count: Some(future_revisions_count),
         retrieved_revision: header.into(),
+        conflicts: match tree.heads.len() {
+            0 | 1 => vec![],
+            _ => tree.heads.iter().map(|h| h.into()).collect(),
+        },
     })
 }
 
 /**
  * Derive metadata for a record's full revision history by querying the DHT
  *
- * :TODO: handle conflicts @see https://github.com/h-REA/hREA/issues/196
- *
  * :TODO: think of some sensible way to differentiate a delete revision from
  * others if it is the one being requested
  */
@@ -53,49 +137,42 @@ pub fn read_revision_metadata_full(header: &SignedActionHashed) -> RecordAPIResu
 {
     match get_details(get_action_hash(header), GetOptions { strategy: GetStrategy::Latest }) {
         Ok(Some(Details::Record(details))) => match details.validation_status {
-            ValidationStatus::Valid => {
-                // find previous Element first so we can reuse it to recurse backwards to original
-                let maybe_previous_element = get_previous_revision(header)?;
-
-                // recurse backwards from previous to determine original,
-                // or indicate current as original if no previous Element exists
-                let (_first, _previous_revisions_count) = match maybe_previous_element.clone() {
-                    Some(previous_element) => find_earliest_revision(previous_element.signed_action(), 1)?,
-                    None => (header.to_owned(), 0),
-                };
-
-                match details.updates.len() {
-                    // no updates referencing this Element; therefore there are no future revisions and we are the latest
-                    0 => {
-                        Ok(RecordMeta {
-                            // original_revision: (&first).into(),
-                            previous_revision: maybe_previous_element.map(|e| e.into()),
-                            // previous_revisions_count,
-                            // future_revisions_count: 0,
-                            // latest_revision: e.clone().into(),
-                            retrieved_revision: header.into(),
-                        })
-                    },
-                    // updates found, recurse to determine latest
-                    _ => {
-                        let (_latest, _future_revisions_count) = find_latest_revision(details.updates.as_slice(), 0)?;
-                        Ok(RecordMeta {
-                            // original_revision: (&first).into(),
-                            previous_revision: maybe_previous_element.map(|e| e.into()),
-                            // previous_revisions_count,
-                            // future_revisions_count,
-                            // latest_revision: (&latest).into(),
-                            retrieved_revision: header.into(),
-                        })
-                    },
-                }
-            },
+            ValidationStatus::Valid => read_revision_metadata_abbreviated(header),
             _ => Err(DataIntegrityError::EntryNotFound),
         },
         _ => Err(DataIntegrityError::EntryNotFound),
     }
 }
 
+/// Retrieve a page of the full edit history of a record, given its original revision.
+///
+/// All revisions in every branch of the update tree are included, ordered by authoring time.
+/// Paging begins after the revision `after` if specified, returning up to `limit` results.
+///
+pub fn read_revision_history(
+    original: &SignedActionHashed,
+    after: Option<ActionHash>,
+    limit: Option<usize>,
+) -> RecordAPIResult<RevisionHistoryPage>
+{
+    let revisions = read_revision_tree(original)?.revisions;
+
+    let start = match after {
+        None => 0,
+        Some(cursor) => match revisions.iter().position(|(r, _)| get_action_hash(r) == cursor) {
+            Some(idx) => idx + 1,
+            None => return Err(SemanticIndexError::BadCursorError(cursor.to_string()).into()),
+        },
+    };
+    let remaining = revisions.len().saturating_sub(start);
+    let count = limit.unwrap_or(remaining).min(remaining);
+
+    Ok(RevisionHistoryPage {
+        revisions: revisions[start..start + count].iter().map(|(r, _)| r.into()).collect(),
+        has_next_page: count < remaining,
+    })
+}
+
 impl TryFrom<Record> for RecordMeta {
     type Error = DataIntegrityError;
 
@@ -156,47 +233,134 @@ fn get_previous_revision(signed_action: &SignedActionHashed) -> RecordAPIResult<
     }
 }
 
-/**
- * Recursive helper for determining earliest revision in chain, and count of prior revisions.
- */
-fn find_earliest_revision(signed_action: &SignedActionHashed, revisions_before: u32) -> RecordAPIResult<(SignedActionHashed, u32)> {
-    let prev_record = get_previous_revision(signed_action)?;
+/// Retrieve the originating `Create` action for the record initially written with the given `EntryHash`.
+///
+/// Any `Update` which may have coincidentally reverted the entry back to its initial content is ignored.
+///
+pub (crate) fn get_original_revision(entry_hash: EntryHash) -> RecordAPIResult<SignedActionHashed> {
+    match get_details(entry_hash, GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Entry(details)) => {
+            let mut roots: Vec<SignedActionHashed> = details.actions.iter()
+                .filter(|a| matches!(a.action(), Action::Create(_)))
+                .cloned()
+                .collect();
+            roots.sort_by_key(|a| a.action().timestamp().as_micros());
+
+            roots.first().cloned().ok_or(DataIntegrityError::EntryNotFound)
+        },
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
 
-    match prev_record {
-        None => Ok((signed_action.to_owned(), revisions_before)),
-        Some(e) => find_earliest_revision(e.signed_action(), revisions_before + 1),
+/// The identity field common to all record storage entries, readable without knowledge of their type.
+///
+/// :NOTE: relies on entries being serialized with named fields. @see `generate_record_entry!`
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+struct RecordIdentity {
+    id_hash: Option<EntryHash>,
+}
+
+/// Determine the originating `Create` action for the record that the given revision belongs to,
+/// by way of the identity stored alongside its entry data.
+///
+fn get_original_revision_of(signed_action: &SignedActionHashed) -> RecordAPIResult<SignedActionHashed> {
+    match signed_action.action() {
+        Action::Create(_) => Ok(signed_action.to_owned()),
+        Action::Update(update) => {
+            let record = get(update.entry_hash.to_owned(), GetOptions::content())?
+                .ok_or(DataIntegrityError::EntryNotFound)?;
+            let identity = match record.entry().as_option() {
+                Some(Entry::App(bytes)) => RecordIdentity::try_from(bytes.0.to_owned())?,
+                _ => return Err(DataIntegrityError::EntryWrongType),
+            };
+            get_original_revision(identity.id_hash.unwrap_or(update.entry_hash.to_owned()))
+        },
+        Action::Delete(delete) => {
+            let deleted = get(delete.deletes_address.to_owned(), GetOptions { strategy: GetStrategy::Latest })?
+                .ok_or(DataIntegrityError::EntryNotFound)?;
+            get_original_revision_of(deleted.signed_action())
+        },
+        _ => Err(DataIntegrityError::EntryWrongType),
     }
 }
 
-/**
- * Recursive helper for determining latest revision in chain, and count of subsequent revisions.
- *
- * :TODO: currently we assume multiple updates to the same entry were non-conflicting
- * and perceive the most recent as pointing to the next revision. We should not.
- * Instead every update path needs to be checked, all diverging leaf nodes resolved;
- * and if any remain then a DataIntegrityError::UpdateConflict should be thrown
- * with all the conflicting branch heads.
- *
- * :TODO: decide whether to return a delete as the latest revision for deleted entries
- */
-fn find_latest_revision(updates: &[SignedActionHashed], revisions_until: u32) -> RecordAPIResult<(SignedActionHashed, u32)> {
-    let mut sortlist = updates.to_vec();
-    sortlist.sort_by_key(by_action_time);
-    let most_recent = sortlist.last().unwrap().to_owned();
+/// The update tree of a record, as determined by crawling all `Update` actions from its original revision
+///
+struct RevisionTree {
+    /// All revisions in the tree alongside their distance from the original, in ascending order of authoring time
+    revisions: Vec<(SignedActionHashed, u32)>,
+    /// Live leaf revisions, in ascending order of authoring time
+    heads: Vec<SignedActionHashed>,
+}
 
-    match get_details(get_action_hash(&most_recent), GetOptions { strategy: GetStrategy::Latest }) {
-        Ok(Some(Details::Record(details))) => match details.validation_status {
-            ValidationStatus::Valid => match details.updates.len() {
-                // found latest revision
-                0 => Ok((details.record.signed_action().to_owned(), revisions_until + 1)),
-                // still more updates to crawl, keep going
-                _ => find_latest_revision(details.updates.as_slice(), revisions_until + 1),
+impl RevisionTree {
+    fn depth_of(&self, revision: &ActionHash) -> Option<u32> {
+        self.revisions.iter()
+            .find(|(r, _)| get_action_hash(r) == *revision)
+            .map(|(_, depth)| *depth)
+    }
+}
+
+/// Determine all live branch heads of the update tree rooted at the given (original) revision.
+///
+/// Every path of `Update` actions is crawled and each diverging leaf revision collected.
+/// Branches ending in a `Delete` or an invalid action are discarded. A non-conflicting
+/// record will yield exactly one head; a fully deleted record will yield none.
+///
+/// Heads are returned in ascending order of authoring time.
+///
+pub fn find_revision_heads(root: &SignedActionHashed) -> RecordAPIResult<Vec<SignedActionHashed>> {
+    Ok(read_revision_tree(root)?.heads)
+}
+
+/// Crawl the update tree rooted at the given revision
+///
+fn read_revision_tree(root: &SignedActionHashed) -> RecordAPIResult<RevisionTree> {
+    let mut visited = HashSet::new();
+    let mut tree = RevisionTree { revisions: vec![], heads: vec![] };
+
+    collect_revision_tree(get_action_hash(root), 0, &mut visited, &mut tree)?;
+
+    tree.revisions.sort_by(|(a, _), (b, _)| by_action_time_and_hash(a, b));
+    tree.heads.sort_by(by_action_time_and_hash);
+    Ok(tree)
+}
+
+/// Recursive helper for `read_revision_tree`
+///
+fn collect_revision_tree(
+    revision: ActionHash,
+    depth: u32,
+    visited: &mut HashSet<ActionHash>,
+    tree: &mut RevisionTree,
+) -> RecordAPIResult<()> {
+    if !visited.insert(revision.to_owned()) {
+        return Ok(());
+    }
+
+    match get_details(revision, GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Record(details)) => match details.validation_status {
+            ValidationStatus::Valid => {
+                let signed_action = details.record.signed_action().to_owned();
+                match details.updates.len() {
+                    // leaf revision; it is a live head unless it has been deleted
+                    0 => if details.deletes.is_empty() {
+                        tree.heads.push(signed_action.to_owned());
+                    },
+                    // keep crawling every branch
+                    _ => for update in details.updates.iter() {
+                        collect_revision_tree(get_action_hash(update), depth + 1, visited, tree)?;
+                    },
+                }
+                tree.revisions.push((signed_action, depth));
+                Ok(())
             },
-            // :TODO: how to handle abandoned validations?
-            _ => Err(DataIntegrityError::EntryNotFound),
+            // abandoned or rejected branches are not considered
+            _ => Ok(()),
         },
         // :TODO: should we account for `None` being returned from the DHT?
-        _ => Err(DataIntegrityError::EntryNotFound),
+        _ => Ok(()),
     }
 }
 
@@ -205,7 +369,8 @@ pub (crate) fn get_action_hash(shh: &record::SignedActionHashed) -> ActionHash {
     shh.as_hash().to_owned()
 }
 
-/// helper for sorting actions by creation time
-fn by_action_time(h: &SignedActionHashed) -> i64 {
-    h.action().timestamp().as_micros()
+/// helper for sorting actions by creation time, using hashes to break ties deterministically
+fn by_action_time_and_hash(a: &SignedActionHashed, b: &SignedActionHashed) -> std::cmp::Ordering {
+    a.action().timestamp().cmp(&b.action().timestamp())
+        .then_with(|| get_action_hash(a).cmp(&get_action_hash(b)))
 }
@@ -0,0 +1,486 @@
+/**
+ * Outbox for cross-cell write operations
+ *
+ * Remote zome calls which fail to reach their destination cell are written to the
+ * calling agent's source chain as private `PendingSync` entries, so that the two
+ * cells can be brought back into a consistent state once the remote becomes reachable.
+ *
+ * The outbox is append-only: every replay attempt is logged as a new entry which
+ * references the originally queued request, and a request is considered resolved
+ * once any of its attempts succeeds, or once it has been discarded.
+ *
+ * Scheduled replays back off exponentially between attempts, and stop once a request has
+ * failed `PENDING_SYNC_MAX_ATTEMPTS` times. Such requests remain in the outbox until they
+ * are retried manually or discarded.
+ *
+ * Requests may be queued with a sync key identifying the state they bring the remote cell up
+ * to date with. Once a request to a DNA is delivered, any requests queued before it for the same
+ * key are discarded as superseded, so that replaying them cannot revert the remote cell to an
+ * older state.
+ *
+ * @see     hdk_records::rpc::call_zome_method_or_queue
+ * @package hREA
+ * @since   2026-10-17
+ */
+use std::collections::HashMap;
+use chrono::{ DateTime, Utc, NaiveDateTime };
+use hdk::prelude::*;
+use holo_hash::DnaHash;
+use hc_zome_dna_auth_resolver_lib::AvailableCapability;
+
+use crate::{
+    RecordAPIResult, CrossCellError, DataIntegrityError,
+    RevisionMeta,
+    rpc_helpers::deliver_zome_call,
+};
+
+/// Delay before the first scheduled replay of a request. Doubles after each failed attempt.
+pub const PENDING_SYNC_RETRY_INTERVAL_SECS: u64 = 60;
+
+/// Number of failed delivery attempts after which a request is no longer replayed automatically
+pub const PENDING_SYNC_MAX_ATTEMPTS: u32 = 8;
+
+/// Name of the scheduled zome function generated by `declare_pending_sync_zome_api!`
+pub const PENDING_SYNC_SCHEDULED_FN: &str = "scheduled_retry_pending_sync";
+
+/// Private source chain entry recording an undelivered cross-cell request,
+/// or the outcome of replaying one.
+///
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub enum PendingSync {
+    Queued {
+        to_dna: DnaHash,
+        remote_permission_id: String,
+        payload: ExternIO,
+        error: String,
+        #[serde(default)]
+        sync_key: Option<String>,
+    },
+    Attempted {
+        queued: ActionHash,
+        error: Option<String>,
+    },
+    // request abandoned, either by the agent or due to rejection by the remote cell
+    Discarded {
+        queued: ActionHash,
+        reason: String,
+    },
+}
+
+/// Externally-facing description of a request which has not yet reached its destination cell
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSyncItem {
+    pub id: ActionHash,
+    pub queued: RevisionMeta,
+    pub to_dna: DnaHash,
+    pub remote_permission_id: String,
+    pub attempts: u32,
+    pub last_error: String,
+    // time of the next scheduled replay, or `None` once automatic retries are exhausted
+    pub next_retry: Option<DateTime<Utc>>,
+}
+
+/// Result of replaying pending requests in the outbox
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSyncRetryResponse {
+    pub delivered: Vec<ActionHash>,
+    // requests rejected by the remote cell, which have been discarded
+    pub rejected: Vec<ActionHash>,
+    // requests overtaken by a later delivered request for the same sync key, which have been discarded
+    pub superseded: Vec<ActionHash>,
+    pub pending: Vec<PendingSyncItem>,
+}
+
+/// Input parameters for outbox operations on a single request
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSyncParams {
+    pub id: ActionHash,
+}
+
+/// Internal representation of a queued request, retaining the payload for replay
+struct QueuedRequest {
+    item: PendingSyncItem,
+    payload: ExternIO,
+    sync_key: Option<String>,
+    last_attempted: Timestamp,
+}
+
+impl QueuedRequest {
+    /// Whether this request synchronises the same state of the same remote cell as a request for `sync_key`
+    fn is_superseded_by(&self, to_dna: &DnaHash, sync_key: &str) -> bool {
+        self.item.to_dna == *to_dna && self.sync_key.as_deref() == Some(sync_key)
+    }
+
+    /// Time at which the request is next due for automatic replay
+    fn next_retry(&self) -> Option<Timestamp> {
+        if self.item.attempts >= PENDING_SYNC_MAX_ATTEMPTS {
+            return None;
+        }
+        let backoff_secs = PENDING_SYNC_RETRY_INTERVAL_SECS.saturating_mul(1 << self.item.attempts.saturating_sub(1).min(16));
+        Some(Timestamp::from_micros(self.last_attempted.as_micros().saturating_add((backoff_secs * 1_000_000) as i64)))
+    }
+
+    fn with_attempt(mut self, error: String, at: Timestamp) -> Self {
+        self.item.attempts += 1;
+        self.item.last_error = error;
+        self.last_attempted = at;
+        self.item.next_retry = self.next_retry().map(to_datetime);
+        self
+    }
+}
+
+fn to_datetime(t: Timestamp) -> DateTime<Utc> {
+    let (secs, nsecs) = t.as_seconds_and_nanos();
+    DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp_opt(secs, nsecs).unwrap_or_default(), Utc)
+}
+
+/// Record a failed request to `remote_permission_id` in the given DNA for later delivery.
+///
+/// Also schedules the outbox for automatic replay, where the host supports it.
+///
+pub fn queue_pending_sync<EN, S, E, E2>(
+    to_dna: &DnaHash,
+    remote_permission_id: &S,
+    payload: ExternIO,
+    sync_key: Option<String>,
+    error: &CrossCellError,
+) -> RecordAPIResult<ActionHash>
+    where S: AsRef<str>,
+        EN: TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    let action_hash = create_entry(EN::try_from(PendingSync::Queued {
+        to_dna: to_dna.to_owned(),
+        remote_permission_id: remote_permission_id.as_ref().to_string(),
+        payload,
+        error: error.to_string(),
+        sync_key,
+    }).map_err(WasmError::from)?)?;
+
+    // :NOTE: scheduling is best-effort; pending requests can always be replayed via `retry_pending_sync`
+    let s = schedule(PENDING_SYNC_SCHEDULED_FN);
+    hdk::prelude::debug!("queue_pending_sync::schedule {:?}", s);
+
+    Ok(action_hash)
+}
+
+/// List all requests in the agent's outbox which have not yet been delivered, oldest first.
+///
+/// `entry_type` must be the `EntryType` of the `PendingSync` variant in the calling zome's entry defs.
+///
+pub fn read_pending_sync(entry_type: EntryType) -> RecordAPIResult<Vec<PendingSyncItem>> {
+    Ok(read_queued_requests(entry_type)?
+        .into_iter()
+        .map(|q| q.item)
+        .collect())
+}
+
+/// Replay all undelivered requests in the agent's outbox, logging the outcome of each attempt.
+///
+/// Requests are replayed regardless of backoff or previously exhausted retries.
+/// Identical requests queued multiple times are delivered once and resolved together.
+/// Requests rejected by the remote cell are discarded rather than retried.
+/// Receiving zome methods are expected to tolerate replays of requests which were
+/// actioned by the remote cell but failed to return a response.
+///
+pub fn retry_pending_sync<EN, LT, E, E2>(
+    entry_type: EntryType,
+    capability_link_type: LT,
+) -> RecordAPIResult<PendingSyncRetryResponse>
+    where ScopedLinkType: TryFrom<LT, Error = E>,
+        LT: Clone + LinkTypeFilterExt,
+        EN: TryFrom<AvailableCapability, Error = E> + TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    replay_requests::<EN, _, _, _>(read_queued_requests(entry_type)?, capability_link_type)
+}
+
+/// Discard any requests in the agent's outbox for the given sync keys, which a request delivered to
+/// the same DNA has overtaken. Where the delivered request was itself queued, only requests queued
+/// before it are discarded.
+///
+pub fn supersede_pending_sync<EN, E, E2>(
+    to_dna: &DnaHash,
+    sync_keys: &[String],
+    delivered: Option<&ActionHash>,
+) -> RecordAPIResult<Vec<ActionHash>>
+    where EN: TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    let superseded: Vec<ActionHash> = read_queued_requests(pending_sync_entry_type::<EN, _, _>()?)?
+        .into_iter()
+        .take_while(|q| Some(&q.item.id) != delivered)
+        .filter(|q| sync_keys.iter().any(|key| q.is_superseded_by(to_dna, key)))
+        .map(|q| q.item.id)
+        .collect();
+
+    for queued in superseded.iter() {
+        create_entry(EN::try_from(PendingSync::Discarded {
+            queued: queued.to_owned(),
+            reason: "superseded by a later request".to_string(),
+        }).map_err(WasmError::from)?)?;
+    }
+
+    Ok(superseded)
+}
+
+/// Discard an undelivered request from the agent's outbox, so that it is no longer replayed.
+///
+pub fn discard_pending_sync<EN, E, E2>(
+    entry_type: EntryType,
+    id: &ActionHash,
+) -> RecordAPIResult<ActionHash>
+    where EN: TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    if !read_queued_requests(entry_type)?.iter().any(|q| q.item.id == *id) {
+        return Err(DataIntegrityError::EntryNotFound);
+    }
+
+    Ok(create_entry(EN::try_from(PendingSync::Discarded {
+        queued: id.to_owned(),
+        reason: "discarded by agent".to_string(),
+    }).map_err(WasmError::from)?)?)
+}
+
+/// Handler for the scheduled outbox replay. Only requests whose backoff period has elapsed
+/// are attempted, and the handler reschedules itself for the next request falling due.
+///
+pub fn scheduled_retry_pending_sync<EN, LT, E, E2>(
+    entry_type: EntryType,
+    capability_link_type: LT,
+) -> Option<Schedule>
+    where ScopedLinkType: TryFrom<LT, Error = E>,
+        LT: Clone + LinkTypeFilterExt,
+        EN: TryFrom<AvailableCapability, Error = E> + TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    let now = sys_time().ok()?;
+    let due: Vec<QueuedRequest> = read_queued_requests(entry_type.to_owned()).ok()?
+        .into_iter()
+        .filter(|q| q.next_retry().map_or(false, |t| t <= now))
+        .collect();
+
+    if !due.is_empty() {
+        let result = replay_requests::<EN, _, _, _>(due, capability_link_type);
+        hdk::prelude::debug!("scheduled_retry_pending_sync {:?}", result);
+    }
+
+    // reschedule for the next request falling due, if any are still retryable
+    read_queued_requests(entry_type).ok()?
+        .iter()
+        .filter_map(|q| q.next_retry())
+        .min()
+        .map(|t| {
+            let delay_micros = t.as_micros().saturating_sub(now.as_micros()).max(1_000_000);
+            Schedule::Ephemeral(std::time::Duration::from_micros(delay_micros as u64))
+        })
+}
+
+/// Attempt delivery of the given requests, logging the outcome of each attempt.
+///
+/// Requests are replayed oldest first. Delivering a request discards any requests queued before it for the same sync key.
+///
+fn replay_requests<EN, LT, E, E2>(
+    requests: Vec<QueuedRequest>,
+    capability_link_type: LT,
+) -> RecordAPIResult<PendingSyncRetryResponse>
+    where ScopedLinkType: TryFrom<LT, Error = E>,
+        LT: Clone + LinkTypeFilterExt,
+        EN: TryFrom<AvailableCapability, Error = E> + TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    let mut outcomes: HashMap<(DnaHash, String, Vec<u8>), Option<CrossCellError>> = HashMap::new();
+    let mut delivered = vec![];
+    let mut rejected = vec![];
+    let mut superseded = vec![];
+    let mut pending = vec![];
+    let now = sys_time()?;
+
+    for request in requests {
+        let item = &request.item;
+        let request_key = (item.to_dna.to_owned(), item.remote_permission_id.to_owned(), request.payload.0.to_owned());
+
+        let error = match outcomes.get(&request_key) {
+            Some(outcome) => outcome.to_owned(),
+            None => {
+                let outcome = deliver_zome_call::<EN, _, _, _, _>(
+                    &item.to_dna, &item.remote_permission_id,
+                    request.payload.to_owned(),
+                    capability_link_type.to_owned(),
+                ).err();
+                outcomes.insert(request_key, outcome.to_owned());
+                outcome
+            },
+        };
+
+        // discard overtaken requests while this one is still queued, so that only those queued before it are affected
+        if let (None, Some(key)) = (&error, &request.sync_key) {
+            let mut overtaken = supersede_pending_sync::<EN, _, _>(&item.to_dna, &[key.to_owned()], Some(&item.id))?;
+            pending.retain(|p: &PendingSyncItem| !overtaken.contains(&p.id));
+            superseded.append(&mut overtaken);
+        }
+
+        let log_entry = match &error {
+            Some(e) if !e.is_transient() => PendingSync::Discarded {
+                queued: item.id.to_owned(),
+                reason: e.to_string(),
+            },
+            _ => PendingSync::Attempted {
+                queued: item.id.to_owned(),
+                error: error.as_ref().map(|e| e.to_string()),
+            },
+        };
+        create_entry(EN::try_from(log_entry).map_err(WasmError::from)?)?;
+
+        match error {
+            None => delivered.push(item.id.to_owned()),
+            Some(e) if !e.is_transient() => rejected.push(item.id.to_owned()),
+            Some(e) => pending.push(request.with_attempt(e.to_string(), now).item),
+        }
+    }
+
+    Ok(PendingSyncRetryResponse { delivered, rejected, superseded, pending })
+}
+
+/// Read all `PendingSync` entries from the agent's source chain and fold replay attempts
+/// into the requests they relate to, returning those which remain unresolved.
+///
+fn read_queued_requests(entry_type: EntryType) -> RecordAPIResult<Vec<QueuedRequest>> {
+    let records = query(ChainQueryFilter::new()
+        .entry_type(entry_type)
+        .include_entries(true))?;
+
+    let mut queued: Vec<QueuedRequest> = vec![];
+
+    for record in records.iter() {
+        let entry: PendingSync = match record.entry().to_app_option()? {
+            Some(e) => e,
+            None => continue,
+        };
+        let written = record.action().timestamp();
+
+        match entry {
+            PendingSync::Queued { to_dna, remote_permission_id, payload, error, sync_key } => {
+                let mut request = QueuedRequest {
+                    item: PendingSyncItem {
+                        id: record.action_address().to_owned(),
+                        queued: RevisionMeta::from(record.signed_action()),
+                        to_dna,
+                        remote_permission_id,
+                        attempts: 1,
+                        last_error: error,
+                        next_retry: None,
+                    },
+                    payload,
+                    sync_key,
+                    last_attempted: written,
+                };
+                request.item.next_retry = request.next_retry().map(to_datetime);
+                queued.push(request);
+            },
+            PendingSync::Attempted { queued: queued_id, error } => {
+                let found = queued.iter().position(|q| q.item.id == queued_id);
+                match (found, error) {
+                    (Some(idx), None) => { queued.remove(idx); },
+                    (Some(idx), Some(e)) => {
+                        let q = queued.remove(idx);
+                        queued.insert(idx, q.with_attempt(e, written));
+                    },
+                    (None, _) => (),
+                }
+            },
+            PendingSync::Discarded { queued: queued_id, .. } => {
+                queued.retain(|q| q.item.id != queued_id);
+            },
+        }
+    }
+
+    Ok(queued)
+}
+
+/// Determine the `EntryType` under which the calling zome stores `PendingSync` entries.
+///
+// :SHONK: only the zome's `EntryTypes` are known here, so the entry type is read from a placeholder entry
+fn pending_sync_entry_type<EN, E, E2>() -> RecordAPIResult<EntryType>
+    where EN: TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        WasmError: From<E> + From<E2>,
+{
+    let placeholder = EN::try_from(PendingSync::Discarded {
+        queued: ActionHash::from_raw_36(vec![0; 36]),
+        reason: String::new(),
+    }).map_err(WasmError::from)?;
+    let ScopedEntryDefIndex { zome_index, zome_type } = ScopedEntryDefIndex::try_from(&placeholder).map_err(WasmError::from)?;
+
+    Ok(EntryType::App(AppEntryDef::new(zome_type, zome_index, EntryVisibility::from(&placeholder))))
+}
+
+/// Generate zome API endpoints for inspecting and replaying the outbox of a zome
+/// which makes calls via `call_zome_method_or_queue`.
+///
+/// Expects the zome's `EntryTypes`, `EntryTypesUnit` and `LinkTypes` to be in scope,
+/// with a `PendingSync` entry variant and `AvailableCapability` link type.
+///
+#[macro_export]
+macro_rules! declare_pending_sync_zome_api {
+    () => {
+        #[hdk_extern]
+        fn get_pending_sync(_: ()) -> ExternResult<Vec<$crate::outbox::PendingSyncItem>> {
+            Ok($crate::outbox::read_pending_sync(EntryTypesUnit::PendingSync.try_into()?)?)
+        }
+
+        #[hdk_extern]
+        fn retry_pending_sync(_: ()) -> ExternResult<$crate::outbox::PendingSyncRetryResponse> {
+            Ok($crate::outbox::retry_pending_sync::<EntryTypes, _, _, _>(
+                EntryTypesUnit::PendingSync.try_into()?,
+                LinkTypes::AvailableCapability,
+            )?)
+        }
+
+        #[hdk_extern]
+        fn discard_pending_sync(params: $crate::outbox::PendingSyncParams) -> ExternResult<ActionHash> {
+            Ok($crate::outbox::discard_pending_sync::<EntryTypes, _, _>(
+                EntryTypesUnit::PendingSync.try_into()?,
+                &params.id,
+            )?)
+        }
+
+        #[hdk_extern(infallible)]
+        fn scheduled_retry_pending_sync(_: Option<Schedule>) -> Option<Schedule> {
+            match EntryTypesUnit::PendingSync.try_into() {
+                Ok(entry_type) => $crate::outbox::scheduled_retry_pending_sync::<EntryTypes, _, _, _>(
+                    entry_type,
+                    LinkTypes::AvailableCapability,
+                ),
+                Err(_) => None,
+            }
+        }
+    };
+}
@@ -23,51 +23,64 @@ use crate::{
     },
     identity_helpers::{
         create_entry_identity,
+        delete_entry_identity,
         infer_local_entry_identity,
         calculate_identity_address,
     },
     metadata_helpers::{
         get_action_hash,
+        get_original_revision,
+        find_revision_heads,
+        read_revision_history,
+        RevisionHistoryPage,
     },
 };
 
 //--------------------------------[ READ ]--------------------------------------
 
+/// Retrieve all live branch heads of the update tree for the record originally created
+/// with the given `EntryHash`, in ascending order of authoring time.
+///
+/// More than one head indicates that the record has been concurrently updated by
+/// different agents and the branches have not yet been merged (@see `merge_record`).
+///
+pub fn get_revision_heads(entry_hash: EntryHash) -> RecordAPIResult<Vec<SignedActionHashed>> {
+    find_revision_heads(&get_original_revision(entry_hash)?)
+}
+
 /// Retrieve the latest available ActionHash for a given EntryHash.
 ///
 /// Useful in coordinating updates between different entry types.
 ///
-/// :TODO: this is a very naive recursive algorithm that basically assumes full network
-/// connectivity between everyone at all times, and Updates form a Linked List, rather
-/// than a multi-branching tree. This should be updated during other 'conflict resolution' related
-/// changes outlined in issue https://github.com/h-REA/hREA/issues/196
+/// If the record's update history has diverged, a `DataIntegrityError::UpdateConflict`
+/// is returned which lists every live branch head. These must be passed to `merge_record`
+/// in order to resolve the conflict before further updates can be coordinated.
+/// Where updates must proceed regardless, use `get_latest_action_hash_or_conflicts`.
 ///
 pub fn get_latest_action_hash(entry_hash: EntryHash) -> RecordAPIResult<ActionHash> {
-    match get_details(entry_hash.clone(), GetOptions { strategy: GetStrategy::Latest })? {
-        Some(Details::Entry(details)) => match details.entry_dht_status {
-            metadata::EntryDhtStatus::Live => match details.updates.len() {
-                0 => {
-                    // https://docs.rs/hdk/latest/hdk/prelude/struct.EntryDetails.html#structfield.actions
-                    Ok(get_action_hash(details.actions.first().unwrap()))
-                },
-                _ => {
-                    // updates exist, find most recent action
-                    let mut sortlist = details.updates.to_vec();
-                    sortlist.sort_by_key(|update| update.action().timestamp().as_micros());
-                    let last = sortlist.last().unwrap();
-                    // (unwrap should be safe because these are Update actions)
-                    let last_entry_hash = last.action().entry_hash().unwrap().clone();
-                    if entry_hash == last_entry_hash {
-                      Ok(get_action_hash(last))
-                    } else {
-                      // recurse
-                      get_latest_action_hash(last_entry_hash)
-                    }
-                },
-            },
-            _ => Err(DataIntegrityError::EntryNotFound),
-        },
-        _ => Err(DataIntegrityError::EntryNotFound),
+    let heads = get_revision_heads(entry_hash)?;
+
+    match heads.len() {
+        0 => Err(DataIntegrityError::EntryNotFound),
+        1 => Ok(get_action_hash(heads.first().unwrap())),
+        _ => Err(DataIntegrityError::UpdateConflict(heads.iter().map(get_action_hash).collect())),
+    }
+}
+
+/// Retrieve the latest available ActionHash for a given EntryHash, tolerating diverged update histories.
+///
+/// Where the record has conflicting update branches, the most recently authored head is chosen
+/// (ties broken by `ActionHash`), so that every agent selects the same branch to build upon.
+/// The remaining heads are returned alongside, so that callers may report the conflict. They
+/// remain live until resolved via `merge_record`.
+///
+pub fn get_latest_action_hash_or_conflicts(entry_hash: EntryHash) -> RecordAPIResult<(ActionHash, Vec<ActionHash>)> {
+    let mut heads: Vec<ActionHash> = get_revision_heads(entry_hash)?
+        .iter().map(get_action_hash).collect();
+
+    match heads.pop() {
+        Some(latest) => Ok((latest, heads)),
+        None => Err(DataIntegrityError::EntryNotFound),
     }
 }
 
@@ -88,11 +101,9 @@ pub fn read_record_entry_by_action<T, R, B>(
 
 /// Read a record's entry data by its identity index
 ///
-/// :TODO: Currently, the most recent version of the given entry will
-///        be provided instead of the exact entry specified.
-///        We should also check for multiple live actions, and throw a
-///        conflict error if necessary. But core may implement this for
-///        us eventually. (@see EntryDhtStatus)
+/// The most recent version of the given entry will be provided. Where the record has
+/// conflicting update branches, the most recently authored head is returned so that reads
+/// remain available; conflicts are surfaced to clients via `RecordMeta.conflicts`.
 ///
 pub (crate) fn read_record_entry_by_identity<T, R, B>(
     identity_address: &EntryHash,
@@ -107,7 +118,8 @@ pub (crate) fn read_record_entry_by_identity<T, R, B>(
     let identifier: B = infer_local_entry_identity(identity_address)?;
     // pull details of the current version, to ensure we have the most recent
     let entry_hash: &EntryHash = identifier.as_ref();
-    let latest_action_hash = get_latest_action_hash(entry_hash.to_owned())?;
+    let heads = get_revision_heads(entry_hash.to_owned())?;
+    let latest_action_hash = get_action_hash(heads.last().ok_or(DataIntegrityError::EntryNotFound)?);
 
     let (meta, read_entry_hash, entry_data) = read_record_entry_by_action(&latest_action_hash)?;
 
@@ -133,6 +145,38 @@ pub fn read_record_entry<T, R, B>(
     read_record_entry_by_identity::<T, R, B>(&identity_address)
 }
 
+/// Read a page of the full edit history of a record by its identity index.
+///
+/// @see hdk_records::metadata::read_revision_history
+///
+pub (crate) fn read_record_revisions_by_identity<B>(
+    identity_address: &EntryHash,
+    after: Option<ActionHash>,
+    limit: Option<usize>,
+) -> RecordAPIResult<RevisionHistoryPage>
+    where B: DnaAddressable<EntryHash>,
+{
+    let identifier: B = infer_local_entry_identity(identity_address)?;
+    let entry_hash: &EntryHash = identifier.as_ref();
+    read_revision_history(&get_original_revision(entry_hash.to_owned())?, after, limit)
+}
+
+/// Read a page of the full edit history of a record, as a list of revision metadata
+/// ordered from the original revision onwards.
+///
+/// Paging begins after the revision `after` if specified, returning up to `limit` results.
+///
+pub fn read_record_revisions<B>(
+    address: &B,
+    after: Option<ActionHash>,
+    limit: Option<usize>,
+) -> RecordAPIResult<RevisionHistoryPage>
+    where B: DnaAddressable<EntryHash>,
+{
+    let identity_address = calculate_identity_address(address)?;
+    read_record_revisions_by_identity::<B>(&identity_address, after, limit)
+}
+
 //-------------------------------[ CREATE ]-------------------------------------
 
 /// Creates a new record in the DHT, assigns it an identity index (@see identity_helpers.rs)
@@ -176,6 +220,29 @@ pub fn create_record<T, I, R: Clone, B, C, E, S, F, G>(
     Ok((meta, identity, entry_data))
 }
 
+/// Locate a record previously written by `create_record` for an identical `create_payload`.
+///
+/// Allows receivers of replicated records to treat repeated delivery of the same creation
+/// request as a no-op. Only meaningful for payloads which carry the record's nonce, since
+/// entry data is otherwise never identical between two requests.
+///
+pub fn read_existing_record<I, R, B, C, E>(
+    create_payload: C,
+) -> RecordAPIResult<Option<(SignedActionHashed, B, I)>>
+    where B: DnaAddressable<EntryHash>,
+        C: TryInto<I, Error = DataIntegrityError>,
+        I: std::fmt::Debug + Identifiable<R>,
+        WasmError: From<E>,
+        Entry: TryFrom<R, Error = E>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        R: std::fmt::Debug + Identified<I, B>,
+{
+    let entry_data: I = create_payload.try_into()?;
+    let entry_hash = hash_entry(entry_data.with_identity(None))?;
+
+    Ok(read_record_entry::<I, R, B>(&entry_hash).ok())
+}
+
 //-------------------------------[ UPDATE ]-------------------------------------
 
 /// Updates a record in the DHT by its `ActionHash` (revision ID)
@@ -214,21 +281,163 @@ pub fn update_record<I, R: Clone, B, U, E>(
     Ok((meta, identity, new_entry, prev_entry))
 }
 
+/// Updates a record in the DHT by its `ActionHash` (revision ID), unless an identical update
+/// has already been made to the same revision.
+///
+/// Allows receivers of replicated records to treat repeated delivery of the same update
+/// request as a no-op, since the result of applying a payload to a given revision is deterministic.
+/// The previously written revision is returned in place of a new one in this case.
+///
+pub fn update_record_once<I, R: Clone, B, U, E>(
+    address: &ActionHash,
+    update_payload: U,
+) -> RecordAPIResult<(SignedActionHashed, B, I, I)>
+    where B: DnaAddressable<EntryHash>,
+        I: Identifiable<R> + Updateable<U>,
+        WasmError: From<E>,
+        Entry: TryFrom<R, Error = E>,
+        R: Clone + Identified<I, B>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+{
+    let (_meta, previous): (_, R) = get_entry_by_action(address)?;
+    let prev_entry = previous.entry();
+    let identity = previous.identity()?;
+    let identity_hash: &EntryHash = identity.as_ref();
+
+    let new_entry = prev_entry.update_with(update_payload)?;
+    let storage: R = new_entry.with_identity(Some(identity_hash.clone()));
+    let new_entry_hash = hash_entry(storage.to_owned())?;
+
+    let existing = match get_details(address.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Record(details)) => details.updates.into_iter()
+            .find(|u| u.action().entry_hash() == Some(&new_entry_hash)),
+        _ => None,
+    };
+
+    let meta = match existing {
+        Some(meta) => meta,
+        None => update_entry(address, storage)?.0,
+    };
+
+    Ok((meta, identity, new_entry, prev_entry))
+}
+
+/// Resolves a conflicting record by writing a merge update which supersedes multiple revisions.
+///
+/// `revision_ids` must name every live branch head of the record's update tree, otherwise
+/// a `DataIntegrityError::UpdateConflict` listing the current heads is returned so that the caller
+/// may review them and retry. All revisions must belong to the same record.
+///
+/// The `update_payload` is applied against the most recently authored revision, which is then
+/// updated in the usual way. Since an `Update` can only reference a single previous revision, all other
+/// named revisions are marked as deleted in order to close off their branches of the update tree.
+///
+pub fn merge_record<I, R: Clone, B, U, E>(
+    revision_ids: &[ActionHash],
+    update_payload: U,
+) -> RecordAPIResult<(SignedActionHashed, B, I, I)>
+    where B: DnaAddressable<EntryHash>,
+        I: Identifiable<R> + Updateable<U>,
+        WasmError: From<E>,
+        Entry: TryFrom<R, Error = E>,
+        R: Clone + Identified<I, B>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+{
+    // load all referenced revisions, most recent last
+    let mut revisions = revision_ids.iter()
+        .map(|id| get_entry_by_action(id))
+        .collect::<RecordAPIResult<Vec<(SignedActionHashed, R)>>>()?;
+    revisions.sort_by(|(a, _), (b, _)| a.action().timestamp().cmp(&b.action().timestamp())
+        .then_with(|| get_action_hash(a).cmp(&get_action_hash(b))));
+    revisions.dedup_by_key(|(meta, _)| get_action_hash(meta));
+
+    let (latest_meta, latest) = revisions.pop().ok_or(DataIntegrityError::EntryNotFound)?;
+    let prev_entry = latest.entry();
+    let identity = latest.identity()?;
+    let identity_hash: &EntryHash = identity.as_ref();
+
+    // ensure all revisions refer to the same record
+    for (_meta, revision) in revisions.iter() {
+        let revision_identity = revision.identity()?;
+        let revision_identity_hash: &EntryHash = revision_identity.as_ref();
+        if revision_identity_hash != identity_hash {
+            return Err(DataIntegrityError::EntryWrongType);
+        }
+    }
+
+    // ensure every branch is accounted for
+    let heads: Vec<ActionHash> = get_revision_heads(identity_hash.to_owned())?
+        .iter().map(get_action_hash).collect();
+    if heads.iter().any(|head| !revision_ids.contains(head)) {
+        return Err(DataIntegrityError::UpdateConflict(heads));
+    }
+
+    // apply update payload to the latest revision
+    let new_entry = prev_entry.update_with(update_payload)?;
+    let storage: R = new_entry.with_identity(Some(identity_hash.clone()));
+    let (meta, _entry_addr) = update_entry(&get_action_hash(&latest_meta), storage)?;
+
+    // close off all superseded branches
+    for (superseded, _) in revisions.iter() {
+        delete_entry::<R>(&get_action_hash(superseded))?;
+    }
+
+    Ok((meta, identity, new_entry, prev_entry))
+}
+
 //-------------------------------[ DELETE ]-------------------------------------
 
 /// Removes a record of the given `ActionHash` from the DHT by marking it as deleted.
 ///
-/// Links are not affected so as to retain a link to the referencing information, which may now need to be updated.
+/// Once no live revisions of the record remain, its identity is removed from the
+/// global index in the companion index zome, such that it is no longer listed.
+///
+/// Other links are not affected so as to retain a link to the referencing information, which may now need to be updated.
 ///
-pub fn delete_record<T>(address: &ActionHash) -> RecordAPIResult<bool>
-    where SerializedBytes: TryInto<T, Error = SerializedBytesError>,
+pub fn delete_record<R, I, B, S, F, G>(
+    indexing_zome_name_from_config: F,
+    entry_def_id: S,
+    address: &ActionHash,
+) -> RecordAPIResult<bool>
+    where S: AsRef<str> + std::fmt::Display,
+        B: DnaAddressable<EntryHash>,
+        Entry: TryFrom<R>,
+        R: Identified<I, B>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+        F: FnOnce(G) -> Option<String>,
+        G: std::fmt::Debug,
+        SerializedBytes: TryInto<G, Error = SerializedBytesError>,
 {
-    // :TODO: handle deletion of the identity `Path` for the referenced entry if this is the last action being deleted
+    let (_meta, storage): (_, R) = get_entry_by_action(address)?;
+    let identity = storage.identity()?;
+
+    delete_entry::<R>(address)?;
+
+    // remove from indexes only if no other branches of the record's update tree remain
+    // :TODO: move this to a postcommit hook in coordination zome; see #264
+    let identity_hash: &EntryHash = identity.as_ref();
+    if get_revision_heads(identity_hash.to_owned())?.is_empty() {
+        delete_entry_identity(
+            indexing_zome_name_from_config,
+            &entry_def_id, &identity,
+        )?;
+    }
 
-    delete_entry::<T>(address)?;
     Ok(true)
 }
 
+/// Determine whether the given revision of a record has already been marked as deleted.
+///
+/// Allows receivers of replicated records to treat repeated delivery of the same
+/// deletion request as a no-op.
+///
+pub fn is_revision_deleted(address: &ActionHash) -> RecordAPIResult<bool> {
+    match get_details(address.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Record(details)) => Ok(!details.deletes.is_empty()),
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,7 +508,7 @@ mod tests {
         assert_eq!(third_entry, Entry { field: Some("value".into()) }, "retrieved record should be changed after update");
 
         // DELETE
-        let _ = delete_record::<Entry>(&updated_action_addr);
+        let _ = delete_record::<EntryWithIdentity,_,EntryId,_,_,_>(indexing_zome_name_from_config, &entry_type, &updated_action_addr);
 
         // Verify read failure
         let _failure = read_record_entry::<Entry, EntryWithIdentity, EntryId,_,_>(&identity_address).err().unwrap();
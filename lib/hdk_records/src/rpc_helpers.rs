@@ -9,12 +9,15 @@
  */
 
 use hdk::prelude::*;
+use hdk::hdk::{HDK, HdkT};
 use holo_hash::DnaHash;
 use hc_zome_dna_auth_resolver_lib::{DNAConnectionAuth, ensure_authed, AvailableCapability};
 
 use crate::{
     OtherCellResult,
     CrossCellError,
+    DataIntegrityError,
+    outbox::{PendingSync, queue_pending_sync, supersede_pending_sync},
 };
 
 /**
@@ -40,17 +43,168 @@ pub fn call_zome_method<EN, H, R, I, S, LT, E, E2>(
         Entry: TryFrom<EN, Error = E>,
         // links and entries
         WasmError: From<E> + From<E2>,
+{
+    let resp = deliver_zome_call::<EN, _, _, _, _>(
+        to_registered_dna.as_ref(), remote_permission_id,
+        ExternIO::encode(payload)?,
+        capability_link_type,
+    )?;
+
+    Ok(resp.decode()?)
+}
+
+/**
+ * Variant of `call_zome_method` for write operations which must eventually reach the remote cell.
+ *
+ * If the call cannot be delivered (remote cell offline, capability not yet granted etc) the
+ * request is written to the calling agent's source chain as a private `PendingSync` entry, to
+ * be replayed later via `hdk_records::outbox::retry_pending_sync`. Requests rejected by the
+ * remote cell are not queued, since replaying them would fail in the same way.
+ * The original error is returned in either case so that callers may report on it.
+ *
+ * `sync_key` identifies the record whose state the request carries, where later requests for the
+ * same record make earlier ones obsolete. Once such a request is delivered, any requests queued for
+ * the same key are discarded, so that replaying them cannot revert the remote cell to an older state.
+ */
+pub fn call_zome_method_or_queue<EN, H, R, I, S, LT, E, E2>(
+    to_registered_dna: &H,
+    remote_permission_id: &S,
+    payload: I,
+    sync_key: Option<String>,
+    capability_link_type: LT,
+) -> OtherCellResult<R>
+    where S: AsRef<str>,
+        H: AsRef<DnaHash>,
+        I: serde::Serialize + std::fmt::Debug,
+        R: serde::de::DeserializeOwned + std::fmt::Debug,
+        // links
+        ScopedLinkType: TryFrom<LT, Error = E>, // associated with create_link
+        LT: Clone + LinkTypeFilterExt, // LinkTypeFilterExt associated with get_links
+        // entries
+        EN: TryFrom<AvailableCapability, Error = E> + TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        // links and entries
+        WasmError: From<E> + From<E2>,
+{
+    let to_dna: &DnaHash = to_registered_dna.as_ref();
+    let payload = ExternIO::encode(payload)?;
+
+    match deliver_zome_call::<EN, _, _, _, _>(to_dna, remote_permission_id, payload.to_owned(), capability_link_type) {
+        Ok(resp) => {
+            if let Some(key) = sync_key {
+                let superseded = supersede_pending_sync::<EN, _, _>(to_dna, &[key], None);
+                hdk::prelude::debug!("call_zome_method_or_queue::supersede_pending_sync {:?}", superseded);
+            }
+            Ok(resp.decode()?)
+        },
+        Err(e) if e.is_transient() => {
+            let queued = queue_pending_sync::<EN, _, _, _>(to_dna, remote_permission_id, payload, sync_key, &e);
+            hdk::prelude::debug!("call_zome_method_or_queue::queue_pending_sync {:?}", queued);
+            Err(e)
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/**
+ * Variant of `call_zome_method_or_queue` for requests which carry the state of several records at once.
+ *
+ * `queue_as` splits the request into parts which can be replayed independently, each paired with
+ * the sync key of the state it carries. Once the request is delivered, any requests queued for those
+ * keys are discarded. Undelivered requests are queued as their separate parts.
+ */
+pub fn call_zome_method_or_queue_split<EN, H, R, I, S, LT, E, E2>(
+    to_registered_dna: &H,
+    remote_permission_id: &S,
+    payload: I,
+    queue_as: Vec<(String, I)>,
+    capability_link_type: LT,
+) -> OtherCellResult<R>
+    where S: AsRef<str>,
+        H: AsRef<DnaHash>,
+        I: serde::Serialize + std::fmt::Debug,
+        R: serde::de::DeserializeOwned + std::fmt::Debug,
+        // links
+        ScopedLinkType: TryFrom<LT, Error = E>, // associated with create_link
+        LT: Clone + LinkTypeFilterExt, // LinkTypeFilterExt associated with get_links
+        // entries
+        EN: TryFrom<AvailableCapability, Error = E> + TryFrom<PendingSync, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        // links and entries
+        WasmError: From<E> + From<E2>,
 {
     let to_dna: &DnaHash = to_registered_dna.as_ref();
+    let payload = ExternIO::encode(payload)?;
+
+    match deliver_zome_call::<EN, _, _, _, _>(to_dna, remote_permission_id, payload, capability_link_type) {
+        Ok(resp) => {
+            let sync_keys: Vec<String> = queue_as.into_iter().map(|(key, _)| key).collect();
+            let superseded = supersede_pending_sync::<EN, _, _>(to_dna, sync_keys.as_slice(), None);
+            hdk::prelude::debug!("call_zome_method_or_queue_split::supersede_pending_sync {:?}", superseded);
+            Ok(resp.decode()?)
+        },
+        Err(e) if e.is_transient() => {
+            for (sync_key, part) in queue_as {
+                let queued = ExternIO::encode(part)
+                    .map_err(DataIntegrityError::from)
+                    .and_then(|part| queue_pending_sync::<EN, _, _, _>(to_dna, remote_permission_id, part, Some(sync_key), &e));
+                hdk::prelude::debug!("call_zome_method_or_queue_split::queue_pending_sync {:?}", queued);
+            }
+            Err(e)
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/**
+ * Authenticates against the remote DNA and sends a pre-encoded payload to the
+ * method registered for `remote_permission_id`, returning the raw response.
+ *
+ * Only failures which prevented the call from executing in the remote cell are returned as errors.
+ */
+pub(crate) fn deliver_zome_call<EN, S, LT, E, E2>(
+    to_dna: &DnaHash,
+    remote_permission_id: &S,
+    payload: ExternIO,
+    capability_link_type: LT,
+) -> OtherCellResult<ExternIO>
+    where S: AsRef<str>,
+        // links
+        ScopedLinkType: TryFrom<LT, Error = E>, // associated with create_link
+        LT: Clone + LinkTypeFilterExt, // LinkTypeFilterExt associated with get_links
+        // entries
+        EN: TryFrom<AvailableCapability, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        // links and entries
+        WasmError: From<E> + From<E2>,
+{
     let auth_data = ensure_authed(to_dna, remote_permission_id, capability_link_type)?;
 
     let DNAConnectionAuth { claim, method } = auth_data;
 
     let to_cell = CallTargetCell::OtherCell(CellId::new(to_dna.clone(), claim.grantor().to_owned()));
-    let resp = call(to_cell, method.0, method.1, Some(claim.secret().to_owned()), payload)
-        .map_err(CrossCellError::from)?;
 
-    handle_resp(resp)
+    // :NOTE: bypasses `hdk::call` in order to send the payload as-is, since it may
+    //        have been previously encoded and stored in an outbox entry.
+    let resp = HDK.with(|h| {
+        h.borrow().call(vec![Call::new(
+            CallTarget::ConductorCell(to_cell),
+            method.0, method.1,
+            Some(claim.secret().to_owned()),
+            payload,
+        )])
+    })
+        .map_err(CrossCellError::from)?
+        .into_iter().next()
+        .ok_or_else(|| CrossCellError::Internal("no response from remote zome call".to_string()))?;
+
+    unwrap_resp(resp)
 }
 
 /**
@@ -90,15 +244,60 @@ pub fn call_local_zome_method<C, F, R, I, S>(
 
 }
 
+/**
+ * Helper for making calls to zomes in other cells of the same hApp, where the target cell is
+ * determined by its role name as configured in the DNA properties of the calling zome.
+ *
+ * Calls are made by the agent's own cells, and so implicitly carry author privileges in the target cell.
+ */
+pub fn call_role_zome_method<C, F, R, I, Z, S>(
+    role_name_from_config: F,
+    zome_name: Z,
+    method_name: S,
+    payload: I,
+) -> OtherCellResult<R>
+    where S: AsRef<str>,
+        Z: AsRef<str>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+        F: FnOnce(C) -> Option<String>,
+        I: serde::Serialize + std::fmt::Debug,
+        R: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let this_zome = zome_info()?.name;
+    let remote_zome_method = FunctionName(method_name.as_ref().to_string());
+
+    let zome_props: C = dna_info()?.properties
+        .try_into()
+        .map_err(|_| { CrossCellError::NotConfigured(this_zome.to_owned(), remote_zome_method.to_owned()) })?;
+
+    match role_name_from_config(zome_props) {
+        None => Err(CrossCellError::NotConfigured(this_zome, remote_zome_method)),
+        Some(role_name) => {
+            let resp = call(CallTargetCell::OtherRole(role_name.into()), ZomeName::new(zome_name.as_ref()), remote_zome_method, None, payload)
+                .map_err(CrossCellError::from)?;
+
+            handle_resp(resp)
+        },
+    }
+}
+
 fn handle_resp<R>(
     resp: ZomeCallResponse,
 ) -> OtherCellResult<R>
     // :TODO: data.decode() requires Debug to be implemented. Is this expected behaviour?
     where R: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    Ok(unwrap_resp(resp)?.decode()?)
+}
+
+fn unwrap_resp(
+    resp: ZomeCallResponse,
+) -> OtherCellResult<ExternIO>
 {
     match resp {
         ZomeCallResponse::Ok(data) =>
-            Ok(data.decode()?),
+            Ok(data),
         ZomeCallResponse::Unauthorized(_auth, cell, zome, fname, agent) =>
             Err(CrossCellError::Unauthorized(cell, zome, fname, agent)),
         ZomeCallResponse::NetworkError(msg) =>
@@ -32,6 +32,22 @@ pub enum CrossCellError {
 
 pub type OtherCellResult<T> = Result<T, CrossCellError>;
 
+impl CrossCellError {
+    /// Whether the error may be resolved by repeating the same request later, as opposed
+    /// to a deterministic rejection of the request by the remote cell.
+    ///
+    /// :NOTE: depending on the conductor, errors returned by the remote zome may surface as
+    /// `WasmErrorInner::Host` and so be treated as transient.
+    ///
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::NetworkError(_) | Self::Unauthorized(_, _, _, _) | Self::CellAuthFailed(_, _) => true,
+            Self::Wasm(WasmError { error: WasmErrorInner::Host(_), .. }) => true,
+            _ => false,
+        }
+    }
+}
+
 impl From<CrossCellError> for WasmError {
     fn from(e: CrossCellError) -> WasmError {
         wasm_error!(WasmErrorInner::CallError(e.to_string()))
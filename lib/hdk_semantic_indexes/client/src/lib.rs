@@ -26,6 +26,7 @@
  * @package hdk_semantic_indexes_client_lib
  * @since   2020-08-07
  */
+use std::cell::RefCell;
 use std::collections::HashMap;
 use hdk::prelude::*;
 use holo_hash::DnaHash;
@@ -34,12 +35,14 @@ use hdk_records::{
     DnaAddressable,
     rpc::{
         call_local_zome_method,
-        call_zome_method,
+        call_zome_method_or_queue_split,
     },
+    outbox::PendingSync,
 };
 use hdk_semantic_indexes_zome_rpc::{
     ByAddress,
     RemoteEntryLinkRequest, RemoteEntryLinkResponse,
+    IndexingReport, IndexingFailure,
 };
 use hc_zome_dna_auth_resolver_lib::AvailableCapability;
 
@@ -318,7 +321,7 @@ pub fn manage_index<EN, LT, E, E2, C, F, G, A, B, S>(
         ScopedLinkType: TryFrom<LT, Error = E>, // associated with create_link
         LT: Clone + LinkTypeFilterExt, // LinkTypeFilterExt associated with get_links
         // entries
-        EN: TryFrom<AvailableCapability, Error = E>,
+        EN: TryFrom<AvailableCapability, Error = E> + TryFrom<PendingSync, Error = E>,
         ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
         EntryVisibility: for<'a> From<&'a EN>,
         Entry: TryFrom<EN, Error = E>,
@@ -360,7 +363,9 @@ pub fn manage_index<EN, LT, E, E2, C, F, G, A, B, S>(
 
     let mut local_updates = vec![];
     let local_reciprocal_update =
-        if targets.local_dests.0.len() > 0 || targets.local_dests.1.len() > 0 {
+        // self-referential indexes have no destination index to update
+        if dest_fn_name.as_ref().is_empty() { vec![] }
+        else if targets.local_dests.0.len() > 0 || targets.local_dests.1.len() > 0 {
             let mut others = vec![request_sync_local_index(
                 dest_zome_name_from_config, dest_fn_name,
                 source, targets.local_dests.0.as_slice(), targets.local_dests.1.as_slice(),
@@ -375,38 +380,154 @@ pub fn manage_index<EN, LT, E, E2, C, F, G, A, B, S>(
     // calls fired for local targets + remote targets add up to equal
     // the number of input `dest_addresses` & `remove_addresses`
 
-    Ok(std::iter::empty()
+    let (remote_adds, remote_removes): (Vec<B>, Vec<B>) = targets.remote_dests.into_values()
+        .fold((vec![], vec![]), |(mut adds, mut removes), (mut a, mut r)| {
+            adds.append(&mut a);
+            removes.append(&mut r);
+            (adds, removes)
+        });
+
+    let remote_forward_add = remote_adds.iter()
+        .map(|dest| {
+            request_sync_local_index(
+                origin_zome_name_from_config, origin_fn_name,
+                dest, &sources, &vec![],
+            )
+        });
+
+    let remote_forward_remove = remote_removes.iter()
+        .map(|dest| {
+            request_sync_local_index(
+                origin_zome_name_from_config, origin_fn_name,
+                dest, &vec![], &sources,
+            )
+        });
+
+    let remote_reciprocal_update =
+        if remote_adds.len() > 0 || remote_removes.len() > 0 {
+            vec![request_sync_remote_index::<EN, _, _, _, _, _, _>(
+                remote_permission_id,
+                source, remote_adds.as_slice(), remote_removes.as_slice(),
+                capability_link_type,
+            )]
+        } else { vec![] };
+
+    let results: Vec<OtherCellResult<RemoteEntryLinkResponse>> = std::iter::empty()
         .chain(local_forward_add)
         .chain(local_forward_remove)
         .chain(local_reciprocal_update)
-        .chain(targets.remote_dests.iter()
-            .flat_map(|(_dna, (add_dests, remove_dests))| {
-                let remote_forward_add = add_dests.iter()
-                    .map(|dest| {
-                        request_sync_local_index(
-                            origin_zome_name_from_config, origin_fn_name,
-                            dest, &sources, &vec![],
-                        )
-                    });
-                let remote_forward_remove = remove_dests.iter()
-                    .map(|dest| {
-                        request_sync_local_index(
-                            origin_zome_name_from_config, origin_fn_name,
-                            dest, &vec![], &sources,
-                        )
-                    });
-                let remote_reciprocal_update = std::iter::once(request_sync_remote_index::<EN, _, _, _, _, _, _>(
-                    remote_permission_id,
-                    source, add_dests, remove_dests,
-                    capability_link_type.clone(),
-                ));
-
-                std::iter::empty()
-                    .chain(remote_forward_add)
-                    .chain(remote_forward_remove)
-                    .chain(remote_reciprocal_update)
-            }))
-        .collect())
+        .chain(remote_forward_add)
+        .chain(remote_forward_remove)
+        .chain(remote_reciprocal_update)
+        .collect();
+
+    record_indexing_outcome(origin_fn_name.as_ref().trim_start_matches("_internal_index_"), &results);
+
+    Ok(results)
+}
+
+//-------------------------------[ REPORTING ]-------------------------------------
+
+/// Configuration object which may be set in the host DNA's properties
+#[derive(Serialize, Deserialize, Debug, SerializedBytes)]
+pub struct IndexingConfiguration {
+    /// Fail zome calls which modify records if any of their index updates fail
+    #[serde(default)]
+    pub strict_indexing: bool,
+}
+
+thread_local! {
+    // outcomes of `manage_index` calls made during the current zome call
+    static INDEXING_REPORT: RefCell<Option<IndexingReport>> = RefCell::new(None);
+}
+
+/// Discard the outcome of any index updates recorded prior to the current operation.
+/// Record modification handlers should call this before updating any indexes, so that
+/// their report only covers the writes made by the current zome call.
+///
+pub fn clear_indexing_report() {
+    INDEXING_REPORT.with(|r| r.borrow_mut().take());
+}
+
+/// Retrieve the outcome of all index updates made since the last call, clearing it
+/// for subsequent operations. Record modification handlers should call this once
+/// all related indexes have been updated. Read handlers should not call it.
+///
+/// Errors if any index update failed and the `strict_indexing` DNA property is set,
+/// causing the zome call (and any writes to the local source chain) to be aborted.
+///
+pub fn take_indexing_report() -> RecordAPIResult<Option<IndexingReport>> {
+    let report = INDEXING_REPORT.with(|r| r.borrow_mut().take());
+
+    match &report {
+        Some(IndexingReport { failed, .. }) if failed.len() > 0 && is_strict_indexing()? => {
+            Err(SemanticIndexError::IndexUpdateFailed(failed.iter()
+                .map(|f| format!("{} ({})", f.relationship, f.errors.join("; ")))
+                .collect::<Vec<String>>()
+                .join(", ")
+            ).into())
+        },
+        _ => Ok(report),
+    }
+}
+
+/// Attach the outcome of all index updates made by a record modification handler to its response.
+/// `$response` must evaluate to a `RecordAPIResult` of a response struct with an `indexing_report` field.
+///
+/// Errors under `strict_indexing` if any index update failed; @see `take_indexing_report`.
+///
+#[macro_export]
+macro_rules! with_indexing_report {
+    ($response:expr) => {
+        $response.and_then(|mut response| {
+            response.indexing_report = $crate::take_indexing_report()?;
+            Ok(response)
+        })
+    };
+}
+
+fn is_strict_indexing() -> RecordAPIResult<bool> {
+    let config: Result<IndexingConfiguration, _> = dna_info()?.properties.try_into();
+    Ok(config.map(|c| c.strict_indexing).unwrap_or(false))
+}
+
+fn record_indexing_outcome(relationship: &str, results: &[OtherCellResult<RemoteEntryLinkResponse>]) {
+    let errors: Vec<String> = results.iter()
+        .flat_map(|r| match r {
+            Err(e) => vec![e.to_string()],
+            Ok(resp) => resp.indexes_created.iter()
+                .chain(resp.indexes_removed.iter())
+                .filter_map(|i| i.as_ref().err().map(|e| e.to_string()))
+                .collect(),
+        })
+        .collect();
+
+    push_indexing_outcome(relationship, errors);
+}
+
+/// Include the outcome of an index update made without the index management macros
+/// (eg. repositioning a record within a time index) in the current zome call's `IndexingReport`,
+/// such that its failure is reported to the client and respected by `strict_indexing`.
+///
+pub fn report_indexing_outcome<T, E>(relationship: &str, result: &Result<T, E>)
+    where E: std::fmt::Display,
+{
+    push_indexing_outcome(relationship, match result {
+        Ok(_) => vec![],
+        Err(e) => vec![e.to_string()],
+    });
+}
+
+fn push_indexing_outcome(relationship: &str, errors: Vec<String>) {
+    INDEXING_REPORT.with(|r| {
+        let mut r = r.borrow_mut();
+        let report = r.get_or_insert_with(IndexingReport::default);
+        if errors.is_empty() {
+            report.succeeded.push(relationship.to_string());
+        } else {
+            report.failed.push(IndexingFailure { relationship: relationship.to_string(), errors });
+        }
+    });
 }
 
 //--------------------------------[ READ ]--------------------------------------
@@ -437,9 +558,15 @@ pub fn read_local_index<'a, O, A, S, F, C>(
 
 //-------------------------------[ UPDATE ]-------------------------------------
 
-/// Ask another bridged cell to build a 'destination query index' to match the
+/// Ask other bridged cells to build 'destination query indexes' to match the
 /// 'origin' one that we have just created locally.
-/// When calling zomes within the same DNA, use `None` as `to_cell`.
+///
+/// Targets are grouped by `DnaHash` and a request sent to each destination cell.
+/// Responses are merged into a single `RemoteEntryLinkResponse`; where a cell could
+/// not be reached, the error is reported against each of the targets in that cell.
+///
+/// Undelivered requests are queued separately for each target, so that a later request
+/// linking or unlinking the same pair of records supersedes them once delivered.
 ///
 fn request_sync_remote_index<EN, A, B, I, LT, E, E2>(
     remote_permission_id: &I,
@@ -455,32 +582,61 @@ fn request_sync_remote_index<EN, A, B, I, LT, E, E2>(
         ScopedLinkType: TryFrom<LT, Error = E>, // associated with create_link
         LT: Clone + LinkTypeFilterExt, // LinkTypeFilterExt associated with get_links
         // entries
-        EN: TryFrom<AvailableCapability, Error = E>,
+        EN: TryFrom<AvailableCapability, Error = E> + TryFrom<PendingSync, Error = E>,
         ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
         EntryVisibility: for<'a> From<&'a EN>,
         Entry: TryFrom<EN, Error = E>,
         // links and entries
         WasmError: From<E> + From<E2>,
 {
-    // :TODO: :SHONK: currently, all destination/removal addresses are assumed to
-    //        be of the same DNA. We should partition inputs into sets keyed by
-    //        destination DNA before firing off these operations.
-    let context_dna = dest_addresses.iter().cloned().nth(0)
-        .unwrap_or_else(|| {
-            removed_addresses.iter().cloned().nth(0)
-                .unwrap()
-        });
+    Ok(partition_by_dna(dest_addresses, removed_addresses).into_values()
+        .fold(RemoteEntryLinkResponse {
+            indexes_created: vec![],
+            indexes_removed: vec![],
+        }, |mut response, (add_dests, remove_dests)| {
+            // any address in the group identifies the destination cell
+            let context_dna = add_dests.first().or(remove_dests.first()).unwrap();
+
+            // Call into remote DNA to enable target entries to setup data structures
+            // for querying the associated remote entry records back out.
+            // Undelivered requests are queued for replay in the local outbox.
+            let queue_as = add_dests.iter()
+                .map(|dest| (index_sync_key(remote_permission_id, source, dest), RemoteEntryLinkRequest::new(source, &[dest.to_owned()], &[])))
+                .chain(remove_dests.iter()
+                    .map(|dest| (index_sync_key(remote_permission_id, source, dest), RemoteEntryLinkRequest::new(source, &[], &[dest.to_owned()]))))
+                .collect();
+            let result: OtherCellResult<RemoteEntryLinkResponse> = call_zome_method_or_queue_split::<EN, _, _, _, _, _, _, _>(
+                context_dna, remote_permission_id,
+                RemoteEntryLinkRequest::new(
+                    source,
+                    add_dests.as_slice(), remove_dests.as_slice(),
+                ),
+                queue_as,
+                capability_link_type.to_owned(),
+            );
+
+            match result {
+                Ok(mut r) => {
+                    response.indexes_created.append(&mut r.indexes_created);
+                    response.indexes_removed.append(&mut r.indexes_removed);
+                },
+                Err(e) => {
+                    response.indexes_created.extend(add_dests.iter().map(|_| Err(e.to_owned())));
+                    response.indexes_removed.extend(remove_dests.iter().map(|_| Err(e.to_owned())));
+                },
+            }
+            response
+        }))
+}
 
-    // Call into remote DNA to enable target entries to setup data structures
-    // for querying the associated remote entry records back out.
-    Ok(call_zome_method::<EN, _, _, _, _, _, _, _>(
-        &context_dna, remote_permission_id,
-        RemoteEntryLinkRequest::new(
-            source,
-            dest_addresses, removed_addresses,
-        ),
-        capability_link_type
-    )?)
+/// Identifies the link between a pair of records which a remote index request synchronises
+///
+fn index_sync_key<I, A, B>(remote_permission_id: &I, source: &A, dest: &B) -> String
+    where I: AsRef<str>,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    format!("{}:{}>{}", remote_permission_id.as_ref(), source, dest)
 }
 
 /// Request for another cell to sync its indexes for a record updated within this cell
@@ -527,45 +683,31 @@ fn prefilter_target_dnas<'a, B>(
     where B: DnaAddressable<EntryHash>,
 {
     let local_dna = dna_info()?.hash;
+    let mut remote_dests = partition_by_dna(dest_addresses, remove_addresses);
+    let local_dests = remote_dests.remove(&local_dna).unwrap_or_else(|| (vec![], vec![]));
 
-    let results = dest_addresses.iter()
-        .fold(TargetsByDna {
-            local_dests: (vec![], vec![]),
-            remote_dests: HashMap::new(),
-        }, |mut targets: TargetsByDna<B>, val| {
-            let target_dna: &DnaHash = val.as_ref();
-            if local_dna == target_dna.to_owned() {
-                targets.local_dests.0.push(val.to_owned());
-            } else {
-                match targets.remote_dests.insert(target_dna.to_owned(), (vec![val.to_owned()], vec![])) {
-                    Some(mut prev_val) => {
-                        let vals = targets.remote_dests.get_mut(target_dna).unwrap();
-                        vals.0.append(&mut prev_val.0);
-                    },
-                    None => (),
-                }
-            }
-            targets
-        });
+    Ok(TargetsByDna { local_dests, remote_dests })
+}
 
-    Ok(remove_addresses.iter()
-        .fold(results, |mut targets: TargetsByDna<B>, val| {
-            let target_dna: &DnaHash = val.as_ref();
-            if local_dna == target_dna.to_owned() {
-                targets.local_dests.1.push(val.to_owned());
-            } else {
-                match targets.remote_dests.insert(target_dna.to_owned(), (vec![], vec![val.to_owned()])) {
-                    Some(mut prev_val) => {
-                        let vals = targets.remote_dests.get_mut(target_dna).unwrap();
-                        vals.0.append(&mut prev_val.0);
-                        vals.1.append(&mut prev_val.1);
-                    },
-                    None => (),
-                }
-            }
-            targets
-        })
-    )
+// group added & removed IDs by the DNA they reside in
+fn partition_by_dna<B>(
+    dest_addresses: &[B],
+    remove_addresses: &[B],
+) -> HashMap<DnaHash, (Vec<B>, Vec<B>)>
+    where B: DnaAddressable<EntryHash>,
+{
+    let mut targets: HashMap<DnaHash, (Vec<B>, Vec<B>)> = HashMap::new();
+
+    for val in dest_addresses {
+        let target_dna: &DnaHash = val.as_ref();
+        targets.entry(target_dna.to_owned()).or_insert_with(|| (vec![], vec![])).0.push(val.to_owned());
+    }
+    for val in remove_addresses {
+        let target_dna: &DnaHash = val.as_ref();
+        targets.entry(target_dna.to_owned()).or_insert_with(|| (vec![], vec![])).1.push(val.to_owned());
+    }
+
+    targets
 }
 
 pub fn string_index_hashes<T>(dest_string_ids: Vec<String>) -> RecordAPIResult<Vec<T>>
@@ -589,3 +731,53 @@ fn string_index_hash<T>(index_value: &String) -> RecordAPIResult<T>
     let index_path: Path = index_value.try_into()?;
     Ok(T::new(dna_info()?.hash, index_path.path_entry_hash()?))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk_uuid_types::addressable_identifier;
+
+    addressable_identifier!(TestAddress => EntryHash);
+
+    fn address(dna: u8, entry: u8) -> TestAddress {
+        TestAddress(DnaHash::from_raw_36(vec![dna; 36]), EntryHash::from_raw_36(vec![entry; 36]))
+    }
+
+    #[test]
+    fn test_partition_by_dna_groups_mixed_dnas() {
+        let dna_a = address(0xa1, 0).0;
+        let dna_b = address(0xb2, 0).0;
+
+        let targets = partition_by_dna(
+            &[address(0xa1, 1), address(0xb2, 2), address(0xa1, 3)],
+            &[address(0xb2, 4)],
+        );
+
+        assert_eq!(targets.len(), 2, "targets grouped into one set per DNA");
+        assert_eq!(targets.get(&dna_a), Some(&(vec![address(0xa1, 1), address(0xa1, 3)], vec![])), "additions grouped by DNA in order");
+        assert_eq!(targets.get(&dna_b), Some(&(vec![address(0xb2, 2)], vec![address(0xb2, 4)])), "additions & removals for the same DNA grouped together");
+    }
+
+    #[test]
+    fn test_partition_by_dna_add_and_remove_same_dna() {
+        let targets = partition_by_dna(
+            &[address(0xa1, 1), address(0xa1, 2)],
+            &[address(0xa1, 3), address(0xa1, 4)],
+        );
+
+        assert_eq!(targets.len(), 1, "single DNA yields a single request");
+        assert_eq!(
+            targets.values().next(),
+            Some(&(vec![address(0xa1, 1), address(0xa1, 2)], vec![address(0xa1, 3), address(0xa1, 4)])),
+            "additions and removals kept distinct",
+        );
+    }
+
+    #[test]
+    fn test_partition_by_dna_removals_only() {
+        let targets = partition_by_dna(&[], &[address(0xa1, 1)]);
+
+        assert_eq!(targets.get(&address(0xa1, 0).0), Some(&(vec![], vec![address(0xa1, 1)])), "DNAs with only removals are targeted");
+        assert!(partition_by_dna::<TestAddress>(&[], &[]).is_empty(), "no targets when nothing changes");
+    }
+}
@@ -17,6 +17,12 @@ pub enum SemanticIndexError {
     BadStringIndexError(Vec<u8>),
     #[error("Time indexing error {0}")]
     BadTimeIndexError(String),
+    #[error("No time index named {0}")]
+    UnknownTimeIndex(String),
+    #[error("Invalid pagination cursor {0}")]
+    BadCursorError(String),
+    #[error("Index updates failed in strict indexing mode: {0}")]
+    IndexUpdateFailed(String),
 }
 
 impl From<FromUtf8Error> for SemanticIndexError {
@@ -29,6 +29,14 @@ pub struct ByAddress<T> {
     pub address: T,
 }
 
+/// Shared parameter struct for paging through the revision history of a record
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevisionsByAddress<T> {
+    pub address: T,
+    pub first: Option<usize>,
+    pub after: Option<ActionHash>,
+}
+
 /// Shared parameter struct for indexing endpoints to respond to record creation
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppendAddress<T> {
@@ -36,6 +44,21 @@ pub struct AppendAddress<T> {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Shared parameter struct for reading records indexed within a range of times,
+/// from `from` (inclusive) to `to` (exclusive). `order_by` names the time index to read,
+/// defaulting to record creation time. Results are paged as for the `read_all_X` list APIs.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeRangeParams {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub first: Option<usize>,
+    pub after: Option<String>,
+    pub last: Option<usize>,
+    pub before: Option<String>,
+    pub order_by: Option<String>,
+}
+
 /// Common request format (zome trait) for linking remote entries in cooperating DNAs
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RemoteEntryLinkRequest<A, B>
@@ -90,3 +113,29 @@ pub struct RemoteEntryLinkResponse {
     pub indexes_created: Vec<OtherCellResult<ActionHash>>,
     pub indexes_removed: Vec<OtherCellResult<ActionHash>>,
 }
+
+/// Outcome of the index updates made whilst modifying a record, for reporting to clients.
+/// Relationships are identified by the name of the index on the modified record.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingReport {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<IndexingFailure>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingFailure {
+    pub relationship: String,
+    pub errors: Vec<String>,
+}
+
+/// Response format for record deletions, reporting the outcome of any index updates
+/// made whilst removing the record's relationships.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteResponse {
+    pub deleted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
+}
@@ -5,20 +5,29 @@
  * @package hdk_semantic_indexes
  * @since   2021-09-30
  */
+use std::collections::HashSet;
 use chrono::{DateTime, Utc};
 use hdk::prelude::*;
-use holo_hash::{DnaHash, HOLO_HASH_FULL_LEN};
+use holo_hash::{DnaHash, EntryHashB64, HOLO_HASH_FULL_LEN};
 use hdk_records::{
     identities::calculate_identity_address,
     rpc::call_local_zome_method,
 };
-use hdk_time_indexing::{ index_entry };
+use hdk_time_indexing::{ index_entry, reindex_entry, tombstone_entry, TimeIndexingError };
 pub use hdk_semantic_indexes_error::*;
 pub use hdk_time_indexing::{
     TimeIndex,
     read_all_entry_hashes,
-    // get_latest_entry_hashes,
-    // get_older_entry_hashes,
+    get_latest_entry_hashes,
+    get_earliest_entry_hashes,
+    get_older_entry_hashes,
+    get_newer_entry_hashes,
+    has_newer_entries,
+    has_older_entries,
+    get_entry_hashes_between,
+    get_earliest_entry_hashes_between,
+    get_older_entry_hashes_between,
+    get_newer_entry_hashes_between,
     sort_entries_by_time_index,
 };
 pub use hdk_records::{
@@ -33,6 +42,8 @@ pub const RECORD_GLOBAL_INDEX_LINK_TAG: &'static [u8] = b"all_entries";
 
 pub const RECORD_IDENTITY_LINK_TAG: &'static [u8] = b"id|"; // :WARNING: byte length is important here. @see read_remote_entry_identity
 
+pub const RECORD_TOMBSTONE_LINK_TAG: &'static [u8] = b"deleted|";
+
 //--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
 
 /// Configuration attributes from indexing zomes which link to records in other zomes
@@ -64,7 +75,9 @@ pub fn read_index<'a, O, A, S, I>(
         SerializedBytes: TryInto<O, Error = SerializedBytesError>,
 {
     let index_address = calculate_identity_address(base_address)?;
-    let mut refd_index_addresses = get_linked_addresses(&index_address, LinkTag::new(link_tag.as_ref()))?;
+    let mut refd_index_addresses = without_tombstoned_identities(
+        get_linked_addresses(&index_address, LinkTag::new(link_tag.as_ref()))?
+    )?;
     refd_index_addresses.sort_by(sort_entries_by_time_index(order_by_time_index));
 
     let (existing_link_results, read_errors): (Vec<RecordAPIResult<O>>, Vec<RecordAPIResult<O>>) = refd_index_addresses.iter()
@@ -101,9 +114,54 @@ pub fn query_index<'a, T, O, C, F, A, S, I, J>(
         C: std::fmt::Debug,
         SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<O, Error = SerializedBytesError>,
         F: Fn(C) -> Option<String>,
+{
+    let addrs_result = query_index_identities(base_address, link_tag)?;
+
+    query_identities::<T, O, C, F, I, J>(
+        &addrs_result,
+        order_by_time_index,
+        foreign_zome_name_from_config,
+        foreign_read_method_name,
+    )
+}
+
+/// Given a base address to query from, returns the identity `EntryHash`es of all records
+/// referenced via the given link tag, without retrieving the records themselves.
+///
+/// Use this method to build up sets of matching records for combining multiple query
+/// conditions with `intersect_identities`, `union_identities` & `subtract_identities`;
+/// then load the final results with `query_identities`.
+///
+pub fn query_index_identities<'a, A, S>(
+    base_address: &A,
+    link_tag: &S,
+) -> RecordAPIResult<Vec<EntryHash>>
+    where S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
 {
     let index_address = calculate_identity_address(base_address)?;
-    let mut addrs_result = get_linked_addresses(&index_address, LinkTag::new(link_tag.as_ref()))?;
+    without_tombstoned_identities(get_linked_addresses(&index_address, LinkTag::new(link_tag.as_ref()))?)
+}
+
+/// Loads the full records referenced by a set of identity `EntryHash`es from the
+/// associated foreign CRUD zome, sorted in reverse order of their position in
+/// the `order_by_time_index` time index.
+///
+pub fn query_identities<'a, T, O, C, F, I, J>(
+    identities: &[EntryHash],
+    order_by_time_index: &I,
+    foreign_zome_name_from_config: &F,
+    foreign_read_method_name: &J,
+) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
+    where I: AsRef<str> + std::fmt::Debug,
+        J: AsRef<str>,
+        O: DnaAddressable<EntryHash>,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<O, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    let mut addrs_result = identities.to_vec();
     addrs_result.sort_by(sort_entries_by_time_index(order_by_time_index));
 
     let entries = retrieve_foreign_records::<T, O, C, F, J>(
@@ -114,11 +172,63 @@ pub fn query_index<'a, T, O, C, F, A, S, I, J>(
     Ok(entries)
 }
 
+/// Returns all identities present in both `a` and `b`, in the order they appear in `a`.
+///
+pub fn intersect_identities(a: &[EntryHash], b: &[EntryHash]) -> Vec<EntryHash> {
+    let other: HashSet<&EntryHash> = b.iter().collect();
+    dedupe_identities(a.iter().filter(|id| other.contains(id)))
+}
+
+/// Returns all identities present in either `a` or `b`, without duplicates.
+///
+pub fn union_identities(a: &[EntryHash], b: &[EntryHash]) -> Vec<EntryHash> {
+    dedupe_identities(a.iter().chain(b.iter()))
+}
+
+/// Returns all identities present in `a` which are not present in `b`.
+///
+pub fn subtract_identities(a: &[EntryHash], b: &[EntryHash]) -> Vec<EntryHash> {
+    let other: HashSet<&EntryHash> = b.iter().collect();
+    dedupe_identities(a.iter().filter(|id| !other.contains(id)))
+}
+
+fn dedupe_identities<'a, I>(ids: I) -> Vec<EntryHash>
+    where I: Iterator<Item = &'a EntryHash>,
+{
+    let mut seen: HashSet<&EntryHash> = HashSet::new();
+    ids.filter(|id| seen.insert(*id))
+        .cloned()
+        .collect()
+}
+
+/// Position and size of a page of results to read from a time-ordered index.
+///
+/// Mirrors the argument semantics of Relay's connections spec, where the list is ordered
+/// from most recent to oldest.
+/// @see https://relay.dev/graphql/connections.htm
+///
+#[derive(Debug, Clone)]
+pub enum TimeIndexPage {
+    // read `limit` records older than `after`, or the most recent records if no cursor is given
+    Forward { after: Option<EntryHash>, limit: usize },
+    // read `limit` records newer than `before`, or the earliest records if no cursor is given
+    Backward { before: Option<EntryHash>, limit: usize },
+}
+
+/// A page of results read from a time-ordered index, along with flags
+/// indicating whether further results are available on either side of it.
+///
+#[derive(Debug)]
+pub struct TimeIndexPageResults<T> {
+    pub results: Vec<RecordAPIResult<T>>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+}
+
 /// Query foreign entries pointers from a time-ordered index, in order from most recent to oldest.
 ///
-/// If `start_from` is provided, the given `EntryHash` is used to determine the starting location
-/// for reading results. Otherwise the newest entries (as determined by their ordering in the time
-/// index) are returned.
+/// The section of the index to read is determined by `page`. Cursors are the `EntryHash`es of
+/// previously returned records; the cursor record itself is not included in the results.
 ///
 /// Full entry data is returned by querying from the associated record storage zome determined by
 /// `zome_name_from_config` and `read_method_name`.
@@ -127,9 +237,8 @@ pub fn query_time_index<'a, T, B, C, F, I>(
     zome_name_from_config: &'a F,
     read_method_name: &I,
     index_name: &I,
-    _start_from: Option<EntryHash>,
-    _limit: usize,
-) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
+    page: TimeIndexPage,
+) -> RecordAPIResult<TimeIndexPageResults<T>>
     where T: serde::de::DeserializeOwned + std::fmt::Debug,
         B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
         I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
@@ -137,25 +246,139 @@ pub fn query_time_index<'a, T, B, C, F, I>(
         SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
         F: Fn(C) -> Option<String>,
 {
-    // this algorithm is the 'make it work' current pass, pending the full implementation mentioned
-    // in the TODO below, regarding efficiency and completeness
-    let linked_records = read_all_entry_hashes(index_name)
+    let (linked_records, has_previous_page, has_next_page) = read_time_index_page(index_name, page, None)
         .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
 
-    // :TODO: efficient paginated retrieval
-    // let linked_records = match start_from {
-    //     None => get_latest_entry_hashes(index_name, limit),
-    //     Some(cursor) => get_older_entry_hashes(index_name, cursor, limit),
-    // }.map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, read_method_name);
+
+    Ok(TimeIndexPageResults {
+        results: linked_records.iter()
+            .map(|addr| {
+                // query full record from the associated CRUD zome
+                read_single_record(addr)
+            })
+            .collect(),
+        has_previous_page,
+        has_next_page,
+    })
+}
+
+/// Query foreign entries pointers indexed in a time-ordered index at times from `from` (inclusive)
+/// to `to` (exclusive), in order from most recent to oldest.
+///
+/// The section of the range to read is determined by `page`, as for `query_time_index`.
+///
+/// Full entry data is returned by querying from the associated record storage zome determined by
+/// `zome_name_from_config` and `read_method_name`.
+///
+pub fn query_time_index_between<'a, T, B, C, F, I>(
+    zome_name_from_config: &'a F,
+    read_method_name: &I,
+    index_name: &I,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    page: TimeIndexPage,
+) -> RecordAPIResult<TimeIndexPageResults<T>>
+    where T: serde::de::DeserializeOwned + std::fmt::Debug,
+        B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    let (linked_records, has_previous_page, has_next_page) = read_time_index_page(index_name, page, Some((from, to)))
+        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
 
     let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, read_method_name);
 
-    Ok(linked_records.iter()
-        .map(|addr| {
-            // query full record from the associated CRUD zome
-            read_single_record(addr)
-        })
-        .collect())
+    Ok(TimeIndexPageResults {
+        results: linked_records.iter()
+            .map(|addr| read_single_record(addr))
+            .collect(),
+        has_previous_page,
+        has_next_page,
+    })
+}
+
+/// Read the `EntryHash`es for a page of a time-ordered index, optionally restricted to those indexed
+/// within `range`, along with flags indicating whether there are previous and next pages.
+///
+fn read_time_index_page<I>(
+    index_name: &I,
+    page: TimeIndexPage,
+    range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Result<(Vec<EntryHash>, bool, bool), TimeIndexingError>
+    where I: AsRef<str>,
+{
+    let latest = |limit: usize| match range {
+        None => get_latest_entry_hashes(index_name, limit),
+        Some((from, to)) => get_entry_hashes_between(index_name, from, to, limit),
+    };
+    let earliest = |limit: usize| match range {
+        None => get_earliest_entry_hashes(index_name, limit),
+        Some((from, to)) => get_earliest_entry_hashes_between(index_name, from, to, limit),
+    };
+    let older = |cursor: EntryHash, limit: usize| match range {
+        None => get_older_entry_hashes(index_name, cursor, limit),
+        Some((from, to)) => get_older_entry_hashes_between(index_name, cursor, from, to, limit),
+    };
+    let newer = |cursor: EntryHash, limit: usize| match range {
+        None => get_newer_entry_hashes(index_name, cursor, limit),
+        Some((from, to)) => get_newer_entry_hashes_between(index_name, cursor, from, to, limit),
+    };
+
+    // read one more result than requested in order to determine whether there are further pages
+    // in the direction of reading. Pages in the opposite direction exist if any records are indexed
+    // beyond the page's nearest result, or anywhere in the index should the page be empty.
+    Ok(match page {
+        TimeIndexPage::Forward { after, limit } => {
+            let mut hashes = match &after {
+                None => latest(limit + 1),
+                Some(cursor) => older(cursor.to_owned(), limit + 1),
+            }?;
+
+            let has_more = hashes.len() > limit;
+            hashes.truncate(limit);
+            let has_preceding = match (&after, hashes.first()) {
+                (None, _) => false,
+                (Some(_), Some(first)) => !newer(first.to_owned(), 1)?.is_empty(),
+                (Some(_), None) => !latest(1)?.is_empty(),
+            };
+            (hashes, has_preceding, has_more)
+        },
+        TimeIndexPage::Backward { before, limit } => {
+            let mut hashes = match &before {
+                None => earliest(limit + 1),
+                Some(cursor) => newer(cursor.to_owned(), limit + 1),
+            }?;
+
+            // results are ordered newest first, so the surplus record is at the start of the list
+            let has_more = hashes.len() > limit;
+            let hashes = if has_more { hashes.split_off(hashes.len() - limit) } else { hashes };
+            let has_following = match (&before, hashes.last()) {
+                (None, _) => false,
+                (Some(_), Some(last)) => !older(last.to_owned(), 1)?.is_empty(),
+                (Some(_), None) => !earliest(1)?.is_empty(),
+            };
+            (hashes, has_more, has_following)
+        },
+    })
+}
+
+/// Decode a pagination cursor, as output in the `cursor` field of list API edges, into
+/// the `EntryHash` of the referenced record for use with `query_time_index`.
+///
+/// Cursors are the string-encoded identifier of a record, as produced by the `Display`
+/// implementation of `DnaAddressable` types.
+///
+pub fn parse_time_index_cursor<S>(cursor: &S) -> RecordAPIResult<EntryHash>
+    where S: AsRef<str>,
+{
+    let entry_hash_str = cursor.as_ref().split(':').next().unwrap_or("");
+
+    Ok(EntryHashB64::from_b64_str(entry_hash_str)
+        .map_err(|_e| SemanticIndexError::BadCursorError(cursor.as_ref().to_string()))?
+        .into())
 }
 
 /// Fetches all referenced record entries found corresponding to the input
@@ -278,6 +501,71 @@ pub fn append_to_time_index<'a, A, I>(
     Ok(())
 }
 
+/// Removes an entry pointer from the time-ordered index `index_name` once the record
+/// it refers to has been deleted, and marks the record's identity as deleted so that
+/// any remaining references to it are skipped when reading indexes.
+///
+pub fn remove_from_time_index<'a, A, I>(
+    index_name: &I,
+    entry_address: &A,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display,
+{
+    let entry_hash: &EntryHash = entry_address.as_ref();
+
+    tombstone_entry(index_name, entry_hash.to_owned())
+        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+    link_if_not_linked(
+        entry_hash.to_owned(), entry_hash.to_owned(),
+        LinkTypes::EntryUUID, LinkTag::new(crate::RECORD_TOMBSTONE_LINK_TAG),
+    )?;
+
+    Ok(())
+}
+
+/// Moves an entry pointer to `timestamp` within the time-ordered index `index_name`, adding
+/// it to the index if not already present.
+///
+/// Use this for indexes of record attributes which may change as the record is updated.
+///
+pub fn move_in_time_index<'a, A, I>(
+    index_name: &I,
+    entry_address: &A,
+    timestamp: DateTime<Utc>,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display,
+{
+    let entry_hash: &EntryHash = entry_address.as_ref();
+
+    ensure_id_tag(entry_address)?;
+
+    reindex_entry(index_name, entry_hash.to_owned(), timestamp)
+        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+    Ok(())
+}
+
+/// Removes an entry pointer from the time-ordered index `index_name` without marking the
+/// record itself as deleted. Entries which are not present in the index are ignored.
+///
+pub fn clear_from_time_index<'a, A, I>(
+    index_name: &I,
+    entry_address: &A,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display,
+{
+    let entry_hash: &EntryHash = entry_address.as_ref();
+
+    match tombstone_entry(index_name, entry_hash.to_owned()) {
+        Ok(()) | Err(TimeIndexingError::NotIndexed(_, _)) => Ok(()),
+        Err(e) => Err(SemanticIndexError::BadTimeIndexError(e.to_string()).into()),
+    }
+}
+
 /// Creates a 'destination' query index used for following a link from some external record
 /// into records contained within the current DNA / zome.
 ///
@@ -496,6 +784,20 @@ fn link_if_not_linked(
     }
 }
 
+/// Filter out any identities of records which have been deleted.
+///
+/// @see remove_from_time_index
+///
+fn without_tombstoned_identities(identities: Vec<EntryHash>) -> RecordAPIResult<Vec<EntryHash>> {
+    let mut live = vec![];
+    for identity in identities {
+        if get_links(identity.to_owned(), LinkTypes::EntryUUID, Some(LinkTag::new(crate::RECORD_TOMBSTONE_LINK_TAG)))?.is_empty() {
+            live.push(identity);
+        }
+    }
+    Ok(live)
+}
+
 /// Given an identity `EntryHash` (ie. the result of `calculate_identity_address`),
 /// query the `DnaHash` and `AnyDhtHash` of the record.
 ///
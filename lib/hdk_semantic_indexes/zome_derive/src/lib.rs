@@ -35,6 +35,10 @@ struct MacroArgs {
     // retrieve associated records. Useful for record types with nonstandard (non-`DnaAddressable`) identifiers.
     #[darling(default)]
     record_read_fn_name: Option<String>,
+    // Additional time-ordered indexes to maintain for the record type, besides its creation time.
+    // May be specified multiple times. Each is populated via `record_{record_type}_{time_index}`.
+    #[darling(multiple)]
+    time_index: Vec<String>,
 }
 
 #[proc_macro_attribute]
@@ -58,7 +62,7 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
 
     let record_type_index_attribute = format_ident!("{}_index", record_type_str_attribute);
     let record_read_api_method_name = match &args.record_read_fn_name {
-        None => format_ident!("get_{}", record_type_str_attribute),
+        None => format_ident!("__internal_get_{}_summary", record_type_str_attribute),
         Some(read_fn) => format_ident!("{}", read_fn),
     };
 
@@ -70,10 +74,37 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
         None => format_ident!("read_all_{}s", record_type_str_attribute),
         Some(read_fn) => format_ident!("{}", read_fn),
     };
+    let exposed_read_between_api_method_name = format_ident!("{}_between", exposed_read_api_method_name);
     let exposed_append_api_name = format_ident!("record_new_{}", record_type_str_attribute);
+    let exposed_remove_api_name = format_ident!("record_deleted_{}", record_type_str_attribute);
     let creation_time_index_name = [record_type_str_attribute.clone(), ".created".to_string()].concat();
     let record_index_field_type = format_ident!("{}Address", record_type.to_string().to_case(Case::UpperCamel));
 
+    // build identifiers for any additional named time indexes
+    let time_index_names: Vec<String> = args.time_index.iter()
+        .map(|name| name.to_case(Case::Snake))
+        .collect();
+    let time_index_ids: Vec<String> = time_index_names.iter()
+        .map(|name| format!("{}.{}", record_type_str_attribute, name))
+        .collect();
+    let time_index_mutators = time_index_names.iter().zip(time_index_ids.iter())
+        .map(|(name, index_id)| {
+            let record_time_api_name = format_ident!("record_{}_{}", record_type_str_attribute, name);
+            let clear_time_api_name = format_ident!("record_{}_{}_cleared", record_type_str_attribute, name);
+
+            quote! {
+                #[hdk_extern]
+                fn #record_time_api_name(AppendAddress { address, timestamp }: AppendAddress<#record_index_field_type>) -> ExternResult<()> {
+                    Ok(move_in_time_index(&#index_id, &address, timestamp)?)
+                }
+
+                #[hdk_extern]
+                fn #clear_time_api_name(ByAddress { address }: ByAddress<#record_index_field_type>) -> ExternResult<()> {
+                    Ok(clear_from_time_index(&#index_id, &address)?)
+                }
+            }
+        });
+
     // build iterators for generating index update methods and query conditions
     let all_indexes = fields.iter()
         .map(|field| {
@@ -199,13 +230,10 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
                                 let index_anchor_path = Path::from(#query_field_ident);
                                 let index_anchor_id: #related_index_field_type = DnaAddressable::new(dna_info()?.hash, index_anchor_path.path_entry_hash()?);
 
-                                entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
+                                matched_sets.push(query_index_identities(
                                     &index_anchor_id,
                                     &stringify!(#reciprocal_index_name),
-                                    &LOCAL_TIME_INDEX_ID,
-                                    &read_index_target_zome,
-                                    &QUERY_FN_NAME,
-                                );
+                                )?);
                             },
                             _ => (),
                         };
@@ -216,13 +244,10 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
                 None => quote! {
                     match &params.#query_field_ident {
                         Some(#query_field_ident) => {
-                            entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
+                            matched_sets.push(query_index_identities(
                                 #query_field_ident,
                                 &stringify!(#reciprocal_index_name),
-                                &LOCAL_TIME_INDEX_ID,
-                                &read_index_target_zome,
-                                &QUERY_FN_NAME,
-                            );
+                            )?);
                         },
                         _ => (),
                     };
@@ -248,16 +273,41 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             Some(conf.#record_type_index_attribute.record_storage_zome)
         }
 
-        // define struct to wrap query parameter inputs, so that other meta-args (eg. pagination) can be added later
+        // define struct to wrap query parameter inputs, so that other meta-args (eg. pagination) can be added later.
+        // All fields provided in `params` must match. Nested conditions can be composed via boolean combinators:
+        // records must also match every condition in `and`, at least one condition in `or` and must not match `not`.
         #[derive(Debug, Serialize, Deserialize)]
         struct SearchInputs {
-            pub params: QueryParams,
+            #[serde(default)]
+            pub params: Option<QueryParams>,
+            #[serde(default)]
+            pub and: Vec<SearchInputs>,
+            #[serde(default)]
+            pub or: Vec<SearchInputs>,
+            #[serde(default)]
+            pub not: Option<Box<SearchInputs>>,
         }
 
         // define zome API function name to read indexed records
         const QUERY_FN_NAME: &str = stringify!(#record_read_api_method_name);
         const LOCAL_TIME_INDEX_ID: &str = #creation_time_index_name;
 
+        // additional time indexes which list APIs may be ordered by, as `(name, index ID)`
+        const NAMED_TIME_INDEXES: &[(&str, &str)] = &[
+            #( (#time_index_names, #time_index_ids), )*
+        ];
+
+        // determine the time index to read from list APIs, defaulting to creation time
+        fn resolve_time_index(order_by: &Option<String>) -> RecordAPIResult<&'static str> {
+            match order_by.as_deref() {
+                None | Some("created") => Ok(LOCAL_TIME_INDEX_ID),
+                Some(name) => NAMED_TIME_INDEXES.iter()
+                    .find(|(index_name, _)| *index_name == name)
+                    .map(|(_, index_id)| *index_id)
+                    .ok_or(SemanticIndexError::UnknownTimeIndex(name.to_string()).into()),
+            }
+        }
+
         // pagination constants
         const PAGE_SIZE: usize = 30;
 
@@ -271,17 +321,23 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             #index_mutators
         )*
 
+        // public zome API for updating additional time indexes when associated records change
+        #(
+            #time_index_mutators
+        )*
+
         // query input parameters mimicing Relay's pagination spec
         // @see https://relay.dev/graphql/connections.htm
         // :TODO: extend to allow for filtering with `QueryParams`
         #[derive(Debug, Serialize, Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct PagingParams {
-            // :TODO: forwards pagination
-            // first: Option<usize>,
-            // after: Option<EntryHash>,
+            first: Option<usize>,
+            after: Option<String>,
             last: Option<usize>,
-            before: Option<EntryHash>,
+            before: Option<String>,
+            // name of the time index to order results by, defaulting to creation time
+            order_by: Option<String>,
         }
 
         // query results structure mimicing Relay's pagination format
@@ -305,18 +361,66 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
 
         // declare public list API
         #[hdk_extern]
-        fn #exposed_read_api_method_name(PagingParams { /*first, after,*/ last, before }: PagingParams) -> ExternResult<QueryResults> {
-            let mut entries_result: RecordAPIResult<Vec<RecordAPIResult<ResponseData>>> = Err(SemanticIndexError::EmptyQuery.into());
+        fn #exposed_read_api_method_name(PagingParams { first, after, last, before, order_by }: PagingParams) -> ExternResult<QueryResults> {
+            let time_index_id = resolve_time_index(&order_by)?;
+            let (page, page_limit) = read_page_position(first, after, last, before)?;
 
-            entries_result = query_time_index::<ResponseData, #record_index_field_type,_,_,_>(
+            let TimeIndexPageResults { results, has_previous_page, has_next_page } = query_time_index::<ResponseData, #record_index_field_type,_,_,_>(
                 &read_index_target_zome,
                 &QUERY_FN_NAME,
-                &LOCAL_TIME_INDEX_ID,
-                before,
-                last.unwrap_or(PAGE_SIZE),
-            );
+                &time_index_id,
+                page,
+            )?;
 
-            Ok(handle_list_output(entries_result?.as_slice())?)
+            let mut output = handle_list_output(results.as_slice())?;
+            output.page_info.has_previous_page = has_previous_page;
+            output.page_info.has_next_page = has_next_page;
+            output.page_info.page_limit = Some(page_limit);
+
+            Ok(output)
+        }
+
+        // declare public API for listing records indexed within a range of times
+        #[hdk_extern]
+        fn #exposed_read_between_api_method_name(TimeRangeParams { from, to, first, after, last, before, order_by }: TimeRangeParams) -> ExternResult<QueryResults> {
+            let time_index_id = resolve_time_index(&order_by)?;
+            let (page, page_limit) = read_page_position(first, after, last, before)?;
+
+            let TimeIndexPageResults { results, has_previous_page, has_next_page } = query_time_index_between::<ResponseData, #record_index_field_type,_,_,_>(
+                &read_index_target_zome,
+                &QUERY_FN_NAME,
+                &time_index_id,
+                from, to,
+                page,
+            )?;
+
+            let mut output = handle_list_output(results.as_slice())?;
+            output.page_info.has_previous_page = has_previous_page;
+            output.page_info.has_next_page = has_next_page;
+            output.page_info.page_limit = Some(page_limit);
+
+            Ok(output)
+        }
+
+        // determine the page of a time index to read from Relay-style pagination arguments
+        fn read_page_position(
+            first: Option<usize>, after: Option<String>,
+            last: Option<usize>, before: Option<String>,
+        ) -> RecordAPIResult<(TimeIndexPage, usize)> {
+            // backwards pagination only applies when requested without any forwards pagination arguments
+            Ok(if first.is_none() && after.is_none() && (last.is_some() || before.is_some()) {
+                let limit = last.unwrap_or(PAGE_SIZE);
+                (TimeIndexPage::Backward {
+                    before: before.map(|c| parse_time_index_cursor(&c)).transpose()?,
+                    limit,
+                }, limit)
+            } else {
+                let limit = first.unwrap_or(PAGE_SIZE);
+                (TimeIndexPage::Forward {
+                    after: after.map(|c| parse_time_index_cursor(&c)).transpose()?,
+                    limit,
+                }, limit)
+            })
         }
 
         // declare API for global list API management
@@ -325,20 +429,82 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             Ok(append_to_time_index(&LOCAL_TIME_INDEX_ID, &address, timestamp)?)
         }
 
+        #[hdk_extern]
+        fn #exposed_remove_api_name(ByAddress { address }: ByAddress<#record_index_field_type>) -> ExternResult<()> {
+            remove_from_time_index(&LOCAL_TIME_INDEX_ID, &address)?;
+            for (_, index_id) in NAMED_TIME_INDEXES.iter() {
+                clear_from_time_index(index_id, &address)?;
+            }
+            Ok(())
+        }
+
         // declare public query method with injected handler logic
         #[hdk_extern]
-        fn #exposed_query_api_method_name(SearchInputs { params }: SearchInputs) -> ExternResult<QueryResults>
+        fn #exposed_query_api_method_name(search: SearchInputs) -> ExternResult<QueryResults>
         {
-            let mut entries_result: RecordAPIResult<Vec<RecordAPIResult<ResponseData>>> = Err(SemanticIndexError::EmptyQuery.into());
+            let matching_identities: RecordAPIResult<Vec<EntryHash>> = resolve_search_identities(&search)?
+                .ok_or(SemanticIndexError::EmptyQuery.into());
 
-            // :TODO: proper search combinator logic, this just does exclusive boolean ops
-            #(
-                #query_handlers
-            )*
+            let entries_result = query_identities::<ResponseData, #record_index_field_type, _,_,_,_>(
+                matching_identities?.as_slice(),
+                &LOCAL_TIME_INDEX_ID,
+                &read_index_target_zome,
+                &QUERY_FN_NAME,
+            );
 
             Ok(handle_list_output(entries_result?.as_slice())?)
         }
 
+        // Determine the identities of all records matching some `SearchInputs`, prior to loading them.
+        // Returns `None` if the inputs place no constraints on the results.
+        fn resolve_search_identities(search: &SearchInputs) -> RecordAPIResult<Option<Vec<EntryHash>>>
+        {
+            let mut matched_sets: Vec<Vec<EntryHash>> = vec![];
+
+            if let Some(params) = &search.params {
+                #(
+                    #query_handlers
+                )*
+            }
+
+            for condition in search.and.iter() {
+                if let Some(ids) = resolve_search_identities(condition)? {
+                    matched_sets.push(ids);
+                }
+            }
+
+            if !search.or.is_empty() {
+                let mut any_matched: Option<Vec<EntryHash>> = Some(vec![]);
+                for condition in search.or.iter() {
+                    any_matched = match (any_matched, resolve_search_identities(condition)?) {
+                        (Some(matched), Some(ids)) => Some(union_identities(matched.as_slice(), ids.as_slice())),
+                        // an unconstrained alternative matches everything
+                        _ => None,
+                    };
+                }
+                if let Some(ids) = any_matched {
+                    matched_sets.push(ids);
+                }
+            }
+
+            let mut result = matched_sets.into_iter()
+                .reduce(|matched, ids| intersect_identities(matched.as_slice(), ids.as_slice()));
+
+            if let Some(excluded) = &search.not {
+                if let Some(excluded_ids) = resolve_search_identities(excluded)? {
+                    // exclusions on their own are applied against the full set of records
+                    let included = match result {
+                        Some(ids) => ids,
+                        None => read_all_entry_hashes(&LOCAL_TIME_INDEX_ID)
+                            .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?,
+                    };
+                    result = Some(subtract_identities(included.as_slice(), excluded_ids.as_slice()));
+                }
+            }
+
+            Ok(result)
+        }
+
         fn handle_list_output(entries: &[RecordAPIResult<ResponseData>]) -> RecordAPIResult<QueryResults>
         {
             let valid_edges = entries.iter()
@@ -367,9 +533,9 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
                 page_info: PageInfo {
                     end_cursor: edge_cursors.last().unwrap_or(first_cursor.clone()),
                     start_cursor: first_cursor,
-                    // :TODO:
-                    has_next_page: true,
-                    has_previous_page: true,
+                    // unpaginated results are always complete
+                    has_next_page: false,
+                    has_previous_page: false,
                     page_limit: None,
                     total_count: None,
                 },
@@ -70,6 +70,18 @@ impl IndexSegment {
         ].concat())
     }
 
+    /// Generate a `LinkTag` for marking entries linked from this (leafmost) segment as removed
+    /// from the index tree rooted at `index_name`.
+    ///
+    pub fn tombstone_tag_for_index<I>(index_name: &I) -> LinkTag
+        where I: AsRef<str>,
+    {
+        LinkTag::new([
+            index_name.as_ref().as_bytes(), // prefix with index ID
+            &[0x1 as u8],                   // tombstone separator, distinct from that of index nodes
+        ].concat())
+    }
+
     /// What is the hash for the current [ `IndexSegment` ]?
     pub fn hash(&self) -> TimeIndexResult<EntryHash> {
         Ok(hash_entry(self.to_owned())?)
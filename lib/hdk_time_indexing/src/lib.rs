@@ -42,11 +42,19 @@ pub mod reading;
 pub mod sorting;
 
 pub use index_tree::IndexSegment as TimeIndex;
-pub use writing::index_entry;
+pub use writing::{index_entry, reindex_entry, tombstone_entry};
 pub use reading::{
     read_all_entry_hashes,
     get_latest_entry_hashes,
+    get_earliest_entry_hashes,
     get_older_entry_hashes,
+    get_newer_entry_hashes,
+    has_newer_entries,
+    has_older_entries,
+    get_entry_hashes_between,
+    get_earliest_entry_hashes_between,
+    get_older_entry_hashes_between,
+    get_newer_entry_hashes_between,
 };
 pub use sorting::sort_entries_by_time_index;
 
@@ -1,4 +1,5 @@
-use chrono::{DateTime, Utc, Duration};
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
 use hdk::prelude::*;
 use crate::{
     INDEX_DEPTH, HAS_CHUNK_LEAVES,
@@ -15,283 +16,412 @@ use hdk_semantic_indexes_core::LinkTypes;
 pub fn read_all_entry_hashes<I>(index_name: &I) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    let root_hash = get_root_hash(index_name)?;
+    read_ordered_entry_hashes(index_name, ReadDirection::Older, None, usize::MAX)
+}
 
-    match root_hash {
-        None => Ok(vec![]),
-        Some(hash) => {
-            Ok(collect_leaf_index_hashes(index_name, hash, (*INDEX_DEPTH).len() as isize)?)
-        }
-    }
+/**
+ * Retrieve the most recent entry hashes stored in the `index_name` time-ordered index,
+ * up to a maximum of `limit`. Results are ordered from most recent to oldest.
+ */
+pub fn get_latest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    read_ordered_entry_hashes(index_name, ReadDirection::Older, None, limit)
 }
 
-/// Recursively performs a depth-first traversal of the specified time index tree, returning the `EntryHash`es
-/// of all the leafmost nodes (i.e. indexed entries) present in the index.
-///
-fn collect_leaf_index_hashes<I>(index_name: &I, context_hash: EntryHash, context_depth: isize) -> TimeIndexResult<Vec<EntryHash>>
+/**
+ * Retrieve the earliest entry hashes stored in the `index_name` time-ordered index,
+ * up to a maximum of `limit`. Results are ordered from most recent to oldest.
+ */
+pub fn get_earliest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    let children = get_ordered_child_links_of_node(
-        index_name,
-        context_hash.clone(),
-    )?;
+    let mut results = read_ordered_entry_hashes(index_name, ReadDirection::Newer, None, limit)?;
+    results.reverse();
+    Ok(results)
+}
 
-    // last hop outside the index tree links to the targeted nodes, so return them
-    if (*HAS_CHUNK_LEAVES && context_depth == -1) || (!(*HAS_CHUNK_LEAVES) && context_depth == 0) {
-        return Ok(children.iter()
-            .map(|link| EntryHash::from(link.target.to_owned()))
-            .collect());
-    }
+/**
+ * Retrieve entry hashes indexed in the `index_name` time-ordered index immediately
+ * before `before_entry` (not inclusive), up to a maximum of `limit`. Results are ordered
+ * from most recent to oldest.
+ *
+ * This method is best used with cursor-based pagination, where the previously oldest
+ * returned `EntryHash` is used as a cursor to return the next most recent page of entries.
+ */
+pub fn get_older_entry_hashes<I>(index_name: &I, before_entry: EntryHash, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    let cursor = get_cursor_position(index_name, before_entry)?;
+    read_ordered_entry_hashes(index_name, ReadDirection::Older, Some(cursor), limit)
+}
 
-    // still recursing downwards- load descendent nodes for every child found
-    let (descendents, errors): (Vec<TimeIndexResult<Vec<EntryHash>>>, Vec<TimeIndexResult<Vec<EntryHash>>>) = children.iter()
-        .map(|link| {
-            collect_leaf_index_hashes(index_name, EntryHash::from(link.target.to_owned()), context_depth - 1)
-        })
-        .partition(Result::is_ok);
+/**
+ * Retrieve entry hashes indexed in the `index_name` time-ordered index immediately
+ * after `after_entry` (not inclusive), up to a maximum of `limit`. Results are ordered
+ * from most recent to oldest, so that pages read backwards through an index can be
+ * concatenated with those read via `get_older_entry_hashes`.
+ */
+pub fn get_newer_entry_hashes<I>(index_name: &I, after_entry: EntryHash, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    let cursor = get_cursor_position(index_name, after_entry)?;
+    let mut results = read_ordered_entry_hashes(index_name, ReadDirection::Newer, Some(cursor), limit)?;
+    results.reverse();
+    Ok(results)
+}
 
-    // return any errors fetching children if encountered
-    if errors.len() > 0 {
-        return errors.first().unwrap().to_owned();
-    }
+/**
+ * Determine whether any entries are indexed in the `index_name` time-ordered index more
+ * recently than `entry_hash`. Used to determine whether a page of results read via
+ * `get_older_entry_hashes` is preceded by any others.
+ */
+pub fn has_newer_entries<I>(index_name: &I, entry_hash: EntryHash) -> TimeIndexResult<bool>
+    where I: AsRef<str>,
+{
+    Ok(!get_newer_entry_hashes(index_name, entry_hash, 1)?.is_empty())
+}
 
-    // flatten the descendent nodes into a single vector
-    Ok(descendents.iter().cloned()
-        .flat_map(Result::unwrap)
-        .collect())
+/**
+ * Determine whether any entries are indexed in the `index_name` time-ordered index earlier
+ * than `entry_hash`. Used to determine whether a page of results read via
+ * `get_newer_entry_hashes` is followed by any others.
+ */
+pub fn has_older_entries<I>(index_name: &I, entry_hash: EntryHash) -> TimeIndexResult<bool>
+    where I: AsRef<str>,
+{
+    Ok(!get_older_entry_hashes(index_name, entry_hash, 1)?.is_empty())
 }
 
 /**
- * Retrieve the most recent entry hashes stored in the `index_name` time-ordered index,
- * up to a maximum of `limit`.
+ * Retrieve entry hashes indexed in the `index_name` time-ordered index at times within
+ * the range `from` (inclusive) to `to` (exclusive), up to a maximum of `limit`. Results
+ * are ordered from most recent to oldest.
+ *
+ * Only those segments of the index tree which overlap the requested range are traversed.
  */
-pub fn get_latest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+pub fn get_entry_hashes_between<I>(index_name: &I, from: DateTime<Utc>, to: DateTime<Utc>, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    // find the most recently indexed EntryHash as a starting point
-    let most_recent = get_latest_indexed_entry_hash(index_name)?;
-
-    match most_recent {
-        None => Ok(vec![]),
-        Some(latest) => {
-            // load a page of links to entries immediately before the latest
-            let mut earlier_page = get_older_entry_hashes(index_name, latest.to_owned(), limit)?;
-
-            // remove the earliest linked entry
-            let num_earlier = earlier_page.len();
-            if num_earlier > 0 {
-                earlier_page.truncate(num_earlier - 1);
-            }
+    if from >= to {
+        return Ok(vec![]);
+    }
+    read_entry_hashes(index_name, ReadDirection::Older, None, Some((from, to)), limit)
+}
 
-            // prepend the most recent one
-            Ok([
-                vec![latest.to_owned()],
-                earlier_page,
-            ].concat())
-        },
+/**
+ * Retrieve the earliest entry hashes indexed in the `index_name` time-ordered index within
+ * the range `from` (inclusive) to `to` (exclusive), up to a maximum of `limit`. Results
+ * are ordered from most recent to oldest.
+ */
+pub fn get_earliest_entry_hashes_between<I>(index_name: &I, from: DateTime<Utc>, to: DateTime<Utc>, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    if from >= to {
+        return Ok(vec![]);
     }
+    let mut results = read_entry_hashes(index_name, ReadDirection::Newer, None, Some((from, to)), limit)?;
+    results.reverse();
+    Ok(results)
 }
 
 /**
- * Retrieve entry hashes indexed in the `index_name` time-ordered index immediately
- * before `before_entry` (not inclusive), up to a maximum of `limit`.
- *
- * This method is best used with cursor-based pagination, where the previously oldest
- * returned `EntryHash` is used as a cursor to return the next most recent page of entries.
+ * As with `get_older_entry_hashes`, excluding any entries indexed outside of the range
+ * `from` (inclusive) to `to` (exclusive).
  */
-pub fn get_older_entry_hashes<I>(index_name: &I, before_entry: EntryHash, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+pub fn get_older_entry_hashes_between<I>(index_name: &I, before_entry: EntryHash, from: DateTime<Utc>, to: DateTime<Utc>, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    Ok(get_ordered_links_before(index_name, before_entry, limit)?
-        .iter()
-        .map(|link| { EntryHash::from(link.target.to_owned()) })
-        .collect())
+    if from >= to {
+        return Ok(vec![]);
+    }
+    let cursor = get_cursor_position(index_name, before_entry)?;
+    read_entry_hashes(index_name, ReadDirection::Older, Some(cursor), Some((from, to)), limit)
 }
 
-/// Return a maximum of `limit` `Link`s, in order from most recent to oldest in `index_name`
-/// starting at the given (already indexed) `entry_hash`.
-///
-/// :TODO: account for the possibility that an entry might be validly linked multiple times in the same index
-/// :TODO: de-duplicate links to account for network partitions erroneously causing multiple writes of the same index
-///
-fn get_ordered_links_before<I>(index_name: &I, entry_hash: EntryHash, limit: usize) -> TimeIndexResult<Vec<Link>>
+/**
+ * As with `get_newer_entry_hashes`, excluding any entries indexed outside of the range
+ * `from` (inclusive) to `to` (exclusive).
+ */
+pub fn get_newer_entry_hashes_between<I>(index_name: &I, after_entry: EntryHash, from: DateTime<Utc>, to: DateTime<Utc>, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    // inspect link from entry to index in order to determine indexed time & parent node in index tree
-    let parents = get_links(
-        entry_hash.to_owned(),
-        LinkTypes::TimeIndex,
-        Some(link_prefix_for_index(index_name)),
-    )?;
-    let leaf_link = parents.first().ok_or(
-        TimeIndexingError::NotIndexed(index_name.as_ref().to_string(), entry_hash)
-    )?;
-    let this_index: IndexSegment = leaf_link.tag.to_owned().try_into()?;
-    let this_timestamp = this_index.into();
-
-    let leaf_hash = EntryHash::from(leaf_link.target.to_owned());
-
-    // find all our older siblings
-    let mut older_siblings = get_ordered_child_links_of_node_older_than(index_name, leaf_hash, this_timestamp)?;
-
-    // truncate to requested limit
-    older_siblings.truncate(limit);
-
-    // continue fetching links from previous leaf nodes in the index until we have reached the limit
-    let mut context_timestamp = this_timestamp;
-    while older_siblings.len() < limit {
-        let previous_leaf_link = get_previous_leaf(
-            index_name,
-            &context_timestamp,
-            // :NOTE: if there are chunk nodes in the tree, immediate parent will be at
-            //        first INDEX_DEPTH, otherwise it will be second tier.
-            if *HAS_CHUNK_LEAVES { 0 } else { 1 },
-            if *HAS_CHUNK_LEAVES { 0 } else { 1 },
-        )?;
-
-        match previous_leaf_link {
-            // stop looking if there are no more leaves to query
-            None => { break; },
-            Some(prev) => {
-                // append any found link targets from the previous leaf
-                let more_older_siblings = get_ordered_child_links_of_node_older_than(index_name, prev.hash()?, this_timestamp)?;
-                older_siblings = [older_siblings, more_older_siblings].concat();
-                older_siblings.truncate(limit);
-
-                // look further backward from previous leaf if we still need to find more
-                let prev_time: DateTime<Utc> = prev.into();
-                context_timestamp = prev_time + Duration::milliseconds(-1);
-            },
-        }
+    if from >= to {
+        return Ok(vec![]);
     }
+    let cursor = get_cursor_position(index_name, after_entry)?;
+    let mut results = read_entry_hashes(index_name, ReadDirection::Newer, Some(cursor), Some((from, to)), limit)?;
+    results.reverse();
+    Ok(results)
+}
 
-    Ok(older_siblings)
+/// Which way to walk the index tree when reading pages of results
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReadDirection {
+    Older,
+    Newer,
 }
 
-/// Locate all the child links of the node with hash `leaf_hash`, ordered from newest to oldest.
+/// Position of an indexed entry in the time index, used as a pagination cursor.
+/// The `EntryHash` is included so that entries indexed at identical times have a stable order.
+///
+type CursorPosition = (DateTime<Utc>, EntryHash);
+
+/// Bounds of a range of times to read from the time index, as `(from, to)`.
+/// The lower bound is inclusive and the upper bound exclusive.
+///
+type TimeRange = (DateTime<Utc>, DateTime<Utc>);
+
+/// Read up to `limit` entry hashes from `index_name`, walking the tree in the given `direction`
+/// and excluding any entries not strictly older / newer than `cursor`.
+///
+/// Results are returned in the order they were walked- nearest to the cursor first.
 ///
-fn get_ordered_child_links_of_node<I>(index_name: &I, leaf_hash: EntryHash) -> TimeIndexResult<Vec<Link>>
+fn read_ordered_entry_hashes<I>(index_name: &I, direction: ReadDirection, cursor: Option<CursorPosition>, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    // query children of parent node
-    let mut siblings = get_links(
-        leaf_hash,
-        LinkTypes::TimeIndex,
-        Some(link_prefix_for_index(index_name)),
-    )?;
+    read_entry_hashes(index_name, direction, cursor, None, limit)
+}
 
-    // order them from newest to oldest
-    siblings.sort_unstable_by(|a, b| b.tag.cmp(&a.tag));
+/// As with `read_ordered_entry_hashes`, additionally excluding any entries indexed outside of `range`.
+///
+fn read_entry_hashes<I>(index_name: &I, direction: ReadDirection, cursor: Option<CursorPosition>, range: Option<TimeRange>, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    let mut results = vec![];
+    if limit == 0 {
+        return Ok(results);
+    }
 
-    Ok(siblings)
+    match get_root_hash(index_name)? {
+        None => Ok(results),
+        Some(root) => {
+            collect_ordered_leaf_hashes(index_name, root, (*INDEX_DEPTH).len() as isize, direction, &cursor, &range, limit, &mut results)?;
+            Ok(results)
+        },
+    }
 }
 
-/// Locate all the child links of the node with hash `leaf_hash` which are older than `this_timestamp`,
-/// ordered from newest to oldest.
+/// Performs a depth-first traversal of the specified time index tree in order of `direction`,
+/// appending the `EntryHash`es of leafmost nodes (i.e. indexed entries) to `results` until
+/// `limit` is reached.
 ///
-fn get_ordered_child_links_of_node_older_than<I>(index_name: &I, leaf_hash: EntryHash, this_timestamp: DateTime<Utc>) -> TimeIndexResult<Vec<Link>>
+/// Branches of the tree which cannot contain entries beyond the `cursor`, or within the `range`,
+/// are not traversed.
+///
+fn collect_ordered_leaf_hashes<I>(
+    index_name: &I,
+    context_hash: EntryHash,
+    context_depth: isize,
+    direction: ReadDirection,
+    cursor: &Option<CursorPosition>,
+    range: &Option<TimeRange>,
+    limit: usize,
+    results: &mut Vec<EntryHash>,
+) -> TimeIndexResult<()>
     where I: AsRef<str>,
 {
-    // query children of parent node
-    let siblings = get_ordered_child_links_of_node(
-        index_name,
-        leaf_hash,
+    let mut links = get_links(
+        context_hash.to_owned(),
+        LinkTypes::TimeIndex,
+        Some(link_prefix_for_index(index_name)),
     )?;
 
-    // filter out those newer
-    Ok(siblings.iter().cloned().filter(|sib| {
-        let this_index: Result<IndexSegment, _> = sib.tag.to_owned().try_into();
-        match this_index {
-            Ok(time) => {
-                let dt: DateTime<Utc> = time.into();
-                dt < this_timestamp
-            },
-            Err(_e) => false,
+    // entries moved within the same leaf node are linked once per position; only the latest applies
+    links.sort_unstable_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    let mut seen_targets = HashSet::new();
+    links.retain(|link| seen_targets.insert(link.target.to_owned()));
+
+    let linked_at: HashMap<EntryHash, Timestamp> = links
+        .iter()
+        .map(|link| (EntryHash::from(link.target.to_owned()), link.timestamp))
+        .collect();
+
+    let mut children: Vec<(Option<DateTime<Utc>>, EntryHash)> = links
+        .iter()
+        .map(|link| (get_link_time(link), EntryHash::from(link.target.to_owned())))
+        .collect();
+
+    // order children from nearest to furthest in the direction of reading
+    children.sort_unstable_by(|a, b| match direction {
+        ReadDirection::Older => b.cmp(a),
+        ReadDirection::Newer => a.cmp(b),
+    });
+
+    // last hop outside the index tree links to the targeted nodes, so return them
+    if (*HAS_CHUNK_LEAVES && context_depth == -1) || (!(*HAS_CHUNK_LEAVES) && context_depth == 0) {
+        let tombstoned = get_tombstoned_hashes(index_name, context_hash)?;
+        for (time, hash) in children {
+            if results.len() >= limit {
+                break;
+            }
+            if is_tombstoned(&tombstoned, &hash, linked_at.get(&hash)) {
+                continue;
+            }
+            let is_in_range = match (cursor, time) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(cursor), Some(time)) => {
+                    let position = (time, hash.to_owned());
+                    match direction {
+                        ReadDirection::Older => position < *cursor,
+                        ReadDirection::Newer => position > *cursor,
+                    }
+                },
+            };
+            let is_in_bounds = match (range, time) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some((from, to)), Some(time)) => time >= *from && time < *to,
+            };
+            if is_in_range && is_in_bounds {
+                results.push(hash);
+            }
         }
-    }).collect())
+        return Ok(());
+    }
+
+    // still recursing downwards- load descendent nodes for every child not excluded by the cursor
+    for (idx, (time, hash)) in children.iter().enumerate() {
+        if results.len() >= limit {
+            break;
+        }
+        if let (Some(cursor), Some(time)) = (cursor, time) {
+            let can_skip = match direction {
+                // index segments are timestamped with the start of their period, so any which begin after
+                // the cursor contain only newer entries
+                ReadDirection::Older => *time > cursor.0,
+                // when reading forwards, a segment can be skipped if the next one begins at or before the cursor
+                ReadDirection::Newer => match children.get(idx + 1) {
+                    Some((Some(next_time), _)) => *next_time <= cursor.0,
+                    _ => false,
+                },
+            };
+            if can_skip {
+                continue;
+            }
+        }
+        if let (Some((from, to)), Some(time)) = (range, time) {
+            let next_time = match children.get(idx + 1) {
+                Some((next_time, _)) => *next_time,
+                None => None,
+            };
+            match direction {
+                ReadDirection::Older => {
+                    // segments beginning at or after the end of the range contain only later entries
+                    if *time >= *to {
+                        continue;
+                    }
+                    collect_ordered_leaf_hashes(index_name, hash.to_owned(), context_depth - 1, direction, cursor, range, limit, results)?;
+                    // all remaining segments end before this one began, and so before the range
+                    if *time <= *from {
+                        break;
+                    }
+                },
+                ReadDirection::Newer => {
+                    // segments beginning at or after the end of the range, and all following them, are excluded
+                    if *time >= *to {
+                        break;
+                    }
+                    // a segment ends before the range if the next one begins at or before its start
+                    if let Some(next_time) = next_time {
+                        if next_time <= *from {
+                            continue;
+                        }
+                    }
+                    collect_ordered_leaf_hashes(index_name, hash.to_owned(), context_depth - 1, direction, cursor, range, limit, results)?;
+                },
+            }
+            continue;
+        }
+        collect_ordered_leaf_hashes(index_name, hash.to_owned(), context_depth - 1, direction, cursor, range, limit, results)?;
+    }
+
+    Ok(())
 }
 
-/// Find the previous leafmost node in the time index tree
-/// :TODO: make this work
+/// Determine the indexed time of `entry_hash` within `index_name`, for use as a pagination offset.
 ///
-fn get_previous_leaf<I>(index_name: &I, from_timestamp: &DateTime<Utc>, try_depth: usize, starting_depth: usize) -> TimeIndexResult<Option<IndexSegment>>
+/// :TODO: account for the possibility that an entry might be validly linked multiple times in the same index
+///
+fn get_cursor_position<I>(index_name: &I, entry_hash: EntryHash) -> TimeIndexResult<CursorPosition>
     where I: AsRef<str>,
 {
-    let mut this_depth = try_depth;
-    while this_depth >= starting_depth {
-        let try_parent_hash = if this_depth >= INDEX_DEPTH.len() {
-            // can't go upwards anymore, so go to the root
-            Path::from(index_name.as_ref()).typed(LinkTypes::TimeIndex)?.path_entry_hash()?
-        } else {
-            // determine next parent upwards
-            IndexSegment::new(from_timestamp, INDEX_DEPTH.get(this_depth).unwrap()).hash()?
-        };
-
-        // find all nodes in the tree at this depth that are older than the starting offset
-        let older_siblings = get_ordered_child_links_of_node_older_than(index_name, try_parent_hash, from_timestamp.to_owned())?;
-
-        match older_siblings.first() {
-            // there are no older siblings in this list
-            None => {
-                if this_depth >= INDEX_DEPTH.len() {
-                    // if we are at the root, there are no more index links to try
-                    return Ok(None);
-                } else {
-                    // if we are not yet at the root, go up a level and try reading more links
-                    this_depth += 1;
-                    continue;
-                }
-            },
-            // if there was an older sibling, come back down to the earliest leaf node
-            Some(link) => {
-                let _found_at_this_depth: IndexSegment = link.tag.to_owned().try_into()?;
-                while this_depth >= starting_depth {
-                    // :TODO: finish this, or scrap it and start again
-
-                    this_depth -= 1;
-                }
-                // this isn't what we want...
-                return Ok(Some(IndexSegment::new(from_timestamp, INDEX_DEPTH.get(starting_depth).unwrap())))
-            },
-        }
-    }
+    // inspect link from entry to index in order to determine indexed time
+    let parents = get_links(
+        entry_hash.to_owned(),
+        LinkTypes::TimeIndex,
+        Some(link_prefix_for_index(index_name)),
+    )?;
+    // the most recent link reflects the entry's current position, should it have been reindexed
+    let leaf_link = parents.iter().max_by_key(|l| l.timestamp).ok_or(
+        TimeIndexingError::NotIndexed(index_name.as_ref().to_string(), entry_hash.to_owned())
+    )?;
+    let this_index: IndexSegment = leaf_link.tag.to_owned().try_into()?;
 
-    Ok(None)    // also probably wrong
+    Ok((this_index.into(), entry_hash))
 }
 
-/// Determine the hash of the most recent `IndexSegment` written for the given `index_name`.
-/// If the index has not been created yet, `None` is returned in the result.
+/// Determine which entries linked from the leaf node `leaf_hash` have been removed from `index_name`,
+/// along with the time of the most recent removal of each.
+///
+/// @see crate::writing::tombstone_entry
 ///
-fn get_latest_indexed_entry_hash<I>(index_name: &I) -> TimeIndexResult<Option<EntryHash>>
+pub (crate) fn get_tombstoned_hashes<I>(index_name: &I, leaf_hash: EntryHash) -> TimeIndexResult<HashMap<EntryHash, Timestamp>>
     where I: AsRef<str>,
 {
-    match get_root_hash(index_name)? {
-        None => Ok(None),
-        // recurse into the tree, returning leafmost node
-        Some(hash) => Ok(Some(get_newest_leafmost_hash(hash, index_name)?)),
+    let mut tombstoned: HashMap<EntryHash, Timestamp> = HashMap::new();
+    for link in get_links(
+        leaf_hash,
+        LinkTypes::TimeIndex,
+        Some(IndexSegment::tombstone_tag_for_index(index_name)),
+    )? {
+        let removed_at = tombstoned.entry(EntryHash::from(link.target)).or_insert(link.timestamp);
+        if link.timestamp > *removed_at {
+            *removed_at = link.timestamp;
+        }
     }
+    Ok(tombstoned)
 }
 
-/// Find the most recent indexed hash in all children of `current_segment_hash`
-/// by recursively performing a depth-first search of its children.
+/// Determine whether `entry_hash` is currently removed from the leaf node `leaf_hash` of `index_name`.
 ///
-fn get_newest_leafmost_hash<I>(current_segment_hash: EntryHash, index_name: &I) -> TimeIndexResult<EntryHash>
+/// @see is_tombstoned
+///
+pub (crate) fn is_removed_from_leaf<I>(index_name: &I, leaf_hash: &EntryHash, entry_hash: &EntryHash) -> TimeIndexResult<bool>
     where I: AsRef<str>,
 {
-    let mut children = get_links(
-        current_segment_hash.to_owned(),
-        LinkTypes::TimeIndex,
-        Some(link_prefix_for_index(index_name)),
-    )?;
+    let tombstoned = get_tombstoned_hashes(index_name, leaf_hash.to_owned())?;
+    let linked_at = get_links(leaf_hash.to_owned(), LinkTypes::TimeIndex, Some(link_prefix_for_index(index_name)))?
+        .iter()
+        .filter(|link| EntryHash::from(link.target.to_owned()) == *entry_hash)
+        .map(|link| link.timestamp)
+        .max();
 
-    children.sort_unstable_by(|a, b| a.tag.cmp(&b.tag));
+    Ok(is_tombstoned(&tombstoned, entry_hash, linked_at.as_ref()))
+}
 
-    match children.last() {
-        None => Ok(current_segment_hash),
-        Some(l) => get_newest_leafmost_hash(EntryHash::from(l.target.to_owned()), index_name),
+/// Tombstones are versioned by position: since they can only be deleted by their author, an entry
+/// moved back to a leaf it was removed from is instead linked again, and only tombstones written at
+/// or after the entry's most recent link from the leaf apply.
+///
+fn is_tombstoned(tombstoned: &HashMap<EntryHash, Timestamp>, entry_hash: &EntryHash, linked_at: Option<&Timestamp>) -> bool {
+    match (tombstoned.get(entry_hash), linked_at) {
+        (None, _) => false,
+        (Some(_), None) => true,
+        (Some(removed_at), Some(linked_at)) => removed_at >= linked_at,
     }
 }
 
+/// Decode the time encoded in the `LinkTag` of some link within a time index tree.
+/// Returns `None` for malformed link tags.
+///
+fn get_link_time(link: &Link) -> Option<DateTime<Utc>> {
+    let segment: Result<IndexSegment, _> = link.tag.to_owned().try_into();
+    segment.ok().map(|s| s.into())
+}
+
 /// Determine the hash of the root node for the given index.
 ///
 fn get_root_hash<I>(index_name: &I) -> TimeIndexResult<Option<EntryHash>>
@@ -51,8 +51,9 @@ pub fn sort_entries_by_time_index<'a, I>(index_name: &'a I) -> Box<dyn for<'r> F
 ///     stored into the index before querying.
 ///
 /// Note that entries written multiple times into the same index will be sorted based
-/// upon the *first observed* indexing time in the local Holochain Cell DHT. This may
-/// result in discrepancy in the ordering of data for different peers in a loosely-
+/// upon the *most recently* written indexing time observed in the local Holochain Cell DHT,
+/// such that entries moved via `reindex_entry` are ordered by their current position. This
+/// may result in discrepancy in the ordering of data for different peers in a loosely-
 /// partitioned network.
 ///
 fn get_time_for_entry_hash(index_link_prefix: LinkTag, entry_hash: &EntryHash) -> DateTime<Utc>
@@ -69,9 +70,8 @@ fn get_time_for_entry_hash(index_link_prefix: LinkTag, entry_hash: &EntryHash) -
             if links.len() < 1 {
                 return null_time();
             }
-            // take first link for the index as source of truth- the first agent who
-            // knew about a thing probably knew about it first.
-            let try_segment: TimeIndexResult<IndexSegment> = links.first().unwrap().tag.to_owned().try_into();
+            // take latest link for the index as source of truth, since entries may be reindexed
+            let try_segment: TimeIndexResult<IndexSegment> = links.iter().max_by_key(|l| l.timestamp).unwrap().tag.to_owned().try_into();
             match try_segment {
                 Ok(segment) => match segment.try_into() {
                     Ok(date) => date,
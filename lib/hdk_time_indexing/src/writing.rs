@@ -5,6 +5,7 @@ use crate::{
     index_tree::*,
 };
 use hdk_semantic_indexes_core::LinkTypes;
+use crate::reading::{link_prefix_for_index, is_removed_from_leaf};
 
 /// Index an entry with hash `entry_hash` into the time-ordered index
 /// identified by `index_entry` at the given time point.
@@ -14,14 +15,76 @@ use hdk_semantic_indexes_core::LinkTypes;
 pub fn index_entry<I>(index_name: &I, entry_hash: EntryHash, time: DateTime<Utc>) -> TimeIndexResult<()>
     where I: AsRef<str>,
 {
-    // write the time index tree
-    let leafmost_segment = ensure_time_index(index_name, time)?;
-    let leafmost_hash = leafmost_segment.hash()?;
+    let leafmost_hash = ensure_time_index(index_name, time)?.hash()?;
+    link_entry(index_name, leafmost_hash, entry_hash, time, false)
+}
+
+/// Move an entry with hash `entry_hash` to a new time point within the time-ordered index
+/// identified by `index_name`, indexing it for the first time if it is not yet present.
+///
+/// Any other positions the entry was previously indexed at are tombstoned. If the entry had
+/// previously been removed from its new position (by any agent), it is linked there again so
+/// that the new links supersede the existing tombstone.
+///
+pub fn reindex_entry<I>(index_name: &I, entry_hash: EntryHash, time: DateTime<Utc>) -> TimeIndexResult<()>
+    where I: AsRef<str>,
+{
+    let leafmost_hash = ensure_time_index(index_name, time)?.hash()?;
+
+    // remove the entry from any leaf nodes it was previously indexed under
+    for leaf_hash in get_indexed_leaves(index_name, &entry_hash)? {
+        if leaf_hash != leafmost_hash {
+            tombstone_leaf_link(index_name, leaf_hash, &entry_hash)?;
+        }
+    }
+
+    // restore the entry at its new position, if it had previously been removed from there
+    let is_removed = is_removed_from_leaf(index_name, &leafmost_hash, &entry_hash)?;
+    link_entry(index_name, leafmost_hash, entry_hash, time, is_removed)
+}
 
+/// Mark an entry with hash `entry_hash` as removed from the time-ordered index
+/// identified by `index_name`.
+///
+/// Rather than deleting the links which index the entry (which can only be done by
+/// their author), a tombstone link is written from each leaf node the entry is indexed
+/// under. Tombstoned entries are skipped by all readers, though the entry's own link back
+/// into the index is retained so that it can still be used as a pagination cursor.
+///
+/// Tombstones only apply to links written before them; @see crate::reading::is_removed_from_leaf
+///
+pub fn tombstone_entry<I>(index_name: &I, entry_hash: EntryHash) -> TimeIndexResult<()>
+    where I: AsRef<str>,
+{
+    let leaves = get_indexed_leaves(index_name, &entry_hash)?;
+    if leaves.is_empty() {
+        return Err(TimeIndexingError::NotIndexed(index_name.as_ref().to_string(), entry_hash));
+    }
+
+    for leaf_hash in leaves {
+        tombstone_leaf_link(index_name, leaf_hash, &entry_hash)?;
+    }
+
+    Ok(())
+}
+
+/// Link `entry_hash` from the leaf node `leafmost_hash` at `time`, along with a reciprocal link
+/// from the entry back to the leaf. When `relink` is set, links are written even if identical
+/// ones already exist, in order to supersede any tombstones on the leaf.
+///
+fn link_entry<I>(index_name: &I, leafmost_hash: EntryHash, entry_hash: EntryHash, time: DateTime<Utc>, relink: bool) -> TimeIndexResult<()>
+    where I: AsRef<str>,
+{
     // create a virtual segment for determining the final link tag data
     let target_entry_segment = IndexSegment::leafmost_link(&time);
     let encoded_link_tag = target_entry_segment.tag_for_index(&index_name);
 
+    if relink {
+        create_index_link(leafmost_hash.to_owned(), entry_hash.to_owned(), encoded_link_tag.to_owned())?;
+        create_index_link(entry_hash, leafmost_hash, encoded_link_tag)?;
+        return Ok(());
+    }
+
     // ensure link from the leaf index to the target entry
     link_if_not_linked(leafmost_hash.to_owned(), entry_hash.to_owned(), encoded_link_tag.to_owned())?;
 
@@ -31,6 +94,32 @@ pub fn index_entry<I>(index_name: &I, entry_hash: EntryHash, time: DateTime<Utc>
     Ok(())
 }
 
+/// Write a tombstone for `entry_hash` from the leaf node `leaf_hash`, unless it has already been
+/// removed from there since it was last linked.
+///
+fn tombstone_leaf_link<I>(index_name: &I, leaf_hash: EntryHash, entry_hash: &EntryHash) -> TimeIndexResult<()>
+    where I: AsRef<str>,
+{
+    if is_removed_from_leaf(index_name, &leaf_hash, entry_hash)? {
+        return Ok(());
+    }
+    create_index_link(leaf_hash, entry_hash.to_owned(), IndexSegment::tombstone_tag_for_index(index_name))
+}
+
+/// Determine the distinct leaf nodes that `entry_hash` has been linked from in `index_name`.
+///
+fn get_indexed_leaves<I>(index_name: &I, entry_hash: &EntryHash) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    let mut leaves: Vec<EntryHash> = get_links(entry_hash.to_owned(), LinkTypes::TimeIndex, Some(link_prefix_for_index(index_name)))?
+        .into_iter()
+        .map(|link| EntryHash::from(link.target))
+        .collect();
+    leaves.sort();
+    leaves.dedup();
+    Ok(leaves)
+}
+
 /// Returns the leaf-most `IndexSegment` in the time tree, so that target entries can be
 /// linked from it.
 ///
@@ -84,13 +173,19 @@ fn link_if_not_linked(origin_hash: EntryHash, dest_hash: EntryHash, link_tag: Li
     if false == get_links(origin_hash.to_owned(), LinkTypes::TimeIndex, Some(link_tag.to_owned()))?
         .iter().any(|l| { EntryHash::from(l.target.to_owned()) == dest_hash })
     {
-        create_link(
-            origin_hash.to_owned(),
-            dest_hash.to_owned(),
-            LinkTypes::TimeIndex,
-            link_tag,
-        ).map_err(|e| { TimeIndexingError::NotIndexed(e.to_string(), origin_hash.to_owned()) })?;
+        create_index_link(origin_hash, dest_hash, link_tag)?;
     }
 
     Ok(())
 }
+
+fn create_index_link(origin_hash: EntryHash, dest_hash: EntryHash, link_tag: LinkTag) -> TimeIndexResult<()> {
+    create_link(
+        origin_hash.to_owned(),
+        dest_hash,
+        LinkTypes::TimeIndex,
+        link_tag,
+    ).map_err(|e| { TimeIndexingError::NotIndexed(e.to_string(), origin_hash) })?;
+
+    Ok(())
+}
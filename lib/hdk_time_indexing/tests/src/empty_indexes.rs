@@ -1,20 +1,17 @@
 #[cfg(test)]
 mod empty_indexes {
     use ::fixt::prelude::*;
+    use chrono::DateTime;
     use hdk::prelude::{
         set_hdk, Entry, GetInput, GetOptions, HashInput, HashOutput, MockHdkT, Path, PathEntry,
     };
     use holo_hash::{fixt::EntryHashFixturator, AnyDhtHash};
 
-    // this test demonstrates that if the index is 'untouched' meaning
-    // it can't possibly have children then it will just early exit and
-    // return an empty set of results
-    #[test]
-    fn get_latest_entry_hashes_empty_scenario() {
+    /// Mock the host calls made when reading from an index `index_name` which has never
+    /// been written to, such that it is detected as untouched
+    fn mock_empty_index(index_name: &String) -> MockHdkT {
         let mut mock_hdk = MockHdkT::new();
-
-        let index_name = "test_index_name".to_string();
-        let root = Path::from(&index_name);
+        let root = Path::from(index_name);
 
         // mock the first call to `hash` via `hash_entry` in `Path.path_entry()`
         let path_hash = fixt!(EntryHash);
@@ -49,10 +46,46 @@ mod empty_indexes {
             .times(1)
             .return_once(|_hash_input| Ok(vec![None]));
 
-        set_hdk(mock_hdk);
+        mock_hdk
+    }
+
+    // this test demonstrates that if the index is 'untouched' meaning
+    // it can't possibly have children then it will just early exit and
+    // return an empty set of results
+    #[test]
+    fn get_latest_entry_hashes_empty_scenario() {
+        let index_name = "test_index_name".to_string();
+        set_hdk(mock_empty_index(&index_name));
 
         let result = hdk_time_indexing::reading::get_latest_entry_hashes(&index_name, 2);
         assert!(result.is_ok());
         assert_eq!(result.ok().unwrap(), vec![]);
     }
+
+    // as above, but when reading from the oldest end of the index
+    #[test]
+    fn get_earliest_entry_hashes_empty_scenario() {
+        let index_name = "test_index_name".to_string();
+        set_hdk(mock_empty_index(&index_name));
+
+        let result = hdk_time_indexing::reading::get_earliest_entry_hashes(&index_name, 2);
+        assert!(result.is_ok());
+        assert_eq!(result.ok().unwrap(), vec![]);
+    }
+
+    // as above, but when reading a range of times from the index
+    #[test]
+    fn get_entry_hashes_between_empty_scenario() {
+        let index_name = "test_index_name".to_string();
+        set_hdk(mock_empty_index(&index_name));
+
+        let result = hdk_time_indexing::reading::get_entry_hashes_between(
+            &index_name,
+            DateTime::parse_from_rfc3339("2020-07-01T00:00:00+00:00").unwrap().into(),
+            DateTime::parse_from_rfc3339("2020-10-01T00:00:00+00:00").unwrap().into(),
+            2,
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.ok().unwrap(), vec![]);
+    }
 }
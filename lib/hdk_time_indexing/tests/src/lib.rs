@@ -1,5 +1,10 @@
 #[macro_use]
 mod helper_macros;
 
+#[cfg(test)]
+mod mock_dht;
+
 mod empty_indexes;
 mod data_structures;
+mod pagination;
+mod time_ranges;
@@ -0,0 +1,136 @@
+/**
+ * In-memory stand-in for the DHT, allowing time indexes to be written and then
+ * read back within tests
+ *
+ * @package hdk_time_indexing
+ * @since   2026-10-17
+ */
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use hdk::prelude::*;
+use hdk_semantic_indexes_core::LinkTypes;
+use hdk_time_indexing::{IndexConfiguration, writing::index_entry};
+
+/// Chunk size of the mocked host DNA's index, matching that configured for hREA's DNAs
+/// so that leaf chunks of the index tree are exercised.
+const TIME_INDEX_CHUNK_INTERVAL_MS: usize = 30000;
+
+/// Entry times spread across chunks, minutes, hours, days, months and years of the index tree,
+/// in order from oldest to most recent.
+pub const ENTRY_TIMES: [&str; 7] = [
+    "2020-04-12T23:59:59+00:00",
+    "2020-04-13T03:36:00+00:00",
+    "2020-04-13T03:36:40+00:00",
+    "2020-04-13T03:37:10+00:00",
+    "2020-04-13T05:00:00+00:00",
+    "2020-05-01T00:00:00+00:00",
+    "2021-01-01T12:00:00+00:00",
+];
+
+#[derive(Default)]
+struct DhtState {
+    links: Vec<(AnyLinkableHash, Link)>,
+    // incremented with each write, so that links are timestamped in the order they were created
+    clock: i64,
+}
+
+/// Create a mock HDK which stores created links in memory and serves them back from `get_links`.
+///
+pub fn mock_dht() -> MockHdkT {
+    let state = Arc::new(Mutex::new(DhtState::default()));
+    let mut mock_hdk = MockHdkT::new();
+
+    mock_hdk
+        .expect_hash()
+        .returning(|hash_input| match hash_input {
+            HashInput::Entry(entry) => Ok(HashOutput::Entry(mock_entry_hash(&format!("{:?}", entry)))),
+            _ => unimplemented!("only entry hashes are used by time indexes"),
+        });
+
+    mock_hdk
+        .expect_zome_info()
+        .returning(|_| Ok(ZomeInfo {
+            name: "time_index_test".into(),
+            id: ZomeIndex(0),
+            properties: SerializedBytes::from(UnsafeBytes::from(vec![])),
+            entry_defs: EntryDefs(vec![]),
+            extern_fns: vec![],
+            zome_types: ScopedZomeTypesSet {
+                entries: ScopedZomeTypes(vec![]),
+                links: ScopedZomeTypes(vec![(ZomeIndex(0), (0..LinkTypes::len()).map(LinkType).collect())]),
+            },
+        }));
+
+    mock_hdk
+        .expect_dna_info()
+        .returning(|_| Ok(DnaInfo {
+            name: "time_index_test".into(),
+            hash: DnaHash::from_raw_36(vec![0; 36]),
+            properties: IndexConfiguration { time_index_chunk_interval_ms: TIME_INDEX_CHUNK_INTERVAL_MS }.try_into().unwrap(),
+            zome_names: vec![],
+        }));
+
+    let writes = state.clone();
+    mock_hdk
+        .expect_create_link()
+        .returning(move |input| {
+            let mut dht = writes.lock().unwrap();
+            dht.clock += 1;
+            let create_link_hash = ActionHash::from_raw_36(mock_hash_bytes(&dht.clock));
+            let link = Link {
+                target: input.target_address.to_owned(),
+                timestamp: Timestamp::from_micros(dht.clock),
+                tag: input.tag.to_owned(),
+                create_link_hash: create_link_hash.to_owned(),
+            };
+            dht.links.push((input.base_address.to_owned(), link));
+            Ok(create_link_hash)
+        });
+
+    mock_hdk
+        .expect_get_links()
+        .returning(move |inputs| {
+            let dht = state.lock().unwrap();
+            Ok(inputs.iter()
+                .map(|input| dht.links.iter()
+                    .filter(|(base, _)| *base == input.base_address)
+                    .filter(|(_, link)| match &input.tag_prefix {
+                        Some(prefix) => link.tag.0.starts_with(&prefix.0),
+                        None => true,
+                    })
+                    .map(|(_, link)| link.to_owned())
+                    .collect())
+                .collect())
+        });
+
+    mock_hdk
+}
+
+/// Index an entry into `index_name` at each of the given RFC3339 `times`, returning
+/// the hashes of the indexed entries in the same order.
+///
+pub fn index_entries(index_name: &String, times: &[&str]) -> Vec<EntryHash> {
+    times.iter()
+        .map(|time| {
+            let entry_hash = mock_entry_hash(&format!("{}@{}", index_name, time));
+            index_entry(index_name, entry_hash.to_owned(), parse_time(time)).unwrap();
+            entry_hash
+        })
+        .collect()
+}
+
+pub fn parse_time(time: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(time).unwrap().into()
+}
+
+fn mock_entry_hash(data: &String) -> EntryHash {
+    EntryHash::from_raw_36(mock_hash_bytes(data))
+}
+
+fn mock_hash_bytes<T: Hash>(data: &T) -> Vec<u8> {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    [hasher.finish().to_le_bytes().repeat(4), vec![0; 4]].concat()
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod pagination {
+    use hdk::prelude::{set_hdk, EntryHash};
+    use hdk_time_indexing::{
+        reading::{
+            get_latest_entry_hashes, get_earliest_entry_hashes,
+            get_older_entry_hashes, get_newer_entry_hashes,
+            has_newer_entries, has_older_entries,
+        },
+        writing::tombstone_entry,
+    };
+
+    use crate::mock_dht::{ mock_dht, index_entries, ENTRY_TIMES };
+
+    fn newest_first(entries: &Vec<EntryHash>) -> Vec<EntryHash> {
+        entries.iter().rev().cloned().collect()
+    }
+
+    #[test]
+    fn read_pages_forward() {
+        set_hdk(mock_dht());
+        let index_name = "test_index_name".to_string();
+        let expected = newest_first(&index_entries(&index_name, &ENTRY_TIMES));
+
+        let page = get_latest_entry_hashes(&index_name, 3).unwrap();
+        assert_eq!(page, expected[0..3].to_vec());
+        assert!(!has_newer_entries(&index_name, page[0].to_owned()).unwrap(), "first page has no previous page");
+
+        let page = get_older_entry_hashes(&index_name, page[2].to_owned(), 3).unwrap();
+        assert_eq!(page, expected[3..6].to_vec());
+        assert!(has_newer_entries(&index_name, page[0].to_owned()).unwrap(), "second page has a previous page");
+        assert!(has_older_entries(&index_name, page[2].to_owned()).unwrap(), "second page has a next page");
+
+        let page = get_older_entry_hashes(&index_name, page[2].to_owned(), 3).unwrap();
+        assert_eq!(page, expected[6..].to_vec());
+        assert!(has_newer_entries(&index_name, page[0].to_owned()).unwrap(), "last page has a previous page");
+        assert!(!has_older_entries(&index_name, page[0].to_owned()).unwrap(), "last page has no next page");
+
+        assert_eq!(get_older_entry_hashes(&index_name, page[0].to_owned(), 3).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn read_pages_backward() {
+        set_hdk(mock_dht());
+        let index_name = "test_index_name".to_string();
+        let expected = newest_first(&index_entries(&index_name, &ENTRY_TIMES));
+
+        let page = get_earliest_entry_hashes(&index_name, 3).unwrap();
+        assert_eq!(page, expected[4..].to_vec());
+        assert!(!has_older_entries(&index_name, page[2].to_owned()).unwrap(), "first page has no following page");
+
+        let page = get_newer_entry_hashes(&index_name, page[0].to_owned(), 3).unwrap();
+        assert_eq!(page, expected[1..4].to_vec());
+        assert!(has_older_entries(&index_name, page[2].to_owned()).unwrap(), "second page has a following page");
+        assert!(has_newer_entries(&index_name, page[0].to_owned()).unwrap(), "second page has a preceding page");
+
+        let page = get_newer_entry_hashes(&index_name, page[0].to_owned(), 3).unwrap();
+        assert_eq!(page, expected[0..1].to_vec());
+        assert!(!has_newer_entries(&index_name, page[0].to_owned()).unwrap(), "last page has no preceding page");
+
+        assert_eq!(get_newer_entry_hashes(&index_name, page[0].to_owned(), 3).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn pages_concatenate_in_both_directions() {
+        set_hdk(mock_dht());
+        let index_name = "test_index_name".to_string();
+        let expected = newest_first(&index_entries(&index_name, &ENTRY_TIMES));
+
+        let mut forward = get_latest_entry_hashes(&index_name, 2).unwrap();
+        while let Some(cursor) = forward.last().cloned() {
+            let page = get_older_entry_hashes(&index_name, cursor, 2).unwrap();
+            if page.is_empty() {
+                break;
+            }
+            forward.extend(page);
+        }
+        assert_eq!(forward, expected);
+
+        let mut backward = get_earliest_entry_hashes(&index_name, 2).unwrap();
+        while let Some(cursor) = backward.first().cloned() {
+            let mut page = get_newer_entry_hashes(&index_name, cursor, 2).unwrap();
+            if page.is_empty() {
+                break;
+            }
+            page.extend(backward);
+            backward = page;
+        }
+        assert_eq!(backward, expected);
+    }
+
+    #[test]
+    fn removed_entries_skipped_between_pages() {
+        set_hdk(mock_dht());
+        let index_name = "test_index_name".to_string();
+        let expected = newest_first(&index_entries(&index_name, &ENTRY_TIMES));
+
+        tombstone_entry(&index_name, expected[3].to_owned()).unwrap();
+
+        let page = get_older_entry_hashes(&index_name, expected[2].to_owned(), 2).unwrap();
+        assert_eq!(page, vec![expected[4].to_owned(), expected[5].to_owned()]);
+        let page = get_newer_entry_hashes(&index_name, expected[4].to_owned(), 2).unwrap();
+        assert_eq!(page, vec![expected[1].to_owned(), expected[2].to_owned()]);
+
+        // removed entries remain usable as cursors
+        let page = get_older_entry_hashes(&index_name, expected[3].to_owned(), 1).unwrap();
+        assert_eq!(page, vec![expected[4].to_owned()]);
+        assert!(has_newer_entries(&index_name, expected[3].to_owned()).unwrap());
+    }
+}
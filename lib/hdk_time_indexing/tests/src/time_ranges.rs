@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod time_ranges {
+    use hdk::prelude::{set_hdk, EntryHash};
+    use hdk_time_indexing::reading::get_entry_hashes_between;
+
+    use crate::mock_dht::{ mock_dht, index_entries, parse_time, ENTRY_TIMES };
+
+    fn read_between(index_name: &String, from: &str, to: &str, limit: usize) -> Vec<EntryHash> {
+        get_entry_hashes_between(index_name, parse_time(from), parse_time(to), limit).unwrap()
+    }
+
+    #[test]
+    fn range_bounds_crossing_index_segments() {
+        set_hdk(mock_dht());
+        let index_name = "test_index_name".to_string();
+        let e = index_entries(&index_name, &ENTRY_TIMES);
+
+        // spans a day boundary, and ends partway through a minute
+        assert_eq!(
+            read_between(&index_name, "2020-04-12T23:00:00+00:00", "2020-04-13T03:37:00+00:00", 10),
+            vec![e[2].to_owned(), e[1].to_owned(), e[0].to_owned()],
+        );
+        // begins and ends partway through chunks of adjacent minutes
+        assert_eq!(
+            read_between(&index_name, "2020-04-13T03:36:20+00:00", "2020-04-13T03:37:20+00:00", 10),
+            vec![e[3].to_owned(), e[2].to_owned()],
+        );
+        // spans month and year boundaries
+        assert_eq!(
+            read_between(&index_name, "2020-04-30T00:00:00+00:00", "2022-01-01T00:00:00+00:00", 10),
+            vec![e[6].to_owned(), e[5].to_owned()],
+        );
+        // covers the whole index, limited to the most recent results
+        assert_eq!(
+            read_between(&index_name, "2000-01-01T00:00:00+00:00", "2030-01-01T00:00:00+00:00", 2),
+            vec![e[6].to_owned(), e[5].to_owned()],
+        );
+        // falls between indexed entries
+        assert_eq!(
+            read_between(&index_name, "2020-06-01T00:00:00+00:00", "2020-12-01T00:00:00+00:00", 10),
+            vec![],
+        );
+    }
+
+    #[test]
+    fn range_bounds_inclusive_from_exclusive_to() {
+        set_hdk(mock_dht());
+        let index_name = "test_index_name".to_string();
+        let e = index_entries(&index_name, &ENTRY_TIMES);
+
+        // entries at the lower bound are included, those at the upper bound are not
+        assert_eq!(
+            read_between(&index_name, ENTRY_TIMES[1], ENTRY_TIMES[3], 10),
+            vec![e[2].to_owned(), e[1].to_owned()],
+        );
+        assert_eq!(
+            read_between(&index_name, ENTRY_TIMES[4], ENTRY_TIMES[6], 10),
+            vec![e[5].to_owned(), e[4].to_owned()],
+        );
+        assert_eq!(
+            read_between(&index_name, ENTRY_TIMES[6], "2021-01-01T12:00:00.000001+00:00", 10),
+            vec![e[6].to_owned()],
+        );
+    }
+
+    #[test]
+    fn range_bounds_empty_or_reversed() {
+        set_hdk(mock_dht());
+        let index_name = "test_index_name".to_string();
+        index_entries(&index_name, &ENTRY_TIMES);
+
+        assert_eq!(read_between(&index_name, ENTRY_TIMES[2], ENTRY_TIMES[2], 10), vec![]);
+        assert_eq!(read_between(&index_name, ENTRY_TIMES[5], ENTRY_TIMES[1], 10), vec![]);
+        assert_eq!(read_between(&index_name, ENTRY_TIMES[0], ENTRY_TIMES[6], 0), vec![]);
+    }
+}
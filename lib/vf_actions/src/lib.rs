@@ -7,6 +7,10 @@
 use serde::{Deserializer, Serializer, de::Error};
 
 use hdk::prelude::*;
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError,
+    rpc::call_role_zome_method,
+};
 use vf_attributes_hdk::{ ActionId, ProcessAddress, EconomicResourceAddress };
 
 pub mod builtins;
@@ -110,24 +114,133 @@ pub struct Action {
     pub pairs_with: String, // any of the action labels, or "notApplicable"
 }
 
+//---------------- USER-DEFINED ACTIONS ----------------
+
+// :TODO: remove this, replace with reference to appropriate namespacing of zome config
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct DnaConfigSlice {
+    pub action: ActionResolverConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct ActionResolverConfig {
+    pub specification_role: Option<String>,
+}
+
+/// Properties accessor for zome config
+fn read_specification_role(conf: DnaConfigSlice) -> Option<String> {
+    conf.action.specification_role
+}
+
+/// The zome in the specification DNA which manages user-defined actions
+pub const ACTION_ZOME: &str = "action";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ById {
+    id: ActionId,
+}
+
+/// Locate an action definition by ID. Builtin actions are returned directly; any other
+/// IDs are looked up amongst the user-defined actions in the specification DNA configured
+/// via the `action.specification_role` DNA property.
+///
+/// Returns `None` if no action with the given ID exists. Any failure to reach the
+/// specification DNA is returned as an error.
+///
+/// :WARNING: makes zome calls, and so cannot be used within integrity zome validation callbacks.
+///
+pub fn resolve_action(action_id: &ActionId) -> RecordAPIResult<Option<Action>> {
+    if let Some(action) = get_builtin_action(action_id.as_ref()) {
+        return Ok(Some(action));
+    }
+    Ok(call_role_zome_method(
+        read_specification_role, ACTION_ZOME, "__internal_find_action",
+        ById { id: action_id.to_owned() },
+    )?)
+}
+
+/// Copy of a user-defined action, written to each DNA which references it so that records
+/// using the action can be validated without calling out to the specification DNA.
+///
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct ActionDefinition {
+    pub action: Action,
+}
+
+/// Resolve a user-defined action and ensure a copy of its definition is present in the local DNA,
+/// returning the definition's `EntryHash` for referencing from records which use the action.
+/// Builtin actions have no definition, and `None` is returned.
+///
+/// `EN` is the calling zome's `EntryTypes`, which must include an `ActionDefinition` variant.
+/// Process linkage of the record is checked against the action, so that invalid records can
+/// be rejected before they are written.
+///
+pub fn ensure_action_definition<EN, E, E2>(
+    action_id: &ActionId,
+    input_process: Option<ProcessAddress>,
+    output_process: Option<ProcessAddress>,
+) -> RecordAPIResult<Option<EntryHash>>
+    where EN: TryFrom<ActionDefinition, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    if get_builtin_action(action_id.as_ref()).is_some() {
+        return Ok(None);
+    }
+
+    let action = resolve_action(action_id)?
+        .ok_or_else(|| DataIntegrityError::InvalidAction(format!("Unknown action '{}'", action_id.as_ref())))?;
+    validate_action_processes(&action, input_process, output_process)
+        .map_err(DataIntegrityError::InvalidAction)?;
+
+    let definition = ActionDefinition { action };
+    let definition_hash = hash_entry(definition.to_owned())?;
+    if get(definition_hash.to_owned(), GetOptions::content())?.is_none() {
+        create_entry(EN::try_from(definition).map_err(WasmError::from)?)?;
+    }
+
+    Ok(Some(definition_hash))
+}
+
+//---------------- VALIDATION ----------------
+
+fn validate_action_processes(action: &Action, input_process: Option<ProcessAddress>, output_process: Option<ProcessAddress>) -> Result<(), String> {
+    match action.input_output {
+        ProcessType::NotApplicable => if input_process.is_some() || output_process.is_some() {
+            Err(format!("EconomicEvent of '{:}' action cannot link to processes", action.id).into())
+        } else { Ok(()) },
+        ProcessType::Input => if input_process.is_none() {
+            Err(format!("EconomicEvent input process required for '{:}' action", action.id).into())
+        } else { Ok(()) },
+        ProcessType::Output => if output_process.is_none() {
+            Err(format!("EconomicEvent output process required for '{:}' action", action.id).into())
+        } else { Ok(()) },
+    }
+}
+
 /**
  * Validation for EconomicEvent, Commitment and Process to ensure correct use of actions & Processes
+ *
+ * User-defined actions are checked against the copy of their definition referenced by
+ * `action_definition` (@see `ensure_action_definition`), which must be present in the local DNA.
+ * Failure to retrieve the definition is returned as an `Err`, so that validation may be retried.
  */
-pub fn validate_flow_action(action_id: ActionId, input_process: Option<ProcessAddress>, output_process: Option<ProcessAddress>) -> Result<(), String> {
+pub fn validate_flow_action(action_id: ActionId, action_definition: Option<EntryHash>, input_process: Option<ProcessAddress>, output_process: Option<ProcessAddress>) -> ExternResult<Result<(), String>> {
     if let Some(action) = get_builtin_action(action_id.as_ref()) {
-        match action.input_output {
-            ProcessType::NotApplicable => if input_process.is_some() || output_process.is_some() {
-                Err(format!("EconomicEvent of '{:}' action cannot link to processes", action.id).into())
-            } else { Ok(()) },
-            ProcessType::Input => if input_process.is_none() {
-                Err(format!("EconomicEvent input process required for '{:}' action", action.id).into())
-            } else { Ok(()) },
-            ProcessType::Output => if output_process.is_none() {
-                Err(format!("EconomicEvent output process required for '{:}' action", action.id).into())
-            } else { Ok(()) },
-        }
-    } else {
-        Err("Unknown action".to_string())
+        return Ok(validate_action_processes(&action, input_process, output_process));
+    }
+
+    let definition_hash = match action_definition {
+        Some(hash) => hash,
+        None => return Ok(Err(format!("Records using custom action '{}' must reference its definition", action_id.as_ref()))),
+    };
+    match ActionDefinition::try_from(must_get_entry(definition_hash)?.content) {
+        Ok(ActionDefinition { action }) if action.id == *action_id.as_ref() =>
+            Ok(validate_action_processes(&action, input_process, output_process)),
+        _ => Ok(Err(format!("Invalid definition referenced for custom action '{}'", action_id.as_ref()))),
     }
 }
 
@@ -0,0 +1,422 @@
+/**
+ * Fixed-precision decimal numbers for measurement arithmetic
+ *
+ * Values are held as an integer mantissa and a base-10 scale, such that
+ * `mantissa * 10^-scale` gives the represented number. Addition, subtraction
+ * and multiplication are exact; division and conversion from floating-point
+ * round to a given number of decimal places.
+ *
+ * Values are serialized as plain numbers wherever doing so is lossless, and as
+ * strings otherwise. Both representations are accepted when deserializing, so that
+ * clients sending IEEE-754 numbers continue to work unmodified.
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use std::{cmp::Ordering, fmt, str::FromStr};
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de::{self, Visitor}};
+
+/// Number of decimal places retained by lossy operations when no other scale is configured.
+/// Quantity arithmetic uses the `decimal_scale` DNA property where set; @see `crate::decimal_scale`.
+/// Floating-point conversion always uses this scale, since IEEE-754 inputs carry no more precision.
+pub const DEFAULT_DECIMAL_SCALE: u32 = 18;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+fn pow10(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+impl Decimal {
+    /// Create a decimal representing `mantissa * 10^-scale`.
+    ///
+    /// Values are stored in normalized form (without trailing zeroes), so that
+    /// numerically equal decimals compare as equal regardless of input scale.
+    ///
+    pub fn new(mantissa: i128, scale: u32) -> Decimal {
+        let mut d = Decimal { mantissa, scale };
+        if d.mantissa == 0 {
+            d.scale = 0;
+        }
+        while d.scale > 0 && d.mantissa % 10 == 0 {
+            d.mantissa /= 10;
+            d.scale -= 1;
+        }
+        d
+    }
+
+    pub fn zero() -> Decimal {
+        Decimal { mantissa: 0, scale: 0 }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Express both operands as mantissas of a common scale
+    fn aligned(&self, other: &Decimal) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        Some((
+            self.mantissa.checked_mul(pow10(scale - self.scale)?)?,
+            other.mantissa.checked_mul(pow10(scale - other.scale)?)?,
+            scale,
+        ))
+    }
+
+    /// Exact addition. Returns `None` on overflow.
+    pub fn checked_add(&self, other: &Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.aligned(other)?;
+        Some(Decimal::new(a.checked_add(b)?, scale))
+    }
+
+    /// Exact subtraction. Returns `None` on overflow.
+    pub fn checked_sub(&self, other: &Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.aligned(other)?;
+        Some(Decimal::new(a.checked_sub(b)?, scale))
+    }
+
+    /// Exact multiplication. Returns `None` on overflow.
+    pub fn checked_mul(&self, other: &Decimal) -> Option<Decimal> {
+        Some(Decimal::new(
+            self.mantissa.checked_mul(other.mantissa)?,
+            self.scale.checked_add(other.scale)?,
+        ))
+    }
+
+    /// Division, rounded half away from zero to `scale` decimal places.
+    /// Returns `None` on overflow or division by zero.
+    pub fn checked_div(&self, other: &Decimal, scale: u32) -> Option<Decimal> {
+        if other.is_zero() {
+            return None;
+        }
+        // self / other = (m1 * 10^(s2 + scale - s1)) / m2 * 10^-scale
+        let shift = (other.scale as i64) + (scale as i64) - (self.scale as i64);
+        let (numerator, denominator) = if shift >= 0 {
+            (self.mantissa.checked_mul(pow10(shift as u32)?)?, other.mantissa)
+        } else {
+            (self.mantissa, other.mantissa.checked_mul(pow10((-shift) as u32)?)?)
+        };
+        Some(Decimal::new(div_round(numerator, denominator)?, scale))
+    }
+
+    /// Round half away from zero to at most `scale` decimal places.
+    pub fn round_dp(&self, scale: u32) -> Decimal {
+        if self.scale <= scale {
+            return *self;
+        }
+        match pow10(self.scale - scale) {
+            Some(divisor) => Decimal::new(div_round(self.mantissa, divisor).unwrap_or(0), scale),
+            // more places being dropped than an i128 can hold means the value rounds to zero
+            None => Decimal::zero(),
+        }
+    }
+
+    /// Convert from a floating-point number via its shortest round-trip representation,
+    /// such that eg. `0.1f64` becomes exactly `0.1`.
+    /// Returns `None` for non-finite inputs or magnitudes which cannot be represented.
+    pub fn from_f64(val: f64) -> Option<Decimal> {
+        if !val.is_finite() {
+            return None;
+        }
+        Decimal::from_str(&format!("{:e}", val)).ok().map(|d| d.round_dp(DEFAULT_DECIMAL_SCALE))
+    }
+
+    /// Nearest floating-point representation of this value.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+}
+
+/// Integer division rounding half away from zero
+fn div_round(numerator: i128, denominator: i128) -> Option<i128> {
+    let quotient = numerator.checked_div(denominator)?;
+    let remainder = numerator.checked_rem(denominator)?;
+    if remainder.unsigned_abs().checked_mul(2)? >= denominator.unsigned_abs() {
+        if (numerator < 0) == (denominator < 0) {
+            quotient.checked_add(1)
+        } else {
+            quotient.checked_sub(1)
+        }
+    } else {
+        Some(quotient)
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let signs = self.mantissa.signum().cmp(&other.mantissa.signum());
+        if signs != Ordering::Equal || self.is_zero() {
+            return signs;
+        }
+        match self.aligned(other) {
+            Some((a, b, _)) => a.cmp(&b),
+            // alignment can only overflow for the operand with the smaller scale (and hence larger magnitude)
+            None => {
+                let magnitudes = other.scale.cmp(&self.scale);
+                if self.is_negative() { magnitudes.reverse() } else { magnitudes }
+            },
+        }
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(val: i64) -> Decimal {
+        Decimal::new(val as i128, 0)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        if digits.len() > scale {
+            let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+            write!(f, "{}{}.{}", sign, int_part, frac_part)
+        } else {
+            write!(f, "{}0.{}{}", sign, "0".repeat(scale - digits.len()), digits)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDecimalError(String);
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid decimal value: {}", self.0)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    /// Parses plain (`-12.345`) and exponent (`1.2345e-3`) notation
+    fn from_str(s: &str) -> Result<Decimal, ParseDecimalError> {
+        let err = || ParseDecimalError(s.to_string());
+        let trimmed = s.trim();
+        let (number, exponent) = match trimmed.find(|c| c == 'e' || c == 'E') {
+            Some(idx) => (&trimmed[..idx], trimmed[idx + 1..].parse::<i64>().map_err(|_| err())?),
+            None => (trimmed, 0),
+        };
+        let (negative, unsigned) = match number.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, number.strip_prefix('+').unwrap_or(number)),
+        };
+        let (int_part, frac_part) = match unsigned.split_once('.') {
+            Some((i, f)) => (i, f),
+            None => (unsigned, ""),
+        };
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(err());
+        }
+        if !int_part.chars().chain(frac_part.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(err());
+        }
+
+        let mut mantissa: i128 = 0;
+        for c in int_part.chars().chain(frac_part.chars()) {
+            mantissa = mantissa.checked_mul(10)
+                .and_then(|m| m.checked_add(c.to_digit(10).unwrap() as i128))
+                .ok_or_else(err)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        let scale = frac_part.len() as i64 - exponent;
+        if scale >= 0 {
+            Ok(Decimal::new(mantissa, u32::try_from(scale).map_err(|_| err())?))
+        } else {
+            let multiplier = u32::try_from(-scale).ok().and_then(pow10).ok_or_else(err)?;
+            Ok(Decimal::new(mantissa.checked_mul(multiplier).ok_or_else(err)?, 0))
+        }
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        // emit numbers for compatibility wherever no precision would be lost
+        let as_float = self.to_f64();
+        if Decimal::from_f64(as_float) == Some(*self) {
+            serializer.serialize_f64(as_float)
+        } else {
+            serializer.serialize_str(&self.to_string())
+        }
+    }
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or decimal string")
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Decimal, E>
+        where E: de::Error
+    {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Decimal, E>
+        where E: de::Error
+    {
+        Ok(Decimal::new(v as i128, 0))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Decimal, E>
+        where E: de::Error
+    {
+        Decimal::from_f64(v).ok_or_else(|| E::custom(format!("Invalid decimal value: {}", v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Decimal, E>
+        where E: de::Error
+    {
+        Decimal::from_str(v).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk::prelude::*;
+
+    #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+    struct TestEntry {
+        value: Decimal,
+    }
+
+    #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+    struct TestFloatEntry {
+        value: f64,
+    }
+
+    #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+    struct TestStringEntry {
+        value: String,
+    }
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        assert_eq!(dec("0.1").checked_add(&dec("0.2")), Some(dec("0.3")));
+        assert_eq!(dec("1").checked_sub(&dec("0.9")), Some(dec("0.1")));
+        assert_eq!(dec("1.5").checked_mul(&dec("-2")), Some(dec("-3")));
+        assert_eq!(dec("2").checked_div(&dec("3"), 4), Some(dec("0.6667")));
+        assert_eq!(dec("1").checked_div(&dec("0"), 4), None);
+
+        let mut total = Decimal::zero();
+        for _ in 0..10 {
+            total = total.checked_add(&dec("0.1")).unwrap();
+        }
+        assert_eq!(total, Decimal::from(1));
+    }
+
+    #[test]
+    fn test_parsing_and_display() {
+        assert_eq!(dec("1.500"), dec("1.5"));
+        assert_eq!(dec("1.5e-3").to_string(), "0.0015");
+        assert_eq!(dec("-12.34").to_string(), "-12.34");
+        assert_eq!(dec("2e3").to_string(), "2000");
+        assert!(Decimal::from_str("abc").is_err());
+        assert!(Decimal::from_str(".").is_err());
+        assert!(dec("-0.5") < dec("0.25"));
+        assert_eq!(Decimal::from_f64(0.1), Some(dec("0.1")));
+        assert_eq!(Decimal::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_ordering_across_scales() {
+        // operands whose scales are too far apart to align
+        assert!(Decimal::zero() < dec("1e-50"));
+        assert!(dec("-1e-50") < Decimal::zero());
+        assert!(dec("-1e-50") < dec("1e-60"));
+        assert!(dec("1e-50") > dec("1e-60"));
+        assert!(dec("-1e-50") < dec("-1e-60"));
+        assert!(dec("12345678901234567890") > dec("0.000000000000000000000000000001"));
+        assert!(dec("-12345678901234567890") < dec("-0.000000000000000000000000000001"));
+        assert_eq!(dec("1e-50").cmp(&dec("1e-50")), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_serialization() {
+        // plain numbers from existing clients are accepted
+        let sb: SerializedBytes = TestFloatEntry { value: 0.3 }.try_into().unwrap();
+        let e: TestEntry = sb.try_into().unwrap();
+        assert_eq!(e.value, dec("0.3"));
+
+        // representable values are written as numbers
+        let sb: SerializedBytes = TestEntry { value: dec("0.3") }.try_into().unwrap();
+        let e: TestFloatEntry = sb.try_into().unwrap();
+        assert_eq!(e.value, 0.3);
+
+        // values which floats cannot hold are written as strings
+        let precise = dec("12345678901234567890.123456789");
+        let sb: SerializedBytes = TestEntry { value: precise }.try_into().unwrap();
+        let e: TestStringEntry = sb.clone().try_into().unwrap();
+        assert_eq!(e.value, "12345678901234567890.123456789");
+        let e: TestEntry = sb.try_into().unwrap();
+        assert_eq!(e.value, precise);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // values at and beyond the default scale survive serialization unchanged
+        for value in ["0.1", "-0.000000000000000001", "0.1234567890123456789012345", "99999999999999999999.99", "1e-30"] {
+            let sb: SerializedBytes = TestEntry { value: dec(value) }.try_into().unwrap();
+            let e: TestEntry = sb.try_into().unwrap();
+            assert_eq!(e.value, dec(value), "{} round-trips", value);
+            assert_eq!(Decimal::from_str(&e.value.to_string()), Ok(dec(value)), "{} round-trips via display", value);
+        }
+
+        // division retains the requested scale rather than the default
+        let third = dec("1").checked_div(&dec("3"), 30).unwrap();
+        assert_eq!(third.scale(), 30);
+        let sb: SerializedBytes = TestEntry { value: third }.try_into().unwrap();
+        let e: TestEntry = sb.try_into().unwrap();
+        assert_eq!(e.value, third);
+        assert_eq!(dec("1").checked_div(&dec("3"), 2), Some(dec("0.33")));
+    }
+}
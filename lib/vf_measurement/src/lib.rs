@@ -9,31 +9,96 @@ use vf_attributes_hdk::UnitId;
 use hdk_records::{RecordAPIResult, DataIntegrityError};
 use hdk::prelude::*;
 
+mod decimal;
+pub use decimal::{Decimal, ParseDecimalError, DEFAULT_DECIMAL_SCALE};
+
+/// Configuration object which may be set in the host DNA's properties
+#[derive(Serialize, Deserialize, Debug, SerializedBytes)]
+pub struct MeasurementConfiguration {
+    /// Number of decimal places retained when quantities are divided, scaled or converted between units
+    #[serde(default = "default_decimal_scale")]
+    pub decimal_scale: u32,
+}
+
+fn default_decimal_scale() -> u32 {
+    DEFAULT_DECIMAL_SCALE
+}
+
+/// Number of decimal places retained by lossy quantity arithmetic in the current DNA,
+/// as configured by the `decimal_scale` DNA property.
+///
+/// Falls back to `DEFAULT_DECIMAL_SCALE` where the property is unset or DNA info is unavailable.
+///
+pub fn decimal_scale() -> u32 {
+    dna_info().ok()
+        .and_then(|info| MeasurementConfiguration::try_from(info.properties).ok())
+        .map_or(DEFAULT_DECIMAL_SCALE, |config| config.decimal_scale)
+}
+
 #[derive(Debug, Clone)]
 pub struct Unit {
     pub id: UnitId,
     pub name: Option<String>,
     pub symbol: Option<String>,
+    pub conversion: Option<UnitConversion>,
+}
+
+/// Describes how to express a unit in terms of some other base unit, such that
+/// `value_in_base_unit = value * factor + offset`.
+///
+/// Base units may themselves be defined in terms of other units. Any units whose
+/// chains of base units end at the same root unit can be converted between.
+///
+#[derive(Clone, PartialEq, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitConversion {
+    pub base_unit: UnitId,
+    pub factor: Decimal,
+    #[serde(default)]
+    pub offset: Decimal,
+}
+
+impl<'a> Unit {
+    /// The unit which this unit's values are directly expressed in terms of
+    pub fn get_base_unit(&'a self) -> UnitId {
+        match &self.conversion {
+            Some(conversion) => conversion.base_unit.to_owned(),
+            None => self.id.to_owned(),
+        }
+    }
+
+    fn to_base_value(&'a self, value: &Decimal) -> Option<Decimal> {
+        match &self.conversion {
+            Some(UnitConversion { factor, offset, .. }) => value.checked_mul(factor)?.checked_add(offset),
+            None => Some(*value),
+        }
+    }
+
+    fn from_base_value(&'a self, value: &Decimal) -> Option<Decimal> {
+        match &self.conversion {
+            Some(UnitConversion { factor, offset, .. }) => value.checked_sub(offset)?.checked_div(factor, decimal_scale()),
+            None => Some(*value),
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct QuantityValue {
-    // :TODO: https://users.rust-lang.org/t/currency-in-rust/890/9 ?
-    has_numerical_value: f64,     // :NOTE: uses https://en.wikipedia.org/wiki/IEEE_754 for math
+    has_numerical_value: Decimal,
     #[serde(default)]
     has_unit: Option<UnitId>,
 }
 
 impl<'a> QuantityValue {
-    pub fn new(has_numerical_value: f64, has_unit: Option<UnitId>) -> QuantityValue {
+    pub fn new(has_numerical_value: Decimal, has_unit: Option<UnitId>) -> QuantityValue {
         QuantityValue {
             has_numerical_value,
             has_unit,
         }
     }
 
-    pub fn get_numerical_value(&'a self) -> f64 {
+    pub fn get_numerical_value(&'a self) -> Decimal {
         self.has_numerical_value.to_owned()
     }
 
@@ -48,7 +113,8 @@ pub fn add(q1: QuantityValue, q2: QuantityValue) -> RecordAPIResult<QuantityValu
     }
     Ok(
         QuantityValue {
-            has_numerical_value: q1.has_numerical_value + q2.has_numerical_value,
+            has_numerical_value: q1.has_numerical_value.checked_add(&q2.has_numerical_value)
+                .ok_or(DataIntegrityError::QuantityOverflow)?,
             has_unit: q1.has_unit,
         }
     )
@@ -60,8 +126,199 @@ pub fn subtract(q1: QuantityValue, q2: QuantityValue) -> RecordAPIResult<Quantit
     }
     Ok(
         QuantityValue {
-            has_numerical_value: q1.has_numerical_value - q2.has_numerical_value,
+            has_numerical_value: q1.has_numerical_value.checked_sub(&q2.has_numerical_value)
+                .ok_or(DataIntegrityError::QuantityOverflow)?,
             has_unit: q1.has_unit,
         }
     )
 }
+
+pub fn multiply(q: QuantityValue, factor: Decimal) -> RecordAPIResult<QuantityValue> {
+    Ok(
+        QuantityValue {
+            has_numerical_value: q.has_numerical_value.checked_mul(&factor)
+                .ok_or(DataIntegrityError::QuantityOverflow)?
+                .round_dp(decimal_scale()),
+            has_unit: q.has_unit,
+        }
+    )
+}
+
+/// Total a set of quantities, all of which must be expressed in `unit`.
+pub fn sum<I>(quantities: I, unit: Option<UnitId>) -> RecordAPIResult<QuantityValue>
+    where I: IntoIterator<Item = QuantityValue>,
+{
+    quantities.into_iter().try_fold(QuantityValue::new(Decimal::zero(), unit), add)
+}
+
+/// Total a set of quantities in `unit`, converting any which are expressed in other units.
+///
+/// `resolve_unit` is used to load conversion information, as for `convert`. Fails with
+/// `DataIntegrityError::MismatchingUnits` if any quantity cannot be expressed in `unit`.
+///
+pub fn sum_converted<I, F>(quantities: I, unit: Option<UnitId>, resolve_unit: F) -> RecordAPIResult<QuantityValue>
+    where I: IntoIterator<Item = QuantityValue>,
+        F: Fn(&UnitId) -> RecordAPIResult<Unit>,
+{
+    quantities.into_iter().try_fold(QuantityValue::new(Decimal::zero(), unit.to_owned()), |total, q| {
+        let q = match (&unit, &q.has_unit) {
+            (Some(to_unit), Some(from_unit)) if from_unit != to_unit => convert(q, to_unit, &resolve_unit)?,
+            _ => q,
+        };
+        add(total, q)
+    })
+}
+
+/// The portion of a `planned` quantity not yet accounted for by `completed`.
+/// Where `completed` exceeds `planned`, nothing remains.
+pub fn remaining(planned: QuantityValue, completed: QuantityValue) -> RecordAPIResult<QuantityValue> {
+    let result = subtract(planned, completed)?;
+    if result.has_numerical_value.is_negative() {
+        return Ok(QuantityValue::new(Decimal::zero(), result.has_unit));
+    }
+    Ok(result)
+}
+
+/// Express a quantity in a different unit of measure.
+///
+/// `resolve_unit` is used to load conversion information for the source and target units.
+/// Chains of base units are followed transitively, such that any two units ultimately
+/// expressed in terms of the same root unit can be converted between.
+///
+pub fn convert<F>(q: QuantityValue, to_unit: &UnitId, resolve_unit: F) -> RecordAPIResult<QuantityValue>
+    where F: Fn(&UnitId) -> RecordAPIResult<Unit>,
+{
+    let from_unit_id = match &q.has_unit {
+        Some(unit) if unit == to_unit => return Ok(q),
+        Some(unit) => unit.to_owned(),
+        None => return Err(DataIntegrityError::MismatchingUnits(None, Some(to_unit.1.to_owned()))),
+    };
+
+    let from_chain = read_base_unit_chain(&from_unit_id, &resolve_unit)?;
+    let to_chain = read_base_unit_chain(to_unit, &resolve_unit)?;
+    if from_chain.last().map(|u| &u.id) != to_chain.last().map(|u| &u.id) {
+        return Err(DataIntegrityError::MismatchingUnits(Some(from_unit_id.1), Some(to_unit.1.to_owned())));
+    }
+
+    // express in the root unit via the source unit's chain, then back down via the target's
+    let root_value = from_chain.iter()
+        .try_fold(q.has_numerical_value, |value, unit| unit.to_base_value(&value));
+    let value = root_value.and_then(|root_value| to_chain.iter().rev()
+        .try_fold(root_value, |value, unit| unit.from_base_value(&value)));
+
+    Ok(
+        QuantityValue {
+            has_numerical_value: value.ok_or(DataIntegrityError::QuantityOverflow)?,
+            has_unit: Some(to_unit.to_owned()),
+        }
+    )
+}
+
+/// Load `unit_id` and each of its successive base units, ending with the root unit
+/// which has no conversion of its own.
+///
+pub fn read_base_unit_chain<F>(unit_id: &UnitId, resolve_unit: &F) -> RecordAPIResult<Vec<Unit>>
+    where F: Fn(&UnitId) -> RecordAPIResult<Unit>,
+{
+    let mut chain: Vec<Unit> = vec![resolve_unit(unit_id)?];
+    while let Some((unit, conversion)) = chain.last().and_then(|u| u.conversion.as_ref().map(|c| (u, c))) {
+        // :NOTE: guards against records written before zero factors were rejected at creation
+        if conversion.factor.is_zero() {
+            return Err(DataIntegrityError::ZeroConversionFactor(unit.symbol.to_owned().unwrap_or(unit.id.1.to_owned())));
+        }
+        let base_unit = conversion.base_unit.to_owned();
+        if chain.iter().any(|u| u.id == base_unit) {
+            return Err(DataIntegrityError::InvalidUnitConversion(format!("unit '{}' is defined in terms of itself", base_unit.1)));
+        }
+        chain.push(resolve_unit(&base_unit)?);
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn unit_id(symbol: &str) -> UnitId {
+        UnitId(DnaHash::from_raw_36(vec![0; 36]), symbol.to_string())
+    }
+
+    fn unit(symbol: &str, base: Option<(&str, &str, &str)>) -> Unit {
+        Unit {
+            id: unit_id(symbol),
+            name: None,
+            symbol: Some(symbol.to_string()),
+            conversion: base.map(|(base_unit, factor, offset)| UnitConversion {
+                base_unit: unit_id(base_unit),
+                factor: dec(factor),
+                offset: dec(offset),
+            }),
+        }
+    }
+
+    fn quantity(value: &str, symbol: &str) -> QuantityValue {
+        QuantityValue::new(dec(value), Some(unit_id(symbol)))
+    }
+
+    fn units() -> HashMap<UnitId, Unit> {
+        vec![
+            unit("kg", None),
+            unit("g", Some(("kg", "0.001", "0"))),
+            unit("mg", Some(("g", "0.001", "0"))),
+            unit("t", Some(("kg", "1000", "0"))),
+            unit("K", None),
+            unit("Cel", Some(("K", "1", "273.15"))),
+            unit("loopA", Some(("loopB", "2", "0"))),
+            unit("loopB", Some(("loopA", "0.5", "0"))),
+            unit("broken", Some(("kg", "0", "0"))),
+        ].into_iter().map(|u| (u.id.to_owned(), u)).collect()
+    }
+
+    fn convert_between(q: QuantityValue, to_unit: &str) -> RecordAPIResult<QuantityValue> {
+        let units = units();
+        convert(q, &unit_id(to_unit), |id| units.get(id).cloned().ok_or(DataIntegrityError::EntryNotFound))
+    }
+
+    #[test]
+    fn test_convert_direct() {
+        assert_eq!(convert_between(quantity("1500", "g"), "kg").unwrap(), quantity("1.5", "kg"));
+        assert_eq!(convert_between(quantity("1.5", "kg"), "g").unwrap(), quantity("1500", "g"));
+        assert_eq!(convert_between(quantity("25", "Cel"), "K").unwrap(), quantity("298.15", "K"));
+        assert_eq!(convert_between(quantity("3", "kg"), "kg").unwrap(), quantity("3", "kg"));
+    }
+
+    #[test]
+    fn test_convert_chained() {
+        // two hops to the root unit and back
+        assert_eq!(convert_between(quantity("2500000", "mg"), "kg").unwrap(), quantity("2.5", "kg"));
+        assert_eq!(convert_between(quantity("2.5", "kg"), "mg").unwrap(), quantity("2500000", "mg"));
+        // derived units sharing a base unit
+        assert_eq!(convert_between(quantity("250", "g"), "t").unwrap(), quantity("0.00025", "t"));
+        assert_eq!(convert_between(quantity("0.002", "t"), "mg").unwrap(), quantity("2000000", "mg"));
+    }
+
+    #[test]
+    fn test_convert_incompatible() {
+        assert!(matches!(convert_between(quantity("1", "g"), "K"), Err(DataIntegrityError::MismatchingUnits(_, _))));
+        assert!(matches!(convert_between(quantity("1", "mg"), "Cel"), Err(DataIntegrityError::MismatchingUnits(_, _))));
+        assert!(matches!(convert_between(QuantityValue::new(dec("1"), None), "kg"), Err(DataIntegrityError::MismatchingUnits(None, _))));
+        assert!(matches!(convert_between(quantity("1", "kg"), "lb"), Err(DataIntegrityError::EntryNotFound)));
+    }
+
+    #[test]
+    fn test_convert_cyclic() {
+        assert!(matches!(convert_between(quantity("1", "loopA"), "kg"), Err(DataIntegrityError::InvalidUnitConversion(_))));
+    }
+
+    #[test]
+    fn test_convert_zero_factor() {
+        assert!(matches!(convert_between(quantity("1", "broken"), "kg"), Err(DataIntegrityError::ZeroConversionFactor(_))));
+        assert!(matches!(convert_between(quantity("1", "kg"), "broken"), Err(DataIntegrityError::ZeroConversionFactor(_))));
+    }
+}
@@ -21,6 +21,7 @@ import deepForEach from 'deep-for-each'
 import isObject from 'is-object'
 import { Buffer } from 'buffer'
 import { format, parse } from 'fecha'
+import Big from 'big.js'
 import { Base64 } from "js-base64"
 import { DNAIdMappings } from './types'
 
@@ -278,6 +279,11 @@ const encodeFields = (args: any): any => {
     return format(args, LONG_DATETIME_FORMAT)
   }
 
+  // encode decimals as strings, which zomes parse without loss of precision
+  else if (args instanceof Big) {
+    return args.toFixed()
+  }
+
   // deserialise any identifiers back to their binary format
   else if (args.match && args.match(serializedHashMatchRegex)) {
     return deserializeHash(args)
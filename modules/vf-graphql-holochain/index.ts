@@ -84,8 +84,8 @@ const bindSchema = async (options: BindSchemaOptions) => {
 
   // extend the base vf-graphql schema with one
   // or more holochain specific schema extensions.
-  // add more here if more are added.
-  const overriddenExtensionSchemas = [...extensionSchemas, hreaExtensionSchemas.associateMyAgentExtension]
+  // add more to `extensionSchemasFor` if more are added.
+  const overriddenExtensionSchemas = [...extensionSchemas, ...hreaExtensionSchemas.extensionSchemasFor(enabledVFModules)]
 
   return makeExecutableSchema({
     typeDefs: printSchema(buildSchema(enabledVFModules, overriddenExtensionSchemas)),
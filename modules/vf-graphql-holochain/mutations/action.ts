@@ -0,0 +1,72 @@
+/**
+ * Mutations for manipulating user-defined actions
+ *
+ * @package: hREA
+ * @since:   2026-10-17
+ */
+
+import { ByRevision, DeleteResponse, DNAIdMappings, IndexingReport } from '../types.js'
+import { mapZomeFn } from '../connection.js'
+import { deleteHandler } from './'
+
+import {
+  Action,
+} from '@valueflows/vf-graphql'
+
+// :NOTE: these types are declared by `customActionsExtension` rather than vf-graphql
+export interface ActionCreateParams {
+  id: string,
+  label: string,
+  resourceEffect: string,
+  onhandEffect: string,
+  inputOutput: string,
+  pairsWith?: string,
+}
+
+export interface ActionUpdateParams {
+  revisionId: string,
+  label?: string,
+  resourceEffect?: string,
+  onhandEffect?: string,
+  inputOutput?: string,
+  pairsWith?: string,
+}
+
+export interface ActionResponse {
+  action: Action,
+  indexingReport?: IndexingReport,
+}
+
+export interface CreateArgs {
+  action: ActionCreateParams,
+}
+export type createHandler = (root: any, args: CreateArgs) => Promise<ActionResponse>
+
+export interface UpdateArgs {
+  action: ActionUpdateParams,
+}
+export type updateHandler = (root: any, args: UpdateArgs) => Promise<ActionResponse>
+
+export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
+  const runCreate = mapZomeFn<CreateArgs, ActionResponse>(dnaConfig, conductorUri, 'specification', 'action', 'create_action')
+  const runUpdate = mapZomeFn<UpdateArgs, ActionResponse>(dnaConfig, conductorUri, 'specification', 'action', 'update_action')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'specification', 'action', 'delete_action')
+
+  const createAction: createHandler = async (root, args) => {
+    return runCreate(args)
+  }
+
+  const updateAction: updateHandler = async (root, args) => {
+    return runUpdate(args)
+  }
+
+  const deleteAction: deleteHandler = async (root, args) => {
+    return (await runDelete(args)).deleted
+  }
+
+  return {
+    createAction,
+    updateAction,
+    deleteAction,
+  }
+}
@@ -5,7 +5,7 @@
  * @since:   2022-06-08
  */
 
-import { AgentAddress, ByRevision, DNAIdMappings } from '../types.js'
+import { AgentAddress, ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -59,7 +59,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreateAgent = mapZomeFn<AgentCreateArgs, AgentResponse>(dnaConfig, conductorUri, 'agent', 'agent', 'create_agent')
   const runAssociateMyAgent = mapZomeFn<AssociateAgentParams, boolean>(dnaConfig, conductorUri, 'agent', 'agent', 'associate_my_agent')
   const runUpdateAgent = mapZomeFn<AgentUpdateArgs, AgentResponse>(dnaConfig, conductorUri, 'agent', 'agent', 'update_agent')
-  const runDeleteAgent = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'agent', 'agent', 'delete_agent')
+  const runDeleteAgent = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'agent', 'agent', 'delete_agent')
 
   const createPerson: createPersonHandler = async (root, args) => {
     const createAgentArgs = {
@@ -85,7 +85,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deletePerson: deleteHandler = async (root, args) => {
-    return runDeleteAgent(args)
+    return (await runDeleteAgent(args)).deleted
   }
 
   const createOrganization: createOrganizationHandler = async (root, args) => {
@@ -108,7 +108,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteOrganization: deleteHandler = async (root, args) => {
-    return runDeleteAgent(args)
+    return (await runDeleteAgent(args)).deleted
   }
 
   const createAgentRelationship = () => {
@@ -5,7 +5,7 @@
  * @since:   2020-06-19
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<AgreementRe
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, AgreementResponse>(dnaConfig, conductorUri, 'agreement', 'agreement', 'create_agreement')
   const runUpdate = mapZomeFn<UpdateArgs, AgreementResponse>(dnaConfig, conductorUri, 'agreement', 'agreement', 'update_agreement')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'agreement', 'agreement', 'delete_agreement')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'agreement', 'agreement', 'delete_agreement')
 
   const createAgreement: createHandler = async (root, args) => {
     // :SHONK: Inject current time as `created` if not present.
@@ -44,7 +44,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteAgreement: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
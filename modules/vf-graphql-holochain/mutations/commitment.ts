@@ -5,7 +5,7 @@
  * @since:   2019-08-28
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<CommitmentR
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, CommitmentResponse>(dnaConfig, conductorUri, 'planning', 'commitment', 'create_commitment')
   const runUpdate = mapZomeFn<UpdateArgs, CommitmentResponse>(dnaConfig, conductorUri, 'planning', 'commitment', 'update_commitment')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'planning', 'commitment', 'delete_commitment')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'planning', 'commitment', 'delete_commitment')
 
   const createCommitment: createHandler = async (root, args) => {
     return runCreate(args)
@@ -39,7 +39,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteCommitment: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
@@ -5,11 +5,12 @@
  * @since:   2019-05-27
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings, EconomicEventAddress } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
 import {
+  EconomicEvent,
   EconomicEventCreateParams,
   EconomicResourceCreateParams,
   EconomicEventUpdateParams,
@@ -28,10 +29,21 @@ export interface UpdateArgs {
 
 export type updateHandler = (root: any, args: UpdateArgs) => Promise<EconomicEventResponse>
 
+export interface CorrectionArgs {
+  event: EconomicEventAddress,
+  replacement?: EconomicEventCreateParams,
+}
+export interface EconomicEventCorrectionResponse {
+  reversal: EconomicEvent,
+  replacement?: EconomicEvent,
+}
+export type correctionHandler = (root: any, args: CorrectionArgs) => Promise<EconomicEventCorrectionResponse>
+
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, EconomicEventResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'create_economic_event')
   const runUpdate = mapZomeFn<UpdateArgs, EconomicEventResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'update_economic_event')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'observation', 'economic_event', 'delete_economic_event')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'delete_economic_event')
+  const runCorrect = mapZomeFn<CorrectionArgs, EconomicEventCorrectionResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'correct_economic_event')
 
   const createEconomicEvent: createHandler = async (root, args) => {
     return runCreate(args)
@@ -41,8 +53,13 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
     return runUpdate(args)
   }
 
+  const correctEconomicEvent: correctionHandler = async (root, args) => {
+    return runCorrect(args)
+  }
+
   return {
     createEconomicEvent,
     updateEconomicEvent,
+    correctEconomicEvent,
   }
 }
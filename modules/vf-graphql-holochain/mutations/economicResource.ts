@@ -5,12 +5,14 @@
  * @since:   2019-10-31
  */
 
-import { DNAIdMappings } from '../types.js'
+import { DNAIdMappings, EconomicResourceAddress, ReadParams } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 
 import {
+  EconomicResource,
   EconomicResourceUpdateParams,
   EconomicResourceResponse,
+  Measure,
 } from '@valueflows/vf-graphql'
 
 export interface UpdateArgs {
@@ -18,14 +20,47 @@ export interface UpdateArgs {
 }
 export type updateHandler = (root: any, args: UpdateArgs) => Promise<EconomicResourceResponse>
 
+export interface RecomputeArgs {
+  id: EconomicResourceAddress,
+  write?: boolean,
+}
+interface RecomputeParams {
+  address: EconomicResourceAddress,
+  write?: boolean,
+}
+interface RecomputeResult {
+  economicResource: EconomicResourceAddress,
+  eventsReplayed: number,
+  storedAccountingQuantity?: Measure,
+  storedOnhandQuantity?: Measure,
+  computedAccountingQuantity?: Measure,
+  computedOnhandQuantity?: Measure,
+  consistent: boolean,
+  revisionId?: string,
+}
+export interface EconomicResourceRecomputeResponse extends Omit<RecomputeResult, 'economicResource'> {
+  economicResource: EconomicResource,
+}
+export type recomputeHandler = (root: any, args: RecomputeArgs) => Promise<EconomicResourceRecomputeResponse>
+
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runUpdate = mapZomeFn<UpdateArgs, EconomicResourceResponse>(dnaConfig, conductorUri, 'observation', 'economic_resource', 'update_economic_resource')
+  const runRecompute = mapZomeFn<RecomputeParams, RecomputeResult>(dnaConfig, conductorUri, 'observation', 'economic_resource', 'recompute_economic_resource')
+  const readOne = mapZomeFn<ReadParams, EconomicResourceResponse>(dnaConfig, conductorUri, 'observation', 'economic_resource', 'get_economic_resource')
 
   const updateEconomicResource: updateHandler = async (root, args) => {
     return runUpdate(args)
   }
 
+  const recomputeEconomicResource: recomputeHandler = async (root, args) => {
+    const result = await runRecompute({ address: args.id, write: args.write || false })
+    // read back after recomputing, so that any corrected quantities are returned
+    const resource = await readOne({ address: args.id })
+    return { ...result, economicResource: resource.economicResource }
+  }
+
   return {
     updateEconomicResource,
+    recomputeEconomicResource,
   }
 }
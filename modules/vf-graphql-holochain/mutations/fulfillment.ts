@@ -5,7 +5,7 @@
  * @since:   2019-08-28
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<Fulfillment
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, FulfillmentResponse>(dnaConfig, conductorUri, 'planning', 'fulfillment', 'create_fulfillment')
   const runUpdate = mapZomeFn<UpdateArgs, FulfillmentResponse>(dnaConfig, conductorUri, 'planning', 'fulfillment', 'update_fulfillment')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'planning', 'fulfillment', 'delete_fulfillment')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'planning', 'fulfillment', 'delete_fulfillment')
 
   const createFulfillment: createHandler = async (root, args) => {
     return runCreate(args)
@@ -39,7 +39,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteFulfillment: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
@@ -7,6 +7,7 @@
 
 import { DNAIdMappings, DEFAULT_VF_MODULES, VfModule, ByRevision } from '../types.js'
 
+import Action from './action.js'
 import ResourceSpecification from './resourceSpecification.js'
 import ProcessSpecification from './processSpecification.js'
 import Unit from './unit.js'
@@ -30,6 +31,7 @@ export type deleteHandler = (root: any, args: ByRevision) => Promise<boolean>
 export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DNAIdMappings, conductorUri: string) => {
   const hasAgent = -1 !== enabledVFModules.indexOf(VfModule.Agent)
   const hasMeasurement = -1 !== enabledVFModules.indexOf(VfModule.Measurement)
+  const hasAction = -1 !== enabledVFModules.indexOf(VfModule.Action)
   const hasProcessSpecification = -1 !== enabledVFModules.indexOf(VfModule.ProcessSpecification)
   const hasResourceSpecification = -1 !== enabledVFModules.indexOf(VfModule.ResourceSpecification)
   const hasObservation = -1 !== enabledVFModules.indexOf(VfModule.Observation)
@@ -43,6 +45,7 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
   const hasPlan = -1 !== enabledVFModules.indexOf(VfModule.Plan)
 
   return Object.assign(
+    (hasAction ? { ...Action(dnaConfig, conductorUri) } : {}),
     (hasMeasurement ? { ...Unit(dnaConfig, conductorUri) } : {}),
     (hasResourceSpecification ? {
       ...ResourceSpecification(dnaConfig, conductorUri),
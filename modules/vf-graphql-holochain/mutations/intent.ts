@@ -5,7 +5,7 @@
  * @since:   2019-08-31
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<IntentRespo
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, IntentResponse>(dnaConfig, conductorUri, 'planning', 'intent', 'create_intent')
   const runUpdate = mapZomeFn<UpdateArgs, IntentResponse>(dnaConfig, conductorUri, 'planning', 'intent', 'update_intent')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'planning', 'intent', 'delete_intent')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'planning', 'intent', 'delete_intent')
 
   const createIntent: createHandler = async (root, args) => {
     return runCreate(args)
@@ -39,7 +39,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteIntent: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
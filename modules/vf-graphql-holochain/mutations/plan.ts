@@ -5,7 +5,7 @@
  * @since:   2022-05-23
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<PlanRespons
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, PlanResponse>(dnaConfig, conductorUri, 'plan', 'plan', 'create_plan')
   const runUpdate = mapZomeFn<UpdateArgs, PlanResponse>(dnaConfig, conductorUri, 'plan', 'plan', 'update_plan')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'plan', 'plan', 'delete_plan')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'plan', 'plan', 'delete_plan')
 
   const createPlan: createHandler = async (root, args) => {
     return runCreate(args)
@@ -39,7 +39,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deletePlan: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
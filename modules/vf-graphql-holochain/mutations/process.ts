@@ -5,7 +5,7 @@
  * @since:   2019-09-12
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<ProcessResp
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const createHandler = mapZomeFn<CreateArgs, ProcessResponse>(dnaConfig, conductorUri, 'observation', 'process', 'create_process')
   const updateHandler = mapZomeFn<UpdateArgs, ProcessResponse>(dnaConfig, conductorUri, 'observation', 'process', 'update_process')
-  const deleteHandler = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'observation', 'process', 'delete_process')
+  const deleteHandler = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'observation', 'process', 'delete_process')
 
   const createProcess: createHandler = async (root, args) => {
     return createHandler(args)
@@ -39,7 +39,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteProcess: deleteHandler = async (root, args) => {
-    return deleteHandler(args)
+    return (await deleteHandler(args)).deleted
   }
 
   return {
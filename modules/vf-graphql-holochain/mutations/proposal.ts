@@ -5,7 +5,7 @@
  * @since:   2019-09-12
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<ProposalRes
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, ProposalResponse>(dnaConfig, conductorUri, 'proposal', 'proposal', 'create_proposal')
   const runUpdate = mapZomeFn<UpdateArgs, ProposalResponse>(dnaConfig, conductorUri, 'proposal', 'proposal', 'update_proposal')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'proposal', 'proposal', 'delete_proposal')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'proposal', 'proposal', 'delete_proposal')
 
   const createProposal: createHandler = async (root, args) => {
     return runCreate(args)
@@ -39,7 +39,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteProposal: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
@@ -5,7 +5,7 @@
  * @since:   2019-09-12
  */
 
-import { ByRevision, DNAIdMappings, IntentAddress, ProposalAddress } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings, IntentAddress, ProposalAddress } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -25,14 +25,14 @@ export type createHandler = (root: any, args: CreateRequest) => Promise<Proposed
 
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateParams, ProposedIntentResponse>(dnaConfig, conductorUri, 'proposal', 'proposed_intent', 'create_proposed_intent')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'proposal', 'proposed_intent', 'delete_proposed_intent')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'proposal', 'proposed_intent', 'delete_proposed_intent')
 
   const proposeIntent: createHandler = async (root, args) => {
     return runCreate({ proposedIntent: args })
   }
 
   const deleteProposedIntent: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
@@ -5,7 +5,7 @@
  * @since:   2019-09-12
  */
 
-import { AgentAddress, ByRevision, DNAIdMappings, ProposalAddress } from '../types.js'
+import { AgentAddress, ByRevision, DeleteResponse, DNAIdMappings, ProposalAddress } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -24,14 +24,14 @@ export type createHandler = (root: any, args: CreateRequest) => Promise<Proposed
 
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateParams, ProposedToResponse>(dnaConfig, conductorUri, 'proposal', 'proposed_to', 'create_proposed_to')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'proposal', 'proposed_to', 'delete_proposed_to')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'proposal', 'proposed_to', 'delete_proposed_to')
 
   const proposeTo: createHandler = async (root, args) => {
     return runCreate({ proposedTo: args })
   }
 
   const deleteProposedTo: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
@@ -5,7 +5,7 @@
  * @since:   2019-08-31
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DeleteResponse, DNAIdMappings } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -28,7 +28,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<Satisfactio
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, SatisfactionResponse>(dnaConfig, conductorUri, 'planning', 'satisfaction', 'create_satisfaction')
   const runUpdate = mapZomeFn<UpdateArgs, SatisfactionResponse>(dnaConfig, conductorUri, 'planning', 'satisfaction', 'update_satisfaction')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'planning', 'satisfaction', 'delete_satisfaction')
+  const runDelete = mapZomeFn<ByRevision, DeleteResponse>(dnaConfig, conductorUri, 'planning', 'satisfaction', 'delete_satisfaction')
 
   const createSatisfaction: createHandler = async (root, args) => {
     return runCreate(args)
@@ -39,7 +39,7 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   }
 
   const deleteSatisfaction: deleteHandler = async (root, args) => {
-    return runDelete(args)
+    return (await runDelete(args)).deleted
   }
 
   return {
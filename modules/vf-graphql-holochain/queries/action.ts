@@ -5,16 +5,18 @@
  * @since:   2019-12-23
  */
 
-import { DNAIdMappings, ReadParams } from '../types.js'
+import { DNAIdMappings, ReadParams, ById } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 
 import {
   Action,
 } from '@valueflows/vf-graphql'
+import { ActionResponse } from '../mutations/action.js'
 
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const read = mapZomeFn<ReadParams, Action>(dnaConfig, conductorUri, 'specification', 'action', 'get_action')
   const readAll = mapZomeFn<null, Action[]>(dnaConfig, conductorUri, 'specification', 'action', 'get_all_actions')
+  const readCustom = mapZomeFn<ById, ActionResponse>(dnaConfig, conductorUri, 'specification', 'action', 'get_custom_action')
 
   return {
     action: async (root, args): Promise<Action> => {
@@ -24,5 +26,9 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
     actions: async (root, args): Promise<Action[]> => {
       return readAll(null)
     },
+
+    customAction: async (root, args): Promise<Action> => {
+      return (await readCustom(args)).action
+    },
   }
 }
@@ -11,11 +11,12 @@ import { mapZomeFn } from '../connection.js'
 import {
   Commitment, CommitmentConnection, CommitmentResponse,
 } from '@valueflows/vf-graphql'
-import { PagingParams } from '../resolvers/zomeSearchInputTypes.js'
+import { PagingParams, TimeRangeParams } from '../resolvers/zomeSearchInputTypes.js'
 
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const readOne = mapZomeFn<ReadParams, CommitmentResponse>(dnaConfig, conductorUri, 'planning', 'commitment', 'get_commitment')
   const readAll = mapZomeFn<PagingParams, CommitmentConnection>(dnaConfig, conductorUri, 'planning', 'commitment_index', 'read_all_commitments')
+  const readBetween = mapZomeFn<TimeRangeParams, CommitmentConnection>(dnaConfig, conductorUri, 'planning', 'commitment_index', 'read_all_commitments_between')
 
   return {
     commitment: injectTypename('Commitment', async (root, args): Promise<Commitment> => {
@@ -25,5 +26,9 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
     commitments: async (root, args: PagingParams): Promise<CommitmentConnection> => {
       return await readAll(args)
     },
+
+    commitmentsBetween: async (root, args: TimeRangeParams): Promise<CommitmentConnection> => {
+      return await readBetween(args)
+    },
   }
 }
@@ -13,11 +13,12 @@ import {
   EconomicEventConnection,
   EconomicEventResponse,
 } from '@valueflows/vf-graphql'
-import { PagingParams } from '../resolvers/zomeSearchInputTypes.js'
+import { PagingParams, TimeRangeParams } from '../resolvers/zomeSearchInputTypes.js'
 
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const readOne = mapZomeFn<ReadParams, EconomicEventResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'get_economic_event')
   const readAll = mapZomeFn<PagingParams, EconomicEventConnection>(dnaConfig, conductorUri, 'observation', 'economic_event_index', 'read_all_economic_events')
+  const readBetween = mapZomeFn<TimeRangeParams, EconomicEventConnection>(dnaConfig, conductorUri, 'observation', 'economic_event_index', 'read_all_economic_events_between')
 
   return {
     economicEvent: injectTypename('EconomicEvent', async (root, args: { id: EconomicEventAddress }): Promise<EconomicEvent> => {
@@ -27,5 +28,9 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
     economicEvents: async (root, args: PagingParams): Promise<EconomicEventConnection> => {
       return await readAll(args)
     },
+
+    economicEventsBetween: async (root, args: TimeRangeParams): Promise<EconomicEventConnection> => {
+      return await readBetween(args)
+    },
   }
 }
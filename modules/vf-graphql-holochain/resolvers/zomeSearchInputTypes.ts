@@ -1,18 +1,34 @@
-import { EntryHash } from "@holochain/client"
 import { AgentAddress, AgreementAddress, CommitmentAddress, EconomicEventAddress, EconomicResourceAddress, FulfillmentAddress, IntentAddress, PlanAddress, ProcessAddress, ProposedIntentAddress, ResourceSpecificationAddress, SatisfactionAddress } from "../types";
 
 // this type name
 // matches the Rust side type name
 export interface PagingParams {
-    // TODO: forwards pagination
-    // first: number
-    // after: string
+    first?: number,
+    after?: string,
     last?: number,
-    before?: EntryHash
+    before?: string,
+    // name of the time index to order results by, defaulting to creation time
+    orderBy?: string,
 }
 
+// matches `TimeRangeParams` on the Rust side
+export interface TimeRangeParams {
+    from: Date,
+    to: Date,
+    first?: number,
+    after?: string,
+    last?: number,
+    before?: string,
+    orderBy?: string,
+}
+
+// all fields in `params` must match; nested conditions may be composed with
+// `and` (all must match), `or` (any must match) and `not` (must not match)
 interface SearchInput<QueryParamType> {
-  params: QueryParamType,
+  params?: QueryParamType,
+  and?: SearchInput<QueryParamType>[],
+  or?: SearchInput<QueryParamType>[],
+  not?: SearchInput<QueryParamType>,
 }
 
 export type CommitmentSearchInput = SearchInput<CommitmentQueryParam>
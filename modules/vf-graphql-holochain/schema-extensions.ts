@@ -1,3 +1,5 @@
+import { VfModule } from './types.js'
+
 const associateMyAgentExtension = `
 type Mutation  {
     "Associates the Agent identified by agentId with the currently authenticated user. Can only be used once."
@@ -5,4 +7,200 @@ type Mutation  {
 }
 `;
 
-export { associateMyAgentExtension };
+const customActionsExtension = `
+type Action {
+    "Revision of a user-defined action, for use in updating or deleting it. Not set for builtin ValueFlows actions."
+    revisionId: ID
+}
+
+type ActionResponse {
+    action: Action!
+}
+
+input ActionCreateParams {
+    id: String!
+    label: String!
+    resourceEffect: String!
+    onhandEffect: String!
+    inputOutput: String!
+    pairsWith: String
+}
+
+input ActionUpdateParams {
+    revisionId: ID!
+    label: String
+    resourceEffect: String
+    onhandEffect: String
+    inputOutput: String
+    pairsWith: String
+}
+
+type Query {
+    "Reads a user-defined action along with its revision metadata."
+    customAction(id: ID!): Action
+}
+
+type Mutation {
+    "Defines an action alongside the builtin ValueFlows actions. Action IDs cannot be changed once created."
+    createAction(action: ActionCreateParams!): ActionResponse!
+    updateAction(action: ActionUpdateParams!): ActionResponse!
+    deleteAction(revisionId: ID!): Boolean!
+}
+`;
+
+const eventCorrectionsExtension = `
+type EconomicEventCorrectionResponse {
+    "Event reversing the effects of the corrected event."
+    reversal: EconomicEvent!
+    "Event recorded in place of the corrected event, if any."
+    replacement: EconomicEvent
+}
+
+type Mutation {
+    "Corrects an EconomicEvent by recording its reversal, and optionally a replacement event. Events are never edited in place."
+    correctEconomicEvent(event: ID!, replacement: EconomicEventCreateParams): EconomicEventCorrectionResponse!
+}
+`;
+
+const resourceRecomputeExtension = `
+type EconomicResourceRecomputeResponse {
+    "The resource, including any corrections written to its quantities."
+    economicResource: EconomicResource!
+    eventsReplayed: Int!
+    storedAccountingQuantity: Measure
+    storedOnhandQuantity: Measure
+    computedAccountingQuantity: Measure
+    computedOnhandQuantity: Measure
+    "Whether the stored quantities matched those computed from the resource's event history."
+    consistent: Boolean!
+    "Revision written to correct the stored quantities, if any."
+    revisionId: ID
+}
+
+type Mutation {
+    "Replays the event history of an EconomicResource to check its stored quantities, correcting any discrepancy if 'write' is set."
+    recomputeEconomicResource(id: ID!, write: Boolean): EconomicResourceRecomputeResponse!
+}
+`;
+
+const commitmentQuantitiesExtension = `
+type Commitment {
+    "Total quantity of all Fulfillments of this Commitment. Not computed for records read via list queries."
+    fulfilledQuantity: Measure
+    "Portion of resourceQuantity not yet fulfilled. Not computed for records read via list queries."
+    remainingQuantity: Measure
+    "Reason fulfilledQuantity and remainingQuantity could not be computed, such as fulfillments recorded in incompatible units."
+    fulfilledQuantityError: String
+}
+`;
+
+const intentQuantitiesExtension = `
+type Intent {
+    "Total quantity of all Satisfactions of this Intent. Not computed for records read via list queries."
+    satisfiedQuantity: Measure
+    "Portion of resourceQuantity not yet satisfied. Not computed for records read via list queries."
+    remainingQuantity: Measure
+    "Reason satisfiedQuantity and remainingQuantity could not be computed, such as satisfactions recorded in incompatible units."
+    satisfiedQuantityError: String
+}
+`;
+
+const commitmentTimeRangeExtension = `
+type Query {
+    "Lists Commitments indexed at or after 'from' and before 'to', most recent first. Paged as for 'commitments'."
+    commitmentsBetween(from: DateTime!, to: DateTime!, first: Int, after: String, last: Int, before: String, orderBy: String): CommitmentConnection!
+}
+`;
+
+const commitmentOrderingExtension = `
+type Query {
+    "Commitments may be ordered by 'created' (the default) or 'due' time."
+    commitments(orderBy: String): CommitmentConnection
+}
+`;
+
+const economicEventTimeRangeExtension = `
+type Query {
+    "Lists EconomicEvents indexed at or after 'from' and before 'to', most recent first. Paged as for 'economicEvents'."
+    economicEventsBetween(from: DateTime!, to: DateTime!, first: Int, after: String, last: Int, before: String, orderBy: String): EconomicEventConnection!
+}
+`;
+
+const economicEventOrderingExtension = `
+type Query {
+    "EconomicEvents may be ordered by 'created' (the default), 'has_point_in_time', 'has_beginning' or 'has_end' time."
+    economicEvents(orderBy: String): EconomicEventConnection
+}
+`;
+
+const indexingReportExtension = `
+"Outcome of the index updates made whilst writing a record."
+type IndexingReport {
+    "Relationships for which all index updates succeeded."
+    succeeded: [String!]!
+    failed: [IndexingFailure!]!
+}
+
+type IndexingFailure {
+    relationship: String!
+    errors: [String!]!
+}
+`;
+
+// adds indexing reports to the mutation responses of the given record types
+const indexingReportFieldsExtension = (responseTypes: string[]): string => responseTypes.map((t) => `
+type ${t} {
+    indexingReport: IndexingReport
+}
+`).join('');
+
+// hREA-specific schema extensions matching the resolvers bound for a given set of VF modules
+const extensionSchemasFor = (enabledVFModules: VfModule[]): string[] => {
+  const hasAction = -1 !== enabledVFModules.indexOf(VfModule.Action)
+  const hasObservation = -1 !== enabledVFModules.indexOf(VfModule.Observation)
+  const hasCommitment = -1 !== enabledVFModules.indexOf(VfModule.Commitment)
+  const hasIntent = -1 !== enabledVFModules.indexOf(VfModule.Intent)
+  const hasAgent = -1 !== enabledVFModules.indexOf(VfModule.Agent)
+  const hasAgreement = -1 !== enabledVFModules.indexOf(VfModule.Agreement)
+  const hasMeasurement = -1 !== enabledVFModules.indexOf(VfModule.Measurement)
+  const hasResourceSpecification = -1 !== enabledVFModules.indexOf(VfModule.ResourceSpecification)
+  const hasProcessSpecification = -1 !== enabledVFModules.indexOf(VfModule.ProcessSpecification)
+  const hasProcess = -1 !== enabledVFModules.indexOf(VfModule.Process)
+  const hasFulfillment = -1 !== enabledVFModules.indexOf(VfModule.Fulfillment)
+  const hasSatisfaction = -1 !== enabledVFModules.indexOf(VfModule.Satisfaction)
+  const hasProposal = -1 !== enabledVFModules.indexOf(VfModule.Proposal)
+  const hasPlan = -1 !== enabledVFModules.indexOf(VfModule.Plan)
+
+  const reportingResponseTypes = [
+    ...(hasAction ? ['ActionResponse'] : []),
+    ...(hasAgent ? ['PersonResponse', 'OrganizationResponse'] : []),
+    ...(hasAgreement ? ['AgreementResponse'] : []),
+    ...(hasMeasurement ? ['UnitResponse'] : []),
+    ...(hasResourceSpecification ? ['ResourceSpecificationResponse'] : []),
+    ...(hasProcessSpecification ? ['ProcessSpecificationResponse'] : []),
+    ...(hasObservation ? ['EconomicEventResponse'] : []),
+    ...(hasProcess ? ['ProcessResponse'] : []),
+    ...(hasCommitment ? ['CommitmentResponse'] : []),
+    ...(hasFulfillment ? ['FulfillmentResponse'] : []),
+    ...(hasIntent ? ['IntentResponse'] : []),
+    ...(hasSatisfaction ? ['SatisfactionResponse'] : []),
+    ...(hasProposal ? ['ProposalResponse', 'ProposedIntentResponse', 'ProposedToResponse'] : []),
+    ...(hasPlan ? ['PlanResponse'] : []),
+  ]
+
+  return [
+    indexingReportExtension,
+    indexingReportFieldsExtension(reportingResponseTypes),
+    associateMyAgentExtension,
+    ...(hasAction ? [customActionsExtension] : []),
+    ...(hasObservation ? [eventCorrectionsExtension, resourceRecomputeExtension, economicEventTimeRangeExtension, economicEventOrderingExtension] : []),
+    ...(hasCommitment ? [commitmentQuantitiesExtension, commitmentTimeRangeExtension, commitmentOrderingExtension] : []),
+    ...(hasIntent ? [intentQuantitiesExtension] : []),
+  ]
+}
+
+export { associateMyAgentExtension, customActionsExtension, eventCorrectionsExtension, resourceRecomputeExtension,
+  commitmentQuantitiesExtension, intentQuantitiesExtension,
+  indexingReportExtension, indexingReportFieldsExtension,
+  commitmentTimeRangeExtension, economicEventTimeRangeExtension,
+  commitmentOrderingExtension, economicEventOrderingExtension, extensionSchemasFor };
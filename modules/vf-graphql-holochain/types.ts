@@ -101,6 +101,15 @@ export type ProcessSpecificationAddress = AddressableIdentifier
 export interface ByRevision {
   revisionId: string
 }
+// outcome of index updates made whilst writing a record, @see `indexingReportExtension`
+export interface IndexingReport {
+  succeeded: string[],
+  failed: { relationship: string, errors: string[] }[],
+}
+export interface DeleteResponse {
+  deleted: boolean,
+  indexingReport?: IndexingReport,
+}
 // end of section
 
 // helpers for resolvers to inject __typename parameter for union type disambiguation
@@ -213,12 +222,13 @@ export const URI = new GraphQLScalarType({
   },
 })
 
-// :TODO: this should be a GraphQLScalarType<Big, string> to avoid precision loss at API boundary
-export const Decimal: GraphQLScalarType<Big, number> = new GraphQLScalarType({
+// Decimals are emitted as strings to avoid precision loss at the API boundary.
+// Zome responses contain plain numbers where lossless and decimal strings otherwise; both are accepted.
+export const Decimal: GraphQLScalarType<Big, string> = new GraphQLScalarType({
   name: 'Decimal',
   description: 'The `Decimal` scalar type to handle precision arithmetic and potentially large values.',
-  serialize: (v: unknown) => (v as Big).toNumber(),
-  parseValue: (v: unknown) => Big(v as number),
+  serialize: (v: unknown) => Big(v as Big | number | string).toFixed(),
+  parseValue: (v: unknown) => Big(v as number | string),
   parseLiteral(ast: ValueNode) {
     if (ast.kind !== Kind.STRING && ast.kind !== Kind.INT && ast.kind !== Kind.FLOAT) {
       // @ts-ignore
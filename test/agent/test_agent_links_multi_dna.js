@@ -0,0 +1,100 @@
+import test from 'tape'
+import { pause, Scenario } from '@holochain/tryorama'
+import {
+  getDNA,
+  mockIdentifier,
+} from '../init.js'
+
+const testProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+const buildCell = (player, role) => {
+  const cell = player.namedCells.get(role)
+  cell.call = (zomeName, fnName, payload) => cell.callZome({
+    zome_name: zomeName,
+    fn_name: fnName,
+    payload,
+  }, 60000)
+  return cell
+}
+
+test('Records indexed into multiple remote DNAs', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const scenario = new Scenario({ timeout: 60000 })
+  try {
+    // two separate agent networks, distinguished by network seed so that their DNA hashes differ
+    const player = await scenario.addPlayerWithApp({
+      bundle: {
+        manifest: {
+          name: 'installed-app-id',
+          manifest_version: '1',
+          roles: [
+            { name: 'hrea_planning_1', provisioning: { strategy: 'create', deferred: false }, dna: { path: getDNA('planning') } },
+            { name: 'hrea_agent_1', provisioning: { strategy: 'create', deferred: false }, dna: { path: getDNA('agent'), modifiers: { network_seed: 'agents-1' } } },
+            { name: 'hrea_agent_2', provisioning: { strategy: 'create', deferred: false }, dna: { path: getDNA('agent'), modifiers: { network_seed: 'agents-2' } } },
+          ],
+        },
+        resources: {},
+      },
+    })
+    const planning = buildCell(player, 'hrea_planning_1')
+    const agents1 = buildCell(player, 'hrea_agent_1')
+    const agents2 = buildCell(player, 'hrea_agent_2')
+
+    let resp = await agents1.call('agent', 'create_agent', { agent: { name: 'alice', agentType: 'Person' } })
+    const aliceId = resp.agent.id
+    resp = await agents1.call('agent', 'create_agent', { agent: { name: 'carol', agentType: 'Person' } })
+    const carolId = resp.agent.id
+    resp = await agents2.call('agent', 'create_agent', { agent: { name: 'bob', agentType: 'Person' } })
+    const bobId = resp.agent.id
+    await pause(100)
+    t.notDeepEqual(aliceId[0], bobId[0], 'agents reside in different DNAs')
+
+    // SCENARIO: commitment between agents in different DNAs
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, provider: aliceId, receiver: bobId } })
+    t.deepEqual(resp.indexingReport.failed, [], 'no index updates failed on create')
+    const commitment = resp.commitment
+    await pause(100)
+
+    resp = await agents1.call('agent', 'get_agent', { address: aliceId })
+    t.deepLooseEqual(resp.agent.commitmentsAsProvider, [commitment.id], 'provider indexed in first agent DNA')
+    resp = await agents2.call('agent', 'get_agent', { address: bobId })
+    t.deepLooseEqual(resp.agent.commitmentsAsReceiver, [commitment.id], 'receiver indexed in second agent DNA')
+
+    // SCENARIO: move provider to another DNA, so that one index update adds into one DNA and removes from the other
+    resp = await planning.call('commitment', 'update_commitment', { commitment: { revisionId: commitment.revisionId, provider: bobId } })
+    const updatedRevisionId = resp.commitment.revisionId
+    t.ok(resp.indexingReport.succeeded.includes('commitment_provider'), 'provider index update reported')
+    t.deepEqual(resp.indexingReport.failed, [], 'no index updates failed in either DNA')
+    await pause(100)
+
+    resp = await agents1.call('agent', 'get_agent', { address: aliceId })
+    t.deepLooseEqual(resp.agent.commitmentsAsProvider, [], 'previous provider index removed from first agent DNA')
+    resp = await agents2.call('agent', 'get_agent', { address: bobId })
+    t.deepLooseEqual(resp.agent.commitmentsAsProvider, [commitment.id], 'new provider index added in second agent DNA')
+    t.deepLooseEqual(resp.agent.commitmentsAsReceiver, [commitment.id], 'unchanged receiver index retained')
+
+    // SCENARIO: move both agents back to the first DNA at once, removing two index entries from the second
+    resp = await planning.call('commitment', 'update_commitment', { commitment: { revisionId: updatedRevisionId, provider: aliceId, receiver: carolId } })
+    t.deepEqual(resp.indexingReport.failed, [], 'no index updates failed when moving both agents')
+    await pause(100)
+
+    resp = await agents2.call('agent', 'get_agent', { address: bobId })
+    t.deepLooseEqual(resp.agent.commitmentsAsProvider, [], 'provider index removed from second agent DNA')
+    t.deepLooseEqual(resp.agent.commitmentsAsReceiver, [], 'receiver index removed from second agent DNA')
+    resp = await agents1.call('agent', 'get_agent', { address: aliceId })
+    t.deepLooseEqual(resp.agent.commitmentsAsProvider, [commitment.id], 'provider index restored in first agent DNA')
+    resp = await agents1.call('agent', 'get_agent', { address: carolId })
+    t.deepLooseEqual(resp.agent.commitmentsAsReceiver, [commitment.id], 'receiver index added in first agent DNA')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
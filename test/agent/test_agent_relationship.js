@@ -0,0 +1,78 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+test('AgentRelationship records & indexes', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    // SCENARIO: write records
+    let resp = await agent.call('agent', 'create_agent', { agent: { name: 'test person', agentType: 'Person' } })
+    const personId = resp.agent.id
+    resp = await agent.call('agent', 'create_agent', { agent: { name: 'test organization', agentType: 'Organization' } })
+    const orgId = resp.agent.id
+    await pause(100)
+
+    resp = await agent.call('agent_relationship_role', 'create_agent_relationship_role', { agentRelationshipRole: {
+      roleLabel: 'member of',
+      inverseRoleLabel: 'has member',
+      note: 'membership role',
+    } })
+    t.ok(resp.agentRelationshipRole && resp.agentRelationshipRole.id, 'role created successfully')
+    const roleId = resp.agentRelationshipRole.id
+    await pause(100)
+
+    const relationship = {
+      subject: personId,
+      object: orgId,
+      relationship: roleId,
+      note: 'person is a member of the organization',
+    }
+    resp = await agent.call('agent_relationship', 'create_agent_relationship', { agentRelationship: relationship })
+    t.ok(resp.agentRelationship && resp.agentRelationship.id, 'relationship created successfully')
+    await pause(100)
+    const relationshipId = resp.agentRelationship.id
+    const relationshipRevisionId = resp.agentRelationship.revisionId
+
+    // ASSERT: check relationship fields
+    resp = await agent.call('agent_relationship', 'get_agent_relationship', { address: relationshipId })
+    t.deepLooseEqual(resp.agentRelationship.subject, personId, 'AgentRelationship.subject saved')
+    t.deepLooseEqual(resp.agentRelationship.object, orgId, 'AgentRelationship.object saved')
+    t.deepLooseEqual(resp.agentRelationship.relationship, roleId, 'AgentRelationship.relationship saved')
+    t.equal(resp.agentRelationship.note, relationship.note, 'AgentRelationship.note saved')
+
+    // ASSERT: check agent reciprocal indexes
+    resp = await agent.call('agent', 'get_agent', { address: personId })
+    t.deepLooseEqual(resp.agent.relationshipsAsSubject, [relationshipId], 'Agent.relationshipsAsSubject reciprocal index OK')
+    resp = await agent.call('agent', 'get_agent', { address: orgId })
+    t.deepLooseEqual(resp.agent.relationshipsAsObject, [relationshipId], 'Agent.relationshipsAsObject reciprocal index OK')
+
+    // ASSERT: check query indexes
+    resp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { object: orgId } })
+    t.equal(resp.edges.length, 1, 'members of organization queryable')
+    t.deepLooseEqual(resp.edges[0] && resp.edges[0].node.id, relationshipId, 'AgentRelationship.object indexed correctly')
+
+    resp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { subject: personId } })
+    t.equal(resp.edges.length, 1, 'relationships of subject queryable')
+
+    resp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { relationship: roleId } })
+    t.equal(resp.edges.length, 1, 'relationships by role queryable')
+
+    // SCENARIO: delete relationship
+    await agent.call('agent_relationship', 'delete_agent_relationship', { revisionId: relationshipRevisionId })
+    await pause(100)
+
+    resp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { object: orgId } })
+    t.equal(resp.edges.length, 0, 'relationship removed from object index after deletion')
+    resp = await agent.call('agent', 'get_agent', { address: personId })
+    t.notOk(resp.agent.relationshipsAsSubject, 'Agent.relationshipsAsSubject reciprocal index removed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,132 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('claims and settlements can be written and read between DNAs', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    // SCENARIO: write records
+    const triggerResp = await observation.call('economic_event', 'create_economic_event', { event: {
+      note: 'delivery which triggers an invoice',
+      action: 'transfer-custody',
+      ...testEventProps,
+    } })
+    t.ok(triggerResp.economicEvent && triggerResp.economicEvent.id, 'triggering event created successfully')
+    await pause(100)
+    const triggerId = triggerResp.economicEvent.id
+
+    const claim = {
+      action: 'transfer',
+      provider: testEventProps.receiver,
+      receiver: testEventProps.provider,
+      triggeredBy: triggerId,
+      resourceClassifiedAs: ['currency'],
+      resourceQuantity: { hasNumericalValue: 10, hasUnit: mockIdentifier(false) },
+      due: '2019-12-19T04:29:55.056Z',
+      note: 'invoice for delivery',
+    }
+    const claimResp = await planning.call('claim', 'create_claim', { claim })
+    t.ok(claimResp.claim && claimResp.claim.id, 'claim created successfully')
+    t.equal(claimResp.claim.finished, false, 'claim defaults to unfinished')
+    await pause(100)
+    const claimId = claimResp.claim.id
+    const claimIdObs = [triggerId[0], claimId[1]] // :NOTE: ID in dest network will be same EntryHash, different DnaHash
+
+    // ASSERT: check claim in originating network
+    let readResponse = await planning.call('claim', 'get_claim', { address: claimId })
+    t.deepLooseEqual(readResponse.claim.triggeredBy, triggerId, 'Claim.triggeredBy reference saved in planning DNA')
+    t.equal(readResponse.claim.note, claim.note, 'Claim fields saved in planning DNA')
+
+    // ASSERT: check claim in destination network
+    readResponse = await observation.call('claim', 'get_claim', { address: claimIdObs })
+    t.deepLooseEqual(readResponse.claim.triggeredBy, triggerId, 'Claim.triggeredBy reference saved in observation DNA')
+    t.equal(readResponse.claim.note, claim.note, 'Claim fields replicated to observation DNA')
+
+    // ASSERT: check triggering event
+    readResponse = await observation.call('economic_event', 'get_economic_event', { address: triggerId })
+    t.equal(readResponse.economicEvent.triggers && readResponse.economicEvent.triggers.length, 1, 'EconomicEvent.triggers reference saved in observation DNA')
+    t.deepLooseEqual(readResponse.economicEvent.triggers && readResponse.economicEvent.triggers[0], claimIdObs, 'EconomicEvent.triggers reference OK in observation DNA')
+
+    // ASSERT: check query indexes
+    readResponse = await observation.call('claim_index', 'query_claims', { params: { triggeredBy: triggerId } })
+    t.equal(readResponse.edges.length, 1, 'read claims by triggering event OK')
+    t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node && readResponse.edges[0].node.id, claimIdObs, 'Claim.triggeredBy indexed correctly in observation DNA')
+
+    readResponse = await planning.call('claim_index', 'query_claims', { params: { provider: claim.provider } })
+    t.equal(readResponse.edges.length, 1, 'read claims by provider OK')
+    t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node && readResponse.edges[0].node.id, claimId, 'Claim.provider indexed correctly in planning DNA')
+
+    // SCENARIO: settle the claim
+    const paymentResp = await observation.call('economic_event', 'create_economic_event', { event: {
+      note: 'payment of invoice',
+      action: 'transfer',
+      ...testEventProps,
+      provider: claim.provider,
+      receiver: claim.receiver,
+    } })
+    await pause(100)
+    const paymentId = paymentResp.economicEvent.id
+
+    const settlement = {
+      settles: claimId,
+      settledBy: paymentId,
+      resourceQuantity: { hasNumericalValue: 10, hasUnit: mockIdentifier(false) },
+      note: 'paid in full',
+    }
+    const settlementResp = await planning.call('settlement', 'create_settlement', { settlement })
+    t.ok(settlementResp.settlement && settlementResp.settlement.id, 'settlement created successfully')
+    await pause(100)
+    const settlementId = settlementResp.settlement.id
+    const settlementIdObs = [paymentId[0], settlementId[1]]
+
+    // ASSERT: check claim
+    readResponse = await planning.call('claim', 'get_claim', { address: claimId })
+    t.equal(readResponse.claim.settledBy && readResponse.claim.settledBy.length, 1, 'Claim.settledBy reciprocal reference saved in planning DNA')
+    t.deepLooseEqual(readResponse.claim.settledBy && readResponse.claim.settledBy[0], settlementId, 'Claim.settledBy reciprocal settlement reference OK in planning DNA')
+
+    // ASSERT: check settling event
+    readResponse = await observation.call('economic_event', 'get_economic_event', { address: paymentId })
+    t.equal(readResponse.economicEvent.settles && readResponse.economicEvent.settles.length, 1, 'EconomicEvent.settles reference saved in observation DNA')
+    t.deepLooseEqual(readResponse.economicEvent.settles && readResponse.economicEvent.settles[0], settlementIdObs, 'EconomicEvent.settles reference OK in observation DNA')
+
+    // ASSERT: check settlement in destination network
+    readResponse = await observation.call('settlement', 'get_settlement', { address: settlementIdObs })
+    t.deepLooseEqual(readResponse.settlement.settledBy, paymentId, 'Settlement.settledBy reference saved in observation DNA')
+    t.deepLooseEqual(readResponse.settlement.settles, claimId, 'Settlement.settles reference saved in observation DNA')
+
+    // ASSERT: check settlement query indexes
+    readResponse = await planning.call('settlement_index', 'query_settlements', { params: { settles: claimId } })
+    t.equal(readResponse.edges.length, 1, 'read settlements by claim OK')
+    t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node && readResponse.edges[0].node.id, settlementId, 'Settlement.settles indexed correctly in planning DNA')
+
+    readResponse = await observation.call('settlement_index', 'query_settlements', { params: { settledBy: paymentId } })
+    t.equal(readResponse.edges.length, 1, 'read settlements by event OK')
+    t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node && readResponse.edges[0].node.id[1], settlementId[1], 'Settlement.settledBy indexed correctly in observation DNA')
+
+    // SCENARIO: mark the claim as settled
+    const updateResp = await planning.call('claim', 'update_claim', { claim: { revisionId: claimResp.claim.revisionId, finished: true } })
+    await pause(100)
+    t.equal(updateResp.claim.finished, true, 'claim updated')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,105 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+}
+
+test('commitments can be listed in order of due date', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    // SCENARIO: write records, backdating the later one
+    const cResp1 = await planning.call('commitment', 'create_commitment', { commitment: { ...testEventProps, due: '2019-11-19T04:29:55.056Z' } })
+    t.ok(cResp1.commitment && cResp1.commitment.id, 'commitment created successfully')
+    await pause(100)
+    const cResp2 = await planning.call('commitment', 'create_commitment', { commitment: { ...testEventProps, due: '2019-08-01T00:00:00.000Z' } })
+    t.ok(cResp2.commitment && cResp2.commitment.id, 'backdated commitment created successfully')
+    await pause(100)
+
+    let listResp = await planning.call('commitment_index', 'read_all_commitments', {})
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [cResp2.commitment.id, cResp1.commitment.id], 'listed by creation time by default')
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments', { orderBy: 'due' })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [cResp1.commitment.id, cResp2.commitment.id], 'listed by due date when requested')
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', {
+      from: '2019-07-01T00:00:00.000Z', to: '2019-10-01T00:00:00.000Z', orderBy: 'due',
+    })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [cResp2.commitment.id], 'commitments due within range listed')
+
+    // SCENARIO: update due date
+    await planning.call('commitment', 'update_commitment', {
+      commitment: { revisionId: cResp2.commitment.revisionId, due: '2019-12-01T00:00:00.000Z' },
+    })
+    await pause(100)
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments', { orderBy: 'due' })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [cResp2.commitment.id, cResp1.commitment.id], 'reordered after due date changes')
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', {
+      from: '2019-07-01T00:00:00.000Z', to: '2019-10-01T00:00:00.000Z', orderBy: 'due',
+    })
+    t.equal(listResp.edges.length, 0, 'commitment no longer listed at previous due date')
+
+    // SCENARIO: unknown time index
+    try {
+      await planning.call('commitment_index', 'read_all_commitments', { orderBy: 'not_a_time' })
+      t.fail('unknown time index should error')
+    } catch (err) {
+      t.ok(err.data.data.includes('No time index named not_a_time'), 'unknown time index rejected')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('economic events can be listed in order of occurrence', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const eResp1 = await observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, hasPointInTime: '2019-11-19T04:29:55.056Z' } })
+    t.ok(eResp1.economicEvent && eResp1.economicEvent.id, 'event created successfully')
+    await pause(100)
+    const eResp2 = await observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, hasPointInTime: '2019-08-01T00:00:00.000Z' } })
+    t.ok(eResp2.economicEvent && eResp2.economicEvent.id, 'backdated event created successfully')
+    await pause(100)
+    const eResp3 = await observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, hasBeginning: '2019-09-01T00:00:00.000Z' } })
+    t.ok(eResp3.economicEvent && eResp3.economicEvent.id, 'event with duration created successfully')
+    await pause(100)
+
+    let listResp = await observation.call('economic_event_index', 'read_all_economic_events', { orderBy: 'has_point_in_time' })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [eResp1.economicEvent.id, eResp2.economicEvent.id], 'listed by time of occurrence')
+
+    listResp = await observation.call('economic_event_index', 'read_all_economic_events', { orderBy: 'has_beginning' })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [eResp3.economicEvent.id], 'only events with a beginning listed by beginning')
+
+    listResp = await observation.call('economic_event_index', 'read_all_economic_events_between', {
+      from: '2019-07-01T00:00:00.000Z', to: '2019-10-01T00:00:00.000Z', orderBy: 'has_point_in_time',
+    })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [eResp2.economicEvent.id], 'events occurring within range listed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,81 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'consume',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('record modification responses report index update outcomes', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning'])
+  try {
+    const { cells: [observation, planning] } = alice
+
+    const cResp = await planning.call('commitment', 'create_commitment', { commitment: testEventProps })
+    t.ok(cResp.commitment && cResp.commitment.id, 'commitment created successfully')
+    t.ok(cResp.indexingReport, 'create response includes indexing report')
+    t.deepEqual(cResp.indexingReport.failed, [], 'no index updates failed on create')
+    t.ok(cResp.indexingReport.succeeded.includes('commitment_due'), 'time index updates included in report')
+    await pause(100)
+    const commitmentId = cResp.commitment.id
+
+    const eResp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'consume',
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+        hasPointInTime: '2019-11-19T04:29:55.056Z',
+      },
+    })
+    t.ok(eResp.economicEvent && eResp.economicEvent.id, 'event created successfully')
+    t.ok(eResp.indexingReport.succeeded.includes('economic_event_has_point_in_time'), 'event time index updates included in report')
+    await pause(100)
+
+    const fResp = await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentId, fulfilledBy: eResp.economicEvent.id },
+    })
+    t.ok(fResp.fulfillment && fResp.fulfillment.id, 'fulfillment created successfully')
+    t.ok(fResp.indexingReport.succeeded.includes('fulfillment_fulfills'), 'successful index update reported by relationship name')
+    t.deepEqual(fResp.indexingReport.failed, [], 'no failures reported for reachable indexes')
+    await pause(100)
+
+    const readResp = await planning.call('fulfillment', 'get_fulfillment', { address: fResp.fulfillment.id })
+    t.equal(readResp.indexingReport, undefined, 'read responses omit indexing report')
+
+    const uResp = await planning.call('fulfillment', 'update_fulfillment', {
+      fulfillment: { revisionId: fResp.fulfillment.revisionId, note: 'updated' },
+    })
+    t.ok(uResp.fulfillment, 'fulfillment updated successfully')
+    t.equal(uResp.indexingReport, undefined, 'updates which alter no relationships omit indexing report')
+    await pause(100)
+
+    const listResp = await planning.call('fulfillment_index', 'read_all_fulfillments', {})
+    t.ok(listResp.edges.every(e => e.node.indexingReport === undefined), 'list responses omit indexing report')
+
+    const dResp = await planning.call('fulfillment', 'delete_fulfillment', { revisionId: uResp.fulfillment.revisionId })
+    t.equal(dResp.deleted, true, 'fulfillment deleted successfully')
+    t.ok(dResp.indexingReport.succeeded.includes('fulfillment_fulfills'), 'delete response reports index removals')
+    t.deepEqual(dResp.indexingReport.failed, [], 'no failures reported for index removals')
+    await pause(100)
+
+    const cReadResp = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.equal(cReadResp.indexingReport, undefined, 'reads following a modification do not inherit its indexing report')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,97 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'consume',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('undelivered cross-DNA requests are queued for retry', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning'])
+  try {
+    const { cells: [observation, planning] } = alice
+
+    const cResp = await planning.call('commitment', 'create_commitment', { commitment: testEventProps })
+    t.ok(cResp.commitment && cResp.commitment.id, 'commitment created successfully')
+    await pause(100)
+    const commitmentId = cResp.commitment.id
+
+    const eResp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'consume',
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+        hasPointInTime: '2019-11-19T04:29:55.056Z',
+      },
+    })
+    t.ok(eResp.economicEvent && eResp.economicEvent.id, 'event created successfully')
+    await pause(100)
+
+    // SCENARIO: delivered requests are not queued
+    let fResp = await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentId, fulfilledBy: eResp.economicEvent.id },
+    })
+    t.ok(fResp.fulfillment && fResp.fulfillment.id, 'fulfillment created successfully')
+    await pause(100)
+
+    let pending = await planning.call('fulfillment', 'get_pending_sync', null)
+    t.equal(pending.length, 0, 'successfully replicated record leaves nothing in outbox')
+
+    // SCENARIO: request to an unreachable DNA is queued
+    fResp = await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentId, fulfilledBy: mockAddress(false) },
+    })
+    t.ok(fResp.fulfillment && fResp.fulfillment.id, 'record is still created locally when remote cell is unreachable')
+    await pause(100)
+
+    pending = await planning.call('fulfillment', 'get_pending_sync', null)
+    t.equal(pending.length, 1, 'undelivered replication request queued in outbox')
+    t.equal(pending[0].remotePermissionId, 'create_fulfillment', 'queued request references remote method')
+    t.equal(pending[0].attempts, 1, 'initial delivery counted as first attempt')
+    t.ok(pending[0].lastError, 'delivery error recorded')
+    t.ok(pending[0].nextRetry, 'automatic retry scheduled')
+
+    // SCENARIO: failed replay remains pending
+    const retried = await planning.call('fulfillment', 'retry_pending_sync', null)
+    t.equal(retried.delivered.length, 0, 'unreachable request not delivered on retry')
+    t.equal(retried.pending.length, 1, 'unreachable request still pending after retry')
+    t.equal(retried.pending[0].attempts, 2, 'retry attempt counted')
+
+    pending = await planning.call('fulfillment', 'get_pending_sync', null)
+    t.deepEqual(pending.map(p => p.id), retried.pending.map(p => p.id), 'pending list reflects retry outcome')
+    t.equal(pending[0].attempts, 2, 'retry attempts persisted in outbox')
+    t.ok(new Date(pending[0].nextRetry) > new Date(pending[0].queued.time), 'retry backed off after repeated failure')
+
+    // SCENARIO: discard undeliverable request
+    await planning.call('fulfillment', 'discard_pending_sync', { id: pending[0].id })
+    pending = await planning.call('fulfillment', 'get_pending_sync', null)
+    t.equal(pending.length, 0, 'discarded request removed from outbox')
+
+    const emptyRetry = await planning.call('fulfillment', 'retry_pending_sync', null)
+    t.equal(emptyRetry.delivered.length + emptyRetry.rejected.length + emptyRetry.superseded.length + emptyRetry.pending.length, 0, 'discarded request not replayed')
+
+    try {
+      await planning.call('fulfillment', 'discard_pending_sync', { id: retried.pending[0].id })
+      t.fail('discarding resolved requests should error')
+    } catch (err) {
+      t.ok(err, 'resolved request cannot be discarded again')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -39,7 +39,7 @@ test('record deletion API', async (t) => {
 
     // perform deletion
     const delResp = await planning.call('commitment', 'delete_commitment', { revisionId: commitmentResponse.commitment.revisionId })
-    t.ok(delResp, 'record deleted successfully')
+    t.ok(delResp.deleted, 'record deleted successfully')
     await pause(100)
 
     // attempt retrieval
@@ -55,6 +55,40 @@ test('record deletion API', async (t) => {
   await alice.scenario.cleanUp()
 })
 
+test('deleted records are removed from list APIs', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    // SCENARIO: write records
+    const commitmentResponse = await planning.call('commitment', 'create_commitment', { commitment: { note: 'a commitment to keep', ...testEventProps } })
+    t.ok(commitmentResponse.commitment && commitmentResponse.commitment.id, 'commitment created successfully')
+    await pause(100)
+    const commitmentResponse2 = await planning.call('commitment', 'create_commitment', { commitment: { note: 'a commitment to delete', ...testEventProps } })
+    t.ok(commitmentResponse2.commitment && commitmentResponse2.commitment.id, 'second commitment created successfully')
+    await pause(100)
+
+    let listResp = await planning.call('commitment_index', 'read_all_commitments', {})
+    t.equal(listResp.edges.length, 2, 'both records listed prior to deletion')
+
+    // SCENARIO: delete one of them
+    await planning.call('commitment', 'delete_commitment', { revisionId: commitmentResponse2.commitment.revisionId })
+    await pause(100)
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments', {})
+    t.equal(listResp.edges.length, 1, 'deleted record no longer listed')
+    t.deepLooseEqual(listResp.edges[0].node.id, commitmentResponse.commitment.id, 'remaining record listed')
+    t.notOk(listResp.errors, 'no errors from attempting to load deleted records')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
 test('Cannot delete records of a different type via zome API deletion handlers', async (t) => {
   // display the filename for context in the terminal and use .warn
   // to override the tap testing log filters
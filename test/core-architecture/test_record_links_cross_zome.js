@@ -195,7 +195,7 @@ test('removing records with linked local indexes clears them in associated recor
 
     // SCENARIO: wipe associated record
     const delResp = await observation.call('economic_event', 'delete_economic_event', { revisionId: iEventRev })
-    t.ok(delResp, 'input record deleted')
+    t.ok(delResp.deleted, 'input record deleted')
     await pause(100)
 
     // ASSERT: test forward link field
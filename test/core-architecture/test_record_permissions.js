@@ -0,0 +1,349 @@
+import test from 'tape'
+import { pause, Scenario } from '@holochain/tryorama'
+import {
+  getDNA,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+const buildCell = (player, role) => {
+  const cell = player.namedCells.get(role)
+  cell.call = (zomeName, fnName, payload) => cell.callZome({
+    zome_name: zomeName,
+    fn_name: fnName,
+    payload,
+  }, 60000)
+  return cell
+}
+
+const buildAppBundle = (roles) => ({
+  bundle: {
+    manifest: {
+      name: 'installed-app-id',
+      manifest_version: '1',
+      roles: roles.map((role) => ({
+        name: `hrea_${role}_1`,
+        provisioning: { strategy: 'create', deferred: false },
+        dna: { path: getDNA(role) },
+      })),
+    },
+    resources: {},
+  },
+})
+
+test('Record update & delete permissions', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const scenario = new Scenario({ timeout: 60000 })
+  try {
+    const appBundle = buildAppBundle(['agent', 'planning'])
+    const [alicePlayer, bobPlayer] = await scenario.addPlayersWithApps([appBundle, appBundle])
+    await scenario.shareAllAgents()
+    const alice = buildCell(alicePlayer, 'hrea_planning_1')
+    const bob = buildCell(bobPlayer, 'hrea_planning_1')
+    const bobAgent = buildCell(bobPlayer, 'hrea_agent_1')
+
+    // SCENARIO: bob creates & associates an Agent profile
+    let resp = await bobAgent.call('agent', 'create_agent', { agent: { name: 'bob', agentType: 'Person' } })
+    const bobId = resp.agent.id
+    await bobAgent.call('agent', 'associate_my_agent', { agentAddress: bobId })
+    await pause(100)
+
+    // SCENARIO: alice creates one commitment between other agents, and one naming bob as receiver
+    resp = await alice.call('commitment', 'create_commitment', { commitment: {
+      note: 'unrelated commitment',
+      receiver: mockAddress(false),
+      ...testEventProps,
+    } })
+    const otherCommitment = resp.commitment
+    resp = await alice.call('commitment', 'create_commitment', { commitment: {
+      note: 'commitment to bob',
+      receiver: bobId,
+      ...testEventProps,
+    } })
+    const bobsCommitment = resp.commitment
+    await pause(1000)
+
+    // ASSERT: bob cannot modify records he has no part in
+    try {
+      await bob.call('commitment', 'update_commitment', { commitment: { revisionId: otherCommitment.revisionId, note: 'hijacked' } })
+      t.fail('non-participants should not be able to update records')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to update/), 'non-participants cannot update records')
+    }
+    try {
+      await bob.call('commitment', 'delete_commitment', { revisionId: otherCommitment.revisionId })
+      t.fail('non-participants should not be able to delete records')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to delete/), 'non-participants cannot delete records')
+    }
+
+    // ASSERT: registered participants cannot modify records by default, since registrations are unverified
+    await bob.call('commitment', 'register_my_agent', { agentAddress: bobId })
+    await pause(1000)
+
+    try {
+      await bob.call('commitment', 'update_commitment', { commitment: { revisionId: bobsCommitment.revisionId, note: 'updated by receiver' } })
+      t.fail('participants should not be able to update records authored by others')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to update/), 'participants cannot update records authored by others')
+    }
+    try {
+      await bob.call('commitment', 'delete_commitment', { revisionId: bobsCommitment.revisionId })
+      t.fail('participants should not be able to delete records authored by others')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to delete/), 'participants cannot delete records authored by others')
+    }
+
+    // ASSERT: authors can modify their records
+    resp = await alice.call('commitment', 'update_commitment', { commitment: { revisionId: bobsCommitment.revisionId, note: 'updated by author' } })
+    t.equal(resp.commitment.note, 'updated by author', 'authors can update records')
+
+    resp = await alice.call('commitment', 'delete_commitment', { revisionId: otherCommitment.revisionId })
+    t.ok(resp.deleted, 'authors can delete records')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
+
+test('Inventory updates by event participants not accountable for a resource', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const scenario = new Scenario({ timeout: 60000 })
+  try {
+    const appBundle = buildAppBundle(['agent', 'observation'])
+    const [alicePlayer, bobPlayer] = await scenario.addPlayersWithApps([appBundle, appBundle])
+    await scenario.shareAllAgents()
+    const alice = buildCell(alicePlayer, 'hrea_observation_1')
+    const bob = buildCell(bobPlayer, 'hrea_observation_1')
+    const bobAgent = buildCell(bobPlayer, 'hrea_agent_1')
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: bob creates & associates an Agent profile
+    let resp = await bobAgent.call('agent', 'create_agent', { agent: { name: 'bob', agentType: 'Person' } })
+    const bobId = resp.agent.id
+    await bobAgent.call('agent', 'associate_my_agent', { agentAddress: bobId })
+
+    // SCENARIO: alice observes a resource
+    resp = await alice.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+        hasPointInTime: '2019-11-19T04:29:55.056Z',
+      },
+      newInventoriedResource: { note: 'alice\'s resource' },
+    })
+    const resource = resp.economicResource
+    await pause(1000)
+
+    // ASSERT: agents cannot update inventory via events they are not party to
+    try {
+      await bob.call('economic_event', 'create_economic_event', { event: {
+        action: 'lower',
+        resourceInventoriedAs: resource.id,
+        resourceQuantity: { hasNumericalValue: 3, hasUnit: resourceUnitId },
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+        hasPointInTime: '2019-11-20T04:29:55.056Z',
+      } })
+      t.fail('events recorded by non-participants should not update inventory')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to update/), 'non-participants cannot update inventory via events')
+    }
+
+    // ASSERT: agents must register their Agent profile before being recognised as event participants
+    try {
+      await bob.call('economic_event', 'create_economic_event', { event: {
+        action: 'lower',
+        resourceInventoriedAs: resource.id,
+        resourceQuantity: { hasNumericalValue: 3, hasUnit: resourceUnitId },
+        provider: bobId,
+        receiver: mockAddress(false),
+        hasPointInTime: '2019-11-20T04:29:55.056Z',
+      } })
+      t.fail('events recorded by unregistered agents should not update inventory')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to update/), 'unregistered agents cannot update inventory via events naming their profile')
+    }
+
+    await bob.call('economic_event', 'register_my_agent', { agentAddress: bobId })
+    await pause(1000)
+
+    // ASSERT: events recorded by participating agents update the resource
+    resp = await bob.call('economic_event', 'create_economic_event', { event: {
+      action: 'lower',
+      resourceInventoriedAs: resource.id,
+      resourceQuantity: { hasNumericalValue: 3, hasUnit: resourceUnitId },
+      provider: bobId,
+      receiver: mockAddress(false),
+      hasPointInTime: '2019-11-20T04:29:55.056Z',
+    } })
+    t.ok(resp.economicEvent && resp.economicEvent.id, 'events affecting resources can be recorded by event participants')
+    await pause(1000)
+
+    resp = await alice.call('economic_resource', 'get_economic_resource', { address: resource.id })
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 7, 'inventory updated by event of another agent')
+    t.ok(Buffer.from(resp.economicResource.meta.retrievedRevision.agentPubKey).equals(Buffer.from(bobPlayer.agentPubKey)), 'inventory update authored by recording agent')
+
+    // ASSERT: other fields remain protected
+    try {
+      await bob.call('economic_resource', 'update_economic_resource', { resource: { revisionId: resp.economicResource.revisionId, note: 'hijacked' } })
+      t.fail('non-participants should not be able to edit resources')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to update/), 'non-participants cannot edit resources')
+    }
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
+
+test('Time index positions of records moved back to previous positions', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const scenario = new Scenario({ timeout: 60000 })
+  try {
+    const appBundle = buildAppBundle(['agent', 'planning'])
+    const [alicePlayer, bobPlayer] = await scenario.addPlayersWithApps([appBundle, appBundle])
+    await scenario.shareAllAgents()
+    const alice = buildCell(alicePlayer, 'hrea_planning_1')
+    const bob = buildCell(bobPlayer, 'hrea_planning_1')
+    const bobAgent = buildCell(bobPlayer, 'hrea_agent_1')
+    const earlyRange = { from: '2019-07-01T00:00:00.000Z', to: '2019-10-01T00:00:00.000Z', orderBy: 'due' }
+    const lateRange = { from: '2019-11-01T00:00:00.000Z', to: '2019-12-01T00:00:00.000Z', orderBy: 'due' }
+
+    let resp = await bobAgent.call('agent', 'create_agent', { agent: { name: 'bob', agentType: 'Person' } })
+    const bobId = resp.agent.id
+    await bobAgent.call('agent', 'associate_my_agent', { agentAddress: bobId })
+    await bob.call('commitment', 'register_my_agent', { agentAddress: bobId })
+    await pause(1000)
+
+    // SCENARIO: alice indexes a commitment to bob at a late due date
+    resp = await alice.call('commitment', 'create_commitment', { commitment: {
+      ...testEventProps,
+      receiver: bobId,
+    } })
+    const commitmentId = resp.commitment.id
+    await pause(1000)
+
+    // ASSERT: other agents cannot move it
+    try {
+      await bob.call('commitment', 'update_commitment', { commitment: { revisionId: resp.commitment.revisionId, due: '2019-08-01T00:00:00.000Z' } })
+      t.fail('agents should not be able to move records authored by others')
+    } catch (err) {
+      t.ok(err.toString().match(/not permitted to update/), 'agents cannot move records authored by others')
+    }
+    await pause(1000)
+
+    resp = await bob.call('commitment_index', 'read_all_commitments_between', lateRange)
+    t.deepLooseEqual(resp.edges.map(e => e.node.id), [commitmentId], 'rejected update leaves index position in place')
+
+    // SCENARIO: alice moves it earlier, tombstoning its index link
+    resp = await alice.call('commitment', 'update_commitment', { commitment: { revisionId: resp.edges[0].node.revisionId, due: '2019-08-01T00:00:00.000Z' } })
+    await pause(1000)
+
+    resp = await bob.call('commitment_index', 'read_all_commitments_between', lateRange)
+    t.equal(resp.edges.length, 0, 'commitment removed from previous position')
+    resp = await bob.call('commitment_index', 'read_all_commitments_between', earlyRange)
+    t.deepLooseEqual(resp.edges.map(e => e.node.id), [commitmentId], 'commitment indexed at new position')
+
+    // SCENARIO: alice moves it back to the position it was removed from
+    resp = await alice.call('commitment', 'update_commitment', { commitment: { revisionId: resp.edges[0].node.revisionId, due: testEventProps.due } })
+    await pause(1000)
+
+    resp = await bob.call('commitment_index', 'read_all_commitments_between', lateRange)
+    t.deepLooseEqual(resp.edges.map(e => e.node.id), [commitmentId], 'commitment restored at tombstoned position')
+    resp = await bob.call('commitment_index', 'read_all_commitments_between', earlyRange)
+    t.equal(resp.edges.length, 0, 'superseded position removed')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
+
+test('Corrections of events recorded by other agents', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const scenario = new Scenario({ timeout: 60000 })
+  try {
+    const appBundle = buildAppBundle(['observation'])
+    const [alicePlayer, bobPlayer] = await scenario.addPlayersWithApps([appBundle, appBundle])
+    await scenario.shareAllAgents()
+    const alice = buildCell(alicePlayer, 'hrea_observation_1')
+    const bob = buildCell(bobPlayer, 'hrea_observation_1')
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: alice observes a resource
+    let resp = await alice.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+        hasPointInTime: '2019-11-19T04:29:55.056Z',
+      },
+      newInventoriedResource: { note: 'alice\'s resource' },
+    })
+    const eventId = resp.economicEvent.id
+    const resourceId = resp.economicResource.id
+    await pause(1000)
+
+    // ASSERT: other agents cannot reverse the event
+    try {
+      await bob.call('economic_event', 'correct_economic_event', { event: eventId })
+      t.fail('agents should not be able to correct events authored by others')
+    } catch (err) {
+      // the rejected inventory update of alice's resource may be reported in place of the correction itself
+      t.ok(err.toString().match(/not permitted to (correct|update)/), 'agents cannot correct events authored by others')
+    }
+    await pause(1000)
+
+    resp = await alice.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 10, 'rejected correction leaves resource unchanged')
+
+    // ASSERT: corrections cannot be forged by referencing the event directly
+    try {
+      await bob.call('economic_event', 'create_economic_event', { event: {
+        action: 'lower',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+        hasPointInTime: '2019-11-20T04:29:55.056Z',
+        corrects: eventId,
+        reversal: true,
+      } })
+      t.fail('corrections without a corrected revision should be rejected')
+    } catch (err) {
+      t.ok(err.toString().match(/must reference the corrected event and its revision/), 'corrections must reference the corrected revision')
+    }
+
+    // ASSERT: authors can correct their events
+    resp = await alice.call('economic_event', 'correct_economic_event', { event: eventId })
+    t.deepLooseEqual(resp.reversal.corrects, eventId, 'authors can correct events')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
@@ -80,3 +80,57 @@ test('record previous revision metadata', async (t) => {
   }
   await alice.scenario.cleanUp()
 })
+
+test('record revision history API', async (t) => {
+  const alice = await buildPlayer(['agreement'])
+  try {
+    const { cells: [agreement] } = alice
+
+    const r1 = await agreement.call('agreement', 'create_agreement', { agreement: { name: exampleEntry.name } })
+    await pause(100)
+    const recordId = r1.agreement.id
+    const r1Id = r1.agreement.revisionId
+    t.equal(r1.agreement.meta.originalRevision.id, r1Id, 'new record is its own original revision')
+    t.equal(r1.agreement.meta.previousRevisionsCount, 0, 'new record has no previous revisions')
+
+    const r2 = await agreement.call('agreement', 'update_agreement', { agreement: { revisionId: r1Id, ...update } })
+    await pause(100)
+    const r2Id = r2.agreement.revisionId
+    const r3 = await agreement.call('agreement', 'update_agreement', { agreement: { revisionId: r2Id, ...update2 } })
+    await pause(100)
+    const r3Id = r3.agreement.revisionId
+    t.equal(r3.agreement.meta.originalRevision.id, r1Id, 'original revision tracked through updates')
+    t.equal(r3.agreement.meta.previousRevisionsCount, 2, 'previous revisions counted')
+    t.equal(r3.agreement.meta.latestRevision.id, r3Id, 'latest revision is self')
+    t.equal(r3.agreement.meta.futureRevisionsCount, 0, 'latest revision has no future revisions')
+
+    const old = await agreement.call('agreement', 'get_revision', { revisionId: r1Id })
+    t.equal(old.agreement.meta.latestRevision.id, r3Id, 'latest revision referenced from historical revision')
+    t.equal(old.agreement.meta.futureRevisionsCount, 2, 'future revisions counted from historical revision')
+
+    let history = await agreement.call('agreement', 'get_agreement_revisions', { address: recordId })
+    t.deepEqual(history.revisions.map(r => r.id), [r1Id, r2Id, r3Id], 'full revision history returned in order')
+    t.ok(history.revisions.every(r => r.agentPubKey && r.time), 'revision author and time included')
+    t.equal(history.hasNextPage, false, 'no further revisions')
+
+    history = await agreement.call('agreement', 'get_agreement_revisions', { address: recordId, first: 2 })
+    t.deepEqual(history.revisions.map(r => r.id), [r1Id, r2Id], 'first page of revision history returned')
+    t.equal(history.hasNextPage, true, 'further revisions indicated')
+
+    history = await agreement.call('agreement', 'get_agreement_revisions', { address: recordId, first: 2, after: r2Id })
+    t.deepEqual(history.revisions.map(r => r.id), [r3Id], 'next page of revision history returned')
+    t.equal(history.hasNextPage, false, 'end of revision history indicated')
+
+    const list = await agreement.call('agreement_index', 'read_all_agreements', {})
+    const listed = list.edges.find(e => e.node.id.toString() === recordId.toString()).node
+    t.equal(listed.revisionId.toString(), r3Id.toString(), 'latest revision listed')
+    t.equal(listed.meta.originalRevision.id.toString(), r1Id.toString(), 'original revision included in list responses')
+    t.equal(listed.meta.previousRevision.id.toString(), r2Id.toString(), 'previous revision included in list responses')
+    t.equal(listed.meta.previousRevisionsCount, undefined, 'revision counts omitted from list responses')
+    t.equal(listed.meta.futureRevisionsCount, undefined, 'future revision counts omitted from list responses')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,118 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('list APIs can be filtered to records created within a range of times', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    const startTime = new Date(Date.now() - 60000)
+
+    // SCENARIO: write records
+    const commitmentResponse = await planning.call('commitment', 'create_commitment', { commitment: { note: 'first commitment', ...testEventProps } })
+    t.ok(commitmentResponse.commitment && commitmentResponse.commitment.id, 'commitment created successfully')
+    await pause(100)
+    const commitmentResponse2 = await planning.call('commitment', 'create_commitment', { commitment: { note: 'second commitment', ...testEventProps } })
+    t.ok(commitmentResponse2.commitment && commitmentResponse2.commitment.id, 'second commitment created successfully')
+    await pause(100)
+
+    const endTime = new Date(Date.now() + 60000)
+
+    // SCENARIO: read all records in range
+    let listResp = await planning.call('commitment_index', 'read_all_commitments_between', {
+      from: startTime.toISOString(), to: endTime.toISOString(),
+    })
+    t.equal(listResp.edges.length, 2, 'records created within range are listed')
+    t.deepLooseEqual(listResp.edges[0].node.id, commitmentResponse2.commitment.id, 'most recent record listed first')
+    t.deepLooseEqual(listResp.edges[1].node.id, commitmentResponse.commitment.id, 'earlier record listed last')
+    t.equal(listResp.pageInfo.hasNextPage, false, 'range fully read')
+
+    // SCENARIO: limit results
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', {
+      from: startTime.toISOString(), to: endTime.toISOString(), first: 1,
+    })
+    t.equal(listResp.edges.length, 1, 'range results limited')
+    t.deepLooseEqual(listResp.edges[0].node.id, commitmentResponse2.commitment.id, 'most recent record within limit')
+    t.equal(listResp.pageInfo.hasNextPage, true, 'further results in range indicated')
+
+    // SCENARIO: ranges excluding the records
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', {
+      from: '2019-07-01T00:00:00.000Z', to: '2019-10-01T00:00:00.000Z',
+    })
+    t.equal(listResp.edges.length, 0, 'records created outside range are not listed')
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', {
+      from: endTime.toISOString(), to: startTime.toISOString(),
+    })
+    t.equal(listResp.edges.length, 0, 'empty range returns no results')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('time range queries can be paged through with cursors', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    // SCENARIO: write records due on consecutive days, of which the middle three fall within the range
+    const ids = []
+    for (let day = 1; day <= 5; day++) {
+      const resp = await planning.call('commitment', 'create_commitment', { commitment: {
+        note: `due on day ${day}`,
+        ...testEventProps,
+        due: `2019-11-0${day}T12:00:00.000Z`,
+      } })
+      ids.push(resp.commitment.id)
+      await pause(100)
+    }
+    const range = { from: '2019-11-02T00:00:00.000Z', to: '2019-11-05T00:00:00.000Z', orderBy: 'due' }
+
+    // SCENARIO: page forwards through the range
+    let listResp = await planning.call('commitment_index', 'read_all_commitments_between', { ...range, first: 2 })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [ids[3], ids[2]], 'first page of range listed most recent first')
+    t.equal(listResp.pageInfo.hasNextPage, true, 'further results in range indicated')
+    t.equal(listResp.pageInfo.hasPreviousPage, false, 'first page has no preceding results')
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', { ...range, first: 2, after: listResp.pageInfo.endCursor })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [ids[1]], 'second page continues from cursor')
+    t.equal(listResp.pageInfo.hasNextPage, false, 'records older than the range do not count as further results')
+    t.equal(listResp.pageInfo.hasPreviousPage, true, 'second page preceded by first')
+
+    // SCENARIO: page backwards through the range
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', { ...range, last: 2 })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [ids[2], ids[1]], 'last page of range lists earliest records')
+    t.equal(listResp.pageInfo.hasPreviousPage, true, 'more recent results in range indicated')
+
+    listResp = await planning.call('commitment_index', 'read_all_commitments_between', { ...range, last: 2, before: listResp.pageInfo.startCursor })
+    t.deepLooseEqual(listResp.edges.map(e => e.node.id), [ids[3]], 'preceding page continues from cursor')
+    t.equal(listResp.pageInfo.hasPreviousPage, false, 'records newer than the range do not count as further results')
+    t.equal(listResp.pageInfo.hasNextPage, true, 'preceding page followed by last')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
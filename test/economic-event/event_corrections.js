@@ -0,0 +1,168 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('EconomicEvent corrections', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: raise an inventoried resource with an incorrect quantity
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'miscounted resource' },
+    })
+    await pause(100)
+    const eventId = resp.economicEvent.id
+    const resourceId = resp.economicResource.id
+
+    // SCENARIO: correct the event
+    resp = await observation.call('economic_event', 'correct_economic_event', {
+      event: eventId,
+      replacement: {
+        action: 'raise',
+        resourceInventoriedAs: resourceId,
+        resourceQuantity: { hasNumericalValue: 7, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+    })
+    await pause(100)
+
+    t.deepLooseEqual(resp.reversal.corrects, eventId, 'reversing event references corrected event')
+    t.equal(resp.reversal.resourceQuantity.hasNumericalValue, -10, 'reversing event negates quantities')
+    t.deepLooseEqual(resp.replacement.corrects, eventId, 'replacement event references corrected event')
+    t.notEqual(new Date(resp.reversal.hasPointInTime).getTime(), new Date(testEventProps.hasPointInTime).getTime(), 'reversing event dated at time of correction')
+    const replacementId = resp.replacement.id
+
+    // ASSERT: affected resources are updated
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 7, 'resource accounting quantity corrected')
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 7, 'resource onhand quantity corrected')
+
+    // ASSERT: corrections are linked from the original event
+    resp = await observation.call('economic_event', 'get_economic_event', { address: eventId })
+    t.equal(resp.economicEvent.correctedBy.length, 2, 'corrected event links to reversal & replacement')
+
+    // SCENARIO: correct the original event again, without replacement
+    resp = await observation.call('economic_event', 'correct_economic_event', { event: eventId })
+    await pause(100)
+    t.deepLooseEqual(resp.reversal.corrects, replacementId, 'further corrections apply to the replacement event')
+    t.equal(resp.reversal.resourceQuantity.hasNumericalValue, -7, 'replacement event quantities reversed')
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 0, 'resource accounting quantity corrected again')
+
+    try {
+      await observation.call('economic_event', 'correct_economic_event', { event: eventId })
+      t.fail('events corrected without replacement should not be correctable again')
+    } catch (err) {
+      t.ok(err.toString().match(/already been corrected/), 'fully reversed events cannot be corrected again')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('EconomicEvent corrections restore resource custody', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+    const originalOwner = mockAddress(false)
+    const newOwner = mockAddress(false)
+    const originalLocation = mockAddress(false)
+    const newLocation = mockAddress(false)
+
+    // SCENARIO: raise a resource, then transfer & move it
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        ...testEventProps,
+        receiver: originalOwner,
+      },
+      newInventoriedResource: { note: 'owned resource', currentLocation: originalLocation },
+    })
+    await pause(100)
+    const resourceId = resp.economicResource.id
+
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'transfer',
+        resourceInventoriedAs: resourceId,
+        toResourceInventoriedAs: resourceId,
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        ...testEventProps,
+        provider: originalOwner,
+        receiver: newOwner,
+      },
+    })
+    await pause(100)
+    const transferId = resp.economicEvent.id
+
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'move',
+        resourceInventoriedAs: resourceId,
+        toResourceInventoriedAs: resourceId,
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        ...testEventProps,
+        provider: newOwner,
+        receiver: newOwner,
+        atLocation: newLocation,
+      },
+    })
+    await pause(100)
+    const moveId = resp.economicEvent.id
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.primaryAccountable, newOwner, 'transfer changes custody')
+    t.deepLooseEqual(resp.economicResource.currentLocation, newLocation, 'move changes location')
+
+    // SCENARIO: correct the transfer
+    await observation.call('economic_event', 'correct_economic_event', { event: transferId })
+    await pause(100)
+
+    // ASSERT: custody prior to the transfer is restored, without undoing the later move
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.primaryAccountable, originalOwner, 'custody restored by correction')
+    t.deepLooseEqual(resp.economicResource.currentLocation, newLocation, 'changes by later events retained')
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 10, 'quantity unchanged by reversing a transfer within the same resource')
+
+    // SCENARIO: correct the move
+    await observation.call('economic_event', 'correct_economic_event', { event: moveId })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.currentLocation, originalLocation, 'location restored by correction')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
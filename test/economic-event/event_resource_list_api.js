@@ -167,6 +167,92 @@ test('Event/Resource list APIs', async (t) => {
     )
     t.equal(resp.data.economicResources.pageInfo.startCursor, resource2Id, 'resource start offset cursor OK')
     t.equal(resp.data.economicResources.pageInfo.endCursor, resource1Id, 'resource end offset cursor OK')
+
+    resp = await alice.graphQL(`{
+      economicEvents(first: 2) {
+        pageInfo {
+          hasPreviousPage
+          hasNextPage
+        }
+        edges {
+          node {
+            id
+          }
+        }
+      }
+    }`)
+
+    t.deepLooseEqual(
+      resp.data.economicEvents.edges.map(e => e.node),
+      [{ id: event5Id }, { id: event4Id }],
+      'first page of events OK',
+    )
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, false, 'first page of events has no previous page')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, true, 'first page of events has a next page')
+
+    resp = await alice.graphQL(`{
+      economicEvents(first: 2, after: "${event4Id}") {
+        pageInfo {
+          hasPreviousPage
+          hasNextPage
+        }
+        edges {
+          node {
+            id
+          }
+        }
+      }
+    }`)
+
+    t.deepLooseEqual(
+      resp.data.economicEvents.edges.map(e => e.node),
+      [{ id: event3Id }, { id: event2Id }],
+      'forward pagination of events OK',
+    )
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, true, 'forward page of events has a previous page')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, true, 'forward page of events has a next page')
+
+    resp = await alice.graphQL(`{
+      economicEvents(first: 2, after: "${event2Id}") {
+        pageInfo {
+          hasNextPage
+        }
+        edges {
+          node {
+            id
+          }
+        }
+      }
+    }`)
+
+    t.deepLooseEqual(
+      resp.data.economicEvents.edges.map(e => e.node),
+      [{ id: event1Id }],
+      'final page of events OK',
+    )
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, false, 'final page of events has no next page')
+
+    resp = await alice.graphQL(`{
+      economicEvents(last: 2, before: "${event2Id}") {
+        pageInfo {
+          hasPreviousPage
+          hasNextPage
+        }
+        edges {
+          node {
+            id
+          }
+        }
+      }
+    }`)
+
+    t.deepLooseEqual(
+      resp.data.economicEvents.edges.map(e => e.node),
+      [{ id: event4Id }, { id: event3Id }],
+      'backward pagination of events OK',
+    )
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, true, 'backward page of events has a previous page')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, true, 'backward page of events has a next page')
   } catch (e) {
     await alice.scenario.cleanUp()
     throw e
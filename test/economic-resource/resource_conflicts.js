@@ -0,0 +1,85 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+}
+
+test('EconomicResource conflicting revisions remain updatable and can be merged', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        hasPointInTime: '2019-11-19T04:29:55.056Z',
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'conflicting resource' },
+    })
+    await pause(100)
+    const resourceId = resp.economicResource.id
+    const initialRevision = resp.economicResource.revisionId
+
+    // SCENARIO: two updates made against the same revision diverge the revision history
+    const branch1 = await observation.call('economic_resource', 'update_economic_resource', { resource: { revisionId: initialRevision, note: 'branch 1' } })
+    const branch2 = await observation.call('economic_resource', 'update_economic_resource', { resource: { revisionId: initialRevision, note: 'branch 2' } })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.meta.conflicts.length, 2, 'conflicting branch heads reported')
+
+    // SCENARIO: events affecting the conflicted resource
+    resp = await observation.call('economic_event', 'create_economic_event', { event: {
+      action: 'lower',
+      resourceInventoriedAs: resourceId,
+      resourceQuantity: { hasNumericalValue: 3, hasUnit: resourceUnitId },
+      hasPointInTime: '2019-11-20T04:29:55.056Z',
+      ...testEventProps,
+    } })
+    t.ok(resp.economicEvent && resp.economicEvent.id, 'events can be recorded against conflicted resources')
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 7, 'event applied to deterministically chosen branch')
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    const heads = resp.economicResource.meta.conflicts.map(c => c.id)
+    t.equal(heads.length, 2, 'updated branch replaces its previous head')
+
+    // SCENARIO: incomplete merge
+    try {
+      await observation.call('economic_resource', 'merge_economic_resource', { resource: { revisionIds: [heads[0]] } })
+      t.fail('merges must name every branch head')
+    } catch (err) {
+      t.ok(err.data.data.includes('Conflicting revisions found'), 'incomplete merge rejected with current heads')
+    }
+
+    // SCENARIO: merge all heads
+    resp = await observation.call('economic_resource', 'merge_economic_resource', { resource: { revisionIds: heads, note: 'merged' } })
+    t.equal(resp.economicResource.note, 'merged', 'manual edits applied to merged revision')
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 7, 'merged quantities recomputed from full event history')
+    t.ok(!heads.includes(resp.economicResource.revisionId), 'merge written as new revision')
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.notOk(resp.economicResource.meta.conflicts, 'no conflicts remain after merge')
+    t.notEqual(resp.economicResource.revisionId, branch1.economicResource.revisionId, 'first branch superseded')
+    t.notEqual(resp.economicResource.revisionId, branch2.economicResource.revisionId, 'second branch superseded')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,145 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('EconomicResource quantities do not accumulate rounding errors', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: instantiate a currency-like resource with a fractional quantity
+    const cResp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['currency'],
+        resourceQuantity: { hasNumericalValue: 0.1, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'wallet' },
+    })
+    await pause(100)
+    t.ok(cResp.economicResource && cResp.economicResource.id, 'resource created successfully')
+    const resourceId = cResp.economicResource.id
+
+    // SCENARIO: repeated fractional increments
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      resourceInventoriedAs: resourceId,
+      action: 'raise',
+      resourceQuantity: { hasNumericalValue: 0.2, hasUnit: resourceUnitId },
+      ...testEventProps,
+    } })
+    await pause(100)
+
+    let readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(readResp.economicResource.accountingQuantity.hasNumericalValue, 0.3, '0.1 + 0.2 accumulates exactly')
+
+    // SCENARIO: decimal string input for values beyond floating-point precision
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      resourceInventoriedAs: resourceId,
+      action: 'raise',
+      resourceQuantity: { hasNumericalValue: '12345678901234567890.7', hasUnit: resourceUnitId },
+      ...testEventProps,
+    } })
+    await pause(100)
+
+    readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(readResp.economicResource.accountingQuantity.hasNumericalValue, '12345678901234567891', 'large quantities are returned losslessly as strings')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('EconomicResource quantities round-trip losslessly via GraphQL', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const resourceUnitId = mockIdentifier()
+    const graphQLEventProps = {
+      provider: mockAddress(),
+      receiver: mockAddress(),
+      hasPointInTime: '2019-11-19T04:29:55.056Z',
+    }
+
+    // SCENARIO: create resource with a quantity beyond floating-point precision
+    let resp = await alice.graphQL(`
+      mutation($e: EconomicEventCreateParams!, $r: EconomicResourceCreateParams!) {
+        createEconomicEvent(event: $e, newInventoriedResource: $r) {
+          economicEvent {
+            id
+            resourceQuantity {
+              hasNumericalValue
+            }
+          }
+          economicResource {
+            id
+          }
+        }
+      }
+    `, {
+      e: {
+        action: 'raise',
+        resourceClassifiedAs: ['currency'],
+        resourceQuantity: { hasNumericalValue: '0.1234567890123456789', hasUnit: resourceUnitId },
+        ...graphQLEventProps,
+      },
+      r: { note: 'precise wallet' },
+    })
+    await pause(100)
+
+    t.ok(resp.data.createEconomicEvent.economicResource.id, 'resource created successfully')
+    t.equal(resp.data.createEconomicEvent.economicEvent.resourceQuantity.hasNumericalValue, '0.1234567890123456789', 'event quantity returned as exact decimal string')
+    const resourceId = resp.data.createEconomicEvent.economicResource.id
+
+    // SCENARIO: read back values which floats can and cannot represent
+    await alice.graphQL(`
+      mutation($e: EconomicEventCreateParams!) {
+        createEconomicEvent(event: $e) {
+          economicEvent {
+            id
+          }
+        }
+      }
+    `, {
+      e: {
+        action: 'raise',
+        resourceInventoriedAs: resourceId,
+        resourceQuantity: { hasNumericalValue: 2, hasUnit: resourceUnitId },
+        ...graphQLEventProps,
+      },
+    })
+    await pause(100)
+
+    resp = await alice.graphQL(`
+      query($id: ID!) {
+        economicResource(id: $id) {
+          accountingQuantity {
+            hasNumericalValue
+          }
+        }
+      }
+    `, { id: resourceId })
+    t.equal(resp.data.economicResource.accountingQuantity.hasNumericalValue, '2.1234567890123456789', 'accumulated quantity read back without loss')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
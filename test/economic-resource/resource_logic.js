@@ -306,6 +306,15 @@ test('EconomicResource & EconomicEvent record interactions', async (t) => {
     t.equal(readResource.state, 'fail', 'should take on the last PASS | FAIL event action as its state')
     t.deepLooseEqual(readResource.stage, pSpecId2, 'should take on the stage of the most recent event\'s related output ProcessSpecification')
 
+    let queryResp = await observation.call('economic_resource', 'get_economic_resources_by_state', { state: 'fail' })
+    t.deepLooseEqual(queryResp, [resourceId], 'resources indexed by their current state')
+    queryResp = await observation.call('economic_resource', 'get_economic_resources_by_state', { state: 'pass' })
+    t.deepLooseEqual(queryResp, [], 'resources removed from index of their previous state')
+    queryResp = await observation.call('economic_resource_index', 'query_economic_resources', { params: { stage: pSpecId2 } })
+    t.deepLooseEqual(queryResp.edges.map(e => e.node.id), [resourceId], 'resources indexed by their current stage')
+    queryResp = await observation.call('economic_resource_index', 'query_economic_resources', { params: { stage: pSpecId } })
+    t.equal(queryResp.edges.length, 0, 'resources removed from index of their previous stage')
+
     newEvent = {
       resourceInventoriedAs: resourceId,
       resourceClassifiedAs: ['http://www.productontology.org/doc/Manure_spreader.ttl'],
@@ -0,0 +1,97 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['resource-type-uri'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+}
+
+const exampleBatch = {
+  batchNumber: 'LOT-2022-0042',
+  productionDate: '2022-11-01T00:00:00.000Z',
+  expiryDate: '2023-05-01T00:00:00.000Z',
+  note: 'test batch',
+}
+
+test('ProductBatch record API and resource lot index', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    // SCENARIO: write records
+    const bResp = await observation.call('product_batch', 'create_product_batch', { productBatch: exampleBatch })
+    t.ok(bResp.productBatch && bResp.productBatch.id, 'batch created successfully')
+    await pause(100)
+    const batchId = bResp.productBatch.id
+
+    const event = {
+      note: 'batch resource instantiation event',
+      action: 'raise',
+      ...testEventProps,
+    }
+    const cResp1 = await observation.call('economic_event', 'create_economic_event', { event, newInventoriedResource: { note: 'batch resource 1', lot: batchId } })
+    const cResp2 = await observation.call('economic_event', 'create_economic_event', { event, newInventoriedResource: { note: 'batch resource 2', lot: batchId } })
+    const cResp3 = await observation.call('economic_event', 'create_economic_event', { event, newInventoriedResource: { note: 'unbatched resource' } })
+    await pause(100)
+    const resourceId1 = cResp1.economicResource.id
+    const resourceId2 = cResp2.economicResource.id
+    t.deepLooseEqual(cResp1.economicResource.lot, batchId, 'EconomicResource.lot reference OK in write')
+    t.notOk(cResp3.economicResource.lot, 'EconomicResource.lot not set for unbatched resource')
+
+    // ASSERT: batch references all resources
+    let readResponse = await observation.call('product_batch', 'get_product_batch', { address: batchId })
+    t.equal(readResponse.productBatch.batchNumber, exampleBatch.batchNumber, 'batch read OK')
+    t.equal(readResponse.productBatch.economicResources && readResponse.productBatch.economicResources.length, 2, 'batch resource refs present')
+    t.deepLooseEqual(readResponse.productBatch.economicResources.sort(), [resourceId1, resourceId2].sort(), 'batch resource refs OK')
+
+    readResponse = await observation.call('economic_resource_index', 'query_economic_resources', { params: { lot: batchId } })
+    t.equal(readResponse.edges && readResponse.edges.length, 2, 'resource lot query index present')
+
+    // SCENARIO: update batch
+    const updateResp = await observation.call('product_batch', 'update_product_batch', { productBatch: { revisionId: bResp.productBatch.revisionId, note: 'recalled' } })
+    await pause(100)
+    t.equal(updateResp.productBatch.note, 'recalled', 'batch updated')
+    t.equal(updateResp.productBatch.batchNumber, exampleBatch.batchNumber, 'batch number preserved on update')
+
+    // SCENARIO: move resources between batches
+    const bResp2 = await observation.call('product_batch', 'create_product_batch', { productBatch: { ...exampleBatch, batchNumber: 'LOT-2022-0043' } })
+    await pause(100)
+    const batchId2 = bResp2.productBatch.id
+
+    let rResp = await observation.call('economic_resource', 'update_economic_resource', { resource: { revisionId: cResp2.economicResource.revisionId, lot: batchId2 } })
+    t.deepLooseEqual(rResp.economicResource.lot, batchId2, 'EconomicResource.lot reassigned')
+    rResp = await observation.call('economic_resource', 'update_economic_resource', { resource: { revisionId: cResp3.economicResource.revisionId, lot: batchId2 } })
+    t.deepLooseEqual(rResp.economicResource.lot, batchId2, 'EconomicResource.lot assigned to unbatched resource')
+    await pause(100)
+
+    readResponse = await observation.call('product_batch', 'get_product_batch', { address: batchId })
+    t.deepLooseEqual(readResponse.productBatch.economicResources, [resourceId1], 'reassigned resource removed from previous batch')
+    readResponse = await observation.call('product_batch', 'get_product_batch', { address: batchId2 })
+    t.deepLooseEqual(readResponse.productBatch.economicResources.sort(), [resourceId2, cResp3.economicResource.id].sort(), 'reassigned resources indexed in new batch')
+
+    readResponse = await observation.call('economic_resource_index', 'query_economic_resources', { params: { lot: batchId2 } })
+    t.equal(readResponse.edges && readResponse.edges.length, 2, 'resource lot query index updated')
+
+    rResp = await observation.call('economic_resource', 'update_economic_resource', { resource: { revisionId: rResp.economicResource.revisionId, lot: null } })
+    t.notOk(rResp.economicResource.lot, 'EconomicResource.lot cleared')
+    await pause(100)
+
+    readResponse = await observation.call('product_batch', 'get_product_batch', { address: batchId2 })
+    t.deepLooseEqual(readResponse.productBatch.economicResources, [resourceId2], 'cleared resource removed from batch')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,70 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+}
+
+test('EconomicResource state at a point in time', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        hasPointInTime: '2022-03-01T00:00:00.000Z',
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'period-end resource' },
+    })
+    await pause(100)
+    const resourceId = resp.economicResource.id
+
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      action: 'lower',
+      resourceInventoriedAs: resourceId,
+      resourceQuantity: { hasNumericalValue: 4, hasUnit: resourceUnitId },
+      hasPointInTime: '2022-03-15T00:00:00.000Z',
+      ...testEventProps,
+    } })
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      action: 'lower',
+      resourceInventoriedAs: resourceId,
+      resourceQuantity: { hasNumericalValue: 5, hasUnit: resourceUnitId },
+      hasPointInTime: '2022-04-10T00:00:00.000Z',
+      ...testEventProps,
+    } })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource_at_time', { address: resourceId, at: '2022-03-31T23:59:59.999Z' })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 6, 'onhand quantity reconstructed at period end')
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 6, 'accounting quantity reconstructed at period end')
+
+    resp = await observation.call('economic_resource', 'get_economic_resource_at_time', { address: resourceId, at: '2022-04-30T00:00:00.000Z' })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 1, 'later events applied at later times')
+
+    try {
+      await observation.call('economic_resource', 'get_economic_resource_at_time', { address: resourceId, at: '2022-01-01T00:00:00.000Z' })
+      t.fail('resources should not be readable before they existed')
+    } catch (err) {
+      t.ok(err.toString().match(/No entry at this address/), 'resources cannot be read before their first event')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
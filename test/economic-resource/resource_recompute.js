@@ -0,0 +1,68 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+}
+
+test('EconomicResource quantities rebuilt from event history', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: events authored out of chronological order
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        hasPointInTime: '2019-11-19T04:29:55.056Z',
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'replayed resource' },
+    })
+    await pause(100)
+    const resourceId = resp.economicResource.id
+
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      action: 'lower',
+      resourceInventoriedAs: resourceId,
+      resourceQuantity: { hasNumericalValue: 3, hasUnit: resourceUnitId },
+      hasPointInTime: '2019-11-21T04:29:55.056Z',
+      ...testEventProps,
+    } })
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      action: 'raise',
+      resourceInventoriedAs: resourceId,
+      resourceQuantity: { hasNumericalValue: 2, hasUnit: resourceUnitId },
+      hasPointInTime: '2019-11-20T04:29:55.056Z',
+      ...testEventProps,
+    } })
+    await pause(100)
+
+    // ASSERT: replayed history matches incrementally computed quantities
+    resp = await observation.call('economic_resource', 'recompute_economic_resource', { address: resourceId })
+    t.equal(resp.eventsReplayed, 3, 'all affecting events replayed')
+    t.equal(resp.computedAccountingQuantity.hasNumericalValue, 9, 'accounting quantity recomputed')
+    t.equal(resp.computedOnhandQuantity.hasNumericalValue, 9, 'onhand quantity recomputed')
+    t.ok(resp.consistent, 'no discrepancy reported for consistent resource')
+    t.notOk(resp.revisionId, 'consistent resources are not rewritten')
+
+    resp = await observation.call('economic_resource', 'recompute_economic_resource', { address: resourceId, write: true })
+    t.notOk(resp.revisionId, 'consistent resources are not rewritten in write mode')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
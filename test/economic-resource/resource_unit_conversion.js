@@ -0,0 +1,132 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('EconomicResource quantities are converted between compatible units', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'specification'])
+  try {
+    const { cells: [observation, specification] } = alice
+
+    // SCENARIO: define units with conversion factors
+    let resp = await specification.call('unit', 'create_unit', { unit: { label: 'kilograms', symbol: 'kg' } })
+    const kgId = resp.unit.id
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'grams', symbol: 'g', baseUnit: kgId, conversionFactor: 0.001 } })
+    const gId = resp.unit.id
+    t.equal(resp.unit.conversionFactor, 0.001, 'conversion factor stored')
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'kelvin', symbol: 'K' } })
+    const kelvinId = resp.unit.id
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'degrees Celsius', symbol: 'Cel', baseUnit: kelvinId, conversionFactor: 1, conversionOffset: 273.15 } })
+    const celsiusId = resp.unit.id
+    await pause(100)
+
+    try {
+      await specification.call('unit', 'create_unit', { unit: { label: 'broken', symbol: 'brk', baseUnit: kgId, conversionFactor: 0 } })
+      t.fail('zero conversion factors should be rejected')
+    } catch (err) {
+      t.ok(err.toString().match(/non-zero/), 'zero conversion factors rejected')
+    }
+
+    // SCENARIO: convert API
+    resp = await specification.call('unit', 'convert_quantity', { quantity: { hasNumericalValue: 1500, hasUnit: gId }, toUnit: kgId })
+    t.deepLooseEqual(resp.quantity, { hasNumericalValue: 1.5, hasUnit: kgId }, 'grams convert to kilograms')
+    resp = await specification.call('unit', 'convert_quantity', { quantity: { hasNumericalValue: 25, hasUnit: celsiusId }, toUnit: kelvinId })
+    t.equal(resp.quantity.hasNumericalValue, 298.15, 'offsets are applied when converting')
+    try {
+      await specification.call('unit', 'convert_quantity', { quantity: { hasNumericalValue: 1, hasUnit: gId }, toUnit: kelvinId })
+      t.fail('incompatible units should not be convertible')
+    } catch (err) {
+      t.ok(err.toString().match(/Mismatching units/), 'incompatible units cannot be converted')
+    }
+
+    // SCENARIO: units defined in terms of other derived units
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'milligrams', symbol: 'mg', baseUnit: gId, conversionFactor: 0.001 } })
+    const mgId = resp.unit.id
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'tonnes', symbol: 't', baseUnit: kgId, conversionFactor: 1000 } })
+    const tonneId = resp.unit.id
+    await pause(100)
+
+    resp = await specification.call('unit', 'convert_quantity', { quantity: { hasNumericalValue: 2500000, hasUnit: mgId }, toUnit: kgId })
+    t.deepLooseEqual(resp.quantity, { hasNumericalValue: 2.5, hasUnit: kgId }, 'conversions follow chains of base units')
+    resp = await specification.call('unit', 'convert_quantity', { quantity: { hasNumericalValue: 0.002, hasUnit: tonneId }, toUnit: mgId })
+    t.deepLooseEqual(resp.quantity, { hasNumericalValue: 2000000, hasUnit: mgId }, 'derived units sharing a base unit convert between each other')
+    try {
+      await specification.call('unit', 'convert_quantity', { quantity: { hasNumericalValue: 1, hasUnit: mgId }, toUnit: celsiusId })
+      t.fail('units derived from different base units should not be convertible')
+    } catch (err) {
+      t.ok(err.toString().match(/Mismatching units/), 'incompatible derived units cannot be converted')
+    }
+
+    // SCENARIO: units defined in terms of themselves
+    try {
+      await specification.call('unit', 'create_unit', { unit: { label: 'self', symbol: 'slf', baseUnit: [kgId[0], 'slf'], conversionFactor: 2 } })
+      t.fail('units should not be their own base unit')
+    } catch (err) {
+      t.ok(err.toString().match(/defined in terms of itself/), 'self-referencing base unit rejected')
+    }
+    resp = await specification.call('unit', 'get_unit', { id: kgId })
+    try {
+      await specification.call('unit', 'update_unit', { unit: { revisionId: resp.unit.revisionId, label: 'kilograms', symbol: 'kg', baseUnit: mgId, conversionFactor: 1000000 } })
+      t.fail('cyclic base units should be rejected')
+    } catch (err) {
+      t.ok(err.toString().match(/defined in terms of itself/), 'cyclic base unit rejected')
+    }
+    resp = await specification.call('unit', 'get_unit', { id: gId })
+    try {
+      await specification.call('unit', 'update_unit', { unit: { revisionId: resp.unit.revisionId, label: 'grams', symbol: 'g', baseUnit: kgId, conversionFactor: 0 } })
+      t.fail('zero conversion factors should be rejected on update')
+    } catch (err) {
+      t.ok(err.toString().match(/non-zero/), 'zero conversion factors rejected on update')
+    }
+
+    // SCENARIO: resource tracked in kilograms receives events in grams
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['flour'],
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: kgId },
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'flour sack' },
+    })
+    await pause(100)
+    const resourceId = resp.economicResource.id
+
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      resourceInventoriedAs: resourceId,
+      action: 'raise',
+      resourceQuantity: { hasNumericalValue: 500, hasUnit: gId },
+      ...testEventProps,
+    } })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.accountingQuantity, { hasNumericalValue: 1.5, hasUnit: kgId }, 'event quantity converted into resource unit when incrementing')
+
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      resourceInventoriedAs: resourceId,
+      action: 'lower',
+      resourceQuantity: { hasNumericalValue: 250, hasUnit: gId },
+      ...testEventProps,
+    } })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.accountingQuantity, { hasNumericalValue: 1.25, hasUnit: kgId }, 'event quantity converted into resource unit when decrementing')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,89 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+const nodeIds = (graph, type) => graph.nodes.filter(n => n.record.type === type).map(n => n.record.id)
+
+test('Track & trace resource flows', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: raw materials consumed by a process which produces a new resource
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        resourceClassifiedAs: ['wheat'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'wheat' },
+    })
+    const inputResourceId = resp.economicResource.id
+
+    resp = await observation.call('process', 'create_process', { process: { name: 'milling' } })
+    const processId = resp.process.id
+    await pause(100)
+
+    resp = await observation.call('economic_event', 'create_economic_event', { event: {
+      action: 'consume',
+      inputOf: processId,
+      resourceInventoriedAs: inputResourceId,
+      resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+      ...testEventProps,
+    } })
+    const inputEventId = resp.economicEvent.id
+
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'produce',
+        outputOf: processId,
+        resourceClassifiedAs: ['flour'],
+        resourceQuantity: { hasNumericalValue: 8, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'flour' },
+    })
+    const outputEventId = resp.economicEvent.id
+    const outputResourceId = resp.economicResource.id
+    await pause(100)
+
+    // ASSERT: tracking forward from inputs reaches outputs
+    resp = await observation.call('economic_event', 'track', { start: { type: 'economicResource', id: inputResourceId } })
+    t.deepLooseEqual(nodeIds(resp, 'economicEvent'), [inputEventId, outputEventId], 'track visits input & output events in order')
+    t.deepLooseEqual(nodeIds(resp, 'process'), [processId], 'track visits transforming process')
+    t.deepLooseEqual(nodeIds(resp, 'economicResource'), [inputResourceId, outputResourceId], 'track reaches output resource')
+    t.equal(resp.edges.length, 4, 'track returns flows between visited records')
+
+    // ASSERT: tracing backward from outputs reaches origins
+    resp = await observation.call('economic_event', 'trace', { start: { type: 'economicResource', id: outputResourceId } })
+    t.deepLooseEqual(nodeIds(resp, 'economicResource'), [outputResourceId, inputResourceId], 'trace reaches input resource')
+    t.deepLooseEqual(resp.edges[0], {
+      from: { type: 'economicEvent', id: outputEventId },
+      to: { type: 'economicResource', id: outputResourceId },
+    }, 'trace edges are directed along resource flows')
+
+    // ASSERT: traversal depth is configurable
+    resp = await observation.call('economic_event', 'trace', { start: { type: 'economicResource', id: outputResourceId }, depth: 2 })
+    t.deepLooseEqual(resp.nodes.map(n => n.depth), [0, 1, 2], 'trace stops at requested depth')
+    t.deepLooseEqual(nodeIds(resp, 'process'), [processId], 'trace from resource reaches process within two steps')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,179 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const resourceUnitId = mockIdentifier(false)
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('Commitment fulfilled & remaining quantities', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    // SCENARIO: commit to provide 10 units
+    const commitmentResponse = await planning.call('commitment', 'create_commitment', {
+      commitment: {
+        note: 'a commitment to provide 10 units',
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+    })
+    t.ok(commitmentResponse.commitment && commitmentResponse.commitment.id, 'commitment created successfully')
+    await pause(100)
+    const commitmentId = commitmentResponse.commitment.id
+
+    t.equal(commitmentResponse.commitment.fulfilledQuantity.hasNumericalValue, 0, 'unfulfilled commitment has nothing fulfilled')
+    t.equal(commitmentResponse.commitment.remainingQuantity.hasNumericalValue, 10, 'unfulfilled commitment has full quantity remaining')
+
+    const eventResp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        note: 'test event which is fulfilling a commitment',
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+    })
+    t.ok(eventResp.economicEvent && eventResp.economicEvent.id, 'event created successfully')
+    await pause(100)
+    const eventId = eventResp.economicEvent.id
+
+    // SCENARIO: partially fulfill the commitment
+    const fulfillmentResp = await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: {
+        fulfills: commitmentId,
+        fulfilledBy: eventId,
+        resourceQuantity: { hasNumericalValue: 4, hasUnit: resourceUnitId },
+      },
+    })
+    t.ok(fulfillmentResp.fulfillment && fulfillmentResp.fulfillment.id, 'first fulfillment created successfully')
+    await pause(100)
+
+    let readResponse = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.equal(readResponse.commitment.fulfilledQuantity.hasNumericalValue, 4, 'partial fulfillment quantity totalled')
+    t.equal(readResponse.commitment.remainingQuantity.hasNumericalValue, 6, 'partial fulfillment remaining quantity OK')
+    t.deepLooseEqual(readResponse.commitment.remainingQuantity.hasUnit, resourceUnitId, 'remaining quantity expressed in commitment unit')
+
+    // SCENARIO: overfulfill the commitment
+    const fulfillmentResp2 = await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: {
+        fulfills: commitmentId,
+        fulfilledBy: eventId,
+        resourceQuantity: { hasNumericalValue: 7, hasUnit: resourceUnitId },
+      },
+    })
+    t.ok(fulfillmentResp2.fulfillment && fulfillmentResp2.fulfillment.id, 'second fulfillment created successfully')
+    await pause(100)
+
+    readResponse = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.equal(readResponse.commitment.fulfilledQuantity.hasNumericalValue, 11, 'fulfillment quantities totalled')
+    t.equal(readResponse.commitment.remainingQuantity.hasNumericalValue, 0, 'remaining quantity does not go below zero')
+    t.equal(readResponse.commitment.finished, false, 'commitment not marked as finished unless auto_finish is enabled')
+
+    // SCENARIO: remove a fulfillment
+    await planning.call('fulfillment', 'delete_fulfillment', { revisionId: fulfillmentResp2.fulfillment.revisionId })
+    await pause(100)
+
+    readResponse = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.equal(readResponse.commitment.fulfilledQuantity.hasNumericalValue, 4, 'deleted fulfillment no longer counted')
+    t.equal(readResponse.commitment.remainingQuantity.hasNumericalValue, 6, 'remaining quantity restored after deletion')
+    t.equal(readResponse.commitment.fulfilledBy.length, 1, 'deleted fulfillment unlinked')
+
+    // SCENARIO: correct the fulfilling event
+    await observation.call('economic_event', 'correct_economic_event', { event: eventId })
+    await pause(100)
+
+    readResponse = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.equal(readResponse.commitment.fulfilledQuantity.hasNumericalValue, 0, 'fulfillments by corrected events no longer counted')
+    t.equal(readResponse.commitment.remainingQuantity.hasNumericalValue, 10, 'remaining quantity restored after correction')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Commitment fulfilled quantities are converted between units', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation', 'specification'])
+  try {
+    const { cells: [planning, observation, specification] } = alice
+
+    let resp = await specification.call('unit', 'create_unit', { unit: { label: 'kilograms', symbol: 'kg' } })
+    const kgId = resp.unit.id
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'grams', symbol: 'g', baseUnit: kgId, conversionFactor: 0.001 } })
+    const gId = resp.unit.id
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'litres', symbol: 'l' } })
+    const litreId = resp.unit.id
+    await pause(100)
+
+    const commitmentResponse = await planning.call('commitment', 'create_commitment', {
+      commitment: {
+        note: 'a commitment to provide 2kg',
+        resourceQuantity: { hasNumericalValue: 2, hasUnit: kgId },
+        ...testEventProps,
+      },
+    })
+    await pause(100)
+    const commitmentId = commitmentResponse.commitment.id
+
+    const eventResp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        resourceQuantity: { hasNumericalValue: 2, hasUnit: kgId },
+        ...testEventProps,
+      },
+    })
+    await pause(100)
+    const eventId = eventResp.economicEvent.id
+
+    // SCENARIO: fulfillments in a compatible unit
+    await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentId, fulfilledBy: eventId, resourceQuantity: { hasNumericalValue: 1, hasUnit: kgId } },
+    })
+    await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentId, fulfilledBy: eventId, resourceQuantity: { hasNumericalValue: 500, hasUnit: gId } },
+    })
+    await pause(100)
+
+    let readResponse = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.deepLooseEqual(readResponse.commitment.fulfilledQuantity, { hasNumericalValue: 1.5, hasUnit: kgId }, 'mixed-unit fulfillments totalled in commitment unit')
+    t.deepLooseEqual(readResponse.commitment.remainingQuantity, { hasNumericalValue: 0.5, hasUnit: kgId }, 'remaining quantity accounts for converted fulfillments')
+    t.notOk(readResponse.commitment.fulfilledQuantityError, 'no error reported where quantities are computed')
+
+    // SCENARIO: list pages omit computed quantities
+    const listResp = await planning.call('commitment_index', 'read_all_commitments', {})
+    const listed = listResp.edges.find(e => e.node.id.toString() === commitmentId.toString())
+    t.ok(listed, 'commitment listed')
+    t.notOk(listed.node.fulfilledQuantity, 'fulfilled quantity not computed for list pages')
+
+    // SCENARIO: fulfillment in an incompatible unit
+    await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentId, fulfilledBy: eventId, resourceQuantity: { hasNumericalValue: 1, hasUnit: litreId } },
+    })
+    await pause(100)
+
+    readResponse = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.ok(readResponse.commitment.id, 'commitment still readable with incompatible fulfillments')
+    t.notOk(readResponse.commitment.fulfilledQuantity, 'fulfilled quantity omitted where units cannot be converted')
+    t.notOk(readResponse.commitment.remainingQuantity, 'remaining quantity omitted where units cannot be converted')
+    t.ok(/Mismatching units/.test(readResponse.commitment.fulfilledQuantityError), 'conversion failure reported')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
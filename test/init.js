@@ -62,6 +62,7 @@ const dnaPaths = {
     '../bundles/dna/specification/hrea_specification.dna',
   ),
   plan: path.resolve(__dirname, '../bundles/dna/plan/hrea_plan.dna'),
+  location: path.resolve(__dirname, '../bundles/dna/location/hrea_location.dna'),
 }
 const getDNA = (name) => dnaPaths[name]
 
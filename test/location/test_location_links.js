@@ -0,0 +1,84 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['resource-type-uri'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+}
+
+const exampleLocation = {
+  name: 'test warehouse',
+  lat: -37.8136,
+  long: 144.9631,
+  mappableAddress: '1 Test St, Melbourne',
+  note: 'just testing',
+}
+
+test('Location record API and reciprocal indexes', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['location', 'observation'])
+  try {
+    const { cells: [location, observation] } = alice
+
+    // SCENARIO: write records
+    const lResp = await location.call('location', 'create_location', { location: exampleLocation })
+    t.ok(lResp.location && lResp.location.id, 'location created successfully')
+    await pause(100)
+    const locationId = lResp.location.id
+    const lRevisionId = lResp.location.revisionId
+
+    let readResponse = await location.call('location', 'get_location', { address: locationId })
+    t.deepLooseEqual(readResponse.location, { id: locationId, revisionId: lRevisionId, meta: readResponse.location.meta, ...exampleLocation }, 'location read OK')
+
+    const updateResp = await location.call('location', 'update_location', { location: { revisionId: lRevisionId, name: 'moved warehouse', alt: 12.5 } })
+    await pause(100)
+    t.equal(updateResp.location.name, 'moved warehouse', 'location updated')
+    t.equal(updateResp.location.alt, 12.5, 'location field added')
+    t.equal(updateResp.location.mappableAddress, exampleLocation.mappableAddress, 'unspecified location field preserved')
+
+    const event = {
+      note: 'resource instantiation event',
+      action: 'raise',
+      atLocation: locationId,
+      ...testEventProps,
+    }
+    const resource = {
+      note: 'located resource',
+      currentLocation: locationId,
+    }
+    const cResp = await observation.call('economic_event', 'create_economic_event', { event, newInventoriedResource: resource })
+    await pause(100)
+    const eventId = cResp.economicEvent.id
+    const resourceId = cResp.economicResource.id
+    t.deepLooseEqual(cResp.economicEvent.atLocation, locationId, 'EconomicEvent.atLocation reference OK in write')
+    t.deepLooseEqual(cResp.economicResource.currentLocation, locationId, 'EconomicResource.currentLocation reference OK in write')
+
+    // ASSERT: test location query edges
+    readResponse = await observation.call('economic_event_index', 'query_economic_events', { params: { atLocation: locationId } })
+    t.equal(readResponse.edges && readResponse.edges.length, 1, 'event location query index present')
+    t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node.id, eventId, 'event location query index created')
+
+    readResponse = await observation.call('economic_resource_index', 'query_economic_resources', { params: { currentLocation: locationId } })
+    t.equal(readResponse.edges && readResponse.edges.length, 1, 'resource location query index present')
+    t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node.id, resourceId, 'resource location query index created')
+
+    // SCENARIO: delete location
+    const deleteResult = await location.call('location', 'delete_location', { revisionId: updateResp.location.revisionId })
+    await pause(100)
+    t.equal(deleteResult.deleted, true, 'location deleted')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,83 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testCommitmentProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  provider: mockAddress(),
+  receiver: mockAddress(),
+}
+
+test('Conflicting planning records can be merged', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning', 'plan'])
+  try {
+    const { cells: [_observation, planning, plan] } = alice
+
+    let resp = await plan.call('plan', 'create_plan', { plan: { name: 'test plan' } })
+    const planId = resp.plan.id
+    const planRevision = resp.plan.revisionId
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { note: 'original', ...testCommitmentProps } })
+    const commitmentId = resp.commitment.id
+    const commitmentRevision = resp.commitment.revisionId
+    await pause(100)
+
+    // SCENARIO: two updates made against the same revision diverge the revision history
+    await plan.call('plan', 'update_plan', { plan: { revisionId: planRevision, name: 'branch 1' } })
+    await plan.call('plan', 'update_plan', { plan: { revisionId: planRevision, name: 'branch 2' } })
+    await planning.call('commitment', 'update_commitment', { commitment: { revisionId: commitmentRevision, note: 'branch 1' } })
+    await planning.call('commitment', 'update_commitment', { commitment: { revisionId: commitmentRevision, note: 'branch 2' } })
+    await pause(100)
+
+    resp = await plan.call('plan', 'get_plan', { address: planId })
+    const planHeads = resp.plan.meta.conflicts.map(c => c.id)
+    t.equal(planHeads.length, 2, 'conflicting plan branch heads reported')
+    resp = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    const commitmentHeads = resp.commitment.meta.conflicts.map(c => c.id)
+    t.equal(commitmentHeads.length, 2, 'conflicting commitment branch heads reported')
+
+    // SCENARIO: incomplete merge
+    try {
+      await planning.call('commitment', 'merge_commitment', { commitment: { revisionIds: [commitmentHeads[0]] } })
+      t.fail('merges must name every branch head')
+    } catch (err) {
+      t.ok(err.data.data.includes('Conflicting revisions found'), 'incomplete merge rejected with current heads')
+    }
+
+    // SCENARIO: merge all heads
+    resp = await plan.call('plan', 'merge_plan', { plan: { revisionIds: planHeads, name: 'merged plan' } })
+    t.equal(resp.plan.name, 'merged plan', 'manual edits applied to merged plan')
+    resp = await planning.call('commitment', 'merge_commitment', { commitment: { revisionIds: commitmentHeads, note: 'merged', independentDemandOf: planId } })
+    t.equal(resp.commitment.note, 'merged', 'manual edits applied to merged commitment')
+    t.ok(!commitmentHeads.includes(resp.commitment.revisionId), 'merge written as new revision')
+    await pause(100)
+
+    resp = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.notOk(resp.commitment.meta.conflicts, 'no commitment conflicts remain after merge')
+    resp = await plan.call('plan', 'get_plan', { address: planId })
+    t.notOk(resp.plan.meta.conflicts, 'no plan conflicts remain after merge')
+
+    resp = await alice.graphQL(`
+      query {
+        plan(id: "${planId}") {
+          independentDemands {
+            id
+          }
+        }
+      }
+    `)
+    t.deepEqual(resp.data.plan.independentDemands.map(c => c.id), [commitmentId], 'link fields edited in a merge are indexed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -98,6 +98,21 @@ test('process local query indexes and relationships', async (t) => {
     t.deepLooseEqual(readResponse && readResponse.edges && readResponse.edges.length, 1, 'event output query index present')
     t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node && readResponse.edges[0].node.id, oEventId, 'event output query index created')
 
+    // ASSERT: test combined event query conditions
+    readResponse = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId, outputOf: processId } })
+    t.equal(readResponse.edges.length, 0, 'multiple query params are combined with AND')
+
+    readResponse = await observation.call('economic_event_index', 'query_economic_events', { params: { provider: testEventProps.provider, inputOf: processId } })
+    t.equal(readResponse.edges.length, 1, 'AND query returns matching records only')
+    t.deepLooseEqual(readResponse.edges[0].node.id, iEventId, 'AND query returns correct record')
+
+    readResponse = await observation.call('economic_event_index', 'query_economic_events', { or: [{ params: { inputOf: processId } }, { params: { outputOf: processId } }] })
+    t.equal(readResponse.edges.length, 2, 'OR query returns records matching either condition')
+
+    readResponse = await observation.call('economic_event_index', 'query_economic_events', { params: { provider: testEventProps.provider }, not: { params: { inputOf: processId } } })
+    t.equal(readResponse.edges.length, 1, 'NOT query excludes matching records')
+    t.deepLooseEqual(readResponse.edges[0].node.id, oEventId, 'NOT query returns correct record')
+
     // ASSERT: check process event input query edge
     readResponse = await observation.call('process_index', 'query_processes', { params: { observedInputs: iEventId } })
     t.deepLooseEqual(readResponse && readResponse.edges && readResponse.edges.length, 1, 'process.observedInputs query succeeded')
@@ -0,0 +1,155 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('User-defined actions', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'specification'])
+  try {
+    const { cells: [observation, specification] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: register custom actions
+    let resp = await specification.call('action', 'create_action', { action: {
+      id: 'repair',
+      label: 'repair',
+      resourceEffect: 'noEffect',
+      onhandEffect: 'noEffect',
+      inputOutput: 'notApplicable',
+    } })
+    t.ok(resp.action && resp.action.id, 'custom action created successfully')
+    t.equal(resp.action.pairsWith, 'notApplicable', 'pairsWith defaults to notApplicable')
+    resp = await specification.call('action', 'create_action', { action: {
+      id: 'harvest',
+      label: 'harvest',
+      resourceEffect: 'increment',
+      onhandEffect: 'increment',
+      inputOutput: 'notApplicable',
+    } })
+    resp = await specification.call('action', 'create_action', { action: {
+      id: 'mend',
+      label: 'mend',
+      resourceEffect: 'noEffect',
+      onhandEffect: 'noEffect',
+      inputOutput: 'input',
+    } })
+    await pause(100)
+
+    try {
+      await specification.call('action', 'create_action', { action: {
+        id: 'raise',
+        label: 'shadowed raise',
+        resourceEffect: 'decrement',
+        onhandEffect: 'decrement',
+        inputOutput: 'notApplicable',
+      } })
+      t.fail('builtin actions should not be redefinable')
+    } catch (err) {
+      t.ok(err.toString().match(/builtin action/), 'builtin actions cannot be redefined')
+    }
+
+    // ASSERT: custom actions are readable alongside builtins
+    resp = await specification.call('action', 'get_action', { id: 'harvest' })
+    t.deepLooseEqual(resp, {
+      id: 'harvest',
+      label: 'harvest',
+      resourceEffect: 'increment',
+      onhandEffect: 'increment',
+      inputOutput: 'notApplicable',
+      pairsWith: 'notApplicable',
+    }, 'custom action read via get_action')
+
+    try {
+      await specification.call('action', 'get_action', { id: 'teleport' })
+      t.fail('reading undefined actions should error')
+    } catch (err) {
+      t.ok(err.toString().match(/No action with ID 'teleport'/), 'undefined actions reported as missing')
+    }
+
+    resp = await specification.call('action', 'get_all_actions', null)
+    t.equal(resp.length, 21, 'custom actions listed after builtins')
+
+    // SCENARIO: use custom actions in observed flows
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'harvest',
+        resourceClassifiedAs: ['apples'],
+        resourceQuantity: { hasNumericalValue: 8, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+      newInventoriedResource: { note: 'apple crate' },
+    })
+    await pause(100)
+    t.ok(resp.economicEvent && resp.economicEvent.id, 'event with custom action created')
+    const resourceId = resp.economicResource.id
+
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      resourceInventoriedAs: resourceId,
+      action: 'harvest',
+      resourceQuantity: { hasNumericalValue: 2, hasUnit: resourceUnitId },
+      ...testEventProps,
+    } })
+    await observation.call('economic_event', 'create_economic_event', { event: {
+      resourceInventoriedAs: resourceId,
+      action: 'repair',
+      resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+      ...testEventProps,
+    } })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.accountingQuantity.hasNumericalValue, 10, 'custom action effects applied by inventory reducer')
+
+    try {
+      await observation.call('economic_event', 'create_economic_event', { event: {
+        resourceInventoriedAs: resourceId,
+        action: 'teleport',
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+        ...testEventProps,
+      } })
+      t.fail('unknown actions should be rejected')
+    } catch (err) {
+      t.ok(err.toString().match(/Unknown action/), 'unknown actions rejected')
+    }
+
+    // SCENARIO: process rules of custom actions are enforced
+    try {
+      await observation.call('economic_event', 'create_economic_event', { event: {
+        resourceInventoriedAs: resourceId,
+        action: 'mend',
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+        ...testEventProps,
+      } })
+      t.fail('custom input actions should require an input process')
+    } catch (err) {
+      t.ok(err.toString().match(/input process required for 'mend' action/), 'custom action process rules applied')
+    }
+
+    const processResp = await observation.call('process', 'create_process', { process: { name: 'repair shop' } })
+    await pause(100)
+    resp = await observation.call('economic_event', 'create_economic_event', { event: {
+      resourceInventoriedAs: resourceId,
+      action: 'mend',
+      inputOf: processResp.process.id,
+      resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+      ...testEventProps,
+    } })
+    t.ok(resp.economicEvent && resp.economicEvent.id, 'event satisfying custom action process rules passes validation')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,318 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const unitId = mockIdentifier(false)
+
+test('Recipe records and plan generation', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification', 'plan', 'observation', 'planning'])
+  try {
+    const { cells: [specification, plan, observation, planning] } = alice
+
+    // SCENARIO: define a recipe where 2 planks are assembled into 1 shelf over 2 hours
+    const shelfResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'shelf', resourceClassifiedAs: ['furniture'] } })
+    const plankResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'plank' } })
+    await pause(100)
+    const shelfId = shelfResp.recipeResource.id
+    const plankId = plankResp.recipeResource.id
+
+    const rpResp = await specification.call('recipe_process', 'create_recipe_process', { recipeProcess: {
+      name: 'assemble shelf',
+      hasDuration: { numericDuration: 2, unitType: 'hour' },
+    } })
+    await pause(100)
+    t.ok(rpResp.recipeProcess && rpResp.recipeProcess.id, 'recipe process created successfully')
+    const recipeProcessId = rpResp.recipeProcess.id
+
+    const outputResp = await specification.call('recipe_flow', 'create_recipe_flow', { recipeFlow: {
+      action: 'produce',
+      recipeFlowResource: shelfId,
+      resourceQuantity: { hasNumericalValue: 1, hasUnit: unitId },
+      recipeOutputOf: recipeProcessId,
+    } })
+    const inputResp = await specification.call('recipe_flow', 'create_recipe_flow', { recipeFlow: {
+      action: 'consume',
+      recipeFlowResource: plankId,
+      resourceQuantity: { hasNumericalValue: 2, hasUnit: unitId },
+      recipeInputOf: recipeProcessId,
+    } })
+    await pause(100)
+    const outputFlowId = outputResp.recipeFlow.id
+    const inputFlowId = inputResp.recipeFlow.id
+
+    // ASSERT: recipe indexes
+    let readResponse = await specification.call('recipe_process', 'get_recipe_process', { address: recipeProcessId })
+    t.deepLooseEqual(readResponse.recipeProcess.recipeOutputs, [outputFlowId], 'RecipeProcess.recipeOutputs reference OK')
+    t.deepLooseEqual(readResponse.recipeProcess.recipeInputs, [inputFlowId], 'RecipeProcess.recipeInputs reference OK')
+
+    readResponse = await specification.call('recipe_flow_index', 'query_recipe_flows', { params: { recipeFlowResource: plankId } })
+    t.equal(readResponse.edges && readResponse.edges.length, 1, 'recipe flow resource query index present')
+    t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node.id, inputFlowId, 'recipe flow resource query index created')
+
+    // SCENARIO: generate a plan for 3 shelves
+    const due = '2022-12-01T12:00:00.000Z'
+    const pResp = await specification.call('recipe_process', 'create_plan_from_recipe', { plan: {
+      recipeResource: shelfId,
+      resourceQuantity: { hasNumericalValue: 3, hasUnit: unitId },
+      due,
+      provider: mockAddress(false),
+      receiver: mockAddress(false),
+      name: 'shelf order',
+    } })
+    await pause(100)
+    t.ok(pResp.plan, 'plan generated')
+    t.equal(pResp.processes.length, 1, 'one process planned')
+    t.equal(pResp.commitments.length, 2, 'commitments planned for each recipe flow')
+
+    readResponse = await plan.call('plan', 'get_plan', { address: pResp.plan })
+    t.equal(readResponse.plan.name, 'shelf order', 'plan record written')
+
+    readResponse = await observation.call('process', 'get_process', { address: pResp.processes[0] })
+    t.equal(readResponse.process.name, 'assemble shelf', 'process named after recipe process')
+    t.deepLooseEqual(readResponse.process.plannedWithin, pResp.plan, 'process planned within generated plan')
+    t.equal(new Date(readResponse.process.hasEnd).getTime(), new Date(due).getTime(), 'process ends at due date')
+    t.equal(new Date(readResponse.process.hasBeginning).getTime(), new Date(due).getTime() - 2 * 60 * 60 * 1000, 'process begins recipe duration before due date')
+
+    const commitments = await Promise.all(pResp.commitments.map((address) => planning.call('commitment', 'get_commitment', { address })))
+    const output = commitments.find(c => c.commitment.action === 'produce').commitment
+    const input = commitments.find(c => c.commitment.action === 'consume').commitment
+    t.equal(output.resourceQuantity.hasNumericalValue, 3, 'output commitment quantity matches request')
+    t.deepLooseEqual(output.independentDemandOf, pResp.plan, 'output commitment is independent demand of plan')
+    t.deepLooseEqual(output.plannedWithin, pResp.plan, 'output commitment planned within generated plan')
+    t.deepLooseEqual(input.plannedWithin, pResp.plan, 'input commitment planned within generated plan')
+    t.deepLooseEqual(output.resourceClassifiedAs, ['furniture'], 'output commitment classification from recipe resource')
+    t.equal(input.resourceQuantity.hasNumericalValue, 6, 'input commitment quantity scaled by recipe ratio')
+    t.deepLooseEqual(input.inputOf, pResp.processes[0], 'input commitment linked to planned process')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Plan generation writes nothing when recipes cannot be planned', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification', 'plan', 'observation', 'planning'])
+  try {
+    const { cells: [specification, plan, observation, planning] } = alice
+
+    // SCENARIO: shelves are assembled from planks, which are sawn from logs whose recipe resource no longer exists
+    const shelfResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'shelf' } })
+    const plankResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'plank' } })
+    const logResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'log' } })
+    const assembleResp = await specification.call('recipe_process', 'create_recipe_process', { recipeProcess: { name: 'assemble shelf' } })
+    const sawResp = await specification.call('recipe_process', 'create_recipe_process', { recipeProcess: { name: 'saw planks' } })
+    await pause(100)
+
+    const flows = [
+      { action: 'produce', recipeFlowResource: shelfResp.recipeResource.id, recipeOutputOf: assembleResp.recipeProcess.id },
+      { action: 'consume', recipeFlowResource: plankResp.recipeResource.id, recipeInputOf: assembleResp.recipeProcess.id },
+      { action: 'produce', recipeFlowResource: plankResp.recipeResource.id, recipeOutputOf: sawResp.recipeProcess.id },
+      { action: 'consume', recipeFlowResource: logResp.recipeResource.id, recipeInputOf: sawResp.recipeProcess.id },
+    ]
+    for (const recipeFlow of flows) {
+      await specification.call('recipe_flow', 'create_recipe_flow', { recipeFlow: {
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: unitId },
+        ...recipeFlow,
+      } })
+    }
+    await specification.call('recipe_resource', 'delete_recipe_resource', { revisionId: logResp.recipeResource.revisionId })
+    await pause(100)
+
+    // ASSERT: planning fails partway through the recipe graph
+    try {
+      await specification.call('recipe_process', 'create_plan_from_recipe', { plan: {
+        recipeResource: shelfResp.recipeResource.id,
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: unitId },
+        due: '2022-12-01T12:00:00.000Z',
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+      } })
+      t.fail('planning should fail when recipe records are missing')
+    } catch (err) {
+      t.ok(err.toString().match(/No entry at this address/), 'missing recipe records reported')
+    }
+    await pause(100)
+
+    // ASSERT: requesting a resource which no recipe produces is an error
+    const glueResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'glue' } })
+    await pause(100)
+    try {
+      await specification.call('recipe_process', 'create_plan_from_recipe', { plan: {
+        recipeResource: glueResp.recipeResource.id,
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: unitId },
+        due: '2022-12-01T12:00:00.000Z',
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+      } })
+      t.fail('planning should fail when no recipe produces the requested resource')
+    } catch (err) {
+      t.ok(err.toString().match(/No recipe process produces resource/), 'unproducible resource reported')
+    }
+    await pause(100)
+
+    // ASSERT: no orphaned records were left behind
+    let readResponse = await plan.call('plan_index', 'read_all_plans', {})
+    t.equal(readResponse.edges.length, 0, 'no plan written')
+    readResponse = await observation.call('process_index', 'read_all_processes', {})
+    t.equal(readResponse.edges.length, 0, 'no processes written')
+    readResponse = await planning.call('commitment_index', 'read_all_commitments', {})
+    t.equal(readResponse.edges.length, 0, 'no commitments written')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Plan generation combines demand upon shared recipe processes', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification', 'plan', 'observation', 'planning'])
+  try {
+    const { cells: [specification, plan, observation, planning] } = alice
+
+    // SCENARIO: a shelf consumes 2 planks and 1 frame, a frame consumes 2 planks, and planks are sawn from logs
+    const resources = {}
+    for (const name of ['shelf', 'frame', 'plank', 'log']) {
+      resources[name] = (await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name } })).recipeResource.id
+    }
+    const processes = {}
+    for (const [name, hours] of [['assemble shelf', 1], ['build frame', 2], ['saw planks', 3]]) {
+      processes[name] = (await specification.call('recipe_process', 'create_recipe_process', { recipeProcess: {
+        name,
+        hasDuration: { numericDuration: hours, unitType: 'hour' },
+      } })).recipeProcess.id
+    }
+    await pause(100)
+
+    const flows = [
+      { action: 'produce', recipeFlowResource: resources.shelf, quantity: 1, recipeOutputOf: processes['assemble shelf'] },
+      { action: 'consume', recipeFlowResource: resources.plank, quantity: 2, recipeInputOf: processes['assemble shelf'] },
+      { action: 'consume', recipeFlowResource: resources.frame, quantity: 1, recipeInputOf: processes['assemble shelf'] },
+      { action: 'produce', recipeFlowResource: resources.frame, quantity: 1, recipeOutputOf: processes['build frame'] },
+      { action: 'consume', recipeFlowResource: resources.plank, quantity: 2, recipeInputOf: processes['build frame'] },
+      { action: 'produce', recipeFlowResource: resources.plank, quantity: 1, recipeOutputOf: processes['saw planks'] },
+      { action: 'consume', recipeFlowResource: resources.log, quantity: 1, recipeInputOf: processes['saw planks'] },
+    ]
+    for (const { quantity, ...recipeFlow } of flows) {
+      await specification.call('recipe_flow', 'create_recipe_flow', { recipeFlow: {
+        resourceQuantity: { hasNumericalValue: quantity, hasUnit: unitId },
+        ...recipeFlow,
+      } })
+    }
+    await pause(100)
+
+    // SCENARIO: generate a plan for 1 shelf
+    const due = '2022-12-01T12:00:00.000Z'
+    const pResp = await specification.call('recipe_process', 'create_plan_from_recipe', { plan: {
+      recipeResource: resources.shelf,
+      resourceQuantity: { hasNumericalValue: 1, hasUnit: unitId },
+      due,
+      provider: mockAddress(false),
+      receiver: mockAddress(false),
+    } })
+    await pause(100)
+
+    // ASSERT: plank sawing is planned once, for both consumers
+    t.equal(pResp.processes.length, 3, 'shared recipe process planned once')
+    const planned = await Promise.all(pResp.processes.map((address) => observation.call('process', 'get_process', { address })))
+    const sawing = planned.find(p => p.process.name === 'saw planks').process
+    t.equal(new Date(sawing.hasEnd).getTime(), new Date(due).getTime() - 3 * 60 * 60 * 1000, 'shared process completes in time for its earliest consumer')
+
+    const commitments = await Promise.all(pResp.commitments.map((address) => planning.call('commitment', 'get_commitment', { address })))
+    const plankOutputs = commitments.filter(c => c.commitment.outputOf === sawing.id)
+    t.equal(plankOutputs.length, 1, 'single output commitment for shared process')
+    t.equal(plankOutputs[0].commitment.resourceQuantity.hasNumericalValue, 4, 'shared process output accumulates demand from all consumers')
+    const logInputs = commitments.filter(c => c.commitment.inputOf === sawing.id)
+    t.equal(logInputs.length, 1, 'single input commitment for shared process')
+    t.equal(logInputs[0].commitment.resourceQuantity.hasNumericalValue, 4, 'shared process inputs scaled by combined demand')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Plan generation converts requested quantities to recipe units', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification', 'plan', 'observation', 'planning'])
+  try {
+    const { cells: [specification, plan, observation, planning] } = alice
+
+    let resp = await specification.call('unit', 'create_unit', { unit: { label: 'kilograms', symbol: 'kg' } })
+    const kgId = resp.unit.id
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'grams', symbol: 'g', baseUnit: kgId, conversionFactor: 0.001 } })
+    const gId = resp.unit.id
+    resp = await specification.call('unit', 'create_unit', { unit: { label: 'litres', symbol: 'l' } })
+    const litreId = resp.unit.id
+    await pause(100)
+
+    // SCENARIO: 500g of dough is mixed from 300g of flour
+    const doughResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'dough' } })
+    const flourResp = await specification.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'flour' } })
+    const mixResp = await specification.call('recipe_process', 'create_recipe_process', { recipeProcess: { name: 'mix dough' } })
+    await pause(100)
+    await specification.call('recipe_flow', 'create_recipe_flow', { recipeFlow: {
+      action: 'produce',
+      recipeFlowResource: doughResp.recipeResource.id,
+      resourceQuantity: { hasNumericalValue: 500, hasUnit: gId },
+      recipeOutputOf: mixResp.recipeProcess.id,
+    } })
+    await specification.call('recipe_flow', 'create_recipe_flow', { recipeFlow: {
+      action: 'consume',
+      recipeFlowResource: flourResp.recipeResource.id,
+      resourceQuantity: { hasNumericalValue: 300, hasUnit: gId },
+      recipeInputOf: mixResp.recipeProcess.id,
+    } })
+    await pause(100)
+
+    // SCENARIO: request 1kg of dough
+    const pResp = await specification.call('recipe_process', 'create_plan_from_recipe', { plan: {
+      recipeResource: doughResp.recipeResource.id,
+      resourceQuantity: { hasNumericalValue: 1, hasUnit: kgId },
+      due: '2022-12-01T12:00:00.000Z',
+      provider: mockAddress(false),
+      receiver: mockAddress(false),
+    } })
+    await pause(100)
+
+    // ASSERT: recipe is scaled by the converted quantity
+    const commitments = await Promise.all(pResp.commitments.map((address) => planning.call('commitment', 'get_commitment', { address })))
+    const output = commitments.find(c => c.commitment.action === 'produce').commitment
+    const input = commitments.find(c => c.commitment.action === 'consume').commitment
+    t.equal(output.resourceQuantity.hasNumericalValue, 1000, 'output commitment expressed in recipe unit')
+    t.deepLooseEqual(output.resourceQuantity.hasUnit, gId, 'output commitment unit from recipe')
+    t.equal(input.resourceQuantity.hasNumericalValue, 600, 'input commitment scaled by converted quantity')
+
+    // ASSERT: requests in incompatible units are rejected
+    try {
+      await specification.call('recipe_process', 'create_plan_from_recipe', { plan: {
+        recipeResource: doughResp.recipeResource.id,
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: litreId },
+        due: '2022-12-01T12:00:00.000Z',
+        provider: mockAddress(false),
+        receiver: mockAddress(false),
+      } })
+      t.fail('planning should fail for quantities which cannot be converted to recipe units')
+    } catch (err) {
+      t.ok(err.toString().match(/Mismatching units/), 'incompatible units reported')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,175 @@
+/**
+ * hREA action zome library API
+ *
+ * Contains helper methods that can be used to manipulate user-defined `Action` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Builtin ValueFlows actions are not stored in the DHT, and are always resolved first.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError, SemanticIndexError,
+    records_anchored::{
+        create_anchored_record,
+        read_anchored_record_entry,
+        read_anchored_record_revisions,
+        update_anchored_record,
+        delete_anchored_record,
+    },
+    records::{
+        read_record_entry,
+        read_record_entry_by_action,
+    },
+    metadata::read_revision_metadata_abbreviated,
+    rpc::call_local_zome_method,
+};
+use hdk_semantic_indexes_client_lib::{clear_indexing_report, take_indexing_report, with_indexing_report};
+
+pub use vf_attributes_hdk::{
+    ByAction, ByAddress,
+    DnaIdentifiable,
+};
+use vf_actions::{ Action, get_builtin_action, get_all_builtin_actions };
+
+pub use hc_zome_rea_action_storage_consts::*;
+use hc_zome_rea_action_storage::*;
+use hc_zome_rea_action_rpc::*;
+
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.action.index_zome)
+}
+
+pub fn handle_create_action<S>(entry_def_id: S, action: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let (meta, entry_id, entry_resp): (_,CustomActionId,_) =
+      create_anchored_record::<_, _, _, _, _, _, EntryTypes, _, _, _, _>(
+        LinkTypes::ActionIdentifier,
+        read_index_zome,
+        &entry_def_id,
+        action.to_owned()
+      )?;
+    with_indexing_report!(construct_response(&entry_id, &meta, &entry_resp))
+}
+
+/// Read any action by ID, whether builtin or user-defined.
+///
+/// Returns `None` if no action with the given ID has been defined.
+///
+pub fn handle_get_action(id: ActionId) -> RecordAPIResult<Option<Action>>
+{
+    if let Some(action) = get_builtin_action(id.as_ref()) {
+        return Ok(Some(action));
+    }
+    match handle_get_custom_action(id.as_ref()) {
+        Ok(response) => Ok(Some(response.action.to_action())),
+        Err(DataIntegrityError::SemanticIndexingError(SemanticIndexError::IndexNotFound(_))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn handle_get_custom_action<S>(id: S) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str>,
+{
+    let (meta, entry_id, entry): (_,CustomActionId,_) =
+      read_anchored_record_entry::<LinkTypes, EntryData, EntryStorage, CustomActionInternalAddress, _,_>(
+        LinkTypes::ActionIdentifier,
+        id.as_ref()
+      )?;
+    construct_response(&entry_id, &meta, &entry)
+}
+
+// internal method used by index zomes to locate indexed action record data
+pub fn handle_get_action_by_address(address: CustomActionInternalAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&CustomActionId::new(
+        dna_info()?.hash,
+        entry.get_id(),
+    ), &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, _base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&CustomActionId::new(
+        dna_info()?.hash,
+        entry.get_id(),
+    ), &meta, &entry)
+}
+
+pub fn handle_get_action_revisions(id: ActionId, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    let id_str: &String = id.as_ref();
+    read_anchored_record_revisions::<LinkTypes, CustomActionInternalAddress, _>(
+        LinkTypes::ActionIdentifier,
+        id_str,
+        after, first,
+    )
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PagingParams {
+    first: Option<usize>,
+    after: Option<String>,
+}
+
+/// Read all builtin actions, followed by all user-defined actions registered in this DNA.
+pub fn handle_get_all_actions() -> RecordAPIResult<Vec<Action>>
+{
+    let mut actions = get_all_builtin_actions();
+    let mut after = None;
+
+    loop {
+        let page: ResponseCollection = call_local_zome_method(
+            read_index_zome, "read_all_actions",
+            PagingParams { first: None, after },
+        )?;
+        actions.extend(page.edges.iter().map(|edge| edge.node.to_action()));
+
+        if !page.page_info.has_next_page || page.edges.is_empty() {
+            break;
+        }
+        after = Some(page.page_info.end_cursor);
+    }
+
+    Ok(actions)
+}
+
+pub fn handle_update_action(action: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_id = action.get_revision_id().clone();
+    let (meta, new_id, new_entry, _prev_entry): (_,CustomActionId,_,_) = update_anchored_record::<LinkTypes, EntryData, EntryStorage, CustomActionInternalAddress, _,_,_>(LinkTypes::ActionIdentifier, &revision_id, action)?;
+    with_indexing_report!(construct_response(&new_id, &meta, &new_entry))
+}
+
+pub fn handle_delete_action(revision_id: ActionHash) -> RecordAPIResult<DeleteResponse> {
+    clear_indexing_report();
+    let deleted = delete_anchored_record::<EntryStorage>(&revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+fn construct_response<'a>(
+    id: &CustomActionId, meta: &SignedActionHashed, e: &EntryData
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        action: Response {
+            id: id.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            label: e.label.to_owned(),
+            accounting_effect: e.accounting_effect,
+            onhand_effect: e.onhand_effect,
+            input_output: e.input_output,
+            pairs_with: e.pairs_with.to_owned(),
+        },
+        indexing_report: None,
+    })
+}
@@ -0,0 +1,174 @@
+/**
+ * hREA action zome I/O data structures
+ *
+ * Required by packages wishing to interact with the zome via its standard RPC interface,
+ * and by the zome API handlers accepting these parameters.
+ *
+ * @package hREA
+ */
+use holochain_serialized_bytes::prelude::*;
+
+use hdk_records::{
+    MaybeUndefined, RecordAPIResult,
+    record_interface::{ UniquelyIdentifiable, UpdateableIdentifier },
+};
+use hdk_relay_pagination::PageInfo;
+
+//---------------- EXTERNAL RECORD STRUCTURE ----------------
+
+// Export external type interface to allow consuming zomes to easily import & define zome API
+pub use vf_attributes_hdk::{
+    IndexingReport, DeleteResponse,
+    ActionHash,
+    ActionId,
+    CustomActionId,
+    CustomActionInternalAddress as ActionAddress,
+    ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
+};
+pub use vf_actions::{ Action, ActionEffect, ProcessType };
+
+/// I/O struct to describe the complete record, including all managed link fields
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub id: CustomActionId,
+    pub revision_id: ActionHash,
+    pub meta: RecordMeta,
+    pub label: String,
+    // :TODO: rename when vf-graphql moves to `accountingEffect` (@see `vf_actions::Action`)
+    #[serde(rename = "resourceEffect")]
+    pub accounting_effect: ActionEffect,
+    pub onhand_effect: ActionEffect,
+    pub input_output: ProcessType,
+    pub pairs_with: String,
+}
+
+impl<'a> Response {
+    pub fn into_cursor(&'a self) -> Result<String, std::string::FromUtf8Error> {
+        let s: &String = self.id.as_ref();
+        Ok(s.to_owned())
+    }
+
+    /// Action metadata in the same format as builtin actions, for use in validation & resource accounting
+    pub fn to_action(&'a self) -> Action {
+        Action {
+            id: self.id.as_ref().to_owned(),
+            label: self.label.to_owned(),
+            accounting_effect: self.accounting_effect,
+            onhand_effect: self.onhand_effect,
+            input_output: self.input_output,
+            pairs_with: self.pairs_with.to_owned(),
+        }
+    }
+}
+
+/// I/O struct to describe what is returned outside the gateway.
+/// Responses are usually returned as named attributes in order to leave space
+/// for future additional return values.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseData {
+    pub action: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
+}
+
+/// I/O struct for reading pages of user-defined actions from the index zome
+/// Conforms to Relay connections spec
+/// :TODO: parameterize type and abstract declarations
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseCollection {
+    pub edges: Vec<ResponseEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseEdge {
+    pub node: Response,
+    pub cursor: String,
+}
+
+//---------------- CREATE REQUEST ----------------
+
+fn default_pairs_with() -> String {
+    "notApplicable".to_string()
+}
+
+/// I/O struct to describe the complete input record, including all managed links
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRequest {
+    pub id: String,
+    pub label: String,
+    #[serde(rename = "resourceEffect")]
+    pub accounting_effect: ActionEffect,
+    pub onhand_effect: ActionEffect,
+    pub input_output: ProcessType,
+    #[serde(default = "default_pairs_with")]
+    pub pairs_with: String,
+}
+
+impl<'a> CreateRequest {
+    pub fn get_id(&'a self) -> &str {
+        &self.id
+    }
+}
+
+impl UniquelyIdentifiable for CreateRequest {
+    fn get_anchor_key(&self) -> RecordAPIResult<String> {
+        Ok(self.get_id().to_string())
+    }
+}
+
+//---------------- UPDATE REQUEST ----------------
+
+/// I/O struct to describe the complete input record, including all managed links
+///
+/// :NOTE: action IDs cannot be changed once created, since events & commitments reference them by ID.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRequest {
+    pub revision_id: ActionHash,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub label: MaybeUndefined<String>,
+    #[serde(rename = "resourceEffect")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub accounting_effect: MaybeUndefined<ActionEffect>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub onhand_effect: MaybeUndefined<ActionEffect>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub input_output: MaybeUndefined<ProcessType>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub pairs_with: MaybeUndefined<String>,
+}
+
+impl<'a> UpdateRequest {
+    pub fn get_revision_id(&'a self) -> &ActionHash {
+        &self.revision_id
+    }
+}
+
+impl UpdateableIdentifier for UpdateRequest {
+    fn get_new_anchor_key(&self) -> Option<String> {
+        None
+    }
+}
+
+//---------------- QUERY FILTER REQUEST ----------------
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParams {
+    // :TODO:
+}
@@ -0,0 +1,149 @@
+/**
+ * hREA action zome internal data structures
+ *
+ * Required by the zome itself, and for any DNA-local zomes interacting with its
+ * storage API directly.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError,
+    generate_record_entry,
+    record_interface::{ Updateable },
+};
+
+use hc_zome_rea_action_rpc::{ CreateRequest, UpdateRequest };
+
+pub use vf_attributes_hdk::{ CustomActionInternalAddress, CustomActionId };
+use vf_actions::{ ActionEffect, ProcessType, get_builtin_action };
+
+// :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
+pub use hdk_records::record_interface::Identified;
+
+//--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
+
+// :TODO: remove this, replace with reference to appropriate namespacing of zome config
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct DnaConfigSlice {
+    pub action: ActionZomeConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct ActionZomeConfig {
+    pub index_zome: String,
+}
+
+//---------------- RECORD INTERNALS & VALIDATION ----------------
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+pub struct EntryData {
+    pub id: String,
+    pub label: String,
+    pub accounting_effect: ActionEffect,
+    pub onhand_effect: ActionEffect,
+    pub input_output: ProcessType,
+    pub pairs_with: String,
+}
+
+impl<'a> EntryData {
+    pub fn get_id(&'a self) -> String {
+        self.id.to_owned()
+    }
+
+    /// User-defined actions may not shadow the builtin ValueFlows actions, since
+    /// builtins are always resolved first.
+    fn validate_id(self) -> RecordAPIResult<EntryData> {
+        if self.id.is_empty() {
+            return Err(DataIntegrityError::InvalidAction("action ID must not be empty".to_string()));
+        }
+        if get_builtin_action(&self.id).is_some() {
+            return Err(DataIntegrityError::InvalidAction(format!("'{}' is a builtin action and cannot be redefined", self.id)));
+        }
+        Ok(self)
+    }
+}
+
+generate_record_entry!(EntryData, CustomActionInternalAddress, EntryStorage);
+
+//---------------- Holochain App Entry And Link Types Setup ----------------
+
+#[hdk_entry_defs(skip_hdk_extern = true)]
+#[unit_enum(EntryTypesUnit)]
+pub enum EntryTypes {
+    ActionEntry(EntryStorage),
+    #[entry_def(visibility = "private")]
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
+}
+
+impl From<EntryStorage> for EntryTypes
+{
+    fn from(e: EntryStorage) -> EntryTypes
+    {
+        EntryTypes::ActionEntry(e)
+    }
+}
+
+impl TryFrom<AvailableCapability> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: AvailableCapability) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::AvailableCapability(e))
+    }
+}
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
+
+#[hdk_link_types(skip_no_mangle = true)]
+pub enum LinkTypes {
+    ActionIdentifier,
+    // relates to dna-auth-resolver mixin
+    // and remote authorizations
+    AvailableCapability
+}
+
+//---------------- CREATE ----------------
+
+/// Pick relevant fields out of I/O record into underlying DHT entry
+impl TryFrom<CreateRequest> for EntryData {
+    type Error = DataIntegrityError;
+
+    fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
+        EntryData {
+            id: e.id.into(),
+            label: e.label.into(),
+            accounting_effect: e.accounting_effect,
+            onhand_effect: e.onhand_effect,
+            input_output: e.input_output,
+            pairs_with: e.pairs_with.into(),
+        }.validate_id()
+    }
+}
+
+//---------------- UPDATE ----------------
+
+/// Handles update operations by merging any newly provided fields
+impl Updateable<UpdateRequest> for EntryData {
+    fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            id: self.id.to_owned(),
+            label: if !e.label.is_some() { self.label.to_owned() } else { e.label.to_owned().unwrap() },
+            accounting_effect: if !e.accounting_effect.is_some() { self.accounting_effect } else { e.accounting_effect.to_owned().unwrap() },
+            onhand_effect: if !e.onhand_effect.is_some() { self.onhand_effect } else { e.onhand_effect.to_owned().unwrap() },
+            input_output: if !e.input_output.is_some() { self.input_output } else { e.input_output.to_owned().unwrap() },
+            pairs_with: if !e.pairs_with.is_some() { self.pairs_with.to_owned() } else { e.pairs_with.to_owned().unwrap() },
+        })
+    }
+}
@@ -0,0 +1,8 @@
+/**
+ * Storage constants for zome entry & link type identifiers
+ *
+ * Used by modules interfacing with the underlying Holochain storage system directly.
+ *
+ * @package hREA
+ */
+pub const ACTION_ENTRY_TYPE: &str = "action";
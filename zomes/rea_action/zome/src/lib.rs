@@ -1,21 +1,21 @@
 /**
  * ValueFlows Actions zome
  *
- * Provides read-only access to built-in action struct metadata.
+ * Provides access to built-in action struct metadata, and management of
+ * user-defined actions for domain-specific flows.
  *
  * @package: HoloREA
  * @since:   2019-12-23
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
-use vf_attributes_hdk::{
-    ActionId,
-};
-use vf_actions::{
-    Action,
-    get_builtin_action,
-    get_all_builtin_actions,
-};
+use vf_actions::Action;
+use hc_zome_rea_action_rpc::*;
+use hc_zome_rea_action_lib::*;
+use vf_attributes_hdk::CustomActionInternalAddress;
+use hc_zome_rea_action_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ById {
@@ -24,13 +24,74 @@ struct ById {
 
 #[hdk_extern]
 fn get_action(ById { id }: ById) -> ExternResult<Action> {
-    match get_builtin_action(id.as_ref()) {
-        Some(action) => Ok(action),
-        None => Err(wasm_error!(WasmErrorInner::Guest(format!("No action with ID '{}' available", id.as_ref())))),
-    }
+    handle_get_action(id.to_owned())?
+        .ok_or_else(|| wasm_error!(WasmErrorInner::Guest(format!("No action with ID '{}' available", id.as_ref()))))
+}
+
+// used by other DNAs to resolve actions, @see vf_actions::resolve_action
+#[hdk_extern]
+fn __internal_find_action(ById { id }: ById) -> ExternResult<Option<Action>> {
+    Ok(handle_get_action(id)?)
 }
 
 #[hdk_extern]
 fn get_all_actions(_: ()) -> ExternResult<Vec<Action>> {
-    Ok(get_all_builtin_actions())
+    Ok(handle_get_all_actions()?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub action: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_action(CreateParams { action }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_action(ACTION_ENTRY_TYPE, action)?)
+}
+
+#[hdk_extern]
+fn get_custom_action(ById { id }: ById) -> ExternResult<ResponseData> {
+    Ok(handle_get_custom_action(id)?)
+}
+
+// used by indexing zomes to retrieve indexed record data
+#[hdk_extern]
+fn __internal_get_action_by_hash(ByAddress { address }: ByAddress<CustomActionInternalAddress>) -> ExternResult<ResponseData> {
+    Ok(with_summary_metadata(|| handle_get_action_by_address(address))?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RevisionsById {
+    id: ActionId,
+    first: Option<usize>,
+    after: Option<ActionHash>,
+}
+
+#[hdk_extern]
+fn get_action_revisions(RevisionsById { id, first, after }: RevisionsById) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_action_revisions(id, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub action: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_action(UpdateParams { action }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_action(action)?)
+}
+
+#[hdk_extern]
+fn delete_action(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_action(revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
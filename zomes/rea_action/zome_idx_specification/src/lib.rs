@@ -0,0 +1,13 @@
+/**
+ * User-defined action query indexes for specification DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_action_rpc::*;
+
+#[index_zome(record_read_fn_name="__internal_get_action_by_hash")]
+struct Action {
+    // :NOTE: blank means only the `read_all_` and `register_new_` APIs will be generated
+}
@@ -14,7 +14,9 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
+        merge_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
@@ -37,11 +39,12 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_agent<S>(entry_def_id: S, agent: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display
 {
+    clear_indexing_report();
     let agent_type = agent.agent_type.clone();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, agent)?;
     let e = update_string_index!(agent(&base_address).agent_type(vec![agent_type])<AgentTypeId>);
     hdk::prelude::debug!("handle_create_agent::agent_type index {:?}", e);
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 /*
@@ -101,20 +104,43 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_agent_revisions(address: AgentAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_agent(agent: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let revision_hash = agent.get_revision_id().clone();
     let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, agent)?;
-    construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+    with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
 }
 
-pub fn handle_delete_agent(revision_id: ActionHash) -> RecordAPIResult<bool> {
+/// Resolve conflicting revisions of an agent, caused by concurrent updates from different agents.
+///
+/// All live branch heads must be named. Edits in the request are applied to the most recent head.
+///
+pub fn handle_merge_agent(agent: MergeRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = agent.revision_ids.first().ok_or(DataIntegrityError::EntryNotFound)?.to_owned();
+    let (meta, identity_address, entry, _prev_entry): (_, AgentAddress, EntryData, EntryData) = merge_record(agent.revision_ids.as_slice(), agent.as_update(revision_hash))?;
+    with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
+}
+
+pub fn handle_delete_agent<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
 
     // load the record to ensure it is of the correct type
     let (_revision, _base_address, _entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
     // This is where indexes would be updated if necessary
 
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -134,6 +160,10 @@ fn construct_response<'a>(
         economic_events_as_provider,
         economic_events_as_receiver,
         inventoried_economic_resources,
+        claims_as_provider,
+        claims_as_receiver,
+        relationships_as_subject,
+        relationships_as_object,
     ): (
         // Vec<CommitmentAddress>,
         // Vec<EconomicEventAddress>,
@@ -149,6 +179,10 @@ fn construct_response<'a>(
         Vec<EconomicEventAddress>,
         Vec<EconomicEventAddress>,
         Vec<EconomicResourceAddress>,
+        Vec<ClaimAddress>,
+        Vec<ClaimAddress>,
+        Vec<AgentRelationshipAddress>,
+        Vec<AgentRelationshipAddress>,
     ),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -175,7 +209,12 @@ fn construct_response<'a>(
             economic_events_as_provider: economic_events_as_provider.to_owned(),
             economic_events_as_receiver: economic_events_as_receiver.to_owned(),
             inventoried_economic_resources: inventoried_economic_resources.to_owned(),
-        }
+            claims_as_provider: claims_as_provider.to_owned(),
+            claims_as_receiver: claims_as_receiver.to_owned(),
+            relationships_as_subject: relationships_as_subject.to_owned(),
+            relationships_as_object: relationships_as_object.to_owned(),
+        },
+        indexing_report: None,
     })
 }
 
@@ -202,6 +241,10 @@ fn get_link_fields(base_address: &AgentAddress) -> RecordAPIResult<(
     Vec<EconomicEventAddress>,
     Vec<EconomicEventAddress>,
     Vec<EconomicResourceAddress>,
+    Vec<ClaimAddress>,
+    Vec<ClaimAddress>,
+    Vec<AgentRelationshipAddress>,
+    Vec<AgentRelationshipAddress>,
 )> {
     Ok((
         // read_index!(agent(base_address).commitments)?,
@@ -218,5 +261,9 @@ fn get_link_fields(base_address: &AgentAddress) -> RecordAPIResult<(
         read_index!(agent(base_address).economic_events_as_provider)?,
         read_index!(agent(base_address).economic_events_as_receiver)?,
         read_index!(agent(base_address).inventoried_economic_resources)?,
+        read_index!(agent(base_address).claims_as_provider)?,
+        read_index!(agent(base_address).claims_as_receiver)?,
+        read_index!(agent(base_address).relationships_as_subject)?,
+        read_index!(agent(base_address).relationships_as_object)?,
     ))
 }
@@ -10,17 +10,20 @@ use serde_maybe_undefined::MaybeUndefined;
 use hdk_uuid_types::{ DnaHash, addressable_identifier };
 pub use hdk::prelude::*;
 pub use vf_attributes_hdk::{
+    IndexingReport, DeleteResponse,
     AgentAddress,
     ProcessAddress,
     EconomicEventAddress,
     CommitmentAddress,
     IntentAddress,
+    ClaimAddress,
+    AgentRelationshipAddress,
     ActionHash,
     ExternalURL,
     EconomicResourceAddress,
     PlanAddress,
     ProposalAddress,
-    ByRevision, RecordMeta, RevisionMeta, ByAddress,
+    ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress, ByAddress,
 };
 
 // internal type for indexing against agent_type string
@@ -65,6 +68,18 @@ pub struct Response {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub inventoried_economic_resources: Vec<EconomicResourceAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub claims_as_provider: Vec<ClaimAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub claims_as_receiver: Vec<ClaimAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub relationships_as_subject: Vec<AgentRelationshipAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub relationships_as_object: Vec<AgentRelationshipAddress>,
 }
 
 /// I/O struct to describe what is returned outside the gateway.
@@ -75,6 +90,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub agent: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -133,6 +150,39 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+// used to resolve concurrent updates to an Agent
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    // every live branch head of the record's revision history
+    pub revision_ids: Vec<ActionHash>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub name: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub image: MaybeUndefined<ExternalURL>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub note: MaybeUndefined<String>,
+}
+
+impl MergeRequest {
+    /// Manual edits to apply to the merged revision, which supersedes `revision_id`
+    pub fn as_update(&self, revision_id: ActionHash) -> UpdateRequest {
+        UpdateRequest {
+            revision_id,
+            name: self.name.to_owned(),
+            image: self.image.to_owned(),
+            classified_as: self.classified_as.to_owned(),
+            note: self.note.to_owned(),
+        }
+    }
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
@@ -152,6 +202,10 @@ pub struct QueryParams {
     pub economic_events_as_provider: Option<EconomicEventAddress>,
     pub economic_events_as_receiver: Option<EconomicEventAddress>,
     pub inventoried_economic_resources: Option<EconomicResourceAddress>,
+    pub claims_as_provider: Option<ClaimAddress>,
+    pub claims_as_receiver: Option<ClaimAddress>,
+    pub relationships_as_subject: Option<AgentRelationshipAddress>,
+    pub relationships_as_object: Option<AgentRelationshipAddress>,
 
     // for internal use in order to query for people or organizations specifically
     pub agent_type: Option<String>,
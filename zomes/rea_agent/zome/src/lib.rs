@@ -8,9 +8,12 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_agent_rpc::*;
 use hc_zome_rea_agent_lib::*;
+use hc_zome_rea_agent_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -47,11 +50,22 @@ fn get_agent(ByAddress { address }: ByAddress<AgentAddress>) -> ExternResult<Res
     Ok(handle_get_agent(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_agent_summary(params: ByAddress<AgentAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_agent(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_agent_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<AgentAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_agent_revisions(address, first, after)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct WhoisParams {
@@ -74,7 +88,20 @@ fn update_agent(UpdateParams { agent }: UpdateParams) -> ExternResult<ResponseDa
     Ok(handle_update_agent(agent)?)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeParams {
+    pub agent: MergeRequest,
+}
+
 #[hdk_extern]
-fn delete_agent(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_agent(revision_id)?)
+fn merge_agent(MergeParams { agent }: MergeParams) -> ExternResult<ResponseData> {
+    Ok(handle_merge_agent(agent)?)
 }
+
+#[hdk_extern]
+fn delete_agent(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_agent(AGENT_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -0,0 +1,160 @@
+/**
+ * hREA agent relationship zome library API
+ *
+ * Contains helper methods that can be used to manipulate `AgentRelationship`
+ * data structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_agent_relationship_storage::*;
+use hc_zome_rea_agent_relationship_rpc::*;
+
+pub use hc_zome_rea_agent_relationship_storage::AGENT_RELATIONSHIP_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.agent_relationship.index_zome)
+}
+
+pub fn handle_create_agent_relationship<S>(entry_def_id: S, agent_relationship: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    clear_indexing_report();
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, agent_relationship.to_owned())?;
+
+    // handle link fields
+    let e = create_index!(agent_relationship.subject(&agent_relationship.subject), agent.relationships_as_subject(&base_address));
+    hdk::prelude::debug!("handle_create_agent_relationship::subject index {:?}", e);
+    let e = create_index!(agent_relationship.object(&agent_relationship.object), agent.relationships_as_object(&base_address));
+    hdk::prelude::debug!("handle_create_agent_relationship::object index {:?}", e);
+    let e = create_index!(agent_relationship.relationship(&agent_relationship.relationship), agent_relationship_role.agent_relationships(&base_address));
+    hdk::prelude::debug!("handle_create_agent_relationship::relationship index {:?}", e);
+
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp))
+}
+
+pub fn handle_get_agent_relationship(address: AgentRelationshipAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_agent_relationship_revisions(address: AgentRelationshipAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_agent_relationship(agent_relationship: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = agent_relationship.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, AgentRelationshipAddress, EntryData, EntryData) = update_record(&revision_hash, agent_relationship)?;
+
+    // handle link fields
+    if new_entry.subject != prev_entry.subject {
+        let e = update_index!(
+            agent_relationship
+                .subject(vec![new_entry.subject.to_owned()].as_slice())
+                .not(vec![prev_entry.subject.to_owned()].as_slice()),
+            agent.relationships_as_subject(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_agent_relationship::subject index {:?}", e);
+    }
+    if new_entry.object != prev_entry.object {
+        let e = update_index!(
+            agent_relationship
+                .object(vec![new_entry.object.to_owned()].as_slice())
+                .not(vec![prev_entry.object.to_owned()].as_slice()),
+            agent.relationships_as_object(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_agent_relationship::object index {:?}", e);
+    }
+    if new_entry.relationship != prev_entry.relationship {
+        let e = update_index!(
+            agent_relationship
+                .relationship(vec![new_entry.relationship.to_owned()].as_slice())
+                .not(vec![prev_entry.relationship.to_owned()].as_slice()),
+            agent_relationship_role.agent_relationships(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_agent_relationship::relationship index {:?}", e);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
+}
+
+pub fn handle_delete_agent_relationship<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    let e = update_index!(agent_relationship.subject.not(&vec![entry.subject]), agent.relationships_as_subject(&base_address));
+    hdk::prelude::debug!("handle_delete_agent_relationship::subject index {:?}", e);
+    let e = update_index!(agent_relationship.object.not(&vec![entry.object]), agent.relationships_as_object(&base_address));
+    hdk::prelude::debug!("handle_delete_agent_relationship::object index {:?}", e);
+    let e = update_index!(agent_relationship.relationship.not(&vec![entry.relationship]), agent_relationship_role.agent_relationships(&base_address));
+    hdk::prelude::debug!("handle_delete_agent_relationship::relationship index {:?}", e);
+
+    // delete entry last, as it must be present in order for links to be removed
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &AgentRelationshipAddress, meta: &SignedActionHashed, e: &EntryData,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        agent_relationship: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            subject: e.subject.to_owned(),
+            object: e.object.to_owned(),
+            relationship: e.relationship.to_owned(),
+            in_scope_of: e.in_scope_of.to_owned(),
+            note: e.note.to_owned(),
+        },
+        indexing_report: None,
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_agent_relationship_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.agent_relationship.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.agent_relationship.agent_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_agent_relationship_role_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.agent_relationship.agent_relationship_role_index_zome
+}
@@ -0,0 +1,71 @@
+/**
+ * hREA AgentRelationship zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_agent_relationship_rpc::*;
+use hc_zome_rea_agent_relationship_lib::*;
+use hc_zome_rea_agent_relationship_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub agent_relationship: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_agent_relationship(CreateParams { agent_relationship }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_agent_relationship(AGENT_RELATIONSHIP_ENTRY_TYPE, agent_relationship)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: AgentRelationshipAddress,
+}
+
+#[hdk_extern]
+fn get_agent_relationship(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_agent_relationship(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_agent_relationship_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_agent_relationship(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_agent_relationship_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<AgentRelationshipAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_agent_relationship_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub agent_relationship: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_agent_relationship(UpdateParams { agent_relationship }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_agent_relationship(agent_relationship)?)
+}
+
+#[hdk_extern]
+fn delete_agent_relationship(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_agent_relationship(AGENT_RELATIONSHIP_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
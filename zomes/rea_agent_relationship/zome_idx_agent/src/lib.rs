@@ -0,0 +1,15 @@
+/**
+ * AgentRelationship query indexes for agent DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_agent_relationship_rpc::*;
+
+#[index_zome]
+struct AgentRelationship {
+    subject: Local<agent, relationships_as_subject>,
+    object: Local<agent, relationships_as_object>,
+    relationship: Local<agent_relationship_role, agent_relationships>,
+}
@@ -0,0 +1,94 @@
+/**
+ * hREA agent relationship role zome library API
+ *
+ * Contains helper methods that can be used to manipulate `AgentRelationshipRole`
+ * data structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::{clear_indexing_report, take_indexing_report, with_indexing_report};
+
+use hc_zome_rea_agent_relationship_role_storage::*;
+use hc_zome_rea_agent_relationship_role_rpc::*;
+
+pub use hc_zome_rea_agent_relationship_role_storage::AGENT_RELATIONSHIP_ROLE_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.agent_relationship_role.index_zome)
+}
+
+pub fn handle_create_agent_relationship_role<S>(entry_def_id: S, agent_relationship_role: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    clear_indexing_report();
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, agent_relationship_role.to_owned())?;
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp))
+}
+
+pub fn handle_get_agent_relationship_role(address: AgentRelationshipRoleAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_agent_relationship_role_revisions(address: AgentRelationshipRoleAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_agent_relationship_role(agent_relationship_role: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = agent_relationship_role.get_revision_id().clone();
+    let (meta, base_address, new_entry, _prev_entry): (_, AgentRelationshipRoleAddress, EntryData, EntryData) = update_record(&revision_hash, agent_relationship_role)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
+}
+
+pub fn handle_delete_agent_relationship_role<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // load the record to ensure it is of the correct type
+    read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &AgentRelationshipRoleAddress, meta: &SignedActionHashed, e: &EntryData,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        agent_relationship_role: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            role_label: e.role_label.to_owned(),
+            inverse_role_label: e.inverse_role_label.to_owned(),
+            role_behavior: e.role_behavior.to_owned(),
+            note: e.note.to_owned(),
+        },
+        indexing_report: None,
+    })
+}
@@ -0,0 +1,71 @@
+/**
+ * hREA AgentRelationshipRole zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_agent_relationship_role_rpc::*;
+use hc_zome_rea_agent_relationship_role_lib::*;
+use hc_zome_rea_agent_relationship_role_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub agent_relationship_role: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_agent_relationship_role(CreateParams { agent_relationship_role }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_agent_relationship_role(AGENT_RELATIONSHIP_ROLE_ENTRY_TYPE, agent_relationship_role)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: AgentRelationshipRoleAddress,
+}
+
+#[hdk_extern]
+fn get_agent_relationship_role(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_agent_relationship_role(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_agent_relationship_role_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_agent_relationship_role(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_agent_relationship_role_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<AgentRelationshipRoleAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_agent_relationship_role_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub agent_relationship_role: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_agent_relationship_role(UpdateParams { agent_relationship_role }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_agent_relationship_role(agent_relationship_role)?)
+}
+
+#[hdk_extern]
+fn delete_agent_relationship_role(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_agent_relationship_role(AGENT_RELATIONSHIP_ROLE_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -0,0 +1,13 @@
+/**
+ * AgentRelationshipRole query indexes for agent DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_agent_relationship_role_rpc::*;
+
+#[index_zome]
+struct AgentRelationshipRole {
+    agent_relationships: Local<agent_relationship, relationship>,
+}
@@ -13,6 +13,7 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
         delete_record,
     },
@@ -34,8 +35,9 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_agreement<S>(entry_def_id: S, agreement: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, agreement)?;
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 pub fn handle_get_agreement(address: AgreementAddress) -> RecordAPIResult<ResponseData>
@@ -50,15 +52,25 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_agreement_revisions(address: AgreementAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_agreement(agreement: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let revision_hash = agreement.get_revision_id().clone();
     let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, agreement)?;
-    construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+    with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
 }
 
-pub fn handle_delete_agreement(address: ActionHash) -> RecordAPIResult<bool> {
-    delete_record::<EntryStorage>(&address)
+pub fn handle_delete_agreement<S>(entry_def_id: S, address: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &address)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -84,7 +96,8 @@ fn construct_response<'a>(
             commitments: commitments.to_owned(),
             economic_events: economic_events.to_owned(),
             // involved_agents: involved_agents.to_owned(),
-        }
+        },
+        indexing_report: None,
     })
 }
 
@@ -10,13 +10,14 @@ use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
+    IndexingReport, DeleteResponse,
     AgreementAddress,
     CommitmentAddress,
     EconomicEventAddress,
     DateTime,
     FixedOffset,
     ByAction, ActionHash,
-    ByRevision, RecordMeta, RevisionMeta,
+    ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     AgentAddress,
 };
 
@@ -55,6 +56,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub agreement: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
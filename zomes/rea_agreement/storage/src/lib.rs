@@ -24,6 +24,7 @@ use hc_zome_rea_agreement_rpc::{ CreateRequest, UpdateRequest };
 pub use vf_attributes_hdk::AgreementAddress;
 pub use hc_zome_rea_agreement_storage_consts::AGREEMENT_ENTRY_TYPE;
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 
 // :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
 pub use hdk_records::record_interface::Identified;
@@ -60,7 +61,9 @@ generate_record_entry!(EntryData, AgreementAddress, EntryStorage);
 pub enum EntryTypes {
     Agreement(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
 }
 impl From<EntryStorage> for EntryTypes
 {
@@ -79,6 +82,14 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
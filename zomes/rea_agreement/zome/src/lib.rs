@@ -8,9 +8,12 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_agreement_rpc::*;
 use hc_zome_rea_agreement_lib::*;
+use hc_zome_rea_agreement_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -33,11 +36,22 @@ fn get_agreement(ReadParams { address }: ReadParams) -> ExternResult<ResponseDat
     Ok(handle_get_agreement(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_agreement_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_agreement(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_agreement_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<AgreementAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_agreement_revisions(address, first, after)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -50,6 +64,8 @@ fn update_agreement(UpdateParams { agreement }: UpdateParams) -> ExternResult<Re
 }
 
 #[hdk_extern]
-fn delete_agreement(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_agreement(revision_id)?)
+fn delete_agreement(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_agreement(AGREEMENT_ENTRY_TYPE, revision_id)?)
 }
+
+declare_pending_sync_zome_api!();
@@ -0,0 +1,48 @@
+/**
+ * hREA claim zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Claim` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Contains functionality shared by both sides of an "indirect remote index" pair
+ * (@see `hdk_records` README).
+ *
+ * @package hREA
+ */
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    metadata::read_revision_metadata_abbreviated,
+};
+use vf_attributes_hdk::{ClaimAddress, SettlementAddress};
+use hc_zome_rea_claim_storage::EntryData;
+use hc_zome_rea_claim_rpc::*;
+
+/// Create response from input DHT primitives
+pub fn construct_response(
+    address: &ClaimAddress, meta: &SignedActionHashed, e: &EntryData,
+    settlements: Vec<SettlementAddress>,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        claim: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            action: e.action.to_owned(),
+            provider: e.provider.to_owned(),
+            receiver: e.receiver.to_owned(),
+            triggered_by: e.triggered_by.to_owned(),
+            resource_classified_as: e.resource_classified_as.to_owned(),
+            resource_conforms_to: e.resource_conforms_to.to_owned(),
+            resource_quantity: e.resource_quantity.to_owned(),
+            effort_quantity: e.effort_quantity.to_owned(),
+            due: e.due.to_owned(),
+            created: e.created.to_owned(),
+            agreed_in: e.agreed_in.to_owned(),
+            in_scope_of: e.in_scope_of.to_owned(),
+            note: e.note.to_owned(),
+            finished: e.finished.to_owned(),
+            settled_by: settlements,
+        },
+        indexing_report: None,
+    })
+}
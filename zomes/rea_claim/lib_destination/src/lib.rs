@@ -0,0 +1,120 @@
+/**
+ * hREA claim zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Claim` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Contains functionality for the "destination" side of an "indirect remote index" pair
+ * (@see `hdk_records` README).
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult,
+    records::{
+        create_record,
+        read_existing_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record_once,
+        delete_record,
+        is_revision_deleted,
+    },
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_claim_storage::*;
+use hc_zome_rea_claim_rpc::*;
+
+use hc_zome_rea_claim_lib::construct_response;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
+    Some(conf.claim.index_zome)
+}
+
+pub fn handle_create_claim<S>(entry_def_id: S, claim: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if let Some((meta, claim_address, entry)) = read_existing_record::<EntryData, EntryStorage, _, _, _>(claim.to_owned())? {
+        return with_indexing_report!(construct_response(&claim_address, &meta, &entry, vec![]));
+    }
+
+    let (meta, claim_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, claim.to_owned())?;
+
+    // link entries in the local DNA
+    let e = create_index!(claim.triggered_by(claim.get_triggered_by()), economic_event.triggers(&claim_address));
+    hdk::prelude::debug!("handle_create_claim::triggered_by index (destination) {:?}", e);
+
+    // Settlements are only indexed in the origin DNA
+    with_indexing_report!(construct_response(&claim_address, &meta, &entry_resp, vec![]))
+}
+
+pub fn handle_get_claim(address: ClaimAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, vec![])
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, vec![])
+}
+
+pub fn handle_get_claim_revisions(address: ClaimAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_claim(claim: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let (meta, base_address, new_entry, prev_entry): (_, ClaimAddress, EntryData, EntryData) = update_record_once(&claim.get_revision_id(), claim.to_owned())?;
+
+    if new_entry.triggered_by != prev_entry.triggered_by {
+        let e = update_index!(
+            claim
+                .triggered_by(&vec![new_entry.triggered_by.clone()])
+                .not(&vec![prev_entry.triggered_by]),
+            economic_event.triggers(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_claim::triggered_by index (destination) {:?}", e);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, vec![]))
+}
+
+pub fn handle_delete_claim<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if is_revision_deleted(&revision_id)? {
+        return Ok(DeleteResponse { deleted: true, indexing_report: None });
+    }
+
+    // read any referencing indexes
+    let (_meta, base_address, claim) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    let e = update_index!(claim.triggered_by.not(&vec![claim.triggered_by]), economic_event.triggers(&base_address));
+    hdk::prelude::debug!("handle_delete_claim::triggered_by index (destination) {:?}", e);
+
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Properties accessor for zome config.
+fn read_economic_event_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
+    Some(conf.claim.economic_event_index_zome)
+}
+
+/// Properties accessor for zome config.
+fn read_claim_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
+    Some(conf.claim.index_zome)
+}
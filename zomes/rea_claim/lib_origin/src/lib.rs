@@ -0,0 +1,182 @@
+/**
+ * hREA claim zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Claim` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Contains functionality for the "origin" side of an "indirect remote index" pair
+ * (@see `hdk_records` README).
+
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, OtherCellResult,
+    MaybeUndefined,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    rpc::call_zome_method_or_queue,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_claim_storage_consts::*;
+use hc_zome_rea_claim_storage::*;
+use hc_zome_rea_claim_rpc::*;
+
+use hc_zome_rea_claim_lib::construct_response;
+
+
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    Some(conf.claim.index_zome)
+}
+
+pub fn handle_create_claim<S>(entry_def_id: S, claim: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let (meta, claim_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, claim.to_owned())?;
+
+    // handle link fields
+    // :TODO: improve error handling
+    let e1 = create_index!(claim.provider(&claim.provider), agent.claims_as_provider(&claim_address));
+    hdk::prelude::debug!("handle_create_claim::provider index (origin) {:?}", e1);
+    let e2 = create_index!(claim.receiver(&claim.receiver), agent.claims_as_receiver(&claim_address));
+    hdk::prelude::debug!("handle_create_claim::receiver index (origin) {:?}", e2);
+
+    // :TODO: report any error
+    // update in the associated foreign DNA as well
+    let pingback: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
+        claim.get_triggered_by(),
+        &REPLICATE_CREATE_API_METHOD,
+        CreateParams { claim: CreateRequest {
+            action: entry_resp.action.to_owned(),
+            provider: entry_resp.provider.to_owned(),
+            receiver: entry_resp.receiver.to_owned(),
+            triggered_by: entry_resp.triggered_by.to_owned(),
+            resource_classified_as: entry_resp.resource_classified_as.to_owned().into(),
+            resource_conforms_to: entry_resp.resource_conforms_to.to_owned().into(),
+            resource_quantity: entry_resp.resource_quantity.to_owned().into(),
+            effort_quantity: entry_resp.effort_quantity.to_owned().into(),
+            due: entry_resp.due.to_owned().into(),
+            created: entry_resp.created.to_owned().into(),
+            agreed_in: entry_resp.agreed_in.to_owned().into(),
+            finished: MaybeUndefined::Some(entry_resp.finished),
+            in_scope_of: entry_resp.in_scope_of.to_owned().into(),
+            note: entry_resp.note.to_owned().into(),
+            nonce: MaybeUndefined::Some(entry_resp._nonce.to_owned()),
+        } },
+        Some(claim_address.to_string()),
+        LinkTypes::AvailableCapability
+    );
+    hdk::prelude::debug!("handle_create_claim::call_zome_method_or_queue::{:?} {:?}", REPLICATE_CREATE_API_METHOD, pingback);
+
+    with_indexing_report!(construct_response(&claim_address, &meta, &entry_resp, get_link_fields(&claim_address)?))
+}
+
+pub fn handle_get_claim(address: ClaimAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_claim_revisions(address: ClaimAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_claim(claim: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let (meta, base_address, new_entry, prev_entry): (_, ClaimAddress, EntryData, EntryData) = update_record(&claim.get_revision_id(), claim.to_owned())?;
+
+    // update agent indexes
+    if new_entry.provider != prev_entry.provider {
+        let e = update_index!(
+            claim
+                .provider(&vec![new_entry.provider.to_owned()])
+                .not(&vec![prev_entry.provider.to_owned()]),
+            agent.claims_as_provider(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_claim::provider index (origin) {:?}", e);
+    }
+    if new_entry.receiver != prev_entry.receiver {
+        let e = update_index!(
+            claim
+                .receiver(&vec![new_entry.receiver.to_owned()])
+                .not(&vec![prev_entry.receiver.to_owned()]),
+            agent.claims_as_receiver(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_claim::receiver index (origin) {:?}", e);
+    }
+
+    // update claim records in remote DNA (and by proxy, event indexes in remote DNA).
+    // Unlike `Fulfillment`, the remote copy carries all claim fields and so is always kept in sync.
+    let pingback: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
+        // :TODO: update to intelligently call remote DNAs if new & old target record are not in same network
+        &prev_entry.triggered_by,
+        &REPLICATE_UPDATE_API_METHOD,
+        UpdateParams { claim: claim.to_owned() },
+        Some(base_address.to_string()),
+        LinkTypes::AvailableCapability
+    );
+    // :TODO: report any error
+    hdk::prelude::debug!("handle_update_claim::call_zome_method_or_queue::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, pingback);
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+pub fn handle_delete_claim<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // update agent indexes
+    let e1 = update_index!(claim.provider.not(&vec![entry.provider]), agent.claims_as_provider(&base_address));
+    hdk::prelude::debug!("handle_delete_claim::provider index (origin) {:?}", e1);
+    let e2 = update_index!(claim.receiver.not(&vec![entry.receiver]), agent.claims_as_receiver(&base_address));
+    hdk::prelude::debug!("handle_delete_claim::receiver index (origin) {:?}", e2);
+
+    // update claim records in remote DNA (and by proxy, event indexes in remote DNA)
+    let pingback: OtherCellResult<DeleteResponse> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
+        &entry.triggered_by,
+        &REPLICATE_DELETE_API_METHOD,
+        ByAction { address: revision_id.to_owned() },
+        Some(base_address.to_string()),
+        LinkTypes::AvailableCapability
+    );
+    // :TODO: report any error
+    hdk::prelude::debug!("handle_delete_claim::call_zome_method_or_queue::{:?} {:?}", REPLICATE_DELETE_API_METHOD, pingback);
+
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Properties accessor for zome config.
+fn read_agent_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    conf.claim.agent_index_zome
+}
+
+/// Properties accessor for zome config.
+fn read_claim_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    Some(conf.claim.index_zome)
+}
+
+// @see construct_response
+fn get_link_fields(claim: &ClaimAddress) -> RecordAPIResult<Vec<SettlementAddress>> {
+    Ok(read_index!(claim(claim).settled_by)?)
+}
@@ -0,0 +1,12 @@
+/**
+ * Storage constants for zome entry & link type identifiers
+ *
+ * Used by modules interfacing with the underlying Holochain storage system directly.
+ *
+ * @package hREA
+ */
+pub const CLAIM_ENTRY_TYPE: &str = "claim";
+
+pub const REPLICATE_CREATE_API_METHOD: &str = "create_claim";
+pub const REPLICATE_UPDATE_API_METHOD: &str = "update_claim";
+pub const REPLICATE_DELETE_API_METHOD: &str = "delete_claim";
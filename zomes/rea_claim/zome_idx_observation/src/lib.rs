@@ -0,0 +1,14 @@
+/**
+ * Claim query indexes for observation DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_claim_rpc::*;
+use hdk_semantic_indexes_zome_lib::ByAddress; // disambiguate from RPC query struct
+
+#[index_zome]
+struct Claim {
+    triggered_by: Local<economic_event, triggers>,
+}
@@ -0,0 +1,18 @@
+/**
+ * Claim query indexes for planning DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_claim_rpc::*;
+use hdk_semantic_indexes_zome_lib::ByAddress; // disambiguate from RPC query struct
+
+#[index_zome]
+struct Claim {
+    settled_by: Local<settlement, settles>,
+
+    // internal indexes (not part of VF spec)
+    provider: Local<agent, claims_as_provider>,
+    receiver: Local<agent, claims_as_receiver>,
+}
@@ -0,0 +1,59 @@
+/**
+ * hREA claim remote index zome API definition
+ *
+ * Manages indexes for querying `EconomicEvents` against remote `Claims`.
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_claim_lib_destination::*;
+use hc_zome_rea_claim_rpc::*;
+use hc_zome_rea_claim_storage_consts::*;
+use hc_zome_rea_claim_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::{declare_pending_sync_zome_api, declare_agent_registration_zome_api};
+
+#[hdk_extern]
+fn claim_created(CreateParams { claim }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_claim(CLAIM_ENTRY_TYPE, claim)?)
+}
+
+#[hdk_extern]
+fn get_claim(ByAddress { address }: ByAddress<ClaimAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_get_claim(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_claim_summary(params: ByAddress<ClaimAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_claim(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_claim_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<ClaimAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_claim_revisions(address, first, after)?)
+}
+
+#[hdk_extern]
+fn claim_updated(UpdateParams { claim }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_claim(claim)?)
+}
+
+#[hdk_extern]
+fn claim_deleted(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_claim(CLAIM_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
+
+declare_agent_registration_zome_api!(LinkTypes::AgentRegistration);
@@ -6,22 +6,39 @@
  *
  * @package hREA
  */
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryInto;
 use paste::paste;
 use hdk_records::{
-    RecordAPIResult, MaybeUndefined, SignedActionHashed,
+    DataIntegrityError, RecordAPIResult, MaybeUndefined, SignedActionHashed, OtherCellResult,
+    dna_info,
+    rpc::call_zome_method,
     records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
+        merge_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    identities::update_entry_time_index,
 };
 use hdk_semantic_indexes_client_lib::*;
 
+use vf_measurement::{QuantityValue, Unit, sum_converted, remaining};
+
+use hc_zome_rea_commitment_storage_consts::COMMITMENT_ENTRY_TYPE;
 use hc_zome_rea_commitment_storage::*;
 use hc_zome_rea_commitment_rpc::*;
+use hc_zome_rea_fulfillment_storage::{
+    EntryData as FulfillmentData,
+    EntryStorage as FulfillmentStorage,
+};
+use hc_zome_rea_unit_rpc::{ResponseData as UnitResponseData, GetUnitRequest, UnitId};
+use hc_zome_rea_economic_event_rpc::{ResponseData as EventResponseData, ByAddress, EconomicEventAddress};
 
 /// properties accessor for zome config
 fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
@@ -31,8 +48,16 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_commitment<S>(entry_def_id: S, commitment: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, commitment.to_owned())?;
 
+    // index due date, so that commitments can be listed in the order they are due
+    if let Some(due) = &entry_resp.due {
+        let e = update_entry_time_index(read_index_zome, &entry_def_id, "due", &base_address, Some(due.to_owned()));
+        hdk::prelude::debug!("handle_create_commitment::due time index {:?}", e);
+        report_indexing_outcome(&format!("{}_due", entry_def_id), &e);
+    }
+
     // handle link fields
     // :TODO: improve error handling
     let e1 = create_index!(commitment.provider(&commitment.provider), agent.commitments_as_provider(&base_address));
@@ -44,6 +69,10 @@ pub fn handle_create_commitment<S>(entry_def_id: S, commitment: CreateRequest) -
         let e = create_index!(commitment.input_of(input_of), process.committed_inputs(&base_address));
         hdk::prelude::debug!("handle_create_commitment::input_of index {:?}", e);
     };
+    if let CreateRequest { at_location: MaybeUndefined::Some(at_location), .. } = &commitment {
+        let e = create_index!(commitment.at_location(at_location), location.commitments(&base_address));
+        hdk::prelude::debug!("handle_create_commitment::at_location index {:?}", e);
+    };
     if let CreateRequest { output_of: MaybeUndefined::Some(output_of), .. } = &commitment {
         let e = create_index!(commitment.output_of(output_of), process.committed_outputs(&base_address));
         hdk::prelude::debug!("handle_create_commitment::output_of index {:?}", e);
@@ -67,7 +96,7 @@ pub fn handle_create_commitment<S>(entry_def_id: S, commitment: CreateRequest) -
     // };
 
     // :TODO: pass results from link creation rather than re-reading
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 pub fn handle_get_commitment(address: CommitmentAddress) -> RecordAPIResult<ResponseData>
@@ -76,17 +105,51 @@ pub fn handle_get_commitment(address: CommitmentAddress) -> RecordAPIResult<Resp
     construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
 }
 
+/// Read a commitment for display in a list of records, omitting fields which are costly to compute
+pub fn handle_get_commitment_summary(address: CommitmentAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    build_response(&base_address, &meta, &entry, get_link_fields(&address)?, (None, None, None))
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_commitment_revisions(address: CommitmentAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let address = commitment.get_revision_id().to_owned();
     let (meta, base_address, new_entry, prev_entry): (_, CommitmentAddress, EntryData, EntryData) = update_record(&address, commitment.to_owned())?;
 
+    update_commitment_indexes(&base_address, &new_entry, &prev_entry)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+/// Resolve conflicting revisions of a commitment, caused by concurrent updates from different agents.
+///
+/// All live branch heads must be named. Edits in the request are applied to the most recent head.
+///
+pub fn handle_merge_commitment(commitment: MergeRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_id = commitment.revision_ids.first().ok_or(DataIntegrityError::EntryNotFound)?.to_owned();
+    let (meta, base_address, new_entry, prev_entry): (_, CommitmentAddress, EntryData, EntryData) = merge_record(commitment.revision_ids.as_slice(), commitment.as_update(revision_id))?;
+
+    update_commitment_indexes(&base_address, &new_entry, &prev_entry)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+/// Update link fields after a record has been revised or merged
+fn update_commitment_indexes(base_address: &CommitmentAddress, new_entry: &EntryData, prev_entry: &EntryData) -> RecordAPIResult<()>
+{
     if new_entry.input_of != prev_entry.input_of {
         let new_value = match &new_entry.input_of { Some(val) => vec![val.to_owned()], None => vec![] };
         let prev_value = match &prev_entry.input_of { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -94,9 +157,20 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
             commitment
                 .input_of(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            process.committed_inputs(&base_address)
+            process.committed_inputs(base_address)
         );
-        hdk::prelude::debug!("handle_update_commitment::input_of index {:?}", e);
+        hdk::prelude::debug!("update_commitment_indexes::input_of index {:?}", e);
+    }
+    if new_entry.at_location != prev_entry.at_location {
+        let new_value = match &new_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            commitment
+                .at_location(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            location.commitments(base_address)
+        );
+        hdk::prelude::debug!("update_commitment_indexes::at_location index {:?}", e);
     }
     if new_entry.output_of != prev_entry.output_of {
         let new_value = match &new_entry.output_of { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -105,9 +179,9 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
             commitment
                 .output_of(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            process.committed_outputs(&base_address)
+            process.committed_outputs(base_address)
         );
-        hdk::prelude::debug!("handle_update_commitment::output_of index {:?}", e);
+        hdk::prelude::debug!("update_commitment_indexes::output_of index {:?}", e);
     }
     if new_entry.clause_of != prev_entry.clause_of {
         let new_value = match &new_entry.clause_of { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -116,9 +190,9 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
             commitment
                 .clause_of(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            agreement.commitments(&base_address)
+            agreement.commitments(base_address)
         );
-        hdk::prelude::debug!("handle_update_commitment::clause_of index {:?}", e);
+        hdk::prelude::debug!("update_commitment_indexes::clause_of index {:?}", e);
     }
     if new_entry.independent_demand_of != prev_entry.independent_demand_of {
         let new_value = match &new_entry.independent_demand_of { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -127,9 +201,9 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
             commitment
                 .independent_demand_of(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            plan.independent_demands(&base_address)
+            plan.independent_demands(base_address)
         );
-        hdk::prelude::debug!("handle_update_commitment::independent_demand_of index {:?}", e);
+        hdk::prelude::debug!("update_commitment_indexes::independent_demand_of index {:?}", e);
     }
 
     if new_entry.planned_within != prev_entry.planned_within {
@@ -139,9 +213,9 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
             commitment
                 .planned_within(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            plan.non_process_commitments(&base_address)
+            plan.non_process_commitments(base_address)
         );
-        hdk::prelude::debug!("handle_update_commitment::planned_within index {:?}", e);
+        hdk::prelude::debug!("update_commitment_indexes::planned_within index {:?}", e);
     }
     // // TODO: ensure handling of vec of ids
     // if new_entry.in_scope_of != prev_entry.in_scope_of {
@@ -151,9 +225,9 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
     //         commitment
     //             .in_scope_of(new_value.as_slice())
     //             .not(prev_value.as_slice()),
-    //         agent.commitments(&base_address)
+    //         agent.commitments(base_address)
     //     );
-    //     hdk::prelude::debug!("handle_update_commitment::in_scope_of index {:?}", e);
+    //     hdk::prelude::debug!("update_commitment_indexes::in_scope_of index {:?}", e);
     // }
     if new_entry.provider != prev_entry.provider {
         let new_value = vec![new_entry.provider.to_owned()];
@@ -162,9 +236,9 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
             commitment
                 .provider(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            agent.commitments_as_provider(&base_address)
+            agent.commitments_as_provider(base_address)
         );
-        hdk::prelude::debug!("handle_update_commitment::provider index {:?}", e);
+        hdk::prelude::debug!("update_commitment_indexes::provider index {:?}", e);
     }
     if new_entry.receiver != prev_entry.receiver {
         let new_value = vec![new_entry.receiver.to_owned()];
@@ -173,16 +247,45 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
             commitment
                 .receiver(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            agent.commitments_as_receiver(&base_address)
+            agent.commitments_as_receiver(base_address)
         );
-        hdk::prelude::debug!("handle_update_commitment::receiver index {:?}", e);
+        hdk::prelude::debug!("update_commitment_indexes::receiver index {:?}", e);
     }
+    if new_entry.due != prev_entry.due {
+        let e = update_entry_time_index(read_index_zome, COMMITMENT_ENTRY_TYPE, "due", base_address, new_entry.due.to_owned());
+        hdk::prelude::debug!("update_commitment_indexes::due time index {:?}", e);
+        report_indexing_outcome(&format!("{}_due", COMMITMENT_ENTRY_TYPE), &e);
+    }
+    Ok(())
+}
 
-    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+/// Mark a commitment as `finished` once its `resource_quantity` has been fully fulfilled.
+///
+/// Called by the `Fulfillment` zome whenever fulfillments change. Has no effect unless
+/// enabled via the `commitment.auto_finish` DNA property.
+///
+pub fn handle_refresh_finished(address: CommitmentAddress) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    let link_fields = get_link_fields(&base_address)?;
+    let conf: DnaConfigSlice = dna_info()?.properties.try_into()?;
+
+    if conf.commitment.auto_finish && !entry.finished {
+        let (_fulfilled, remaining, _error) = get_fulfilled_quantities(&entry, &link_fields.0)?;
+        if remaining.map_or(false, |qty| qty.get_numerical_value().is_zero()) {
+            let (meta, base_address, new_entry, _prev_entry): (_, CommitmentAddress, EntryData, EntryData) = update_record(meta.as_hash(), MarkFinished)?;
+            return with_indexing_report!(construct_response(&base_address, &meta, &new_entry, link_fields));
+        }
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &entry, link_fields))
 }
 
-pub fn handle_delete_commitment(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_commitment<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     // load the record to ensure it is of the correct type
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
@@ -212,12 +315,29 @@ pub fn handle_delete_commitment(revision_id: ActionHash) -> RecordAPIResult<bool
     let e2 = update_index!(commitment.receiver.not(&vec![entry.receiver]), agent.commitments_as_receiver(&base_address));
     hdk::prelude::debug!("handle_delete_commitment::receiver index {:?}", e2);
 
+    if let Some(location_address) = entry.at_location {
+        let e = update_index!(commitment.at_location.not(&vec![location_address]), location.commitments(&base_address));
+        hdk::prelude::debug!("handle_delete_commitment::at_location index {:?}", e);
+    }
+
     // delete entry last, as it must be present in order for links to be removed
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
 fn construct_response<'a>(
+    address: &CommitmentAddress, meta: &SignedActionHashed, e: &EntryData, link_fields: (
+        Vec<FulfillmentAddress>,
+        Vec<SatisfactionAddress>,
+        Vec<AgentAddress>,
+    )
+) -> RecordAPIResult<ResponseData> {
+    let quantities = get_fulfilled_quantities(e, &link_fields.0)?;
+    build_response(address, meta, e, link_fields, quantities)
+}
+
+fn build_response<'a>(
     address: &CommitmentAddress, meta: &SignedActionHashed, e: &EntryData, (
         fulfillments,
         satisfactions,
@@ -226,7 +346,8 @@ fn construct_response<'a>(
         Vec<FulfillmentAddress>,
         Vec<SatisfactionAddress>,
         Vec<AgentAddress>,
-    )
+    ),
+    (fulfilled_quantity, remaining_quantity, fulfilled_quantity_error): (Option<QuantityValue>, Option<QuantityValue>, Option<String>),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
         commitment: Response {
@@ -254,12 +375,16 @@ fn construct_response<'a>(
             clause_of: e.clause_of.to_owned(),
             independent_demand_of: e.independent_demand_of.to_owned(),
             finished: e.finished.to_owned(),
+            fulfilled_quantity,
+            remaining_quantity,
+            fulfilled_quantity_error,
             in_scope_of: e.in_scope_of.to_owned(),
             fulfilled_by: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
             involved_agents: involved_agents.to_owned(),
             // TODO: does this need to be updated to reflect current vf-graphql? For example, shouldn't the field `in_scope_of` not be part of `EntryData` and instead passed in?
-        }
+        },
+        indexing_report: None,
     })
 }
 
@@ -285,6 +410,11 @@ fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.commitment.agent_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_location_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.location_index_zome
+}
+
 /// Properties accessor for zome config
 fn read_plan_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.commitment.plan_index_zome
@@ -302,3 +432,78 @@ fn get_link_fields(commitment: &CommitmentAddress) -> RecordAPIResult<(
         vec![],   // :TODO:
     ))
 }
+
+/// Total the `resource_quantity` of all `Fulfillments` of a commitment, and determine how much of its
+/// own `resource_quantity` remains to be fulfilled.
+///
+/// Fulfillments recorded in other units are converted to the commitment's unit. Where this is not
+/// possible, neither quantity can be determined and the reason is returned in their place.
+///
+/// Fulfillments by events which have since been corrected are not counted.
+///
+fn get_fulfilled_quantities(commitment: &EntryData, fulfillments: &[FulfillmentAddress]) -> RecordAPIResult<(Option<QuantityValue>, Option<QuantityValue>, Option<String>)>
+{
+    let planned = match &commitment.resource_quantity {
+        Some(qty) => qty,
+        None => return Ok((None, None, None)),
+    };
+
+    let mut fulfilled_quantities = vec![];
+    for fulfillment in fulfillments {
+        let (_meta, _base_address, entry) = read_record_entry::<FulfillmentData, FulfillmentStorage, _>(fulfillment.as_ref())?;
+        match is_corrected_event(&entry.fulfilled_by) {
+            Ok(true) => continue,
+            Ok(false) => fulfilled_quantities.push(entry.resource_quantity),
+            Err(e) => return Ok((None, None, Some(e.to_string()))),
+        }
+    }
+
+    let units = RefCell::new(HashMap::new());
+    let fulfilled = match sum_converted(fulfilled_quantities.into_iter().flatten(), planned.get_unit(), |unit_id: &UnitId| get_cached_unit_definition(&units, unit_id)) {
+        Ok(qty) => qty,
+        Err(e) => return Ok((None, None, Some(e.to_string()))),
+    };
+    let remaining = remaining(planned.to_owned(), fulfilled.to_owned())?;
+
+    Ok((Some(fulfilled), Some(remaining), None))
+}
+
+/// Determine whether an event has been corrected, by reading it from the DNA it was recorded in
+fn is_corrected_event(event: &EconomicEventAddress) -> RecordAPIResult<bool> {
+    let event_data: OtherCellResult<EventResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        event,
+        &String::from("read_economic_event"),
+        ByAddress { address: event.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+
+    match event_data {
+        Ok(event_response) => Ok(!event_response.economic_event.corrected_by.is_empty()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Load unit conversion information, reusing definitions already loaded into `cache`
+fn get_cached_unit_definition(cache: &RefCell<HashMap<UnitId, Unit>>, unit_id: &UnitId) -> RecordAPIResult<Unit> {
+    if let Some(unit) = cache.borrow().get(unit_id) {
+        return Ok(unit.to_owned());
+    }
+    let unit = get_unit_definition(unit_id)?;
+    cache.borrow_mut().insert(unit_id.to_owned(), unit.to_owned());
+    Ok(unit)
+}
+
+/// Load unit conversion information from the DNA which the unit is defined in
+fn get_unit_definition(unit_id: &UnitId) -> RecordAPIResult<Unit> {
+    let unit_data: OtherCellResult<UnitResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        unit_id,
+        &String::from("read_unit"),
+        GetUnitRequest { id: unit_id.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+
+    match unit_data {
+        Ok(unit_response) => Ok(unit_response.unit.to_unit()),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -11,7 +11,8 @@ use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::{MaybeUndefined, default_false};
 use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    IndexingReport, DeleteResponse,
+    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     ActionId,
     DateTime, FixedOffset,
     ExternalURL,
@@ -81,6 +82,17 @@ pub struct Response {
 
     pub finished: bool,
 
+    // DERIVED FIELDS
+    // total `resource_quantity` of all linked `Fulfillments`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fulfilled_quantity: Option<QuantityValue>,
+    // portion of `resource_quantity` not yet fulfilled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_quantity: Option<QuantityValue>,
+    // reason `fulfilled_quantity` and `remaining_quantity` could not be determined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fulfilled_quantity_error: Option<String>,
+
     // LINK FIELDS
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -101,6 +113,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub commitment: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -219,6 +233,89 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+// used to resolve concurrent updates to a Commitment
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    // every live branch head of the record's revision history
+    pub revision_ids: Vec<ActionHash>,
+    #[serde(default)]
+    pub action: MaybeUndefined<ActionId>,
+    #[serde(default)]
+    pub note: MaybeUndefined<String>,
+    #[serde(default)]
+    pub input_of: MaybeUndefined<ProcessAddress>,
+    #[serde(default)]
+    pub output_of: MaybeUndefined<ProcessAddress>,
+    #[serde(default)]
+    pub provider: MaybeUndefined<AgentAddress>,
+    #[serde(default)]
+    pub receiver: MaybeUndefined<AgentAddress>,
+    #[serde(default)]
+    pub resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
+    #[serde(default)]
+    pub resource_classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    pub resource_conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
+    #[serde(default)]
+    pub resource_quantity: MaybeUndefined<QuantityValue>,
+    #[serde(default)]
+    pub effort_quantity: MaybeUndefined<QuantityValue>,
+    #[serde(default)]
+    pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub has_point_in_time: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub due: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub at_location: MaybeUndefined<LocationAddress>,
+    #[serde(default)]
+    pub agreed_in: MaybeUndefined<ExternalURL>,
+    #[serde(default)]
+    pub clause_of: MaybeUndefined<AgreementAddress>,
+    #[serde(default)]
+    pub independent_demand_of: MaybeUndefined<PlanAddress>,
+    #[serde(default)]
+    pub planned_within: MaybeUndefined<PlanAddress>,
+    #[serde(default)]
+    pub finished: MaybeUndefined<bool>,
+    #[serde(default)]
+    pub in_scope_of: MaybeUndefined<Vec<String>>,
+}
+
+impl MergeRequest {
+    /// Manual edits to apply to the merged revision, which supersedes `revision_id`
+    pub fn as_update(&self, revision_id: ActionHash) -> UpdateRequest {
+        UpdateRequest {
+            revision_id,
+            action: self.action.to_owned(),
+            note: self.note.to_owned(),
+            input_of: self.input_of.to_owned(),
+            output_of: self.output_of.to_owned(),
+            provider: self.provider.to_owned(),
+            receiver: self.receiver.to_owned(),
+            resource_inventoried_as: self.resource_inventoried_as.to_owned(),
+            resource_classified_as: self.resource_classified_as.to_owned(),
+            resource_conforms_to: self.resource_conforms_to.to_owned(),
+            resource_quantity: self.resource_quantity.to_owned(),
+            effort_quantity: self.effort_quantity.to_owned(),
+            has_beginning: self.has_beginning.to_owned(),
+            has_end: self.has_end.to_owned(),
+            has_point_in_time: self.has_point_in_time.to_owned(),
+            due: self.due.to_owned(),
+            at_location: self.at_location.to_owned(),
+            agreed_in: self.agreed_in.to_owned(),
+            clause_of: self.clause_of.to_owned(),
+            independent_demand_of: self.independent_demand_of.to_owned(),
+            planned_within: self.planned_within.to_owned(),
+            finished: self.finished.to_owned(),
+            in_scope_of: self.in_scope_of.to_owned(),
+        }
+    }
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
@@ -233,4 +330,5 @@ pub struct QueryParams {
     pub receiver: Option<AgentAddress>,
     pub independent_demand_of: Option<PlanAddress>,
     pub planned_within: Option<PlanAddress>,
+    pub at_location: Option<LocationAddress>,
 }
@@ -9,10 +9,12 @@
 use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     MaybeUndefined,
     record_interface::Updateable,
+    authorization::RecordParticipants,
     generate_record_entry,
 };
 use vf_measurement::QuantityValue;
@@ -31,7 +33,7 @@ pub use vf_attributes_hdk::{
     PlanAddress,
 };
 
-use vf_actions::{ validate_flow_action };
+use vf_actions::{ validate_flow_action, ensure_action_definition, ActionDefinition };
 
 use hc_zome_rea_commitment_rpc::{ CreateRequest, UpdateRequest };
 
@@ -52,7 +54,11 @@ pub struct CommitmentZomeConfig {
     pub process_index_zome: Option<String>,
     pub agreement_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub location_index_zome: Option<String>,
     pub plan_index_zome: Option<String>,
+    // when enabled, commitments are marked as `finished` once fully fulfilled
+    #[serde(default)]
+    pub auto_finish: bool,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -60,6 +66,9 @@ pub struct CommitmentZomeConfig {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 pub struct EntryData {
     pub action: ActionId,
+    // local copy of any user-defined `action`, @see vf_actions::ensure_action_definition
+    #[serde(default)]
+    pub action_definition: Option<EntryHash>,
     pub provider: AgentAddress,
     pub receiver: AgentAddress,
     pub input_of: Option<ProcessAddress>,   // :NOTE: shadows link, see https://github.com/h-REA/hREA/issues/60#issuecomment-553756873
@@ -85,8 +94,14 @@ pub struct EntryData {
 }
 
 impl EntryData {
-    pub fn validate_action(&self) -> Result<(), String> {
-        validate_flow_action(self.action.to_owned(), self.input_of.to_owned(), self.output_of.to_owned())
+    pub fn validate_action(&self) -> ExternResult<Result<(), String>> {
+        validate_flow_action(self.action.to_owned(), self.action_definition.to_owned(), self.input_of.to_owned(), self.output_of.to_owned())
+    }
+
+    /// Resolve user-defined actions and reference a local copy of their definition for validation
+    fn with_action_definition(self) -> RecordAPIResult<EntryData> {
+        let action_definition = ensure_action_definition::<EntryTypes, _, _>(&self.action, self.input_of.to_owned(), self.output_of.to_owned())?;
+        Ok(EntryData { action_definition, ..self })
     }
 
     pub fn validate_or_fields(&self) -> Result<(), String> {
@@ -105,6 +120,13 @@ impl EntryData {
 
 generate_record_entry!(EntryData, CommitmentAddress, EntryStorage);
 
+/// Agents named as the provider & receiver may modify the record alongside its author
+impl RecordParticipants for EntryData {
+    fn get_participants(&self) -> Vec<EntryHash> {
+        vec![self.provider.1.to_owned(), self.receiver.1.to_owned()]
+    }
+}
+
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
 #[hdk_entry_defs(skip_hdk_extern = true)]
@@ -112,7 +134,10 @@ generate_record_entry!(EntryData, CommitmentAddress, EntryStorage);
 pub enum EntryTypes {
     Commitment(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
+    ActionDefinition(ActionDefinition),
 }
 impl From<EntryStorage> for EntryTypes
 {
@@ -131,12 +156,30 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
+impl TryFrom<ActionDefinition> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: ActionDefinition) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::ActionDefinition(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // registers the Agent record held by an agent, @see hdk_records::authorization
+    AgentRegistration,
 }
 
 //---------------- CREATE ----------------
@@ -146,8 +189,9 @@ impl TryFrom<CreateRequest> for EntryData {
     type Error = DataIntegrityError;
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        EntryData {
             action: e.action.to_owned(),
+            action_definition: None,
             note: e.note.into(),
             provider: e.provider.into(),
             receiver: e.receiver.into(),
@@ -170,7 +214,7 @@ impl TryFrom<CreateRequest> for EntryData {
             finished: e.finished.to_option().unwrap(),  // :NOTE: unsafe, would crash if not for "default_false" binding via Serde
             in_scope_of: e.in_scope_of.into(),
             _nonce: random_bytes(32)?,
-        })
+        }.with_action_definition()
     }
 }
 
@@ -179,8 +223,9 @@ impl TryFrom<CreateRequest> for EntryData {
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        EntryData {
             action: if !e.action.is_some() { self.action.to_owned() } else { e.action.to_owned().unwrap() },
+            action_definition: None,
             provider: if !e.provider.is_some() { self.provider.to_owned() } else { e.provider.to_owned().unwrap() },
             receiver: if !e.receiver.is_some() { self.receiver.to_owned() } else { e.receiver.to_owned().unwrap() },
             input_of: if e.input_of == MaybeUndefined::Undefined { self.input_of.to_owned() } else { e.input_of.to_owned().into() },
@@ -203,6 +248,18 @@ impl Updateable<UpdateRequest> for EntryData {
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.clone() } else { e.in_scope_of.clone().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.clone() } else { e.note.clone().into() },
             _nonce: self._nonce.to_owned(),
+        }.with_action_definition()
+    }
+}
+
+/// Marks a commitment as `finished`, once it has been fully fulfilled
+pub struct MarkFinished;
+
+impl Updateable<MarkFinished> for EntryData {
+    fn update_with(&self, _e: MarkFinished) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            finished: true,
+            ..self.to_owned()
         })
     }
 }
@@ -12,10 +12,13 @@
  * @since:   2019-02-06
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_commitment_rpc::*;
 use hc_zome_rea_commitment_lib::*;
 use hc_zome_rea_commitment_storage_consts::*;
+use hc_zome_rea_commitment_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::{declare_pending_sync_zome_api, declare_agent_registration_zome_api};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -38,11 +41,22 @@ fn get_commitment(ByAddress { address }: ByAddress) -> ExternResult<ResponseData
     Ok(handle_get_commitment(address)?)
 }
 
+// read API used by index zomes when listing records, @see hc_zome_rea_commitment_lib::handle_get_commitment_summary
+#[hdk_extern]
+fn __internal_get_commitment_summary(ByAddress { address }: ByAddress) -> ExternResult<ResponseData> {
+    Ok(with_summary_metadata(|| handle_get_commitment_summary(address))?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_commitment_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<CommitmentAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_commitment_revisions(address, first, after)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -54,7 +68,28 @@ fn update_commitment(UpdateParams { commitment }: UpdateParams) -> ExternResult<
     Ok(handle_update_commitment(commitment)?)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeParams {
+    pub commitment: MergeRequest,
+}
+
 #[hdk_extern]
-fn delete_commitment(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_commitment(revision_id)?)
+fn merge_commitment(MergeParams { commitment }: MergeParams) -> ExternResult<ResponseData> {
+    Ok(handle_merge_commitment(commitment)?)
 }
+
+// used by the Fulfillment zome to mark commitments as finished once fully fulfilled
+#[hdk_extern]
+fn _internal_refresh_finished(ByAddress { address }: ByAddress) -> ExternResult<ResponseData> {
+    Ok(handle_refresh_finished(address)?)
+}
+
+#[hdk_extern]
+fn delete_commitment(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_commitment(COMMITMENT_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
+
+declare_agent_registration_zome_api!(LinkTypes::AgentRegistration);
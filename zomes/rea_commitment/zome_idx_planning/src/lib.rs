@@ -7,7 +7,7 @@
 use hdk_semantic_indexes_zome_derive::index_zome;
 use hc_zome_rea_commitment_rpc::*;
 
-#[index_zome]
+#[index_zome(time_index="due",record_read_fn_name="__internal_get_commitment_summary")]
 struct Commitment {
     fulfilled_by: Local<fulfillment, fulfills>,
     satisfies: Local<satisfaction, satisfied_by>,
@@ -18,6 +18,7 @@ struct Commitment {
     // internal indexes (not part of VF spec)
     provider: Local<agent, commitments_as_provider>,
     receiver: Local<agent, commitments_as_receiver>,
+    at_location: Local<location, commitments>,
     independent_demand_of: Local<plan, independent_demands>,
     planned_within: Local<plan, non_process_commitments>,
     // in_scope_of: Local<agent, commitments>,
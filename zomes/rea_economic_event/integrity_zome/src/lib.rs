@@ -8,7 +8,12 @@
  * @package hREA
  */
 use hdi::prelude::*;
-pub use hc_zome_rea_economic_event_storage::{Identified, EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::authorization::{
+    AuthorOnly, ModificationPolicy, ModificationRequest,
+    validate_update_authorization, validate_delete_authorization,
+    validate_agent_registration, get_record_origin,
+};
+pub use hc_zome_rea_economic_event_storage::{Identified, EntryData, EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes, DnaConfigSlice};
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -39,35 +44,109 @@ pub fn __num_link_types() -> u8 {
 /// Use this method as a reference, and always call the below logic before any
 /// application-specific validation in custom validation rules.
 ///
+/// `$policy` is the `ModificationPolicy` determining which agents may update or delete
+/// records. Pass your own implementation to apply alternative (eg. role-based) permissions.
+///
+/// When the DNA is configured with `economic_event.immutable_events`, all updates & deletes
+/// of events are rejected regardless of `$policy`.
+///
+/// Events which correct another event (@see `correct_economic_event`) are checked against
+/// `$policy` as though they were updates of the corrected event.
+///
 // #[macro_export]
 macro_rules! declare_economic_event_zome_validation_defaults {
-    ( /*$zome_api:ty*/ ) => {
+    ( $policy:ty ) => {
         #[hdk_extern]
         fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             match op {
                 Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
-                Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
+                Op::StoreEntry(StoreEntry { action, entry }) => match validate_entry(&entry)? {
+                    ValidateCallbackResult::Valid => validate_correction(&action.hashed.content, &entry),
+                    invalid => Ok(invalid),
+                },
+                Op::RegisterCreateLink(RegisterCreateLink { create_link, .. }) => validate_agent_registration(&create_link.hashed.content),
                 Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+                Op::RegisterUpdate(RegisterUpdate { update, .. }) => match validate_mutability(&update.hashed.content.original_entry_address)? {
+                    ValidateCallbackResult::Valid => validate_update_authorization::<$policy, _, _>(&update.hashed.content, parse_entry),
+                    invalid => Ok(invalid),
+                },
+                Op::RegisterDelete(RegisterDelete { delete, .. }) => match validate_mutability(&delete.hashed.content.deletes_entry_address)? {
+                    ValidateCallbackResult::Valid => validate_delete_authorization::<$policy, _, _>(&delete.hashed.content, parse_entry),
+                    invalid => Ok(invalid),
+                },
                 Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
             }
         }
 
-        fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
-            match EntryStorage::try_from(&entry) {
+        fn validate_entry(entry: &Entry) -> ExternResult<ValidateCallbackResult> {
+            match EntryStorage::try_from(entry) {
                 Ok(event_storage) => {
                     let record = event_storage.entry();
+                    let action_result = record.validate_action()?;
                     record.validate_or_fields()
-                        .and_then(|()| { record.validate_action() })
+                        .and_then(|()| { action_result })
                         .and_then(|()| { Ok(ValidateCallbackResult::Valid) })
                         .or_else(|e| { Ok(ValidateCallbackResult::Invalid(e)) })
                 },
                 _ => Ok(ValidateCallbackResult::Valid),
             }
         }
+
+        // correcting an event requires the same permissions as updating it
+        fn validate_correction(action: &EntryCreationAction, entry: &Entry) -> ExternResult<ValidateCallbackResult> {
+            let correction = match parse_entry(entry) {
+                Some(correction) => correction,
+                None => return Ok(ValidateCallbackResult::Valid),
+            };
+            let (corrects, corrects_revision) = match (&correction.corrects, &correction.corrects_revision) {
+                (None, None) if !correction.reversal => return Ok(ValidateCallbackResult::Valid),
+                (Some(corrects), Some(corrects_revision)) => (corrects, corrects_revision),
+                _ => return Ok(ValidateCallbackResult::Invalid("EconomicEvent corrections must reference the corrected event and its revision".to_string())),
+            };
+
+            let (original_author, identity) = get_record_origin(corrects_revision)?;
+            if identity != corrects.1 {
+                return Ok(ValidateCallbackResult::Invalid(format!("Revision {} is not a revision of EconomicEvent {}", corrects_revision, corrects.1)));
+            }
+            let corrected_entry_hash = match must_get_action(corrects_revision.to_owned())?.action().entry_hash() {
+                Some(entry_hash) => entry_hash.to_owned(),
+                None => return Ok(ValidateCallbackResult::Invalid(format!("Revision {} does not reference an EconomicEvent", corrects_revision))),
+            };
+            let corrected = match parse_entry(&must_get_entry(corrected_entry_hash)?.content) {
+                Some(corrected) => corrected,
+                None => return Ok(ValidateCallbackResult::Invalid(format!("Revision {} does not reference an EconomicEvent", corrects_revision))),
+            };
+
+            let req = ModificationRequest {
+                agent: action.author(),
+                original_author: &original_author,
+                identity: &identity,
+                chain_top: action.prev_action(),
+                current_entry: Some(&corrected),
+                new_entry: Some(&correction),
+            };
+            if <$policy as ModificationPolicy<EntryData>>::can_update(&req)? {
+                Ok(ValidateCallbackResult::Valid)
+            } else {
+                Ok(ValidateCallbackResult::Invalid(format!("Agent {} is not permitted to correct EconomicEvent {}", action.author(), corrects.1)))
+            }
+        }
+
+        fn parse_entry(entry: &Entry) -> Option<EntryData> {
+            EntryStorage::try_from(entry).ok().map(|s| s.entry())
+        }
+
+        // events are append-only if the DNA is configured with `economic_event.immutable_events`
+        fn validate_mutability(entry_hash: &EntryHash) -> ExternResult<ValidateCallbackResult> {
+            let immutable = DnaConfigSlice::try_from(dna_info()?.properties)
+                .map_or(false, |conf| conf.economic_event.immutable_events);
+
+            if immutable && parse_entry(&must_get_entry(entry_hash.to_owned())?.content).is_some() {
+                return Ok(ValidateCallbackResult::Invalid("EconomicEvents cannot be modified once created, use `correct_economic_event` to amend them".to_string()));
+            }
+            Ok(ValidateCallbackResult::Valid)
+        }
     };
 }
 
-declare_economic_event_zome_validation_defaults!();
\ No newline at end of file
+declare_economic_event_zome_validation_defaults!(AuthorOnly);
\ No newline at end of file
@@ -6,9 +6,13 @@
  *
  * @package hREA
  */
+use std::convert::TryInto;
+use std::collections::VecDeque;
 use paste::paste;
+use chrono::{ NaiveDateTime, Utc };
 use hdk_records::{
-    RecordAPIResult, OtherCellResult, MaybeUndefined, SignedActionHashed,
+    RecordAPIResult, DataIntegrityError, OtherCellResult, MaybeUndefined, SignedActionHashed,
+    dna_info,
     rpc::{
         call_local_zome_method,
     },
@@ -16,10 +20,13 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
+        merge_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    identities::update_entry_time_index,
 };
 use hdk_semantic_indexes_client_lib::*;
 
@@ -30,8 +37,11 @@ use hc_zome_rea_economic_event_storage::*;
 use hc_zome_rea_economic_event_rpc::{
     CreateRequest as EconomicEventCreateRequest,
     UpdateRequest as EconomicEventUpdateRequest,
+    MergeRequest as EconomicEventMergeRequest,
+    DeleteResponse,
+    ActionHash,
 };
-use hc_zome_rea_economic_resource_rpc::{ CreationPayload as ResourceCreationPayload };
+use hc_zome_rea_economic_resource_rpc::{ CreationPayload as ResourceCreationPayload, InventoryUpdatePayload };
 
 use hc_zome_rea_economic_resource_storage::{
     EntryData as EconomicResourceData,
@@ -40,6 +50,7 @@ use hc_zome_rea_economic_resource_lib::{
     construct_response_record as construct_resource_response,
     get_link_fields as get_resource_link_fields,
 };
+use vf_measurement::{ QuantityValue, Decimal, multiply };
 
 
 
@@ -59,6 +70,11 @@ fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.economic_event.agent_index_zome
 }
 
+/// Properties accessor for zome config.
+fn read_location_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_event.location_index_zome
+}
+
 /// Trait object defining the default ValueFlows EconomicResource zome API.
 /// 'Permissable' denotes the interface as a highly-permissable one, where little
 /// validation on entry contents is performed.
@@ -71,6 +87,7 @@ impl API for EconomicEventZomePermissableDefault {
         entry_def_id: Self::S,
         event: EconomicEventCreateRequest, new_inventoried_resource: Option<ResourceCreateRequest>
     ) -> RecordAPIResult<ResponseData> {
+        clear_indexing_report();
         let mut resources_affected: Vec<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData, EconomicResourceData)> = vec![];
         let mut resource_created: Option<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData)> = None;
 
@@ -83,13 +100,9 @@ impl API for EconomicEventZomePermissableDefault {
             resources_affected.push((new_resource.0, new_resource.1, new_resource.2.clone(), new_resource.2));
         }
 
-        // update any linked resources affected by the event
-        resources_affected.append(&mut handle_update_resource_inventory(&event)?);
-
-        // Now that the resource updates have succeeded, write the event.
+        // Write the event before updating any existing resources, so that their updates can reference it.
+        // Validation of inventory updates made by agents other than the owners of a resource depends on this.
         // Note we ignore the revision ID because events can't be edited (only underwritten by subsequent events)
-        // :TODO: rethinking this, it's probably the event that should be written first, and the resource
-        // validation should eventually depend on an event already having been authored.
         let (meta, event_address, event_entry) = handle_create_economic_event_record(
             &entry_def_id,
             &event, match &resource_created {
@@ -98,6 +111,9 @@ impl API for EconomicEventZomePermissableDefault {
             },
         )?;
 
+        // update any linked resources affected by the event
+        resources_affected.append(&mut handle_update_resource_inventory(&event_address, &event)?);
+
         // Link any affected resources to this event so that we can pull all the events which affect any resource
         for resource_data in resources_affected.iter() {
             let e = create_index!(economic_event.affects(resource_data.1), economic_resource.affected_by(&event_address));
@@ -106,14 +122,14 @@ impl API for EconomicEventZomePermissableDefault {
 
         match resource_created {
             Some((resource_meta, resource_addr, resource_entry)) => {
-                construct_response_with_resource(
+                with_indexing_report!(construct_response_with_resource(
                     &event_address, &meta, &event_entry, get_link_fields(&event_address)?,
                     Some(resource_addr.clone()), &resource_meta, resource_entry, get_resource_link_fields(&resource_addr)?
-                )
+                ))
             },
             None => {
                 // :TODO: pass results from link creation rather than re-reading
-                construct_response(&event_address, &meta, &event_entry, get_link_fields(&event_address)?)
+                with_indexing_report!(construct_response(&event_address, &meta, &event_entry, get_link_fields(&event_address)?))
             },
         }
     }
@@ -129,15 +145,40 @@ impl API for EconomicEventZomePermissableDefault {
         construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
     }
 
+    fn get_economic_event_revisions(address: EconomicEventAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+    {
+        read_record_revisions(&address, after, first)
+    }
+
     fn update_economic_event(event: EconomicEventUpdateRequest) -> RecordAPIResult<ResponseData> {
+        clear_indexing_report();
+        ensure_events_mutable()?;
+
         let address = event.get_revision_id().to_owned();
         let (meta, identity_address, new_entry, _prev_entry): (_, EconomicEventAddress, EntryData, EntryData) = update_record(&address, event)?;
 
         // :TODO: optimise this- should pass results from `replace_direct_index` instead of retrieving from `get_link_fields` where updates
-        construct_response(&identity_address, &meta, &new_entry, get_link_fields(&identity_address)?)
+        with_indexing_report!(construct_response(&identity_address, &meta, &new_entry, get_link_fields(&identity_address)?))
+    }
+
+    /// Resolve conflicting revisions of an event, caused by concurrent updates from different agents.
+    ///
+    /// All live branch heads must be named. Edits in the request are applied to the most recent head.
+    ///
+    fn merge_economic_event(event: EconomicEventMergeRequest) -> RecordAPIResult<ResponseData> {
+        clear_indexing_report();
+        ensure_events_mutable()?;
+
+        let address = event.revision_ids.first().ok_or(DataIntegrityError::EntryNotFound)?.to_owned();
+        let (meta, identity_address, new_entry, _prev_entry): (_, EconomicEventAddress, EntryData, EntryData) = merge_record(event.revision_ids.as_slice(), event.as_update(address))?;
+
+        with_indexing_report!(construct_response(&identity_address, &meta, &new_entry, get_link_fields(&identity_address)?))
     }
 
-    fn delete_economic_event(revision_id: ActionHash) -> RecordAPIResult<bool> {
+    fn delete_economic_event(entry_def_id: Self::S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse> {
+        clear_indexing_report();
+        ensure_events_mutable()?;
+
         // read any referencing indexes
         let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
@@ -159,14 +200,233 @@ impl API for EconomicEventZomePermissableDefault {
         let e = update_index!(economic_event.receiver.not(&vec![entry.receiver]), agent.economic_events_as_receiver(&base_address));
         hdk::prelude::debug!("delete_economic_event::receiver index {:?}", e);
 
+        if let Some(location_address) = entry.at_location {
+            let e = update_index!(economic_event.at_location.not(&vec![location_address]), location.economic_events(&base_address));
+            hdk::prelude::debug!("delete_economic_event::at_location index {:?}", e);
+        }
+
         // :TODO: handle cleanup of foreign key fields? (fulfillment, satisfaction)
         // May not be needed due to cross-record deletion validation logic.
 
         // delete entry last as it must be present in order for links to be removed
-        delete_record::<EntryStorage>(&revision_id)
+        let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+        Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+    }
+
+    fn correct_economic_event(
+        entry_def_id: Self::S,
+        event_address: EconomicEventAddress, replacement: Option<EconomicEventCreateRequest>,
+    ) -> RecordAPIResult<CorrectionResponseData> {
+        // an event which has already been corrected is superseded by its replacement, which is corrected instead
+        let event_address = find_current_replacement(event_address)?;
+        let (meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(event_address.as_ref())?;
+        let revision_id = meta.as_hash().to_owned();
+
+        // write the reversing event first, so that resources are restored to their prior state
+        // before any replacement quantities are applied
+        let reversal = Self::create_economic_event(entry_def_id, reversal_of(&event_address, &revision_id, &entry)?, None)?;
+
+        let replacement = match replacement {
+            Some(event) => match Self::create_economic_event(entry_def_id, event.with_corrects(&event_address, &revision_id), None) {
+                Ok(created) => Some(created.economic_event),
+                Err(e) => {
+                    // don't leave the event reversed without its replacement
+                    let deleted = Self::delete_economic_event(entry_def_id, reversal.economic_event.revision_id.to_owned());
+                    hdk::prelude::debug!("correct_economic_event::replacement failed, deleting reversal {:?}", deleted);
+                    return Err(e);
+                },
+            },
+            None => None,
+        };
+
+        Ok(CorrectionResponseData {
+            reversal: reversal.economic_event,
+            replacement,
+        })
+    }
+
+    /// Follow resources forward from `start` through the events and processes which used them,
+    /// to the resources they were transformed or transferred into.
+    fn track(start: ProvenanceRecord, depth: Option<usize>) -> RecordAPIResult<ProvenanceGraph> {
+        traverse_provenance(start, depth.unwrap_or(DEFAULT_TRAVERSAL_DEPTH), TraversalDirection::Track)
+    }
+
+    /// Follow resources backward from `start` through the events and processes which produced them,
+    /// to the resources they originated from.
+    fn trace(start: ProvenanceRecord, depth: Option<usize>) -> RecordAPIResult<ProvenanceGraph> {
+        traverse_provenance(start, depth.unwrap_or(DEFAULT_TRAVERSAL_DEPTH), TraversalDirection::Trace)
     }
 }
 
+const DEFAULT_TRAVERSAL_DEPTH: usize = 10;
+
+#[derive(Clone, Copy, PartialEq)]
+enum TraversalDirection {
+    Track,
+    Trace,
+}
+
+/// Breadth-first walk of the REA graph from a starting record, in the direction of
+/// resource flows (`Track`) or against them (`Trace`). Records are visited at most once.
+///
+fn traverse_provenance(start: ProvenanceRecord, max_depth: usize, direction: TraversalDirection) -> RecordAPIResult<ProvenanceGraph>
+{
+    let mut nodes = vec![ProvenanceNode { record: start.to_owned(), depth: 0 }];
+    let mut edges = vec![];
+    let mut queue = VecDeque::from(vec![(start, 0)]);
+
+    while let Some((record, depth)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+        for next in get_adjacent_records(&record, direction)? {
+            edges.push(match direction {
+                TraversalDirection::Track => ProvenanceEdge { from: record.to_owned(), to: next.to_owned() },
+                TraversalDirection::Trace => ProvenanceEdge { from: next.to_owned(), to: record.to_owned() },
+            });
+            if !nodes.iter().any(|node| node.record == next) {
+                nodes.push(ProvenanceNode { record: next.to_owned(), depth: depth + 1 });
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+
+    Ok(ProvenanceGraph { nodes, edges })
+}
+
+/// Determine the records one step downstream (`Track`) or upstream (`Trace`) of `record`
+///
+fn get_adjacent_records(record: &ProvenanceRecord, direction: TraversalDirection) -> RecordAPIResult<Vec<ProvenanceRecord>>
+{
+    let mut adjacent = vec![];
+    let mut add = |next: ProvenanceRecord| if !adjacent.contains(&next) { adjacent.push(next) };
+
+    match record {
+        ProvenanceRecord::EconomicResource(resource_address) => {
+            let resource = Some(resource_address.to_owned());
+            let events: Vec<EconomicEventAddress> = read_index!(economic_resource(resource_address).affected_by)?;
+            for event_address in events {
+                let (_meta, _base_address, event) = read_record_entry::<EntryData, EntryStorage, _>(event_address.as_ref())?;
+                let is_provider = event.resource_inventoried_as == resource;
+                let is_transfer = event.to_resource_inventoried_as.is_some() && event.to_resource_inventoried_as != event.resource_inventoried_as;
+                let follows = match direction {
+                    // resource consumed or used by a process, or moved to another resource
+                    TraversalDirection::Track => is_provider && (event.input_of.is_some() || is_transfer),
+                    // resource output by a process, or received from another resource
+                    TraversalDirection::Trace => (is_provider && event.output_of.is_some())
+                        || (is_transfer && event.to_resource_inventoried_as == resource),
+                };
+                if follows {
+                    add(ProvenanceRecord::EconomicEvent(event_address));
+                }
+            }
+            let containers: Vec<EconomicResourceAddress> = match direction {
+                TraversalDirection::Track => read_index!(economic_resource(resource_address).contained_in)?,
+                TraversalDirection::Trace => read_index!(economic_resource(resource_address).contains)?,
+            };
+            containers.into_iter().for_each(|r| add(ProvenanceRecord::EconomicResource(r)));
+        },
+        ProvenanceRecord::EconomicEvent(event_address) => {
+            let (_meta, _base_address, event) = read_record_entry::<EntryData, EntryStorage, _>(event_address.as_ref())?;
+            match direction {
+                TraversalDirection::Track => {
+                    if let Some(process) = event.input_of {
+                        add(ProvenanceRecord::Process(process));
+                    }
+                    if let (Some(_), Some(resource)) = (&event.output_of, &event.resource_inventoried_as) {
+                        add(ProvenanceRecord::EconomicResource(resource.to_owned()));
+                    }
+                    if let Some(resource) = event.to_resource_inventoried_as {
+                        add(ProvenanceRecord::EconomicResource(resource));
+                    }
+                },
+                TraversalDirection::Trace => {
+                    if let Some(process) = event.output_of {
+                        add(ProvenanceRecord::Process(process));
+                    }
+                    if event.input_of.is_some() || event.to_resource_inventoried_as.is_some() {
+                        if let Some(resource) = event.resource_inventoried_as {
+                            add(ProvenanceRecord::EconomicResource(resource));
+                        }
+                    }
+                },
+            }
+        },
+        ProvenanceRecord::Process(process_address) => {
+            let events: Vec<EconomicEventAddress> = match direction {
+                TraversalDirection::Track => read_index!(process(process_address).observed_outputs)?,
+                TraversalDirection::Trace => read_index!(process(process_address).observed_inputs)?,
+            };
+            events.into_iter().for_each(|e| add(ProvenanceRecord::EconomicEvent(e)));
+        },
+    }
+
+    Ok(adjacent)
+}
+
+/// Reject modifications to events where the DNA is configured with `economic_event.immutable_events`
+fn ensure_events_mutable() -> RecordAPIResult<()> {
+    let conf: DnaConfigSlice = dna_info()?.properties.try_into()?;
+    if conf.economic_event.immutable_events {
+        return Err(DataIntegrityError::ImmutableRecord("EconomicEvent".to_string()));
+    }
+    Ok(())
+}
+
+/// Follow the corrections of an event to the replacement which currently stands in for it.
+///
+/// Errors if the event has been corrected without replacement, as there is then nothing left to correct.
+///
+/// :NOTE: concurrent corrections of the same event by different agents cannot be detected, and will
+/// each reverse it. Corrections made after either has propagated are applied to its replacement.
+///
+fn find_current_replacement(event_address: EconomicEventAddress) -> RecordAPIResult<EconomicEventAddress> {
+    let mut current = event_address;
+    loop {
+        let corrected_by: Vec<EconomicEventAddress> = read_index!(economic_event(&current).corrected_by)?;
+        if corrected_by.is_empty() {
+            return Ok(current);
+        }
+
+        let mut replacement = None;
+        for correction in corrected_by {
+            let (_meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(correction.as_ref())?;
+            if !entry.reversal {
+                replacement = Some(correction);
+                break;
+            }
+        }
+        current = replacement.ok_or(DataIntegrityError::AlreadyCorrected(format!("{:?}", current)))?;
+    }
+}
+
+/// Build an event which negates the effects of `event` on any inventoried resources.
+///
+/// The reversal is dated at the time of correction, so that the history of the affected resources
+/// reflects when the correction took place. It references the original event via `corrects`, from
+/// which the resource zome restores any custody or location changed by the original event.
+///
+fn reversal_of(event_address: &EconomicEventAddress, revision_id: &ActionHash, event: &EntryData) -> RecordAPIResult<EconomicEventCreateRequest> {
+    let negate = |qty: &Option<QuantityValue>| -> RecordAPIResult<MaybeUndefined<QuantityValue>> {
+        Ok(qty.to_owned().map(|q| multiply(q, Decimal::from(-1))).transpose()?.into())
+    };
+    let (secs, nsecs) = hdk::prelude::sys_time()?.as_seconds_and_nanos();
+    let corrected_at = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp_opt(secs, nsecs).unwrap(), Utc);
+
+    Ok(EconomicEventCreateRequest {
+        note: MaybeUndefined::Undefined,
+        resource_quantity: negate(&event.resource_quantity)?,
+        effort_quantity: negate(&event.effort_quantity)?,
+        has_beginning: MaybeUndefined::Undefined,
+        has_end: MaybeUndefined::Undefined,
+        has_point_in_time: MaybeUndefined::Some(corrected_at.into()),
+        corrects: MaybeUndefined::Some(event_address.to_owned()),
+        corrects_revision: MaybeUndefined::Some(revision_id.to_owned()),
+        reversal: true,
+        ..event.into()
+    })
+}
+
 // API logic handlers
 
 /// Properties accessor for zome config.
@@ -198,6 +458,19 @@ fn handle_create_economic_event_record<S>(entry_def_id: S, event: &EconomicEvent
         }
     )?;
 
+    // index business times, so that events can be listed in the order they occurred
+    for (time_index_name, time) in [
+        ("has_point_in_time", &entry_resp.has_point_in_time),
+        ("has_beginning", &entry_resp.has_beginning),
+        ("has_end", &entry_resp.has_end),
+    ] {
+        if let Some(time) = time {
+            let e = update_entry_time_index(read_index_zome, &entry_def_id, time_index_name, &base_address, Some(time.to_owned()));
+            hdk::prelude::debug!("handle_create_economic_event_record::{} time index {:?}", time_index_name, e);
+            report_indexing_outcome(&format!("{}_{}", entry_def_id, time_index_name), &e);
+        }
+    }
+
     // handle link fields
     // :TODO: handle errors better https://github.com/h-REA/hREA/issues/264
     let e1 = create_index!(economic_event.provider(event.provider), agent.economic_events_as_provider(&base_address))?;
@@ -217,6 +490,14 @@ fn handle_create_economic_event_record<S>(entry_def_id: S, event: &EconomicEvent
         let e = create_index!(economic_event.realization_of(realization_of), agreement.economic_events(&base_address));
         hdk::prelude::debug!("handle_create_economic_event_record::realization_of index {:?}", e);
     };
+    if let EconomicEventCreateRequest { at_location: MaybeUndefined::Some(at_location), .. } = event {
+        let e = create_index!(economic_event.at_location(at_location), location.economic_events(&base_address));
+        hdk::prelude::debug!("handle_create_economic_event_record::at_location index {:?}", e);
+    };
+    if let EconomicEventCreateRequest { corrects: MaybeUndefined::Some(corrects), .. } = event {
+        let e = create_index!(economic_event(&base_address).corrects(corrects));
+        hdk::prelude::debug!("handle_create_economic_event_record::corrects index {:?}", e);
+    };
 
     Ok((meta, base_address, entry_resp))
 }
@@ -252,13 +533,17 @@ fn resource_creation(event: &CreateRequest, resource: &ResourceCreateRequest) ->
 /// Handle alteration of existing resources via events
 ///
 fn handle_update_resource_inventory(
-    event: &EconomicEventCreateRequest,
+    event_address: &EconomicEventAddress, event: &EconomicEventCreateRequest,
 ) -> RecordAPIResult<Vec<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData, EconomicResourceData)>>
 {
     Ok(call_local_zome_method(
         read_resource_zome,
         INVENTORY_UPDATE_API_METHOD.to_string(),
-        event,
+        InventoryUpdatePayload {
+            event_address: event_address.to_owned(),
+            event: event.to_owned(),
+            restore_custody: None,
+        },
     )?)
 }
 
@@ -273,21 +558,23 @@ pub fn construct_response_with_resource<'a>(
     event: &EntryData, (
         fulfillments,
         satisfactions,
+        claims,
+        settlements,
+        corrected_by,
     ): (
         Vec<FulfillmentAddress>,
         Vec<SatisfactionAddress>,
+        Vec<ClaimAddress>,
+        Vec<SettlementAddress>,
+        Vec<EconomicEventAddress>,
     ),
     resource_address: Option<EconomicResourceAddress>,
     resource_meta: &SignedActionHashed,
     resource: EconomicResourceData, (
         contained_in,
-        stage,
-        state,
         contains,
      ): (
         Option<EconomicResourceAddress>,
-        Option<ProcessSpecificationAddress>,
-        Option<ActionId>,
         Vec<EconomicResourceAddress>,
     ),
 ) -> RecordAPIResult<ResponseData> {
@@ -316,13 +603,18 @@ pub fn construct_response_with_resource<'a>(
             triggered_by: event.triggered_by.to_owned(),
             realization_of: event.realization_of.to_owned(),
             in_scope_of: event.in_scope_of.to_owned(),
+            corrects: event.corrects.to_owned(),
             fulfills: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
+            triggers: claims.to_owned(),
+            settles: settlements.to_owned(),
+            corrected_by: corrected_by.to_owned(),
         },
         economic_resource: match resource_address {
-            Some(addr) => Some(construct_resource_response(&addr, &resource_meta, &resource, (contained_in, stage, state, contains))?),
+            Some(addr) => Some(construct_resource_response(&addr, &resource_meta, &resource, (contained_in, contains))?),
             None => None,
         },
+        indexing_report: None,
     })
 }
 
@@ -331,9 +623,15 @@ pub fn construct_response<'a>(
     address: &EconomicEventAddress, meta: &SignedActionHashed, e: &EntryData, (
         fulfillments,
         satisfactions,
+        claims,
+        settlements,
+        corrected_by,
     ): (
         Vec<FulfillmentAddress>,
         Vec<SatisfactionAddress>,
+        Vec<ClaimAddress>,
+        Vec<SettlementAddress>,
+        Vec<EconomicEventAddress>,
     )
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -361,10 +659,15 @@ pub fn construct_response<'a>(
             triggered_by: e.triggered_by.to_owned(),
             realization_of: e.realization_of.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
+            corrects: e.corrects.to_owned(),
             fulfills: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
+            triggers: claims.to_owned(),
+            settles: settlements.to_owned(),
+            corrected_by: corrected_by.to_owned(),
         },
         economic_resource: None,
+        indexing_report: None,
     })
 }
 
@@ -372,10 +675,16 @@ pub fn construct_response<'a>(
 pub fn get_link_fields(event: &EconomicEventAddress) -> RecordAPIResult<(
     Vec<FulfillmentAddress>,
     Vec<SatisfactionAddress>,
+    Vec<ClaimAddress>,
+    Vec<SettlementAddress>,
+    Vec<EconomicEventAddress>,
 )> {
     Ok((
         read_index!(economic_event(event).fulfills)?,
         read_index!(economic_event(event).satisfies)?,
+        read_index!(economic_event(event).triggers)?,
+        read_index!(economic_event(event).settles)?,
+        read_index!(economic_event(event).corrected_by)?,
     ))
 }
 
@@ -12,7 +12,8 @@ use serde_maybe_undefined::MaybeUndefined;
 use vf_measurement::QuantityValue;
 use hdk_relay_pagination::PageInfo;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    IndexingReport, DeleteResponse,
+    ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     EconomicEventAddress,
     EconomicResourceAddress,
     ActionId,
@@ -26,6 +27,8 @@ pub use vf_attributes_hdk::{
     CommitmentAddress,
     FulfillmentAddress,
     SatisfactionAddress,
+    ClaimAddress,
+    SettlementAddress,
     AgreementAddress,
     ProductBatchAddress,
     UnitId,
@@ -78,6 +81,8 @@ pub struct Response {
     pub triggered_by: Option<EconomicEventAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub in_scope_of: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corrects: Option<EconomicEventAddress>,
 
     // LINK FIELDS
     #[serde(default)]
@@ -86,6 +91,15 @@ pub struct Response {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub satisfies: Vec<SatisfactionAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<ClaimAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub settles: Vec<SettlementAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub corrected_by: Vec<EconomicEventAddress>,
 }
 
 /// I/O struct to describe EconomicResources, including all managed link fields
@@ -145,6 +159,8 @@ pub struct ResponseData {
     pub economic_event: Response,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub economic_resource: Option<ResourceResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 /// I/O struct to describe what is returned outside the gateway
@@ -237,10 +253,23 @@ pub struct CreateRequest {
     pub triggered_by: MaybeUndefined<EconomicEventAddress>,
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    // :NOTE: assigned by `correct_economic_event`, which also writes the reversing event
+    #[serde(default)]
+    pub corrects: MaybeUndefined<EconomicEventAddress>,
+    // :NOTE: assigned by `correct_economic_event` alongside `corrects`
+    #[serde(default)]
+    pub corrects_revision: MaybeUndefined<ActionHash>,
+    // :NOTE: assigned by `correct_economic_event` to the reversing event
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub reversal: bool,
 
     // :SHONK: internal field used in updating linked resource quantities
     #[serde(default)]
     pub target_inventory_type: Option<ResourceInventoryType>,
+    // :SHONK: internal field used in updating the stage of linked resources, resolved from the `ProcessSpecification` of `output_of`
+    #[serde(default)]
+    pub target_stage: Option<ProcessSpecificationAddress>,
 }
 
 impl<'a> CreateRequest {
@@ -258,6 +287,22 @@ impl<'a> CreateRequest {
         }
     }
 
+    pub fn with_stage(&self, stage: Option<ProcessSpecificationAddress>) -> Self {
+        CreateRequest {
+            target_stage: stage,
+            ..self.to_owned()
+        }
+    }
+
+    pub fn with_corrects(&self, event_address: &EconomicEventAddress, revision_id: &ActionHash) -> Self {
+        CreateRequest {
+            corrects: MaybeUndefined::Some(event_address.to_owned()),
+            corrects_revision: MaybeUndefined::Some(revision_id.to_owned()),
+            reversal: false,
+            ..self.to_owned()
+        }
+    }
+
     // accessors for field data
 
     pub fn get_action(&'a self) -> &str {
@@ -335,12 +380,114 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+// used to resolve concurrent updates to an EconomicEvent
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    // every live branch head of the record's revision history
+    pub revision_ids: Vec<ActionHash>,
+    #[serde(default)]
+    pub note: MaybeUndefined<String>,
+    #[serde(default)]
+    pub agreed_in: MaybeUndefined<ExternalURL>,
+    #[serde(default)]
+    pub realization_of: MaybeUndefined<AgreementAddress>,
+    #[serde(default)]
+    pub triggered_by: MaybeUndefined<EconomicEventAddress>,
+    #[serde(default)]
+    pub in_scope_of: MaybeUndefined<Vec<String>>,
+}
+
+impl MergeRequest {
+    /// Manual edits to apply to the merged revision, which supersedes `revision_id`
+    pub fn as_update(&self, revision_id: ActionHash) -> UpdateRequest {
+        UpdateRequest {
+            revision_id,
+            note: self.note.to_owned(),
+            agreed_in: self.agreed_in.to_owned(),
+            realization_of: self.realization_of.to_owned(),
+            triggered_by: self.triggered_by.to_owned(),
+            in_scope_of: self.in_scope_of.to_owned(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateParams {
     pub event: UpdateRequest,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeParams {
+    pub event: MergeRequest,
+}
+
+//---------------- CORRECTION REQUEST ----------------
+
+/// I/O struct for amending an EconomicEvent by writing a reversing event,
+/// optionally followed by a replacement event.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionParams {
+    pub event: EconomicEventAddress,
+    #[serde(default)]
+    pub replacement: Option<CreateRequest>,
+}
+
+/// I/O struct to describe what is returned outside the gateway
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionResponseData {
+    pub reversal: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<Response>,
+}
+
+//---------------- TRACK & TRACE ----------------
+
+/// A record visited when traversing the flows of resources through the REA graph
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "type", content = "id")]
+pub enum ProvenanceRecord {
+    EconomicResource(EconomicResourceAddress),
+    EconomicEvent(EconomicEventAddress),
+    Process(ProcessAddress),
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TraversalParams {
+    pub start: ProvenanceRecord,
+    // maximum number of steps to take from the starting record, defaults to 10
+    #[serde(default)]
+    pub depth: Option<usize>,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceNode {
+    pub record: ProvenanceRecord,
+    pub depth: usize,
+}
+
+/// Flow between two records, always directed from origin towards destination
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceEdge {
+    pub from: ProvenanceRecord,
+    pub to: ProvenanceRecord,
+}
+
+/// Records reached by a traversal in the order they were visited, and the flows connecting them
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceGraph {
+    pub nodes: Vec<ProvenanceNode>,
+    pub edges: Vec<ProvenanceEdge>,
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
@@ -350,8 +497,13 @@ pub struct QueryParams {
     pub output_of: Option<ProcessAddress>,
     pub satisfies: Option<IntentAddress>,
     pub fulfills: Option<CommitmentAddress>,
+    pub triggers: Option<ClaimAddress>,
+    pub settles: Option<SettlementAddress>,
     pub realization_of: Option<AgreementAddress>,
     pub affects: Option<EconomicResourceAddress>,
     pub provider: Option<AgentAddress>,
     pub receiver: Option<AgentAddress>,
+    pub at_location: Option<LocationAddress>,
+    pub corrects: Option<EconomicEventAddress>,
+    pub corrected_by: Option<EconomicEventAddress>,
 }
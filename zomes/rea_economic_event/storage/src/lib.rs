@@ -9,11 +9,13 @@
 use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     generate_record_entry,
     MaybeUndefined,
     record_interface::Updateable,
+    authorization::RecordParticipants,
 };
 
 use vf_measurement::QuantityValue;
@@ -29,7 +31,7 @@ use vf_attributes_hdk::{
     AgreementAddress,
     DateTime, FixedOffset,
 };
-use vf_actions::{ validate_flow_action, validate_move_inventories };
+use vf_actions::{ validate_flow_action, ensure_action_definition, ActionDefinition, validate_move_inventories };
 use hc_zome_rea_economic_event_rpc::*;
 
 // :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
@@ -52,6 +54,10 @@ pub struct EconomicEventZomeConfig {
     pub process_index_zome: Option<String>,
     pub agreement_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub location_index_zome: Option<String>,
+    // when enabled, events cannot be updated or deleted once written and must be amended via `correct_economic_event`
+    #[serde(default)]
+    pub immutable_events: bool,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -59,6 +65,9 @@ pub struct EconomicEventZomeConfig {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 pub struct EntryData {
     pub action: ActionId,
+    // local copy of any user-defined `action`, @see vf_actions::ensure_action_definition
+    #[serde(default)]
+    pub action_definition: Option<EntryHash>,
     pub provider: AgentAddress,
     pub receiver: AgentAddress,
     pub input_of: Option<ProcessAddress>,   // :NOTE: shadows link, see https://github.com/h-REA/hREA/issues/60#issuecomment-553756873
@@ -78,16 +87,30 @@ pub struct EntryData {
     pub triggered_by: Option<EconomicEventAddress>,
     pub in_scope_of: Option<Vec<String>>,
     pub note: Option<String>,
+    #[serde(default)]
+    pub corrects: Option<EconomicEventAddress>,
+    // revision of the `corrects` event which was corrected, from which validation determines the agent who authored it
+    #[serde(default)]
+    pub corrects_revision: Option<ActionHash>,
+    // set on events written by `correct_economic_event` to negate the event they correct
+    #[serde(default)]
+    pub reversal: bool,
     pub _nonce: Bytes,
 }
 
 impl EntryData {
-    pub fn validate_action(&self) -> Result<(), String> {
-        let result = validate_flow_action(self.action.to_owned(), self.input_of.to_owned(), self.output_of.to_owned());
+    pub fn validate_action(&self) -> ExternResult<Result<(), String>> {
+        let result = validate_flow_action(self.action.to_owned(), self.action_definition.to_owned(), self.input_of.to_owned(), self.output_of.to_owned())?;
         if result.is_ok() && self.action.as_ref() == "move" {
-            return validate_move_inventories(self.resource_inventoried_as.to_owned(), self.to_resource_inventoried_as.to_owned());
+            return Ok(validate_move_inventories(self.resource_inventoried_as.to_owned(), self.to_resource_inventoried_as.to_owned()));
         }
-        return result;
+        Ok(result)
+    }
+
+    /// Resolve user-defined actions and reference a local copy of their definition for validation
+    fn with_action_definition(self) -> RecordAPIResult<EntryData> {
+        let action_definition = ensure_action_definition::<EntryTypes, _, _>(&self.action, self.input_of.to_owned(), self.output_of.to_owned())?;
+        Ok(EntryData { action_definition, ..self })
     }
 
     pub fn validate_or_fields(&self) -> Result<(), String> {
@@ -106,6 +129,13 @@ impl EntryData {
 
 generate_record_entry!(EntryData, EconomicEventAddress, EntryStorage);
 
+/// Agents named as the provider & receiver may modify the record alongside its author
+impl RecordParticipants for EntryData {
+    fn get_participants(&self) -> Vec<EntryHash> {
+        vec![self.provider.1.to_owned(), self.receiver.1.to_owned()]
+    }
+}
+
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
 #[hdk_entry_defs(skip_hdk_extern = true)]
@@ -113,7 +143,10 @@ generate_record_entry!(EntryData, EconomicEventAddress, EntryStorage);
 pub enum EntryTypes {
     EconomicEvent(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
+    ActionDefinition(ActionDefinition),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -133,12 +166,30 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
+impl TryFrom<ActionDefinition> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: ActionDefinition) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::ActionDefinition(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // registers the Agent record held by an agent, @see hdk_records::authorization
+    AgentRegistration,
 }
 
 //---------------- CREATE ----------------
@@ -150,8 +201,9 @@ impl TryFrom<CreateRequest> for EntryData {
     type Error = DataIntegrityError;
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        EntryData {
             action: e.action.into(),
+            action_definition: None,
             note: e.note.into(),
             provider: e.provider.into(),
             receiver: e.receiver.into(),
@@ -171,8 +223,44 @@ impl TryFrom<CreateRequest> for EntryData {
             triggered_by: e.triggered_by.into(),
             at_location: e.at_location.into(),
             in_scope_of: e.in_scope_of.into(),
+            corrects: e.corrects.into(),
+            corrects_revision: e.corrects_revision.into(),
+            reversal: e.reversal,
             _nonce: random_bytes(32)?,
-        })
+        }.with_action_definition()
+    }
+}
+
+/// Rebuild the I/O record for a stored event, for replaying events through resource inventory logic
+impl From<&EntryData> for CreateRequest {
+    fn from(e: &EntryData) -> CreateRequest {
+        CreateRequest {
+            action: e.action.to_owned(),
+            note: e.note.to_owned().into(),
+            provider: e.provider.to_owned(),
+            receiver: e.receiver.to_owned(),
+            input_of: e.input_of.to_owned().into(),
+            output_of: e.output_of.to_owned().into(),
+            resource_inventoried_as: e.resource_inventoried_as.to_owned().into(),
+            to_resource_inventoried_as: e.to_resource_inventoried_as.to_owned().into(),
+            resource_classified_as: e.resource_classified_as.to_owned().into(),
+            resource_conforms_to: e.resource_conforms_to.to_owned().into(),
+            resource_quantity: e.resource_quantity.to_owned().into(),
+            effort_quantity: e.effort_quantity.to_owned().into(),
+            has_beginning: e.has_beginning.to_owned().into(),
+            has_end: e.has_end.to_owned().into(),
+            has_point_in_time: e.has_point_in_time.to_owned().into(),
+            at_location: e.at_location.to_owned().into(),
+            agreed_in: e.agreed_in.to_owned().into(),
+            realization_of: e.realization_of.to_owned().into(),
+            triggered_by: e.triggered_by.to_owned().into(),
+            in_scope_of: e.in_scope_of.to_owned().into(),
+            corrects: e.corrects.to_owned().into(),
+            corrects_revision: e.corrects_revision.to_owned().into(),
+            reversal: e.reversal,
+            target_inventory_type: None,
+            target_stage: None,
+        }
     }
 }
 
@@ -183,6 +271,7 @@ impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
             action: self.action.to_owned(),
+            action_definition: self.action_definition.to_owned(),
             provider: self.provider.to_owned(),
             receiver: self.receiver.to_owned(),
             input_of: self.input_of.to_owned(),
@@ -202,6 +291,9 @@ impl Updateable<UpdateRequest> for EntryData {
             at_location: self.at_location.to_owned(),
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
+            corrects: self.corrects.to_owned(),
+            corrects_revision: self.corrects_revision.to_owned(),
+            reversal: self.reversal,
             _nonce: self._nonce.to_owned(),
         })
     }
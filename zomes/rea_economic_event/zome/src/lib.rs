@@ -11,5 +11,11 @@ use hdk::prelude::*;
 
 use hc_zome_rea_economic_event_zome_api::*;
 use hc_zome_rea_economic_event_lib::*;
+use hc_zome_rea_economic_event_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::{declare_pending_sync_zome_api, declare_agent_registration_zome_api};
 
 declare_economic_event_zome_api!(EconomicEventZomePermissableDefault);
+
+declare_pending_sync_zome_api!();
+
+declare_agent_registration_zome_api!(LinkTypes::AgentRegistration);
@@ -10,8 +10,15 @@ pub trait API {
     ) -> RecordAPIResult<ResponseData>;
     fn get_economic_event(address: EconomicEventAddress) -> RecordAPIResult<ResponseData>;
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>;
+    fn get_economic_event_revisions(address: EconomicEventAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>;
     fn update_economic_event(event: UpdateRequest) -> RecordAPIResult<ResponseData>;
-    fn delete_economic_event(revision_id: ActionHash) -> RecordAPIResult<bool>;
+    fn merge_economic_event(event: MergeRequest) -> RecordAPIResult<ResponseData>;
+    fn delete_economic_event(entry_def_id: Self::S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>;
+    fn correct_economic_event(entry_def_id: Self::S,
+        event: EconomicEventAddress, replacement: Option<CreateRequest>,
+    ) -> RecordAPIResult<CorrectionResponseData>;
+    fn track(start: ProvenanceRecord, depth: Option<usize>) -> RecordAPIResult<ProvenanceGraph>;
+    fn trace(start: ProvenanceRecord, depth: Option<usize>) -> RecordAPIResult<ProvenanceGraph>;
 }
 
 /// Macro to programatically and predictably bind an `API` implementation to a
@@ -36,19 +43,56 @@ macro_rules! declare_economic_event_zome_api {
             Ok(<$zome_api>::get_economic_event(address)?)
         }
 
+        // read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+        #[hdk_extern]
+        fn __internal_get_economic_event_summary(params: ByAddress<EconomicEventAddress>) -> ExternResult<ResponseData> {
+            hdk_records::metadata::with_summary_metadata(|| get_economic_event(params))
+        }
+
         #[hdk_extern]
         fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
             Ok(<$zome_api>::get_revision(revision_id)?)
         }
 
+        #[hdk_extern]
+        fn get_economic_event_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<EconomicEventAddress>) -> ExternResult<RevisionHistoryPage> {
+            Ok(<$zome_api>::get_economic_event_revisions(address, first, after)?)
+        }
+
         #[hdk_extern]
         fn update_economic_event(UpdateParams { event }: UpdateParams) -> ExternResult<ResponseData> {
             Ok(<$zome_api>::update_economic_event(event)?)
         }
 
         #[hdk_extern]
-        fn delete_economic_event(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-            Ok(<$zome_api>::delete_economic_event(revision_id)?)
+        fn merge_economic_event(MergeParams { event }: MergeParams) -> ExternResult<ResponseData> {
+            Ok(<$zome_api>::merge_economic_event(event)?)
+        }
+
+        #[hdk_extern]
+        fn delete_economic_event(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+            Ok(<$zome_api>::delete_economic_event(
+                EVENT_ENTRY_TYPE,
+                revision_id,
+            )?)
+        }
+
+        #[hdk_extern]
+        fn correct_economic_event(CorrectionParams { event, replacement }: CorrectionParams) -> ExternResult<CorrectionResponseData> {
+            Ok(<$zome_api>::correct_economic_event(
+                EVENT_ENTRY_TYPE,
+                event, replacement,
+            )?)
+        }
+
+        #[hdk_extern]
+        fn track(TraversalParams { start, depth }: TraversalParams) -> ExternResult<ProvenanceGraph> {
+            Ok(<$zome_api>::track(start, depth)?)
+        }
+
+        #[hdk_extern]
+        fn trace(TraversalParams { start, depth }: TraversalParams) -> ExternResult<ProvenanceGraph> {
+            Ok(<$zome_api>::trace(start, depth)?)
         }
     };
 }
@@ -7,16 +7,21 @@
 use hdk_semantic_indexes_zome_derive::index_zome;
 use hc_zome_rea_economic_event_rpc::*;
 
-#[index_zome]
+#[index_zome(time_index="has_point_in_time",time_index="has_beginning",time_index="has_end")]
 struct EconomicEvent {
     input_of: Local<process, observed_inputs>,
     output_of: Local<process, observed_outputs>,
     realization_of: Local<agreement, economic_events>,
     satisfies: Local<satisfaction, satisfied_by>,
     fulfills: Local<fulfillment, fulfilled_by>,
+    triggers: Local<claim, triggered_by>,
+    settles: Local<settlement, settled_by>,
 
     // internal indexes (not part of REA spec)
     affects: Local<economic_resource, affected_by>,
     provider: Local<agent, economic_events_as_provider>,
     receiver: Local<agent, economic_events_as_receiver>,
+    at_location: Local<location, economic_events>,
+    corrects: Local<economic_event, corrected_by>,
+    corrected_by: Local<economic_event, corrects>,
 }
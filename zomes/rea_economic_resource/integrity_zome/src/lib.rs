@@ -8,8 +8,15 @@
  * @package hREA
  */
 use hdi::prelude::*;
+use std::marker::PhantomData;
+use hdk_records::authorization::{
+    AuthorOnly, ModificationPolicy, ModificationRequest,
+    validate_update_authorization, validate_delete_authorization,
+    validate_agent_registration, read_registered_agent_identities,
+};
 use hc_zome_rea_economic_resource_storage::Identified;
-use hc_zome_rea_economic_resource_storage::{EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
+use hc_zome_rea_economic_resource_storage::{EntryData, EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
+use hc_zome_rea_economic_event_storage::EntryStorage as EventStorage;
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -30,6 +37,57 @@ pub fn __num_link_types() -> u8 {
     LinkTypes::len()
 }
 
+/// Permits inventory updates caused by events the updating agent took part in, and otherwise
+/// defers to the `ModificationPolicy` `P`.
+///
+/// Resource quantities are reduced from events, which may be recorded by agents other than those
+/// accountable for a resource (eg. the provider of a transfer updates the receiver's inventory).
+/// Updates which only alter the quantities or location of a resource are therefore accepted from
+/// the provider or receiver of the event they reference, if that event affects the resource.
+///
+pub struct InventoryUpdatesOr<P>(PhantomData<P>);
+
+impl<P> ModificationPolicy<EntryData> for InventoryUpdatesOr<P>
+    where P: ModificationPolicy<EntryData>,
+{
+    fn can_update(req: &ModificationRequest<EntryData>) -> ExternResult<bool> {
+        if P::can_update(req)? {
+            return Ok(true);
+        }
+        match (req.current_entry, req.new_entry) {
+            (Some(current), Some(new)) if new.is_inventory_update_of(current) => is_event_participant_update(req, new),
+            _ => Ok(false),
+        }
+    }
+
+    fn can_delete(req: &ModificationRequest<EntryData>) -> ExternResult<bool> {
+        P::can_delete(req)
+    }
+}
+
+/// Whether the event referenced by an inventory update affects the resource, and has the updating
+/// agent as its provider or receiver
+fn is_event_participant_update(req: &ModificationRequest<EntryData>, new: &EntryData) -> ExternResult<bool> {
+    let event_address = match &new.last_event {
+        Some(event_address) => event_address,
+        None => return Ok(false),
+    };
+    let event = match EventStorage::try_from(&must_get_entry(event_address.1.to_owned())?.content) {
+        Ok(storage) => storage.entry(),
+        Err(_) => return Ok(false),
+    };
+
+    let affects_resource = [&event.resource_inventoried_as, &event.to_resource_inventoried_as].iter()
+        .any(|resource| resource.as_ref().map_or(false, |r| &r.1 == req.identity));
+    if !affects_resource {
+        return Ok(false);
+    }
+
+    Ok(read_registered_agent_identities(req.agent, req.chain_top)?
+        .iter()
+        .any(|agent_id| *agent_id == event.provider.1 || *agent_id == event.receiver.1))
+}
+
 /// Macro to generate a default (permissable) validation function for EconomicResource
 /// records in the local zome (local field checks only).
 /// This is the minimum validation required by any zome, and regardless of other
@@ -38,18 +96,21 @@ pub fn __num_link_types() -> u8 {
 ///
 /// Use this method as a reference, and always call the below logic before any
 /// application-specific validation.
+///
+/// `$policy` is the `ModificationPolicy` determining which agents may update or delete
+/// records. Pass your own implementation to apply alternative (eg. role-based) permissions.
 // #[macro_export]
 macro_rules! declare_economic_resource_zome_validation_defaults {
-    ( /*$zome_api:ty*/ ) => {
+    ( $policy:ty ) => {
         #[hdk_extern]
         fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             match op {
                 Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
                 Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
-                Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
+                Op::RegisterCreateLink(RegisterCreateLink { create_link, .. }) => validate_agent_registration(&create_link.hashed.content),
                 Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+                Op::RegisterUpdate(RegisterUpdate { update, .. }) => validate_update_authorization::<$policy, _, _>(&update.hashed.content, parse_entry),
+                Op::RegisterDelete(RegisterDelete { delete, .. }) => validate_delete_authorization::<$policy, _, _>(&delete.hashed.content, parse_entry),
                 Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
             }
         }
@@ -66,7 +127,11 @@ macro_rules! declare_economic_resource_zome_validation_defaults {
                 _ => Ok(ValidateCallbackResult::Valid),
             }
         }
+
+        fn parse_entry(entry: &Entry) -> Option<EntryData> {
+            EntryStorage::try_from(entry).ok().map(|s| s.entry())
+        }
     };
 }
 
-declare_economic_resource_zome_validation_defaults!();
+declare_economic_resource_zome_validation_defaults!(InventoryUpdatesOr<AuthorOnly>);
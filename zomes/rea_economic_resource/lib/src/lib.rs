@@ -7,16 +7,20 @@
  * @package hREA
  */
 use paste::paste;
+use hdk::prelude::{Path, LinkTag, Timestamp, Action, Update, create_link, delete_link, get_links, dna_info};
 use hdk_records::{
     DataIntegrityError, RecordAPIResult, MaybeUndefined,
     records::{
-        get_latest_action_hash,
+        get_latest_action_hash_or_conflicts,
         create_record,
+        merge_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    record_interface::Updateable,
     EntryHash, SignedActionHashed,
 };
 use hdk_semantic_indexes_client_lib::*;
@@ -25,7 +29,9 @@ use vf_attributes_hdk::{
     EconomicResourceAddress,
     EconomicEventAddress,
     ActionId,
+    ProcessAddress,
     ProcessSpecificationAddress,
+    DateTime, FixedOffset, Utc,
 };
 
 pub use hc_zome_rea_economic_resource_storage_consts::*;
@@ -75,6 +81,7 @@ impl API for EconomicResourceZomePermissableDefault {
         let event_params = params.get_event_params().clone();
         let resource_params = params.get_resource_params().clone();
         let resource_spec = params.get_resource_specification_id();
+        let stage = get_process_stage(&event_params.output_of)?;
         // :TODO: move this assertion to validation callback
         if let MaybeUndefined::Some(_sent_inventory_id) = event_params.resource_inventoried_as {
             return Err(DataIntegrityError::RemoteRequestError("cannot create a new EconomicResource and specify an inventoried resource ID in the same event".to_string()));
@@ -83,7 +90,9 @@ impl API for EconomicResourceZomePermissableDefault {
         let (meta, base_address, entry_resp): (_, EconomicResourceAddress, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(
             read_index_zome,
             &resource_entry_def_id,
-            params.with_inventory_type(ResourceInventoryType::ProvidingInventory),  // inventories can only be inited by their owners initially
+            params
+                .with_inventory_type(ResourceInventoryType::ProvidingInventory)  // inventories can only be inited by their owners initially
+                .with_stage(stage),
         )?;
 
         // :NOTE: this will always run- resource without a specification ID would fail entry validation (implicit in the above)
@@ -96,11 +105,28 @@ impl API for EconomicResourceZomePermissableDefault {
             hdk::prelude::debug!("create_inventory_from_event::contained_in index {:?}", e);
         };
 
+        if let Some(lot) = &entry_resp.lot {
+            let e = create_index!(economic_resource.lot(lot), product_batch.economic_resources(&base_address));
+            hdk::prelude::debug!("create_inventory_from_event::lot index {:?}", e);
+        }
+        if let Some(current_location) = &entry_resp.current_location {
+            let e = create_index!(economic_resource.current_location(current_location), location.economic_resources(&base_address));
+            hdk::prelude::debug!("create_inventory_from_event::current_location index {:?}", e);
+        }
+
         if entry_resp.primary_accountable.is_some() {
             let e = create_index!(economic_resource.primary_accountable(&event_params.receiver), agent.inventoried_economic_resources(&base_address));
             hdk::prelude::debug!("create_inventory_from_event::new_inventoried_resource::primary_accountable index {:?}", e);
         }
 
+        if let Some(stage) = &entry_resp.stage {
+            let e = create_index!(economic_resource.stage(stage), process_specification.staged_resources(&base_address));
+            hdk::prelude::debug!("create_inventory_from_event::stage index {:?}", e);
+        }
+        if let Some(state) = &entry_resp.state {
+            link_resource_state(&base_address, state)?;
+        }
+
         Ok((meta, base_address, entry_resp))
     }
 
@@ -110,47 +136,83 @@ impl API for EconomicResourceZomePermissableDefault {
         construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
     }
 
+    /// Reconstruct the state of a resource at a given moment by replaying all events which occurred up to that time.
+    ///
+    /// Replay begins from the resource's initial revision. Record metadata and link fields reflect the
+    /// current revision of the resource.
+    ///
+    /// :TODO: manual edits made via `update_economic_resource` before `at` are not reflected in the result.
+    ///
+    fn get_economic_resource_at_time(address: EconomicResourceAddress, at: DateTime<FixedOffset>) -> RecordAPIResult<ResponseData>
+    {
+        let (meta, base_address, _entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+        let original_revision = read_revision_metadata_abbreviated(&meta)?.original_revision;
+        let (_, _, original_entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&original_revision.id)?;
+
+        let events = occurred_until(read_affecting_events_ordered(&address)?, &at);
+        if events.is_empty() {
+            // the resource had not been created at the requested time
+            return Err(DataIntegrityError::EntryNotFound);
+        }
+
+        let mut replayed = original_entry.with_zeroed_quantities();
+        for event in events.iter() {
+            replayed = replay_event(&address, replayed, event)?;
+        }
+
+        construct_response(&base_address, &meta, &replayed, get_link_fields(&address)?)
+    }
+
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
     {
         let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
         construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
     }
 
+    fn get_economic_resource_revisions(address: EconomicResourceAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+    {
+        read_record_revisions(&address, after, first)
+    }
+
     /// Handle update of resources by iterative reduction of event records over time.
     ///
     fn update_inventory_from_event(
-        event: EventCreateRequest,
+        params: InventoryUpdatePayload,
     ) -> RecordAPIResult<Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>>
     {
         let mut resources_affected: Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)> = vec![];
+        let event = params.event.with_stage(get_process_stage(&params.event.output_of)?);
+        let params = InventoryUpdatePayload { event: event.to_owned(), ..params };
 
         // if the event is a transfer-like event, run the receiver's update first
         if let MaybeUndefined::Some(receiver_inventory) = &event.to_resource_inventoried_as {
             let inv_entry_hash: &EntryHash = receiver_inventory.as_ref();
 
+            let revision_id = get_reducible_revision(inv_entry_hash)?;
             let (meta, resource_address, new_resource, prev_resource) = handle_update_inventory_resource(
-                &get_latest_action_hash(inv_entry_hash.clone())?,
-                event.with_inventory_type(ResourceInventoryType::ReceivingInventory),
+                &revision_id,
+                params.with_inventory_type(ResourceInventoryType::ReceivingInventory)
+                    .with_restored_custody(read_custody_before_reversed_event(&revision_id, &event)?),
             )?;
             resources_affected.push((meta, resource_address.clone(), new_resource.clone(), prev_resource.clone()));
-            if new_resource.primary_accountable != prev_resource.primary_accountable {
-                let new_value = if let Some(val) = &new_resource.primary_accountable { vec![val.to_owned()] } else { vec![] };
-                let prev_value = if let Some(val) = &prev_resource.primary_accountable { vec![val.to_owned()] } else { vec![] };
-                let e = update_index!(
-                    economic_resource
-                        .primary_accountable(new_value.as_slice())
-                        .not(prev_value.as_slice()),
-                    agent.inventoried_economic_resources(&resource_address));
-                hdk::prelude::debug!("update_economic_resource::to_resource_inventoried_as::primary_accountable index {:?}", e);
-            }
+            handle_update_accountable_index(&resource_address, &new_resource, &prev_resource);
+            handle_update_location_index(&resource_address, &new_resource, &prev_resource);
+            handle_update_inspection_indexes(&resource_address, &new_resource, &prev_resource)?;
         }
         // after receiver, run provider. This entry data will be returned in the response.
         if let MaybeUndefined::Some(provider_inventory) = &event.resource_inventoried_as {
             let inv_entry_hash: &EntryHash = provider_inventory.as_ref();
-            resources_affected.push(handle_update_inventory_resource(
-                &get_latest_action_hash(inv_entry_hash.clone())?,   // :TODO: temporal reduction here! Should error on mismatch and return latest valid ID
-                event.with_inventory_type(ResourceInventoryType::ProvidingInventory),
-            )?);
+            let revision_id = get_reducible_revision(inv_entry_hash)?;
+            let (meta, resource_address, new_resource, prev_resource) = handle_update_inventory_resource(
+                &revision_id,
+                params.with_inventory_type(ResourceInventoryType::ProvidingInventory)
+                    .with_restored_custody(read_custody_before_reversed_event(&revision_id, &event)?),
+            )?;
+            // primary accountable agents of providing resources only change when restored by reversing events
+            handle_update_accountable_index(&resource_address, &new_resource, &prev_resource);
+            handle_update_location_index(&resource_address, &new_resource, &prev_resource);
+            handle_update_inspection_indexes(&resource_address, &new_resource, &prev_resource)?;
+            resources_affected.push((meta, resource_address, new_resource, prev_resource));
         }
 
         Ok(resources_affected)
@@ -158,6 +220,7 @@ impl API for EconomicResourceZomePermissableDefault {
 
     fn update_economic_resource(resource: UpdateRequest) -> RecordAPIResult<ResponseData>
     {
+        clear_indexing_report();
         let address = resource.get_revision_id().clone();
         let (meta, identity_address, entry, prev_entry): (_,_, EntryData, EntryData) = update_record(&address, resource)?;
 
@@ -169,13 +232,210 @@ impl API for EconomicResourceZomePermissableDefault {
             let e = update_index!(economic_resource(&identity_address).contained_in(now_contained.as_slice()).not(prev_contained.as_slice()));
             hdk::prelude::debug!("update_economic_resource::contained_in index {:?}", e);
         }
+        if entry.lot != prev_entry.lot {
+            let now_lot = if let Some(lot) = &entry.lot { vec![lot.clone()] } else { vec![] };
+            let prev_lot = if let Some(lot) = &prev_entry.lot { vec![lot.clone()] } else { vec![] };
+            let e = update_index!(
+                economic_resource
+                    .lot(now_lot.as_slice())
+                    .not(prev_lot.as_slice()),
+                product_batch.economic_resources(&identity_address)
+            );
+            hdk::prelude::debug!("update_economic_resource::lot index {:?}", e);
+        }
 
 
         // :TODO: optimise this- should pass results from `replace_direct_index` instead of retrieving from `get_link_fields` where updates
-        construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+        with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
+    }
+
+    /// Resolve conflicting revisions of a resource, caused by concurrent updates from different agents.
+    ///
+    /// All live branch heads must be named. Manual edits in the request are applied to the most
+    /// recent head; quantities are recomputed from the resource's full event history.
+    ///
+    fn merge_economic_resource(resource: MergeRequest) -> RecordAPIResult<ResponseData>
+    {
+        clear_indexing_report();
+        let (_meta, address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, EconomicResourceAddress>(
+            resource.revision_ids.first().ok_or(DataIntegrityError::EntryNotFound)?,
+        )?;
+        let (latest, _) = get_latest_action_hash_or_conflicts(address.as_ref().to_owned())?;
+        let (replayed, _) = replay_affecting_events(&address, &entry)?;
+
+        let (meta, identity_address, entry, prev_entry): (_, EconomicResourceAddress, EntryData, EntryData) = merge_record(
+            resource.revision_ids.as_slice(),
+            ResourceMerge {
+                edits: resource.as_update(latest),
+                quantities: QuantityCorrection {
+                    accounting_quantity: replayed.accounting_quantity,
+                    onhand_quantity: replayed.onhand_quantity,
+                },
+            },
+        )?;
+
+        if entry.contained_in != prev_entry.contained_in {
+            let now_contained = if let Some(contained) = &entry.contained_in { vec![contained.clone()] } else { vec![] };
+            let prev_contained = if let Some(contained) = &prev_entry.contained_in { vec![contained.clone()] } else { vec![] };
+            let e = update_index!(economic_resource(&identity_address).contained_in(now_contained.as_slice()).not(prev_contained.as_slice()));
+            hdk::prelude::debug!("merge_economic_resource::contained_in index {:?}", e);
+        }
+        if entry.lot != prev_entry.lot {
+            let now_lot = if let Some(lot) = &entry.lot { vec![lot.clone()] } else { vec![] };
+            let prev_lot = if let Some(lot) = &prev_entry.lot { vec![lot.clone()] } else { vec![] };
+            let e = update_index!(
+                economic_resource
+                    .lot(now_lot.as_slice())
+                    .not(prev_lot.as_slice()),
+                product_batch.economic_resources(&identity_address)
+            );
+            hdk::prelude::debug!("merge_economic_resource::lot index {:?}", e);
+        }
+
+        with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
+    }
+
+    fn get_economic_resources_by_state(state: ActionId) -> RecordAPIResult<Vec<EconomicResourceAddress>>
+    {
+        get_resources_by_state(&state)
+    }
+
+    /// Rebuild resource quantities by replaying all affecting events in time order, and
+    /// compare them with the stored values. Discrepancies are corrected if `write` is set.
+    ///
+    fn recompute_economic_resource(address: EconomicResourceAddress, write: bool) -> RecordAPIResult<RecomputeResponse>
+    {
+        let (_meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+        let (replayed, events_replayed) = replay_affecting_events(&address, &entry)?;
+
+        let consistent = replayed.accounting_quantity == entry.accounting_quantity
+            && replayed.onhand_quantity == entry.onhand_quantity;
+
+        let revision_id = if write && !consistent {
+            let (meta, _, _, _): (_, EconomicResourceAddress, EntryData, EntryData) = update_record(
+                &get_reducible_revision(address.as_ref())?,
+                QuantityCorrection {
+                    accounting_quantity: replayed.accounting_quantity.to_owned(),
+                    onhand_quantity: replayed.onhand_quantity.to_owned(),
+                },
+            )?;
+            Some(meta.as_hash().to_owned())
+        } else {
+            None
+        };
+
+        Ok(RecomputeResponse {
+            economic_resource: address,
+            events_replayed,
+            stored_accounting_quantity: entry.accounting_quantity,
+            stored_onhand_quantity: entry.onhand_quantity,
+            computed_accounting_quantity: replayed.accounting_quantity,
+            computed_onhand_quantity: replayed.onhand_quantity,
+            consistent,
+            revision_id,
+        })
     }
 }
 
+/// Select the revision of a resource to apply inventory updates to.
+///
+/// Concurrent updates by different agents may cause the resource's revision history to diverge.
+/// Rather than blocking further events, updates build upon the most recently authored head, which
+/// every agent selects identically. Quantities on the other branches are superseded when the
+/// conflict is resolved via `merge_economic_resource`, which replays the full event history.
+///
+fn get_reducible_revision(address: &EntryHash) -> RecordAPIResult<ActionHash>
+{
+    let (latest, conflicts) = get_latest_action_hash_or_conflicts(address.to_owned())?;
+    if !conflicts.is_empty() {
+        hdk::prelude::debug!("get_reducible_revision::conflicting revisions {:?}, updating {:?}", conflicts, latest);
+    }
+    Ok(latest)
+}
+
+/// Rebuild the quantities of a resource by replaying all events affecting it, in time order.
+/// Returns the rebuilt entry data and the number of events replayed.
+///
+fn replay_affecting_events(address: &EconomicResourceAddress, entry: &EntryData) -> RecordAPIResult<(EntryData, usize)>
+{
+    let events = read_affecting_events_ordered(address)?;
+
+    let mut replayed = entry.with_zeroed_quantities();
+    for (_time, event) in events.iter() {
+        replayed = replay_event(address, replayed, event)?;
+    }
+    Ok((replayed, events.len()))
+}
+
+/// Read all events affecting a resource, ordered by the time they occurred.
+///
+/// Events are sorted on their `has_point_in_time`, `has_end` or `has_beginning` (in order of preference),
+/// or the time they were authored if they have none of these. Events at equal times are sorted in the
+/// order they were authored. Each event is returned alongside the time it took effect.
+///
+fn read_affecting_events_ordered(resource: &EconomicResourceAddress) -> RecordAPIResult<Vec<(DateTime<FixedOffset>, EventData)>>
+{
+    let events = get_affecting_events(resource)?.iter()
+        .map(|event| {
+            let (meta, _, entry) = read_record_entry::<EventData, EventStorage, _>(event.as_ref())?;
+            Ok((event_time(&entry), meta.action().timestamp(), entry))
+        })
+        .collect::<RecordAPIResult<Vec<_>>>()?;
+
+    Ok(order_by_time_occurred(events))
+}
+
+/// The time at which an event took effect, for ordering inventory updates
+fn event_time(event: &EventData) -> Option<DateTime<FixedOffset>>
+{
+    event.has_point_in_time.or(event.has_end).or(event.has_beginning)
+}
+
+/// Sort items by the time they occurred, or the time they were authored if undated.
+/// Items occurring at equal times are sorted in the order they were authored.
+fn order_by_time_occurred<T>(items: Vec<(Option<DateTime<FixedOffset>>, Timestamp, T)>) -> Vec<(DateTime<FixedOffset>, T)>
+{
+    let mut items: Vec<(DateTime<FixedOffset>, Timestamp, T)> = items.into_iter()
+        .map(|(time, created, item)| (time.unwrap_or_else(|| timestamp_to_datetime(&created)), created, item))
+        .collect();
+
+    items.sort_by(|(a_time, a_created, _), (b_time, b_created, _)| a_time.cmp(b_time).then(a_created.cmp(b_created)));
+
+    items.into_iter().map(|(time, _, item)| (time, item)).collect()
+}
+
+/// Items from a list ordered by `order_by_time_occurred` which had occurred by the time `at`
+fn occurred_until<T>(items: Vec<(DateTime<FixedOffset>, T)>, at: &DateTime<FixedOffset>) -> Vec<T>
+{
+    items.into_iter()
+        .filter(|(time, _)| time <= at)
+        .map(|(_, item)| item)
+        .collect()
+}
+
+fn timestamp_to_datetime(timestamp: &Timestamp) -> DateTime<FixedOffset>
+{
+    let since_epoch = std::time::Duration::from_micros(timestamp.as_micros().max(0) as u64);
+    DateTime::<Utc>::from(std::time::UNIX_EPOCH + since_epoch).into()
+}
+
+/// Apply the effects of an event to a resource via the inventory reducer, as `update_inventory_from_event` would.
+/// Events moving inventory from a resource to itself affect both sides, receiver first.
+///
+fn replay_event(resource: &EconomicResourceAddress, state: EntryData, event: &EventData) -> RecordAPIResult<EntryData>
+{
+    let request = EventCreateRequest::from(event).with_stage(get_process_stage(&event.output_of.to_owned().into())?);
+    let mut state = state;
+
+    if event.to_resource_inventoried_as.as_ref() == Some(resource) {
+        state = state.update_with(request.with_inventory_type(ResourceInventoryType::ReceivingInventory))?;
+    }
+    if event.resource_inventoried_as.as_ref() == Some(resource) {
+        state = state.update_with(request.with_inventory_type(ResourceInventoryType::ProvidingInventory))?;
+    }
+    Ok(state)
+}
+
 /// Properties accessor for zome config
 fn read_economic_resource_index_zome(conf: DnaConfigSlice) -> Option<String> {
     Some(conf.economic_resource.index_zome)
@@ -186,9 +446,180 @@ fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String
     conf.economic_resource.resource_specification_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_location_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_resource.location_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_product_batch_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_resource.product_batch_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_process_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_resource.process_specification_index_zome
+}
+
+/// Reindex the `primary_accountable` agent of a resource if it was modified by an event (eg. `transfer`)
+///
+fn handle_update_accountable_index(resource_address: &EconomicResourceAddress, new_resource: &EntryData, prev_resource: &EntryData)
+{
+    if new_resource.primary_accountable != prev_resource.primary_accountable {
+        let new_value = if let Some(val) = &new_resource.primary_accountable { vec![val.to_owned()] } else { vec![] };
+        let prev_value = if let Some(val) = &prev_resource.primary_accountable { vec![val.to_owned()] } else { vec![] };
+        let e = update_index!(
+            economic_resource
+                .primary_accountable(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            agent.inventoried_economic_resources(resource_address));
+        hdk::prelude::debug!("update_inventory_from_event::primary_accountable index {:?}", e);
+    }
+}
+
+/// Reindex the `current_location` of a resource if it was modified by an event (eg. `move`)
+///
+fn handle_update_location_index(resource_address: &EconomicResourceAddress, new_resource: &EntryData, prev_resource: &EntryData)
+{
+    if new_resource.current_location != prev_resource.current_location {
+        let new_value = if let Some(val) = &new_resource.current_location { vec![val.to_owned()] } else { vec![] };
+        let prev_value = if let Some(val) = &prev_resource.current_location { vec![val.to_owned()] } else { vec![] };
+        let e = update_index!(
+            economic_resource
+                .current_location(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            location.economic_resources(resource_address));
+        hdk::prelude::debug!("update_inventory_from_event::current_location index {:?}", e);
+    }
+}
+
+/// Reindex the `stage` & `state` of a resource if it was modified by an event (eg. `produce`, `pass` or `fail`)
+///
+fn handle_update_inspection_indexes(resource_address: &EconomicResourceAddress, new_resource: &EntryData, prev_resource: &EntryData) -> RecordAPIResult<()>
+{
+    if new_resource.stage != prev_resource.stage {
+        let new_value = if let Some(val) = &new_resource.stage { vec![val.to_owned()] } else { vec![] };
+        let prev_value = if let Some(val) = &prev_resource.stage { vec![val.to_owned()] } else { vec![] };
+        let e = update_index!(
+            economic_resource
+                .stage(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            process_specification.staged_resources(resource_address));
+        hdk::prelude::debug!("update_inventory_from_event::stage index {:?}", e);
+    }
+    if new_resource.state != prev_resource.state {
+        if let Some(state) = &prev_resource.state {
+            unlink_resource_state(resource_address, state)?;
+        }
+        if let Some(state) = &new_resource.state {
+            link_resource_state(resource_address, state)?;
+        }
+    }
+    Ok(())
+}
+
+/// Anchor path for locating resources by their current inspection state.
+///
+/// :NOTE: states are `ActionId`s rather than record addresses, and so cannot be managed by semantic indexes.
+///
+fn resource_state_path(state: &ActionId) -> RecordAPIResult<Path>
+{
+    Ok(Path::from(format!("economic_resource.state.{}", state.as_ref())))
+}
+
+fn link_resource_state(resource_address: &EconomicResourceAddress, state: &ActionId) -> RecordAPIResult<()>
+{
+    let resource_hash: &EntryHash = resource_address.as_ref();
+    create_link(resource_state_path(state)?.path_entry_hash()?, resource_hash.to_owned(), LinkTypes::ResourceState, LinkTag::new(vec![]))?;
+    Ok(())
+}
+
+fn unlink_resource_state(resource_address: &EconomicResourceAddress, state: &ActionId) -> RecordAPIResult<()>
+{
+    let resource_hash: &EntryHash = resource_address.as_ref();
+    for link in get_links(resource_state_path(state)?.path_entry_hash()?, LinkTypes::ResourceState, None)? {
+        let target: EntryHash = link.target.to_owned().into();
+        if target == *resource_hash {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read the addresses of all resources currently in the given inspection state
+pub fn get_resources_by_state(state: &ActionId) -> RecordAPIResult<Vec<EconomicResourceAddress>>
+{
+    let dna_hash = dna_info()?.hash;
+    Ok(get_links(resource_state_path(state)?.path_entry_hash()?, LinkTypes::ResourceState, None)?
+        .into_iter()
+        .map(|link| EconomicResourceAddress::new(dna_hash.to_owned(), link.target.into()))
+        .collect())
+}
+
+/// Determine the stage resources output by an event are at, from the `ProcessSpecification` of its `output_of` process
+///
+fn get_process_stage(output_of: &MaybeUndefined<ProcessAddress>) -> RecordAPIResult<Option<ProcessSpecificationAddress>>
+{
+    match output_of {
+        MaybeUndefined::Some(process_address) => {
+            let (_meta, _base_address, process) = read_record_entry::<ProcessData, ProcessStorage, _>(process_address.as_ref())?;
+            Ok(process.based_on)
+        },
+        _ => Ok(None),
+    }
+}
+
+/// For events reversing another event, determine the custody of a resource with any changes made by
+/// the reversed event undone. Revisions are followed back from `revision_id` to find the inventory
+/// update made by the reversed event, and changes made by later events are retained.
+///
+/// Returns `None` for other events, and where the reversed event did not update the resource (eg. it created it).
+///
+fn read_custody_before_reversed_event(revision_id: &ActionHash, event: &EventCreateRequest) -> RecordAPIResult<Option<ResourceCustody>>
+{
+    let reversed_event = match (event.reversal, &event.corrects) {
+        (true, MaybeUndefined::Some(reversed_event)) => reversed_event,
+        _ => return Ok(None),
+    };
+
+    let (_meta, _base_address, current) = read_record_entry_by_action::<EntryData, EntryStorage, _>(revision_id)?;
+    let mut revision_id = revision_id.to_owned();
+    loop {
+        let (meta, _base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+        let previous_revision_id = match meta.action() {
+            Action::Update(Update { original_action_address, .. }) => original_action_address.to_owned(),
+            _ => return Ok(None),
+        };
+        if entry.last_event.as_ref() == Some(reversed_event) {
+            let previous = read_revision_before_event(&previous_revision_id, reversed_event)?;
+            return Ok(Some(ResourceCustody {
+                primary_accountable: if entry.primary_accountable != previous.primary_accountable { previous.primary_accountable } else { current.primary_accountable },
+                current_location: if entry.current_location != previous.current_location { previous.current_location } else { current.current_location },
+            }));
+        }
+        revision_id = previous_revision_id;
+    }
+}
+
+/// Read the latest revision of a resource prior to any updates made by `event`, starting from `revision_id`.
+/// Events which update both sides of a resource (eg. `transfer` to itself) make multiple updates.
+fn read_revision_before_event(revision_id: &ActionHash, event: &EconomicEventAddress) -> RecordAPIResult<EntryData>
+{
+    let mut revision_id = revision_id.to_owned();
+    loop {
+        let (meta, _base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+        match (entry.last_event.as_ref() == Some(event), meta.action()) {
+            (true, Action::Update(Update { original_action_address, .. })) => {
+                revision_id = original_action_address.to_owned();
+            },
+            _ => return Ok(entry),
+        }
+    }
+}
+
 fn handle_update_inventory_resource(
     resource_addr: &ActionHash,
-    event: EventCreateRequest,
+    event: InventoryUpdatePayload,
 ) -> RecordAPIResult<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>
 {
     Ok(update_record(resource_addr, event)?)
@@ -198,18 +629,15 @@ fn handle_update_inventory_resource(
 pub fn construct_response<'a>(
     address: &EconomicResourceAddress, meta: &SignedActionHashed, e: &EntryData, (
         contained_in,
-        stage,
-        state,
         contains,
      ): (
         Option<EconomicResourceAddress>,
-        Option<ProcessSpecificationAddress>,
-        Option<ActionId>,
         Vec<EconomicResourceAddress>,
     ),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
-        economic_resource: construct_response_record(address, meta, e, (contained_in, stage, state, contains))?
+        economic_resource: construct_response_record(address, meta, e, (contained_in, contains))?,
+        indexing_report: None,
     })
 }
 
@@ -217,13 +645,9 @@ pub fn construct_response<'a>(
 pub fn construct_response_record<'a>(
     address: &EconomicResourceAddress, meta: &SignedActionHashed, e: &EntryData, (
         contained_in,
-        stage,
-        state,
         contains,
      ): (
         Option<EconomicResourceAddress>,
-        Option<ProcessSpecificationAddress>,
-        Option<ActionId>,
         Vec<EconomicResourceAddress>,
     ),
 ) -> RecordAPIResult<Response> {
@@ -241,8 +665,8 @@ pub fn construct_response_record<'a>(
         accounting_quantity: e.accounting_quantity.to_owned(),
         onhand_quantity: e.onhand_quantity.to_owned(),
         unit_of_effort: e.unit_of_effort.to_owned(),
-        stage: stage.to_owned(),
-        state: state.to_owned(),
+        stage: e.stage.to_owned(),
+        state: e.state.to_owned(),
         current_location: e.current_location.to_owned(),
         note: e.note.to_owned(),
         primary_accountable: e.primary_accountable.to_owned(),
@@ -261,85 +685,57 @@ fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
 // @see construct_response
 pub fn get_link_fields(resource: &EconomicResourceAddress) -> RecordAPIResult<(
     Option<EconomicResourceAddress>,
-    Option<ProcessSpecificationAddress>,
-    Option<ActionId>,
     Vec<EconomicResourceAddress>,
 )> {
     Ok((
         read_index!(economic_resource(resource).contained_in)?.pop(),
-        get_resource_stage(resource)?,
-        get_resource_state(resource)?,
         read_index!(economic_resource(resource).contains)?,
     ))
 }
 
-fn get_resource_state(resource: &EconomicResourceAddress) -> RecordAPIResult<Option<ActionId>>
-{
-    let events: Vec<EconomicEventAddress> = get_affecting_events(resource)?;
-
-    // grab the most recent "pass" or "fail" action
-    Ok(events.iter()
-        .fold(None, move |result, event| {
-            // already found it, just fall through
-            // :TODO: figure out the Rust STL method to abort on first Some() value
-            if let Some(_) = result {
-                return result;
-            }
-
-            let evt = read_record_entry::<EventData, EventStorage, _>(event.as_ref());
-            match evt {
-                Err(_) => result, // :TODO: this indicates some data integrity error
-                Ok((_, _, entry)) => {
-                    match &*String::from(entry.action.clone()) {
-                        "pass" | "fail" => Some(entry.action),  // found it! Return this as the current resource state.
-                        _ => result,    // still not located, keep looking...
-                    }
-                },
-            }
-        })
-    )
-}
-
-fn get_resource_stage(resource: &EconomicResourceAddress) -> RecordAPIResult<Option<ProcessSpecificationAddress>>
-{
-    let events: Vec<EconomicEventAddress> = get_affecting_events(resource)?;
-
-    // grab the most recent event with a process output association
-    Ok(events.iter()
-        .fold(None, move |result, event| {
-            // already found it, just fall through
-            // :TODO: figure out the Rust STL method to abort on first Some() value
-            if let Some(_) = result {
-                return result;
-            }
-
-            let evt = read_record_entry::<EventData, EventStorage, _>(event.as_ref());
-            match evt {
-                Err(_) => result, // :TODO: this indicates some data integrity error
-                Ok((_, _, entry)) => {
-                    match &entry.output_of {
-                        Some(output_of) => {
-                            // get the associated process
-                            let maybe_process_entry = read_record_entry::<ProcessData, ProcessStorage, _>(output_of.as_ref());
-                            // check to see if it has an associated specification
-                            match &maybe_process_entry {
-                                Ok((_,_, process_entry)) => match &process_entry.based_on {
-                                    Some(based_on) => Some(based_on.to_owned()),   // found it!
-                                    None => result, // still not located, keep looking...
-                                },
-                                Err(_) => result, // :TODO: this indicates some data integrity error
-                            }
-                        },
-                        None => result,    // still not located, keep looking...
-                    }
-                },
-            }
-        })
-    )
-}
-
 /// Read all the EconomicEvents affecting a given EconomicResource
 fn get_affecting_events(resource: &EconomicResourceAddress) -> RecordAPIResult<Vec<EconomicEventAddress>>
 {
     read_index!(economic_resource(resource).affected_by)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(s).unwrap()
+    }
+
+    fn created(s: &str) -> Timestamp {
+        Timestamp::from_micros(time(s).timestamp_micros())
+    }
+
+    #[test]
+    fn test_undated_items_ordered_by_creation_time() {
+        let ordered = order_by_time_occurred(vec![
+            (Some(time("2019-11-20T00:00:00Z")), created("2019-11-01T00:00:00Z"), "dated late"),
+            (None, created("2019-11-19T00:00:00Z"), "undated"),
+            (Some(time("2019-11-18T00:00:00Z")), created("2019-11-02T00:00:00Z"), "dated early"),
+            (Some(time("2019-11-19T00:00:00Z")), created("2019-11-03T00:00:00Z"), "dated with undated"),
+        ]);
+
+        assert_eq!(ordered.iter().map(|(_, item)| *item).collect::<Vec<_>>(), vec!["dated early", "dated with undated", "undated", "dated late"]);
+        assert_eq!(ordered[2].0, time("2019-11-19T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_undated_items_included_by_creation_time() {
+        let ordered = order_by_time_occurred(vec![
+            (None, created("2019-11-17T00:00:00Z"), "undated creation"),
+            (Some(time("2019-11-18T00:00:00Z")), created("2019-11-18T00:00:00Z"), "dated early"),
+            (None, created("2019-11-21T00:00:00Z"), "undated late"),
+            (Some(time("2019-11-20T00:00:00Z")), created("2019-11-22T00:00:00Z"), "dated late"),
+        ]);
+
+        assert_eq!(occurred_until(ordered.clone(), &time("2019-11-16T00:00:00Z")), Vec::<&str>::new());
+        assert_eq!(occurred_until(ordered.clone(), &time("2019-11-19T00:00:00Z")), vec!["undated creation", "dated early"]);
+        assert_eq!(occurred_until(ordered.clone(), &time("2019-11-20T00:00:00Z")), vec!["undated creation", "dated early", "dated late"]);
+        assert_eq!(occurred_until(ordered, &time("2019-11-21T00:00:00Z")), vec!["undated creation", "dated early", "dated late", "undated late"]);
+    }
+}
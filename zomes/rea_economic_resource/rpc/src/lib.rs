@@ -9,8 +9,9 @@
 use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
+use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAddress, ByRevision, RecordMeta, RevisionMeta,
+    ActionHash, ByAddress, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     EconomicResourceAddress,
     EconomicEventAddress,
     ExternalURL,
@@ -19,6 +20,9 @@ pub use vf_attributes_hdk::{
     UnitId,
     ProductBatchAddress,
     AgentAddress,
+    ProcessSpecificationAddress,
+    ActionId,
+    DateTime, FixedOffset,
 };
 
 use hc_zome_rea_economic_event_rpc::{
@@ -56,6 +60,49 @@ impl<'a> CreationPayload {
             resource: self.resource,
         }
     }
+
+    pub fn with_stage(self, stage: Option<ProcessSpecificationAddress>) -> CreationPayload {
+        CreationPayload {
+            event: self.event.with_stage(stage),
+            resource: self.resource,
+        }
+    }
+}
+
+/// Payload for updating the inventories affected by an event which has already been recorded
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryUpdatePayload {
+    pub event_address: EconomicEventAddress,
+    pub event: EventCreateRequest,
+    // :SHONK: internal field used when reversing events, holding the custody of the resource before the reversed event
+    #[serde(default)]
+    pub restore_custody: Option<ResourceCustody>,
+}
+
+impl<'a> InventoryUpdatePayload {
+    pub fn with_inventory_type(&self, i: ResourceInventoryType) -> InventoryUpdatePayload {
+        InventoryUpdatePayload {
+            event_address: self.event_address.to_owned(),
+            event: self.event.with_inventory_type(i),
+            restore_custody: self.restore_custody.to_owned(),
+        }
+    }
+
+    pub fn with_restored_custody(&self, custody: Option<ResourceCustody>) -> InventoryUpdatePayload {
+        InventoryUpdatePayload {
+            restore_custody: custody,
+            ..self.to_owned()
+        }
+    }
+}
+
+/// Fields of a resource altered by transfer & move events, which are not restored by negating event quantities
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceCustody {
+    pub primary_accountable: Option<AgentAddress>,
+    pub current_location: Option<LocationAddress>,
 }
 
 //---------------- UPDATE REQUEST ----------------
@@ -72,6 +119,8 @@ pub struct UpdateRequest {
     #[serde(default)]
     pub contained_in: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    pub lot: MaybeUndefined<ProductBatchAddress>,
+    #[serde(default)]
     pub unit_of_effort: MaybeUndefined<UnitId>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
@@ -93,6 +142,86 @@ pub struct UpdateParams {
     pub resource: UpdateRequest,
 }
 
+// used to resolve concurrent updates to an EconomicResource
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    // every live branch head of the resource's revision history
+    pub revision_ids: Vec<ActionHash>,
+    #[serde(default)]
+    pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    pub image: MaybeUndefined<ExternalURL>,
+    #[serde(default)]
+    pub contained_in: MaybeUndefined<EconomicResourceAddress>,
+    #[serde(default)]
+    pub lot: MaybeUndefined<ProductBatchAddress>,
+    #[serde(default)]
+    pub unit_of_effort: MaybeUndefined<UnitId>,
+    #[serde(default)]
+    pub note: MaybeUndefined<String>,
+}
+
+impl MergeRequest {
+    /// Manual edits to apply to the merged revision, which supersedes `revision_id`
+    pub fn as_update(&self, revision_id: ActionHash) -> UpdateRequest {
+        UpdateRequest {
+            revision_id,
+            classified_as: self.classified_as.to_owned(),
+            image: self.image.to_owned(),
+            contained_in: self.contained_in.to_owned(),
+            lot: self.lot.to_owned(),
+            unit_of_effort: self.unit_of_effort.to_owned(),
+            note: self.note.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeParams {
+    pub resource: MergeRequest,
+}
+
+//---------------- INVENTORY RECONCILIATION ----------------
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecomputeParams {
+    pub address: EconomicResourceAddress,
+    // if set, any discrepancy found is corrected by writing a new revision of the resource
+    #[serde(default)]
+    pub write: bool,
+}
+
+/// Result of replaying the event history of an `EconomicResource` against its stored quantities
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecomputeResponse {
+    pub economic_resource: EconomicResourceAddress,
+    pub events_replayed: usize,
+    pub stored_accounting_quantity: Option<QuantityValue>,
+    pub stored_onhand_quantity: Option<QuantityValue>,
+    pub computed_accounting_quantity: Option<QuantityValue>,
+    pub computed_onhand_quantity: Option<QuantityValue>,
+    pub consistent: bool,
+    // revision written to correct the stored quantities, if any
+    pub revision_id: Option<ActionHash>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtTimeParams {
+    pub address: EconomicResourceAddress,
+    pub at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ByState {
+    pub state: ActionId,
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
@@ -101,6 +230,9 @@ pub struct QueryParams {
     pub contains: Option<EconomicResourceAddress>,
     pub contained_in: Option<EconomicResourceAddress>,
     pub conforms_to: Option<ResourceSpecificationAddress>,
+    pub lot: Option<ProductBatchAddress>,
     pub affected_by: Option<EconomicEventAddress>,
     pub primary_accountable: Option<AgentAddress>,
+    pub current_location: Option<LocationAddress>,
+    pub stage: Option<ProcessSpecificationAddress>,
 }
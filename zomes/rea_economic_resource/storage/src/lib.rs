@@ -1,4 +1,5 @@
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 /**
  * hREA 'economic resource' zome internal data structures
  *
@@ -14,23 +15,27 @@ use hdk_records::{
     MaybeUndefined, OtherCellResult,
     generate_record_entry,
     record_interface::Updateable,
+    authorization::RecordParticipants,
     rpc::call_zome_method,
 };
 
 use vf_measurement::*;
 use vf_attributes_hdk::{
     EconomicResourceAddress,
+    EconomicEventAddress,
     ExternalURL,
     LocationAddress,
     ResourceSpecificationAddress,
     UnitId,
     ProductBatchAddress,
+    ProcessSpecificationAddress,
     ActionId,
     AgentAddress,
 };
-use vf_actions::{ ActionEffect, ActionInventoryEffect};
+use vf_actions::{ ActionEffect, ActionInventoryEffect, resolve_action };
 pub use vf_actions::get_builtin_action;
 use hc_zome_rea_resource_specification_rpc::{ResponseData as ResourceSpecificationResponseData, Response as ResourceSpecificationResponse};
+use hc_zome_rea_unit_rpc::{ResponseData as UnitResponseData, GetUnitRequest};
 
 use hc_zome_rea_economic_resource_rpc::*;
 use hc_zome_rea_economic_event_rpc::{
@@ -54,6 +59,10 @@ pub struct EconomicResourceZomeConfig {
     pub index_zome: String,
     pub resource_specification_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub location_index_zome: Option<String>,
+    pub product_batch_index_zome: Option<String>,
+    #[serde(default)]
+    pub process_specification_index_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -73,6 +82,15 @@ pub struct EntryData {
     pub contained_in: Option<EconomicResourceAddress>,
     pub note: Option<String>,
     pub primary_accountable: Option<AgentAddress>,
+    // `ProcessSpecification` of the last process the resource was output from
+    #[serde(default)]
+    pub stage: Option<ProcessSpecificationAddress>,
+    // result of the last `pass` or `fail` inspection of the resource
+    #[serde(default)]
+    pub state: Option<ActionId>,
+    // event which caused the latest inventory update, checked when validating updates by agents other than the resource's owners
+    #[serde(default)]
+    pub last_event: Option<EconomicEventAddress>,
     pub _nonce: Bytes,
 }
 
@@ -85,8 +103,53 @@ impl EntryData {
     }
 }
 
+impl EntryData {
+    /// Copy of this resource with quantities reset to zero, as the starting state for replaying its event history
+    pub fn with_zeroed_quantities(&self) -> EntryData {
+        let zeroed = |qty: &Option<QuantityValue>| qty.as_ref().map(|q| QuantityValue::new(Decimal::zero(), q.get_unit()));
+        EntryData {
+            accounting_quantity: zeroed(&self.accounting_quantity),
+            onhand_quantity: zeroed(&self.onhand_quantity),
+            ..self.to_owned()
+        }
+    }
+
+    /// Whether this revision differs from `previous` only in the quantities and location of the resource,
+    /// as updated by a newly referenced event.
+    ///
+    /// Custody, classification, stage and state are excluded, since they determine who controls a
+    /// resource and how it may be used.
+    ///
+    pub fn is_inventory_update_of(&self, previous: &EntryData) -> bool {
+        self.last_event.is_some()
+            && self.last_event != previous.last_event
+            && self.name == previous.name
+            && self.conforms_to == previous.conforms_to
+            && self.classified_as == previous.classified_as
+            && self.tracking_identifier == previous.tracking_identifier
+            && self.lot == previous.lot
+            && self.image == previous.image
+            && self.unit_of_effort == previous.unit_of_effort
+            && self.contained_in == previous.contained_in
+            && self.note == previous.note
+            && self.primary_accountable == previous.primary_accountable
+            && self.stage == previous.stage
+            && self.state == previous.state
+            && self._nonce == previous._nonce
+    }
+}
+
 generate_record_entry!(EntryData, EconomicResourceAddress, EntryStorage);
 
+/// Agents named as the primary accountable agent may modify the record alongside its author
+impl RecordParticipants for EntryData {
+    fn get_participants(&self) -> Vec<EntryHash> {
+        self.primary_accountable.iter()
+            .map(|agent| agent.1.to_owned())
+            .collect()
+    }
+}
+
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
 #[hdk_entry_defs(skip_hdk_extern = true)]
@@ -94,7 +157,9 @@ generate_record_entry!(EntryData, EconomicResourceAddress, EntryStorage);
 pub enum EntryTypes {
     EconomicResource(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -112,12 +177,24 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // anchors resources by their current inspection `state`
+    ResourceState,
+    // registers the Agent record held by an agent, @see hdk_records::authorization
+    AgentRegistration,
 }
 
 //---------------- CREATE ----------------
@@ -160,7 +237,7 @@ impl TryFrom<CreationPayload> for EntryData {
             accounting_quantity: match quantity_value.clone() {
                 Some(resource_quantity) => update_quantity(
                     // instantiate with the correct units
-                    Some(QuantityValue::new(0.0, resource_quantity.get_unit())), 
+                    Some(QuantityValue::new(Decimal::zero(), resource_quantity.get_unit())), 
                     MaybeUndefined::Some(resource_quantity),
                     &e.action,
                     ResourceValueType::AccountingValue,
@@ -174,7 +251,7 @@ impl TryFrom<CreationPayload> for EntryData {
             onhand_quantity: match quantity_value {
                 Some(resource_quantity) => update_quantity(
                     // instantiate with the correct units
-                    Some(QuantityValue::new(0.0, resource_quantity.get_unit())),
+                    Some(QuantityValue::new(Decimal::zero(), resource_quantity.get_unit())),
                     MaybeUndefined::Some(resource_quantity),
                     &e.action,
                     ResourceValueType::OnhandValue,
@@ -189,7 +266,10 @@ impl TryFrom<CreationPayload> for EntryData {
             current_location: if r.current_location == MaybeUndefined::Undefined { None } else { r.current_location.to_owned().to_option() },
             contained_in: if r.contained_in == MaybeUndefined::Undefined { None } else { r.contained_in.to_owned().to_option() },
             note: if r.note == MaybeUndefined::Undefined { None } else { r.note.clone().into() },
-            primary_accountable: if action_id == produce_action.id || action_id == raise_action.id || action_id == lower_action.id { Some(e.receiver) } else { None },
+            primary_accountable: if action_id == produce_action.id || action_id == raise_action.id || action_id == lower_action.id { Some(e.receiver.to_owned()) } else { None },
+            stage: e.target_stage.to_owned(),
+            state: get_inspection_state(&e),
+            last_event: None,
             _nonce: random_bytes(32)?,
         })
     }
@@ -256,6 +336,21 @@ fn get_resource_specification(specification_id: ResourceSpecificationAddress) ->
     }
 }
 
+/// Load unit conversion information from the DNA which the unit is defined in
+fn get_unit_definition(unit_id: &UnitId) -> RecordAPIResult<Unit> {
+    let unit_data: OtherCellResult<UnitResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        unit_id,
+        &String::from("read_unit"),
+        GetUnitRequest { id: unit_id.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+
+    match unit_data {
+        Ok(unit_response) => Ok(unit_response.unit.to_unit()),
+        Err(e) => Err(e.into()),
+    }
+}
+
 //---------------- UPDATE ----------------
 
 /// Handles update operations for correcting data entry errors
@@ -266,7 +361,7 @@ impl Updateable<UpdateRequest> for EntryData {
             conforms_to: self.conforms_to.to_owned(),
             classified_as: if e.classified_as == MaybeUndefined::Undefined { self.classified_as.to_owned() } else { e.classified_as.to_owned().to_option() },
             tracking_identifier: self.tracking_identifier.to_owned(),
-            lot: self.lot.to_owned(),
+            lot: if e.lot == MaybeUndefined::Undefined { self.lot.to_owned() } else { e.lot.to_owned().to_option() },
             image: if e.image == MaybeUndefined::Undefined { self.image.to_owned() } else { e.image.to_owned().to_option() },
             accounting_quantity: self.accounting_quantity.to_owned(),
             onhand_quantity: self.onhand_quantity.to_owned(),
@@ -275,11 +370,48 @@ impl Updateable<UpdateRequest> for EntryData {
             contained_in: if e.contained_in == MaybeUndefined::Undefined { self.contained_in.to_owned() } else { e.contained_in.to_owned().to_option() },
             note: if e.note == MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().to_option() },
             primary_accountable: self.primary_accountable.to_owned(),
+            stage: self.stage.to_owned(),
+            state: self.state.to_owned(),
+            last_event: self.last_event.to_owned(),
             _nonce: self._nonce.to_owned(),
         })
     }
 }
 
+/// Overwrites stored quantities with values recomputed from a resource's event history
+#[derive(Clone, Debug)]
+pub struct QuantityCorrection {
+    pub accounting_quantity: Option<QuantityValue>,
+    pub onhand_quantity: Option<QuantityValue>,
+}
+
+impl Updateable<QuantityCorrection> for EntryData {
+    fn update_with(&self, e: QuantityCorrection) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            accounting_quantity: e.accounting_quantity,
+            onhand_quantity: e.onhand_quantity,
+            ..self.to_owned()
+        })
+    }
+}
+
+/// Resolves diverged branches of a resource's revision history.
+///
+/// Manual edits are applied as for a regular update. Quantities are always taken from a replay
+/// of the resource's full event history, since each branch will only reflect a subset of events.
+///
+#[derive(Clone, Debug)]
+pub struct ResourceMerge {
+    pub edits: UpdateRequest,
+    pub quantities: QuantityCorrection,
+}
+
+impl Updateable<ResourceMerge> for EntryData {
+    fn update_with(&self, e: ResourceMerge) -> RecordAPIResult<EntryData> {
+        self.update_with(e.edits)?.update_with(e.quantities)
+    }
+}
+
 /// Handle update operations by observed events
 ///
 /// :WARNING: we presume the event has already been determined to relate to the
@@ -347,11 +479,40 @@ impl Updateable<EventCreateRequest> for EntryData {
             } else {
                 self.primary_accountable.to_owned()
             },
+            stage: e.target_stage.to_owned().or(self.stage.to_owned()),
+            state: get_inspection_state(&e).or(self.state.to_owned()),
+            last_event: self.last_event.to_owned(),
             _nonce: self._nonce.to_owned(),
         })
     }
 }
 
+/// Handle inventory updates by a recorded event, referencing the event from the updated resource.
+/// Reversing events restore the custody of the resource prior to the event they reverse.
+impl Updateable<InventoryUpdatePayload> for EntryData {
+    fn update_with(&self, e: InventoryUpdatePayload) -> RecordAPIResult<EntryData> {
+        let updated = self.update_with(e.event)?;
+        let (primary_accountable, current_location) = match e.restore_custody {
+            Some(custody) => (custody.primary_accountable, custody.current_location),
+            None => (updated.primary_accountable.to_owned(), updated.current_location.to_owned()),
+        };
+        Ok(EntryData {
+            last_event: Some(e.event_address),
+            primary_accountable,
+            current_location,
+            ..updated
+        })
+    }
+}
+
+/// Resources take on the action of `pass` and `fail` events as their current state
+fn get_inspection_state(e: &EventCreateRequest) -> Option<ActionId> {
+    match e.get_action() {
+        "pass" | "fail" => Some(e.action.to_owned()),
+        _ => None,
+    }
+}
+
 /// Encapsulates the logic for updating EconomicResource quantities in response to event triggers
 fn update_quantity(
     current_val: Option<QuantityValue>,
@@ -369,12 +530,27 @@ fn update_quantity(
     let current = current_val.unwrap();
     let event_qty = event_val.unwrap();
 
-    let action_to_perform = get_event_action(action, which_qty_type, which_inventory_type);
+    let action_to_perform = get_event_action(action, which_qty_type, which_inventory_type)?;
 
     match action_to_perform {
         ActionInventoryEffect::NoEffect => Ok(Some(current)),
-        ActionInventoryEffect::Increment => Ok(Some(add(current, event_qty)?)),
-        ActionInventoryEffect::Decrement => Ok(Some(subtract(current, event_qty)?)),
+        ActionInventoryEffect::Increment => {
+            let event_qty = to_resource_unit(&current, event_qty)?;
+            Ok(Some(add(current, event_qty)?))
+        },
+        ActionInventoryEffect::Decrement => {
+            let event_qty = to_resource_unit(&current, event_qty)?;
+            Ok(Some(subtract(current, event_qty)?))
+        },
+    }
+}
+
+/// Express event quantities in the resource's unit where they differ
+fn to_resource_unit(current: &QuantityValue, event_qty: QuantityValue) -> RecordAPIResult<QuantityValue> {
+    match (current.get_unit(), event_qty.get_unit()) {
+        (Some(resource_unit), Some(event_unit)) if resource_unit != event_unit =>
+            convert(event_qty, &resource_unit, get_unit_definition),
+        _ => Ok(event_qty),
     }
 }
 
@@ -390,17 +566,15 @@ fn get_event_action(
     action: &ActionId,
     which_qty_type: ResourceValueType,
     which_inventory_type: ResourceInventoryType,
-) -> ActionInventoryEffect {
-    let action_str: &str = (*action).as_ref();
-    
-    match get_builtin_action(action_str) {
+) -> RecordAPIResult<ActionInventoryEffect> {
+    match resolve_action(action)? {
         // just work from the configured effect and reverse for the receiver
         Some(action_obj) => {
             let action_effect = match which_qty_type {
                 ResourceValueType::AccountingValue => action_obj.accounting_effect,
                 ResourceValueType::OnhandValue => action_obj.onhand_effect
             };
-            match which_inventory_type {
+            Ok(match which_inventory_type {
                 ResourceInventoryType::ProvidingInventory => match action_effect {
                     ActionEffect::DecrementIncrement => ActionInventoryEffect::Decrement,
                     ActionEffect::NoEffect => ActionInventoryEffect::NoEffect,
@@ -413,12 +587,8 @@ fn get_event_action(
                     ActionEffect::Increment => ActionInventoryEffect::Decrement,
                     ActionEffect::Decrement => ActionInventoryEffect::Increment,
                 },
-            }
+            })
         },
-        None => {
-            let mut err_string: String = "unknown EconomicEvent action type: ".to_string();
-            err_string.push_str(action.as_ref());
-            panic!("{:?}", err_string);
-        }
+        None => Err(DataIntegrityError::InvalidAction(format!("unknown EconomicEvent action type: {}", action.as_ref()))),
     }
 }
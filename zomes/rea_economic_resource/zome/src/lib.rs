@@ -13,5 +13,10 @@ use hc_zome_rea_economic_resource_zome_api::*;
 use hc_zome_rea_economic_resource_lib::*;
 use hc_zome_rea_economic_resource_rpc::*;
 use hc_zome_rea_economic_resource_storage::*;
+use hdk_records::{declare_pending_sync_zome_api, declare_agent_registration_zome_api};
 
 declare_economic_resource_zome_api!(EconomicResourceZomePermissableDefault);
+
+declare_pending_sync_zome_api!();
+
+declare_agent_registration_zome_api!(LinkTypes::AgentRegistration);
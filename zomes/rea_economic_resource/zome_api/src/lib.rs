@@ -13,11 +13,16 @@ pub trait API {
 
     fn create_inventory_from_event(resource_entry_def_id: Self::S, params: CreationPayload) -> RecordAPIResult<(SignedActionHashed, EconomicResourceAddress, EntryData)>;
     fn update_inventory_from_event(
-        event: EventCreateRequest,
+        params: InventoryUpdatePayload,
     ) -> RecordAPIResult<Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>>;
     fn get_economic_resource(address: EconomicResourceAddress) -> RecordAPIResult<ResponseData>;
+    fn get_economic_resource_at_time(address: EconomicResourceAddress, at: DateTime<FixedOffset>) -> RecordAPIResult<ResponseData>;
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>;
+    fn get_economic_resource_revisions(address: EconomicResourceAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>;
     fn update_economic_resource(resource: UpdateRequest) -> RecordAPIResult<ResponseData>;
+    fn merge_economic_resource(resource: MergeRequest) -> RecordAPIResult<ResponseData>;
+    fn get_economic_resources_by_state(state: ActionId) -> RecordAPIResult<Vec<EconomicResourceAddress>>;
+    fn recompute_economic_resource(address: EconomicResourceAddress, write: bool) -> RecordAPIResult<RecomputeResponse>;
 }
 
 /// Macro to programatically and predictably bind an `API` implementation to a
@@ -41,9 +46,9 @@ macro_rules! declare_economic_resource_zome_api {
         }
 
         #[hdk_extern]
-        fn _internal_update_inventory(event: EventCreateRequest) -> ExternResult<Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>>
+        fn _internal_update_inventory(params: InventoryUpdatePayload) -> ExternResult<Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>>
         {
-            Ok(<$zome_api>::update_inventory_from_event(event)?)
+            Ok(<$zome_api>::update_inventory_from_event(params)?)
         }
 
         #[hdk_extern]
@@ -51,14 +56,45 @@ macro_rules! declare_economic_resource_zome_api {
             Ok(<$zome_api>::get_economic_resource(address)?)
         }
 
+        // read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+        #[hdk_extern]
+        fn __internal_get_economic_resource_summary(params: ByAddress<EconomicResourceAddress>) -> ExternResult<$crate::ResponseData> {
+            hdk_records::metadata::with_summary_metadata(|| get_economic_resource(params))
+        }
+
+        #[hdk_extern]
+        fn get_economic_resource_at_time(AtTimeParams { address, at }: AtTimeParams) -> ExternResult<$crate::ResponseData> {
+            Ok(<$zome_api>::get_economic_resource_at_time(address, at)?)
+        }
+
         #[hdk_extern]
         fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<$crate::ResponseData> {
             Ok(<$zome_api>::get_revision(revision_id)?)
         }
 
+        #[hdk_extern]
+        fn get_economic_resource_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<EconomicResourceAddress>) -> ExternResult<RevisionHistoryPage> {
+            Ok(<$zome_api>::get_economic_resource_revisions(address, first, after)?)
+        }
+
         #[hdk_extern]
         fn update_economic_resource(UpdateParams { resource }: UpdateParams) -> ExternResult<$crate::ResponseData> {
             Ok(<$zome_api>::update_economic_resource(resource)?)
         }
+
+        #[hdk_extern]
+        fn merge_economic_resource(MergeParams { resource }: MergeParams) -> ExternResult<$crate::ResponseData> {
+            Ok(<$zome_api>::merge_economic_resource(resource)?)
+        }
+
+        #[hdk_extern]
+        fn get_economic_resources_by_state(ByState { state }: ByState) -> ExternResult<Vec<EconomicResourceAddress>> {
+            Ok(<$zome_api>::get_economic_resources_by_state(state)?)
+        }
+
+        #[hdk_extern]
+        fn recompute_economic_resource(RecomputeParams { address, write }: RecomputeParams) -> ExternResult<RecomputeResponse> {
+            Ok(<$zome_api>::recompute_economic_resource(address, write)?)
+        }
     };
 }
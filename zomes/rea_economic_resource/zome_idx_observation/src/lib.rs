@@ -16,8 +16,11 @@ struct EconomicResource {
     contains: Local<economic_resource, contained_in>,
     contained_in: Local<economic_resource, contains>,
     conforms_to: Local<resource_specification, conforming_resources>,
+    lot: Local<product_batch, economic_resources>,
+    stage: Local<process_specification, staged_resources>,
 
     // internal indexes (not part of REA spec)
     affected_by: Local<economic_event, affects>,
     primary_accountable: Local<agent, inventoried_economic_events>,
+    current_location: Local<location, economic_resources>,
 }
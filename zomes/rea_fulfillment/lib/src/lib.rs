@@ -29,6 +29,7 @@ pub fn construct_response(address: &FulfillmentAddress, meta: &SignedActionHashe
             resource_quantity: e.resource_quantity.to_owned(),
             effort_quantity: e.effort_quantity.to_owned(),
             note: e.note.to_owned(),
-        }
+        },
+        indexing_report: None,
     })
 }
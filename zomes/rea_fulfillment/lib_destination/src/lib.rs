@@ -14,10 +14,13 @@ use hdk_records::{
     RecordAPIResult,
     records::{
         create_record,
+        read_existing_record,
         read_record_entry,
         read_record_entry_by_action,
-        update_record,
+        read_record_revisions,
+        update_record_once,
         delete_record,
+        is_revision_deleted,
     },
 };
 use hdk_semantic_indexes_client_lib::*;
@@ -35,6 +38,12 @@ fn read_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
 pub fn handle_create_fulfillment<S>(entry_def_id: S, fulfillment: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if let Some((meta, fulfillment_address, entry)) = read_existing_record::<EntryData, EntryStorage, _, _, _>(fulfillment.to_owned())? {
+        return with_indexing_report!(construct_response(&fulfillment_address, &meta, &entry));
+    }
+
     let (meta, fulfillment_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, fulfillment.to_owned())?;
 
     // link entries in the local DNA
@@ -43,7 +52,7 @@ pub fn handle_create_fulfillment<S>(entry_def_id: S, fulfillment: CreateRequest)
 
     // :TODO: figure out if necessary/desirable to do bidirectional bridging between observation and other planning DNAs
 
-    construct_response(&fulfillment_address, &meta, &entry_resp)
+    with_indexing_report!(construct_response(&fulfillment_address, &meta, &entry_resp))
 }
 
 pub fn handle_get_fulfillment(address: FulfillmentAddress) -> RecordAPIResult<ResponseData>
@@ -58,9 +67,15 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry)
 }
 
+pub fn handle_get_fulfillment_revisions(address: FulfillmentAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
-    let (meta, base_address, new_entry, prev_entry): (_, FulfillmentAddress, EntryData, EntryData) = update_record(&fulfillment.get_revision_id(), fulfillment.to_owned())?;
+    clear_indexing_report();
+    let (meta, base_address, new_entry, prev_entry): (_, FulfillmentAddress, EntryData, EntryData) = update_record_once(&fulfillment.get_revision_id(), fulfillment.to_owned())?;
 
     if new_entry.fulfilled_by != prev_entry.fulfilled_by {
         let e = update_index!(
@@ -72,11 +87,18 @@ pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<
         hdk::prelude::debug!("handle_update_fulfillment::fulfilled_by index (destination) {:?}", e);
     }
 
-    construct_response(&base_address, &meta, &new_entry)
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
 }
 
-pub fn handle_delete_fulfillment(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_fulfillment<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if is_revision_deleted(&revision_id)? {
+        return Ok(DeleteResponse { deleted: true, indexing_report: None });
+    }
+
     // read any referencing indexes
     let (_meta, base_address, fulfillment) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
@@ -84,7 +106,8 @@ pub fn handle_delete_fulfillment(revision_id: ActionHash) -> RecordAPIResult<boo
     let e = update_index!(fulfillment.fulfilled_by.not(&vec![fulfillment.fulfilled_by]), economic_event.fulfills(&base_address));
     hdk::prelude::debug!("handle_delete_fulfillment::fulfilled_by index (destination) {:?}", e);
 
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Properties accessor for zome config.
@@ -17,10 +17,11 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
         delete_record,
     },
-    rpc::call_zome_method,
+    rpc::{call_zome_method_or_queue, call_local_zome_method},
 };
 use hdk_semantic_indexes_client_lib::*;
 
@@ -29,6 +30,7 @@ use hc_zome_rea_fulfillment_storage::*;
 use hc_zome_rea_fulfillment_rpc::*;
 
 use hc_zome_rea_fulfillment_lib::construct_response;
+use hc_zome_rea_commitment_rpc::ResponseData as CommitmentResponse;
 
 
 
@@ -40,6 +42,7 @@ fn read_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
 pub fn handle_create_fulfillment<S>(entry_def_id: S, fulfillment: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, fulfillment_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, fulfillment.to_owned())?;
 
     // link entries in the local DNA
@@ -48,7 +51,7 @@ pub fn handle_create_fulfillment<S>(entry_def_id: S, fulfillment: CreateRequest)
 
     // :TODO: report any error
     // update in the associated foreign DNA as well
-    let pingback: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+    let pingback: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
         fulfillment.get_fulfilled_by(),
         &REPLICATE_CREATE_API_METHOD,
         CreateParams { fulfillment: CreateRequest {
@@ -59,11 +62,15 @@ pub fn handle_create_fulfillment<S>(entry_def_id: S, fulfillment: CreateRequest)
             note: entry_resp.note.to_owned().into(),
             nonce: MaybeUndefined::Some(entry_resp._nonce.to_owned()),
         } },
+        Some(fulfillment_address.to_string()),
         LinkTypes::AvailableCapability
     );
-    hdk::prelude::debug!("handle_create_fulfillment::call_zome_method::{:?} {:?}", REPLICATE_CREATE_API_METHOD, pingback);
+    hdk::prelude::debug!("handle_create_fulfillment::call_zome_method_or_queue::{:?} {:?}", REPLICATE_CREATE_API_METHOD, pingback);
 
-    construct_response(&fulfillment_address, &meta, &entry_resp)
+    let r = refresh_commitment_finished(&entry_resp.fulfills);
+    hdk::prelude::debug!("handle_create_fulfillment::refresh_commitment_finished {:?}", r);
+
+    with_indexing_report!(construct_response(&fulfillment_address, &meta, &entry_resp))
 }
 
 pub fn handle_get_fulfillment(address: FulfillmentAddress) -> RecordAPIResult<ResponseData>
@@ -78,8 +85,14 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry)
 }
 
+pub fn handle_get_fulfillment_revisions(address: FulfillmentAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let (meta, base_address, new_entry, prev_entry): (_, FulfillmentAddress, EntryData, EntryData) = update_record(&fulfillment.get_revision_id(), fulfillment.to_owned())?;
 
     // update commitment indexes in local DNA
@@ -87,7 +100,7 @@ pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<
         let e = update_index!(
             fulfillment
                 .fulfills(&vec![new_entry.fulfills.clone()])
-                .not(&vec![prev_entry.fulfills]),
+                .not(&vec![prev_entry.fulfills.clone()]),
             commitment.fulfilled_by(&base_address)
         );
         hdk::prelude::debug!("handle_update_fulfillment::fulfills index (origin) {:?}", e);
@@ -95,39 +108,66 @@ pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<
 
     // update fulfillment records in remote DNA (and by proxy, event indexes in remote DNA)
     if new_entry.fulfilled_by != prev_entry.fulfilled_by {
-        let pingback: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        let pingback: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
             // :TODO: update to intelligently call remote DNAs if new & old target record are not in same network
             &prev_entry.fulfilled_by,
             &REPLICATE_UPDATE_API_METHOD,
             UpdateParams { fulfillment: fulfillment.to_owned() },
+            Some(base_address.to_string()),
             LinkTypes::AvailableCapability
         );
         // :TODO: report any error
-        hdk::prelude::debug!("handle_update_fulfillment::call_zome_method::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, pingback);
+        hdk::prelude::debug!("handle_update_fulfillment::call_zome_method_or_queue::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, pingback);
     }
 
-    construct_response(&base_address, &meta, &new_entry)
+    // fulfilled quantities may have changed on either the old or new commitment
+    let r = refresh_commitment_finished(&new_entry.fulfills);
+    hdk::prelude::debug!("handle_update_fulfillment::refresh_commitment_finished {:?}", r);
+    if new_entry.fulfills != prev_entry.fulfills {
+        let r = refresh_commitment_finished(&prev_entry.fulfills);
+        hdk::prelude::debug!("handle_update_fulfillment::refresh_commitment_finished (previous) {:?}", r);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
 }
 
-pub fn handle_delete_fulfillment(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_fulfillment<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
     // update commitment indexes in local DNA
-    let e = update_index!(fulfillment.fulfills.not(&vec![entry.fulfills]), commitment.fulfilled_by(&base_address));
+    let e = update_index!(fulfillment.fulfills.not(&vec![entry.fulfills.clone()]), commitment.fulfilled_by(&base_address));
     hdk::prelude::debug!("handle_delete_fulfillment::fulfills index (origin) {:?}", e);
 
     // update fulfillment records in remote DNA (and by proxy, event indexes in remote DNA)
-    let pingback: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+    let pingback: OtherCellResult<DeleteResponse> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
         &entry.fulfilled_by,
         &REPLICATE_DELETE_API_METHOD,
         ByAction { address: revision_id.to_owned() },
+        Some(base_address.to_string()),
         LinkTypes::AvailableCapability
     );
     // :TODO: report any error
-    hdk::prelude::debug!("handle_delete_fulfillment::call_zome_method::{:?} {:?}", REPLICATE_DELETE_API_METHOD, pingback);
+    hdk::prelude::debug!("handle_delete_fulfillment::call_zome_method_or_queue::{:?} {:?}", REPLICATE_DELETE_API_METHOD, pingback);
 
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+
+    let r = refresh_commitment_finished(&entry.fulfills);
+    hdk::prelude::debug!("handle_delete_fulfillment::refresh_commitment_finished {:?}", r);
+
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Notify the `Commitment` zome that fulfilled quantities have changed, so that it
+/// may mark the commitment as finished if `auto_finish` is enabled.
+fn refresh_commitment_finished(commitment: &CommitmentAddress) -> OtherCellResult<CommitmentResponse> {
+    call_local_zome_method(
+        read_commitment_zome,
+        "_internal_refresh_finished",
+        ByAddress { address: commitment.to_owned() },
+    )
 }
 
 /// Properties accessor for zome config.
@@ -135,6 +175,11 @@ fn read_commitment_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
     Some(conf.fulfillment.commitment_index_zome)
 }
 
+/// Properties accessor for zome config.
+fn read_commitment_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    conf.fulfillment.commitment_zome
+}
+
 /// Properties accessor for zome config.
 fn read_fulfillment_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
     Some(conf.fulfillment.index_zome)
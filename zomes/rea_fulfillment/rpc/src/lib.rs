@@ -12,7 +12,8 @@ use serde_bytes::ByteBuf;
 use serde_maybe_undefined::{MaybeUndefined};
 use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAction, ByAddress, ByRevision, RecordMeta, RevisionMeta,
+    IndexingReport, DeleteResponse,
+    ActionHash, ByAction, ByAddress, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     EconomicEventAddress,
     CommitmentAddress,
 };
@@ -62,6 +63,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub fulfillment: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -9,6 +9,7 @@
 use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     MaybeUndefined,
@@ -43,6 +44,8 @@ pub struct DnaConfigSliceObservation {
 pub struct FulfillmentZomeConfigPlanning {
     pub commitment_index_zome: String,
     pub index_zome: String,
+    #[serde(default)]
+    pub commitment_zome: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
@@ -73,7 +76,9 @@ generate_record_entry!(EntryData, FulfillmentAddress, EntryStorage);
 pub enum EntryTypes {
     Fulfillment(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -91,6 +96,14 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
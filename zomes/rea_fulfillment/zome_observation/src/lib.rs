@@ -10,10 +10,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_fulfillment_lib_destination::*;
 use hc_zome_rea_fulfillment_rpc::*;
 use hc_zome_rea_fulfillment_storage_consts::*;
+use hc_zome_rea_fulfillment_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[hdk_extern]
 fn fulfillment_created(CreateParams { fulfillment }: CreateParams) -> ExternResult<ResponseData> {
@@ -25,17 +28,30 @@ fn get_fulfillment(ByAddress { address }: ByAddress<FulfillmentAddress>) -> Exte
     Ok(handle_get_fulfillment(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_fulfillment_summary(params: ByAddress<FulfillmentAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_fulfillment(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_fulfillment_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<FulfillmentAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_fulfillment_revisions(address, first, after)?)
+}
+
 #[hdk_extern]
 fn fulfillment_updated(UpdateParams { fulfillment }: UpdateParams) -> ExternResult<ResponseData> {
     Ok(handle_update_fulfillment(fulfillment)?)
 }
 
 #[hdk_extern]
-fn fulfillment_deleted(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_fulfillment(revision_id)?)
+fn fulfillment_deleted(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_fulfillment(FULFILLMENT_ENTRY_TYPE, revision_id)?)
 }
+
+declare_pending_sync_zome_api!();
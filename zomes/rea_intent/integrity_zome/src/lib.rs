@@ -8,8 +8,13 @@
  * @package hREA
  */
 use hdi::prelude::*;
+use hdk_records::authorization::{
+    AuthorOnly,
+    validate_update_authorization, validate_delete_authorization,
+    validate_agent_registration,
+};
 use hc_zome_rea_intent_storage::{
-    EntryStorage, EntryTypes, EntryTypesUnit, Identified, LinkTypes,
+    EntryData, EntryStorage, EntryTypes, EntryTypesUnit, Identified, LinkTypes,
 };
 
 #[hdk_extern]
@@ -36,10 +41,10 @@ fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
     match op {
         Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
         Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
-        Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterCreateLink(RegisterCreateLink { create_link, .. }) => validate_agent_registration(&create_link.hashed.content),
         Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
-        Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
-        Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterUpdate(RegisterUpdate { update, .. }) => validate_update_authorization::<AuthorOnly, _, _>(&update.hashed.content, parse_entry),
+        Op::RegisterDelete(RegisterDelete { delete, .. }) => validate_delete_authorization::<AuthorOnly, _, _>(&delete.hashed.content, parse_entry),
         Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
     }
 }
@@ -48,12 +53,17 @@ fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
     match EntryStorage::try_from(&entry) {
         Ok(event_storage) => {
             let record = event_storage.entry();
+            let action_result = record.validate_action()?;
             record
                 .validate_or_fields()
-                .and_then(|()| record.validate_action())
+                .and_then(|()| action_result)
                 .and_then(|()| Ok(ValidateCallbackResult::Valid))
                 .or_else(|e| Ok(ValidateCallbackResult::Invalid(e)))
         }
         _ => Ok(ValidateCallbackResult::Valid),
     }
 }
+
+fn parse_entry(entry: &Entry) -> Option<EntryData> {
+    EntryStorage::try_from(entry).ok().map(|s| s.entry())
+}
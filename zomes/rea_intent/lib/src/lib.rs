@@ -6,22 +6,39 @@
  *
  * @package hREA
  */
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryInto;
 use paste::paste;
 use hdk_records::{
-    RecordAPIResult, MaybeUndefined, SignedActionHashed,
+    DataIntegrityError, RecordAPIResult, MaybeUndefined, SignedActionHashed, OtherCellResult,
+    dna_info,
+    rpc::call_zome_method,
     records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
+        merge_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
 };
 use hdk_semantic_indexes_client_lib::*;
 
+use vf_measurement::{QuantityValue, Unit, sum_converted, remaining};
+
 use hc_zome_rea_intent_storage::*;
 use hc_zome_rea_intent_rpc::*;
+use hc_zome_rea_satisfaction_storage::{
+    EntryData as SatisfactionData,
+    EntryStorage as SatisfactionStorage,
+};
+use hc_zome_rea_unit_rpc::{ResponseData as UnitResponseData, GetUnitRequest, UnitId};
+use hc_zome_rea_economic_event_rpc::{ResponseData as EventResponseData, ByAddress};
+use hc_zome_rea_satisfaction_rpc::EventOrCommitmentAddress;
+use hdk::prelude::DnaHash;
 
 /// properties accessor for zome config
 fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
@@ -31,6 +48,7 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_intent<S>(entry_def_id: S, intent: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, intent.to_owned())?;
 
     // handle link fields
@@ -46,13 +64,17 @@ pub fn handle_create_intent<S>(entry_def_id: S, intent: CreateRequest) -> Record
         let e = create_index!(intent.input_of(input_of), process.intended_inputs(&base_address));
         hdk::prelude::debug!("handle_create_intent::input_of index {:?}", e);
     };
+    if let CreateRequest { at_location: MaybeUndefined::Some(at_location), .. } = &intent {
+        let e = create_index!(intent.at_location(at_location), location.intents(&base_address));
+        hdk::prelude::debug!("handle_create_intent::at_location index {:?}", e);
+    };
     if let CreateRequest { output_of: MaybeUndefined::Some(output_of), .. } = &intent {
         let e = create_index!(intent.output_of(output_of), process.intended_outputs(&base_address));
         hdk::prelude::debug!("handle_create_intent::output_of index {:?}", e);
     };
 
     // return entire record structure
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 pub fn handle_get_intent(address: IntentAddress) -> RecordAPIResult<ResponseData>
@@ -61,18 +83,51 @@ pub fn handle_get_intent(address: IntentAddress) -> RecordAPIResult<ResponseData
     construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
 }
 
+/// Read an intent for display in a list of records, omitting fields which are costly to compute
+pub fn handle_get_intent_summary(address: IntentAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    build_response(&base_address, &meta, &entry, get_link_fields(&address)?, (None, None, None))
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_intent_revisions(address: IntentAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let address = intent.get_revision_id().to_owned();
     let (meta, base_address, new_entry, prev_entry): (_, IntentAddress, EntryData, EntryData) = update_record(&address, intent.to_owned())?;
 
-    // handle link fields
+    update_intent_indexes(&base_address, &new_entry, &prev_entry)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+/// Resolve conflicting revisions of an intent, caused by concurrent updates from different agents.
+///
+/// All live branch heads must be named. Edits in the request are applied to the most recent head.
+///
+pub fn handle_merge_intent(intent: MergeRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_id = intent.revision_ids.first().ok_or(DataIntegrityError::EntryNotFound)?.to_owned();
+    let (meta, base_address, new_entry, prev_entry): (_, IntentAddress, EntryData, EntryData) = merge_record(intent.revision_ids.as_slice(), intent.as_update(revision_id))?;
+
+    update_intent_indexes(&base_address, &new_entry, &prev_entry)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+/// Update link fields after a record has been revised or merged
+fn update_intent_indexes(base_address: &IntentAddress, new_entry: &EntryData, prev_entry: &EntryData) -> RecordAPIResult<()>
+{
     if new_entry.provider != prev_entry.provider {
         let new_value = match &new_entry.provider { Some(val) => vec![val.to_owned()], None => vec![] };
         let prev_value = match &prev_entry.provider { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -80,7 +135,7 @@ pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseDa
             intent
                 .provider(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            agent.intents_as_provider(&base_address)
+            agent.intents_as_provider(base_address)
         )?;
     }
     if new_entry.receiver != prev_entry.receiver {
@@ -90,7 +145,7 @@ pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseDa
             intent
                 .receiver(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            agent.intents_as_receiver(&base_address)
+            agent.intents_as_receiver(base_address)
         )?;
     }
     if new_entry.input_of != prev_entry.input_of {
@@ -100,9 +155,20 @@ pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseDa
             intent
                 .input_of(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            process.intended_inputs(&base_address)
+            process.intended_inputs(base_address)
+        );
+        hdk::prelude::debug!("update_intent_indexes::input_of index {:?}", e);
+    }
+    if new_entry.at_location != prev_entry.at_location {
+        let new_value = match &new_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            intent
+                .at_location(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            location.intents(base_address)
         );
-        hdk::prelude::debug!("handle_update_intent::input_of index {:?}", e);
+        hdk::prelude::debug!("update_intent_indexes::at_location index {:?}", e);
     }
     if new_entry.output_of != prev_entry.output_of {
         let new_value = match &new_entry.output_of { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -111,16 +177,40 @@ pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseDa
             intent
                 .output_of(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            process.intended_outputs(&base_address)
+            process.intended_outputs(base_address)
         );
-        hdk::prelude::debug!("handle_update_intent::output_of index {:?}", e);
+        hdk::prelude::debug!("update_intent_indexes::output_of index {:?}", e);
+    }
+    Ok(())
+}
+
+/// Mark an intent as `finished` once its `resource_quantity` has been fully satisfied.
+///
+/// Called by the `Satisfaction` zome whenever satisfactions change. Has no effect unless
+/// enabled via the `intent.auto_finish` DNA property.
+///
+pub fn handle_refresh_finished(address: IntentAddress) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    let link_fields = get_link_fields(&base_address)?;
+    let conf: DnaConfigSlice = dna_info()?.properties.try_into()?;
+
+    if conf.intent.auto_finish && !entry.finished {
+        let (_satisfied, remaining, _error) = get_satisfied_quantities(&entry, &link_fields.0)?;
+        if remaining.map_or(false, |qty| qty.get_numerical_value().is_zero()) {
+            let (meta, base_address, new_entry, _prev_entry): (_, IntentAddress, EntryData, EntryData) = update_record(meta.as_hash(), MarkFinished)?;
+            return with_indexing_report!(construct_response(&base_address, &meta, &new_entry, link_fields));
+        }
     }
 
-    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry, link_fields))
 }
 
-pub fn handle_delete_intent(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_intent<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     // load the record to ensure it is of the correct type
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
@@ -142,19 +232,36 @@ pub fn handle_delete_intent(revision_id: ActionHash) -> RecordAPIResult<bool>
         hdk::prelude::debug!("handle_delete_intent::receiver index {:?}", e);
     }
 
+    if let Some(location_address) = entry.at_location {
+        let e = update_index!(intent.at_location.not(&vec![location_address]), location.intents(&base_address));
+        hdk::prelude::debug!("handle_delete_intent::at_location index {:?}", e);
+    }
+
     // delete entry last, as it must be present in order for links to be removed
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
 pub fn construct_response<'a>(
+    address: &IntentAddress, meta: &SignedActionHashed, e: &EntryData, link_fields: (
+        Vec<SatisfactionAddress>,
+        // Vec<ProposedIntentAddress>
+    )
+) -> RecordAPIResult<ResponseData> {
+    let quantities = get_satisfied_quantities(e, &link_fields.0)?;
+    build_response(address, meta, e, link_fields, quantities)
+}
+
+fn build_response<'a>(
     address: &IntentAddress, meta: &SignedActionHashed, e: &EntryData, (
         satisfactions,
         // published_in,
     ): (
         Vec<SatisfactionAddress>,
         // Vec<ProposedIntentAddress>
-    )
+    ),
+    (satisfied_quantity, remaining_quantity, satisfied_quantity_error): (Option<QuantityValue>, Option<QuantityValue>, Option<String>),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
         intent: Response {
@@ -181,10 +288,14 @@ pub fn construct_response<'a>(
             at_location: e.at_location.to_owned(),
             agreed_in: e.agreed_in.to_owned(),
             finished: e.finished.to_owned(),
+            satisfied_quantity,
+            remaining_quantity,
+            satisfied_quantity_error,
             in_scope_of: e.in_scope_of.to_owned(),
             satisfied_by: satisfactions.to_owned(),
             // published_in: published_in.to_owned(),
-        }
+        },
+        indexing_report: None,
     })
 }
 
@@ -205,6 +316,11 @@ fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.intent.agent_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_location_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.intent.location_index_zome
+}
+
 // @see construct_response
 pub fn get_link_fields(intent: &IntentAddress) -> RecordAPIResult<(
     Vec<SatisfactionAddress>,
@@ -213,3 +329,84 @@ pub fn get_link_fields(intent: &IntentAddress) -> RecordAPIResult<(
         read_index!(intent(intent).satisfied_by)?,
     ))
 }
+
+/// Total the `resource_quantity` of all `Satisfactions` of an intent, and determine how much of its
+/// own `resource_quantity` remains to be satisfied.
+///
+/// Satisfactions recorded in other units are converted to the intent's unit. Where this is not
+/// possible, neither quantity can be determined and the reason is returned in their place.
+///
+/// Satisfactions by events which have since been corrected are not counted.
+///
+fn get_satisfied_quantities(intent: &EntryData, satisfactions: &[SatisfactionAddress]) -> RecordAPIResult<(Option<QuantityValue>, Option<QuantityValue>, Option<String>)>
+{
+    let planned = match &intent.resource_quantity {
+        Some(qty) => qty,
+        None => return Ok((None, None, None)),
+    };
+
+    let this_dna = dna_info()?.hash;
+    let mut satisfied_quantities = vec![];
+    for satisfaction in satisfactions {
+        let (_meta, _base_address, entry) = read_record_entry::<SatisfactionData, SatisfactionStorage, _>(satisfaction.as_ref())?;
+        // satisfying commitments are local, events are recorded in another DNA
+        let satisfied_by_dna: &DnaHash = entry.satisfied_by.as_ref();
+        if *satisfied_by_dna != this_dna {
+            match is_corrected_event(&entry.satisfied_by) {
+                Ok(true) => continue,
+                Ok(false) => {},
+                Err(e) => return Ok((None, None, Some(e.to_string()))),
+            }
+        }
+        satisfied_quantities.push(entry.resource_quantity);
+    }
+
+    let units = RefCell::new(HashMap::new());
+    let satisfied = match sum_converted(satisfied_quantities.into_iter().flatten(), planned.get_unit(), |unit_id: &UnitId| get_cached_unit_definition(&units, unit_id)) {
+        Ok(qty) => qty,
+        Err(e) => return Ok((None, None, Some(e.to_string()))),
+    };
+    let remaining = remaining(planned.to_owned(), satisfied.to_owned())?;
+
+    Ok((Some(satisfied), Some(remaining), None))
+}
+
+/// Determine whether an event has been corrected, by reading it from the DNA it was recorded in
+fn is_corrected_event(event: &EventOrCommitmentAddress) -> RecordAPIResult<bool> {
+    let event_data: OtherCellResult<EventResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        event,
+        &String::from("read_economic_event"),
+        ByAddress { address: event.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+
+    match event_data {
+        Ok(event_response) => Ok(!event_response.economic_event.corrected_by.is_empty()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Load unit conversion information, reusing definitions already loaded into `cache`
+fn get_cached_unit_definition(cache: &RefCell<HashMap<UnitId, Unit>>, unit_id: &UnitId) -> RecordAPIResult<Unit> {
+    if let Some(unit) = cache.borrow().get(unit_id) {
+        return Ok(unit.to_owned());
+    }
+    let unit = get_unit_definition(unit_id)?;
+    cache.borrow_mut().insert(unit_id.to_owned(), unit.to_owned());
+    Ok(unit)
+}
+
+/// Load unit conversion information from the DNA which the unit is defined in
+fn get_unit_definition(unit_id: &UnitId) -> RecordAPIResult<Unit> {
+    let unit_data: OtherCellResult<UnitResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        unit_id,
+        &String::from("read_unit"),
+        GetUnitRequest { id: unit_id.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+
+    match unit_data {
+        Ok(unit_response) => Ok(unit_response.unit.to_unit()),
+        Err(e) => Err(e.into()),
+    }
+}
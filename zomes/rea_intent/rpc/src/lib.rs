@@ -11,6 +11,7 @@ use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::{MaybeUndefined, default_false};
 use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
+    IndexingReport, DeleteResponse,
     ActionId,
     ExternalURL,
     DateTime, FixedOffset,
@@ -21,7 +22,7 @@ pub use vf_attributes_hdk::{
     SatisfactionAddress,
     LocationAddress,
     ProposedIntentAddress,
-    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
 };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
@@ -77,6 +78,15 @@ pub struct Response {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub in_scope_of: Option<Vec<String>>,
     pub finished: bool,
+    // total `resource_quantity` of all linked `Satisfactions`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub satisfied_quantity: Option<QuantityValue>,
+    // portion of `resource_quantity` not yet satisfied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_quantity: Option<QuantityValue>,
+    // reason `satisfied_quantity` and `remaining_quantity` could not be determined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub satisfied_quantity_error: Option<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub satisfied_by: Vec<SatisfactionAddress>,
@@ -92,6 +102,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub intent: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -208,6 +220,86 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+// used to resolve concurrent updates to an Intent
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    // every live branch head of the record's revision history
+    pub revision_ids: Vec<ActionHash>,
+    #[serde(default)]
+    pub action: MaybeUndefined<ActionId>,
+    #[serde(default)]
+    pub note: MaybeUndefined<String>,
+    #[serde(default)]
+    pub image: MaybeUndefined<ExternalURL>,
+    #[serde(default)]
+    pub input_of: MaybeUndefined<ProcessAddress>,
+    #[serde(default)]
+    pub output_of: MaybeUndefined<ProcessAddress>,
+    #[serde(default)]
+    pub provider: MaybeUndefined<AgentAddress>,
+    #[serde(default)]
+    pub receiver: MaybeUndefined<AgentAddress>,
+    #[serde(default)]
+    pub resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
+    #[serde(default)]
+    pub resource_classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    pub resource_conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
+    #[serde(default)]
+    pub resource_quantity: MaybeUndefined<QuantityValue>,
+    #[serde(default)]
+    pub effort_quantity: MaybeUndefined<QuantityValue>,
+    #[serde(default)]
+    pub available_quantity: MaybeUndefined<QuantityValue>,
+    #[serde(default)]
+    pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub has_point_in_time: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub due: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub at_location: MaybeUndefined<LocationAddress>,
+    #[serde(default)]
+    pub agreed_in: MaybeUndefined<ExternalURL>,
+    #[serde(default)]
+    pub finished: MaybeUndefined<bool>,
+    #[serde(default)]
+    pub in_scope_of: MaybeUndefined<Vec<String>>,
+}
+
+impl MergeRequest {
+    /// Manual edits to apply to the merged revision, which supersedes `revision_id`
+    pub fn as_update(&self, revision_id: ActionHash) -> UpdateRequest {
+        UpdateRequest {
+            revision_id,
+            action: self.action.to_owned(),
+            note: self.note.to_owned(),
+            image: self.image.to_owned(),
+            input_of: self.input_of.to_owned(),
+            output_of: self.output_of.to_owned(),
+            provider: self.provider.to_owned(),
+            receiver: self.receiver.to_owned(),
+            resource_inventoried_as: self.resource_inventoried_as.to_owned(),
+            resource_classified_as: self.resource_classified_as.to_owned(),
+            resource_conforms_to: self.resource_conforms_to.to_owned(),
+            resource_quantity: self.resource_quantity.to_owned(),
+            effort_quantity: self.effort_quantity.to_owned(),
+            available_quantity: self.available_quantity.to_owned(),
+            has_beginning: self.has_beginning.to_owned(),
+            has_end: self.has_end.to_owned(),
+            has_point_in_time: self.has_point_in_time.to_owned(),
+            due: self.due.to_owned(),
+            at_location: self.at_location.to_owned(),
+            agreed_in: self.agreed_in.to_owned(),
+            finished: self.finished.to_owned(),
+            in_scope_of: self.in_scope_of.to_owned(),
+        }
+    }
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
@@ -219,4 +311,5 @@ pub struct QueryParams {
     pub proposed_in: Option<ProposedIntentAddress>,
     pub provider: Option<AgentAddress>,
     pub receiver: Option<AgentAddress>,
+    pub at_location: Option<LocationAddress>,
 }
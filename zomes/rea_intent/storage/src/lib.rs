@@ -9,10 +9,12 @@
 use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     MaybeUndefined,
     record_interface::Updateable,
+    authorization::RecordParticipants,
     generate_record_entry,
 };
 use vf_measurement::QuantityValue;
@@ -29,7 +31,7 @@ pub use vf_attributes_hdk::{
     ResourceSpecificationAddress,
 };
 
-use vf_actions::{ validate_flow_action };
+use vf_actions::{ validate_flow_action, ensure_action_definition, ActionDefinition };
 
 use hc_zome_rea_intent_rpc::{ CreateRequest, UpdateRequest };
 
@@ -49,6 +51,10 @@ pub struct IntentZomeConfig {
     pub index_zome: String,
     pub process_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub location_index_zome: Option<String>,
+    // when enabled, intents are marked as `finished` once fully satisfied
+    #[serde(default)]
+    pub auto_finish: bool,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -56,6 +62,9 @@ pub struct IntentZomeConfig {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 pub struct EntryData {
     pub action: ActionId,
+    // local copy of any user-defined `action`, @see vf_actions::ensure_action_definition
+    #[serde(default)]
+    pub action_definition: Option<EntryHash>,
     pub provider: Option<AgentAddress>,
     pub receiver: Option<AgentAddress>,
     pub input_of: Option<ProcessAddress>,   // :NOTE: shadows link, see https://github.com/h-REA/hREA/issues/60#issuecomment-553756873
@@ -80,8 +89,14 @@ pub struct EntryData {
 }
 
 impl EntryData {
-    pub fn validate_action(&self) -> Result<(), String> {
-        validate_flow_action(self.action.to_owned(), self.input_of.to_owned(), self.output_of.to_owned())
+    pub fn validate_action(&self) -> ExternResult<Result<(), String>> {
+        validate_flow_action(self.action.to_owned(), self.action_definition.to_owned(), self.input_of.to_owned(), self.output_of.to_owned())
+    }
+
+    /// Resolve user-defined actions and reference a local copy of their definition for validation
+    fn with_action_definition(self) -> RecordAPIResult<EntryData> {
+        let action_definition = ensure_action_definition::<EntryTypes, _, _>(&self.action, self.input_of.to_owned(), self.output_of.to_owned())?;
+        Ok(EntryData { action_definition, ..self })
     }
 
     pub fn validate_or_fields(&self) -> Result<(), String> {
@@ -94,6 +109,15 @@ impl EntryData {
 
 generate_record_entry!(EntryData, IntentAddress, EntryStorage);
 
+/// Agents named as the provider & receiver may modify the record alongside its author
+impl RecordParticipants for EntryData {
+    fn get_participants(&self) -> Vec<EntryHash> {
+        self.provider.iter().chain(self.receiver.iter())
+            .map(|agent| agent.1.to_owned())
+            .collect()
+    }
+}
+
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
 #[hdk_entry_defs(skip_hdk_extern = true)]
@@ -101,7 +125,10 @@ generate_record_entry!(EntryData, IntentAddress, EntryStorage);
 pub enum EntryTypes {
     Intent(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
+    ActionDefinition(ActionDefinition),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -119,12 +146,30 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
+impl TryFrom<ActionDefinition> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: ActionDefinition) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::ActionDefinition(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // registers the Agent record held by an agent, @see hdk_records::authorization
+    AgentRegistration,
 }
 
 
@@ -135,8 +180,9 @@ impl TryFrom<CreateRequest> for EntryData {
     type Error = DataIntegrityError;
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        EntryData {
             action: e.action.to_owned(),
+            action_definition: None,
             note: e.note.to_owned().into(),
             image: e.image.to_owned().into(),
             provider: e.provider.to_owned().into(),
@@ -158,7 +204,7 @@ impl TryFrom<CreateRequest> for EntryData {
             finished: e.finished.to_option().unwrap(),  // :NOTE: unsafe, would crash if not for "default_false" binding via Serde
             in_scope_of: e.in_scope_of.to_owned().into(),
             _nonce: random_bytes(32)?,
-        })
+        }.with_action_definition()
     }
 }
 
@@ -167,8 +213,9 @@ impl TryFrom<CreateRequest> for EntryData {
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        EntryData {
             action: if !e.action.is_some() { self.action.to_owned() } else { e.action.to_owned().unwrap() },
+            action_definition: None,
             provider: if e.provider == MaybeUndefined::Undefined { self.provider.to_owned() } else { e.provider.to_owned().into() },
             receiver: if e.receiver == MaybeUndefined::Undefined { self.receiver.to_owned() } else { e.receiver.to_owned().into() },
             input_of: if e.input_of == MaybeUndefined::Undefined { self.input_of.to_owned() } else { e.input_of.to_owned().into() },
@@ -190,6 +237,18 @@ impl Updateable<UpdateRequest> for EntryData {
             image: if e.image== MaybeUndefined::Undefined { self.image.to_owned() } else { e.image.to_owned().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
             _nonce: self._nonce.to_owned(),
+        }.with_action_definition()
+    }
+}
+
+/// Marks an intent as `finished`, once it has been fully satisfied
+pub struct MarkFinished;
+
+impl Updateable<MarkFinished> for EntryData {
+    fn update_with(&self, _e: MarkFinished) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            finished: true,
+            ..self.to_owned()
         })
     }
 }
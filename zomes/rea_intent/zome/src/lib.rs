@@ -8,10 +8,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_intent_rpc::*;
 use hc_zome_rea_intent_lib::*;
 use hc_zome_rea_intent_storage_consts::*;
+use hc_zome_rea_intent_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::{declare_pending_sync_zome_api, declare_agent_registration_zome_api};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -37,11 +40,22 @@ fn get_intent(ByAddress { address }: ByAddress) -> ExternResult<ResponseData> {
     Ok(handle_get_intent(address)?)
 }
 
+// read API used by index zomes when listing records, @see hc_zome_rea_intent_lib::handle_get_intent_summary
+#[hdk_extern]
+fn __internal_get_intent_summary(ByAddress { address }: ByAddress) -> ExternResult<ResponseData> {
+    Ok(with_summary_metadata(|| handle_get_intent_summary(address))?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_intent_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<IntentAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_intent_revisions(address, first, after)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -53,7 +67,28 @@ fn update_intent(UpdateParams { intent }: UpdateParams) -> ExternResult<Response
     Ok(handle_update_intent(intent)?)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeParams {
+    pub intent: MergeRequest,
+}
+
 #[hdk_extern]
-fn delete_intent(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_intent(revision_id)?)
+fn merge_intent(MergeParams { intent }: MergeParams) -> ExternResult<ResponseData> {
+    Ok(handle_merge_intent(intent)?)
 }
+
+// used by the Satisfaction zome to mark intents as finished once fully satisfied
+#[hdk_extern]
+fn _internal_refresh_finished(ByAddress { address }: ByAddress) -> ExternResult<ResponseData> {
+    Ok(handle_refresh_finished(address)?)
+}
+
+#[hdk_extern]
+fn delete_intent(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_intent(INTENT_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
+
+declare_agent_registration_zome_api!(LinkTypes::AgentRegistration);
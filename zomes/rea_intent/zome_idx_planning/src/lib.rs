@@ -7,7 +7,7 @@
 use hdk_semantic_indexes_zome_derive::index_zome;
 use hc_zome_rea_intent_rpc::*;
 
-#[index_zome]
+#[index_zome(record_read_fn_name="__internal_get_intent_summary")]
 struct Intent {
     satisfied_by: Local<satisfaction, satisfies>,
     input_of: Local<process, intended_inputs>,
@@ -17,4 +17,5 @@ struct Intent {
     // internal indexes (not part of VF spec)
     provider: Local<agent, intents_as_provider>,
     receiver: Local<agent, intents_as_receiver>,
+    at_location: Local<location, intents>,
 }
@@ -0,0 +1,97 @@
+/**
+ * hREA location zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Location` (`SpatialThing`)
+ * data structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::{clear_indexing_report, take_indexing_report, with_indexing_report};
+
+use hc_zome_rea_location_storage::*;
+use hc_zome_rea_location_rpc::*;
+
+pub use hc_zome_rea_location_storage::LOCATION_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.location.index_zome)
+}
+
+pub fn handle_create_location<S>(entry_def_id: S, location: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    clear_indexing_report();
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, location)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp))
+}
+
+pub fn handle_get_location(address: LocationAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_location_revisions(address: LocationAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_location(location: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = location.get_revision_id().clone();
+    let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, location)?;
+    with_indexing_report!(construct_response(&identity_address, &meta, &entry))
+}
+
+/// Removes a location record.
+///
+/// Indexes from referencing records are retained, as the `at_location` / `current_location`
+/// fields of those records continue to reference the location until they are themselves updated.
+///
+pub fn handle_delete_location<S>(entry_def_id: S, address: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &address)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &LocationAddress, meta: &SignedActionHashed, e: &EntryData,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        location: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            name: e.name.to_owned(),
+            lat: e.lat.to_owned(),
+            long: e.long.to_owned(),
+            alt: e.alt.to_owned(),
+            mappable_address: e.mappable_address.to_owned(),
+            note: e.note.to_owned(),
+        },
+        indexing_report: None,
+    })
+}
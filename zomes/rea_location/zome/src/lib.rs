@@ -0,0 +1,71 @@
+/**
+ * hREA Location zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_location_rpc::*;
+use hc_zome_rea_location_lib::*;
+use hc_zome_rea_location_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub location: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_location(CreateParams { location }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_location(LOCATION_ENTRY_TYPE, location)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: LocationAddress,
+}
+
+#[hdk_extern]
+fn get_location(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_location(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_location_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_location(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_location_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<LocationAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_location_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub location: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_location(UpdateParams { location }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_location(location)?)
+}
+
+#[hdk_extern]
+fn delete_location(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_location(LOCATION_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -0,0 +1,16 @@
+/**
+ * Location query indexes for location DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_location_rpc::*;
+
+#[index_zome]
+struct Location {
+    economic_events: Remote<economic_event, at_location>,
+    economic_resources: Remote<economic_resource, current_location>,
+    commitments: Remote<commitment, at_location>,
+    intents: Remote<intent, at_location>,
+}
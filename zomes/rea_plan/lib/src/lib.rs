@@ -8,12 +8,14 @@
  */
 use paste::paste;
 use hdk_records::{
-    RecordAPIResult, SignedActionHashed,
+    DataIntegrityError, RecordAPIResult, SignedActionHashed,
     records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
+        merge_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
@@ -34,8 +36,9 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_plan<S>(entry_def_id: S, plan: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, plan)?;
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 pub fn handle_get_plan(address: PlanAddress) -> RecordAPIResult<ResponseData>
@@ -50,15 +53,37 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_plan_revisions(address: PlanAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_plan(plan: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let revision_hash = plan.get_revision_id().clone();
     let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, plan)?;
-    construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+    with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
 }
 
-pub fn handle_delete_plan(address: ActionHash) -> RecordAPIResult<bool> {
-    delete_record::<EntryStorage>(&address)
+/// Resolve conflicting revisions of a plan, caused by concurrent updates from different agents.
+///
+/// All live branch heads must be named. Edits in the request are applied to the most recent head.
+///
+pub fn handle_merge_plan(plan: MergeRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = plan.revision_ids.first().ok_or(DataIntegrityError::EntryNotFound)?.to_owned();
+    let (meta, identity_address, entry, _prev_entry): (_, PlanAddress, EntryData, EntryData) = merge_record(plan.revision_ids.as_slice(), plan.as_update(revision_hash))?;
+    with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
+}
+
+pub fn handle_delete_plan<S>(entry_def_id: S, address: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &address)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -83,7 +108,8 @@ fn construct_response<'a>(
             deletable: e.deletable.to_owned(),
             processes: processes.to_owned(),
             independent_demands: independent_demands.to_owned(),
-        }
+        },
+        indexing_report: None,
     })
 }
 
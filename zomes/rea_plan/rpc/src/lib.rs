@@ -10,13 +10,14 @@ use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
+    IndexingReport, DeleteResponse,
     PlanAddress,
     CommitmentAddress,
     ProcessAddress,
     EconomicEventAddress,
     DateTime,
     FixedOffset,
-    ByAction, ActionHash, ByRevision, RecordMeta, RevisionMeta,
+    ByAction, ActionHash, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
 };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
@@ -55,6 +56,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub plan: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -126,6 +129,43 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+// used to resolve concurrent updates to a Plan
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    // every live branch head of the record's revision history
+    pub revision_ids: Vec<ActionHash>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub name: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub created: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub due: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub note: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub deletable: MaybeUndefined<bool>,
+}
+
+impl MergeRequest {
+    /// Manual edits to apply to the merged revision, which supersedes `revision_id`
+    pub fn as_update(&self, revision_id: ActionHash) -> UpdateRequest {
+        UpdateRequest {
+            revision_id,
+            name: self.name.to_owned(),
+            created: self.created.to_owned(),
+            due: self.due.to_owned(),
+            note: self.note.to_owned(),
+            deletable: self.deletable.to_owned(),
+        }
+    }
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
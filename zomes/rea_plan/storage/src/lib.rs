@@ -9,6 +9,7 @@
 use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     generate_record_entry,
@@ -62,7 +63,9 @@ generate_record_entry!(EntryData, PlanAddress, EntryStorage);
 pub enum EntryTypes {
     Plan(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -80,6 +83,14 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
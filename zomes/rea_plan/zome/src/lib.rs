@@ -8,9 +8,12 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_plan_rpc::*;
 use hc_zome_rea_plan_lib::*;
+use hc_zome_rea_plan_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -33,11 +36,22 @@ fn get_plan(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
     Ok(handle_get_plan(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_plan_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_plan(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_plan_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<PlanAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_plan_revisions(address, first, after)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -49,7 +63,20 @@ fn update_plan(UpdateParams { plan }: UpdateParams) -> ExternResult<ResponseData
     Ok(handle_update_plan(plan)?)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeParams {
+    pub plan: MergeRequest,
+}
+
 #[hdk_extern]
-fn delete_plan(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_plan(revision_id)?)
+fn merge_plan(MergeParams { plan }: MergeParams) -> ExternResult<ResponseData> {
+    Ok(handle_merge_plan(plan)?)
 }
+
+#[hdk_extern]
+fn delete_plan(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_plan(PLAN_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
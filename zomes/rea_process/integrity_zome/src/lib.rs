@@ -8,7 +8,11 @@
  * @package hREA
  */
 use hdi::prelude::*;
-pub use hc_zome_rea_process_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::{
+    authorization::AuthorOnly,
+    declare_record_modification_validation,
+};
+pub use hc_zome_rea_process_storage::{EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -27,4 +31,6 @@ pub fn __num_entry_types() -> u8 {
 #[no_mangle]
 pub fn __num_link_types() -> u8 {
     LinkTypes::len()
-}
\ No newline at end of file
+}
+
+declare_record_modification_validation!(EntryStorage, AuthorOnly);
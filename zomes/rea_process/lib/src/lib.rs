@@ -8,12 +8,14 @@
  */
 use paste::paste;
 use hdk_records::{
-    RecordAPIResult,
+    DataIntegrityError, RecordAPIResult,
     records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
+        merge_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
@@ -33,6 +35,7 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_process<S>(entry_def_id: S, process: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, process.to_owned())?;
 
     // handle link fields
@@ -43,7 +46,7 @@ pub fn handle_create_process<S>(entry_def_id: S, process: CreateRequest) -> Reco
     };
 
     // :TODO: pass results from link creation rather than re-reading
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 pub fn handle_get_process(address: ProcessAddress) -> RecordAPIResult<ResponseData>
@@ -58,12 +61,38 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_process_revisions(address: ProcessAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_process(process: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let address = process.get_revision_id().clone();
     let (meta, base_address, new_entry, prev_entry): (_,_, EntryData, EntryData) = update_record(&address, process)?;
 
-    // handle link fields
+    update_process_indexes(&base_address, &new_entry, &prev_entry)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+/// Resolve conflicting revisions of a process, caused by concurrent updates from different agents.
+///
+/// All live branch heads must be named. Edits in the request are applied to the most recent head.
+///
+pub fn handle_merge_process(process: MergeRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_id = process.revision_ids.first().ok_or(DataIntegrityError::EntryNotFound)?.to_owned();
+    let (meta, base_address, new_entry, prev_entry): (_, ProcessAddress, EntryData, EntryData) = merge_record(process.revision_ids.as_slice(), process.as_update(revision_id))?;
+
+    update_process_indexes(&base_address, &new_entry, &prev_entry)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+/// Update link fields after a record has been revised or merged
+fn update_process_indexes(base_address: &ProcessAddress, new_entry: &EntryData, prev_entry: &EntryData) -> RecordAPIResult<()>
+{
     if new_entry.planned_within != prev_entry.planned_within {
         let new_value = match &new_entry.planned_within { Some(val) => vec![val.to_owned()], None => vec![] };
         let prev_value = match &prev_entry.planned_within { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -71,15 +100,17 @@ pub fn handle_update_process(process: UpdateRequest) -> RecordAPIResult<Response
             process
                 .planned_within(new_value.as_slice())
                 .not(prev_value.as_slice()),
-            plan.processes(&base_address)
+            plan.processes(base_address)
         );
-        hdk::prelude::debug!("handle_update_process::planned_within index {:?}", e);
+        hdk::prelude::debug!("update_process_indexes::planned_within index {:?}", e);
     }
-    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+    Ok(())
 }
 
-pub fn handle_delete_process(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_process<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     // load the record to ensure it is of the correct type
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
@@ -89,7 +120,8 @@ pub fn handle_delete_process(revision_id: ActionHash) -> RecordAPIResult<bool>
         hdk::prelude::debug!("handle_delete_process::planned_within index {:?}", e);
     }
 
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -144,7 +176,8 @@ fn construct_response<'a>(
             working_agents: working_agents.to_owned(),
             trace: trace.to_owned(),
             track: track.to_owned(),
-        }
+        },
+        indexing_report: None,
     })
 }
 
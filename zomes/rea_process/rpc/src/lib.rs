@@ -8,7 +8,8 @@ use serde_maybe_undefined::{
     default_false,
 };
 pub use vf_attributes_hdk::{
-    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    IndexingReport, DeleteResponse,
+    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     ProcessAddress,
     DateTime, FixedOffset,
     ExternalURL,
@@ -95,6 +96,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub process: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -169,6 +172,56 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+// used to resolve concurrent updates to a Process
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    // every live branch head of the record's revision history
+    pub revision_ids: Vec<ActionHash>,
+    #[serde(default)]
+    pub name: MaybeUndefined<String>,
+    #[serde(default)]
+    pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub before: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub after: MaybeUndefined<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    pub based_on: MaybeUndefined<ProcessSpecificationAddress>,
+    #[serde(default)]
+    pub planned_within: MaybeUndefined<PlanAddress>,
+    #[serde(default)]
+    pub finished: MaybeUndefined<bool>,
+    #[serde(default)]
+    pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub note: MaybeUndefined<String>,
+}
+
+impl MergeRequest {
+    /// Manual edits to apply to the merged revision, which supersedes `revision_id`
+    pub fn as_update(&self, revision_id: ActionHash) -> UpdateRequest {
+        UpdateRequest {
+            revision_id,
+            name: self.name.to_owned(),
+            has_beginning: self.has_beginning.to_owned(),
+            has_end: self.has_end.to_owned(),
+            before: self.before.to_owned(),
+            after: self.after.to_owned(),
+            classified_as: self.classified_as.to_owned(),
+            based_on: self.based_on.to_owned(),
+            planned_within: self.planned_within.to_owned(),
+            finished: self.finished.to_owned(),
+            in_scope_of: self.in_scope_of.to_owned(),
+            note: self.note.to_owned(),
+        }
+    }
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
@@ -8,10 +8,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_process_storage_consts::*;
 use hc_zome_rea_process_lib::*;
 use hc_zome_rea_process_rpc::*;
+use hc_zome_rea_process_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,11 +37,22 @@ fn get_process(ReadParams { address }: ReadParams) -> ExternResult<ResponseData>
     Ok(handle_get_process(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_process_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_process(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_process_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<ProcessAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_process_revisions(address, first, after)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -50,7 +64,20 @@ fn update_process(UpdateParams { process }: UpdateParams) -> ExternResult<Respon
     Ok(handle_update_process(process)?)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeParams {
+    pub process: MergeRequest,
+}
+
 #[hdk_extern]
-fn delete_process(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_process(revision_id)?)
+fn merge_process(MergeParams { process }: MergeParams) -> ExternResult<ResponseData> {
+    Ok(handle_merge_process(process)?)
 }
+
+#[hdk_extern]
+fn delete_process(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_process(PROCESS_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
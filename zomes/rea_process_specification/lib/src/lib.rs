@@ -12,11 +12,13 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
 };
+use hdk_semantic_indexes_client_lib::{clear_indexing_report, take_indexing_report, with_indexing_report};
 
 use hc_zome_rea_process_specification_storage::*;
 use hc_zome_rea_process_specification_rpc::*;
@@ -29,9 +31,10 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_process_specification<S>(entry_def_id: S, process_specification: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, process_specification)?;
 
-    construct_response(&base_address, &meta, &entry_resp)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp))
 }
 
 pub fn handle_get_process_specification(address: ProcessSpecificationAddress) -> RecordAPIResult<ResponseData>
@@ -46,16 +49,25 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry)
 }
 
+pub fn handle_get_process_specification_revisions(address: ProcessSpecificationAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_process_specification(process_specification: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let old_revision = process_specification.get_revision_id();
     let (meta, base_address, new_entry, _prev_entry): (_, ProcessSpecificationAddress, EntryData, EntryData) = update_record(old_revision, process_specification.to_owned())?;
-    construct_response(&base_address, &meta, &new_entry)
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
 }
 
-pub fn handle_delete_process_specification(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_process_specification<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
-    delete_record::<EntryStorage>(&revision_id)
+    clear_indexing_report();
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -69,6 +81,7 @@ fn construct_response<'a>(
             meta: read_revision_metadata_abbreviated(meta)?,
             name: e.name.to_owned(),
             note: e.note.to_owned(),
-        }
+        },
+        indexing_report: None,
     })
 }
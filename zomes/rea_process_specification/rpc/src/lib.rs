@@ -9,8 +9,11 @@
 use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    IndexingReport, DeleteResponse,
+    ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     ProcessSpecificationAddress,
+    RecipeProcessAddress,
+    EconomicResourceAddress,
 };
 
 // toplevel I/O structs for WASM API
@@ -46,6 +49,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub process_specification: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -90,4 +95,6 @@ impl<'a> UpdateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
+    pub recipe_processes: Option<RecipeProcessAddress>,
+    pub staged_resources: Option<EconomicResourceAddress>,
 }
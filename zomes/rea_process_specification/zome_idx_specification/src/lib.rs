@@ -9,5 +9,6 @@ use hc_zome_rea_process_specification_rpc::*;
 
 #[index_zome]
 struct ProcessSpecification {
-    // :NOTE: blank means only the `read_all_` and `register_new_` APIs will be generated
+    recipe_processes: Local<recipe_process, process_conforms_to>,
+    staged_resources: Remote<economic_resource, stage>,
 }
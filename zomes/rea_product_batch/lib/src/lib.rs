@@ -0,0 +1,112 @@
+/**
+ * hREA product batch zome library API
+ *
+ * Contains helper methods that can be used to manipulate `ProductBatch` (lot)
+ * data structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_product_batch_storage::*;
+use hc_zome_rea_product_batch_rpc::*;
+
+pub use hc_zome_rea_product_batch_storage::PRODUCT_BATCH_ENTRY_TYPE;
+
+/// Properties accessor for zome config
+fn read_product_batch_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.product_batch.index_zome)
+}
+
+pub fn handle_create_product_batch<S>(entry_def_id: S, product_batch: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    clear_indexing_report();
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_product_batch_index_zome, &entry_def_id, product_batch)?;
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
+}
+
+pub fn handle_get_product_batch(address: ProductBatchAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_product_batch_revisions(address: ProductBatchAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_product_batch(product_batch: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = product_batch.get_revision_id().clone();
+    let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, product_batch)?;
+    with_indexing_report!(construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?))
+}
+
+/// Removes a product batch record.
+///
+/// Indexes from `EconomicResource.lot` are retained, as resources continue to reference
+/// the batch and must remain discoverable for traceability purposes (eg. recalls).
+///
+pub fn handle_delete_product_batch<S>(entry_def_id: S, address: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_product_batch_index_zome, &entry_def_id, &address)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &ProductBatchAddress, meta: &SignedActionHashed, e: &EntryData, (
+        economic_resources,
+    ): (
+        Vec<EconomicResourceAddress>,
+    ),
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        product_batch: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            batch_number: e.batch_number.to_owned(),
+            expiry_date: e.expiry_date.to_owned(),
+            production_date: e.production_date.to_owned(),
+            note: e.note.to_owned(),
+            economic_resources: economic_resources.to_owned(),
+        },
+        indexing_report: None,
+    })
+}
+
+//---------------- READ ----------------
+
+// @see construct_response
+fn get_link_fields(base_address: &ProductBatchAddress) -> RecordAPIResult<(
+    Vec<EconomicResourceAddress>,
+)> {
+    Ok((
+        read_index!(product_batch(base_address).economic_resources)?,
+    ))
+}
@@ -0,0 +1,71 @@
+/**
+ * hREA ProductBatch zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_product_batch_rpc::*;
+use hc_zome_rea_product_batch_lib::*;
+use hc_zome_rea_product_batch_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub product_batch: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_product_batch(CreateParams { product_batch }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_product_batch(PRODUCT_BATCH_ENTRY_TYPE, product_batch)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: ProductBatchAddress,
+}
+
+#[hdk_extern]
+fn get_product_batch(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_product_batch(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_product_batch_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_product_batch(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_product_batch_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<ProductBatchAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_product_batch_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub product_batch: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_product_batch(UpdateParams { product_batch }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_product_batch(product_batch)?)
+}
+
+#[hdk_extern]
+fn delete_product_batch(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_product_batch(PRODUCT_BATCH_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -0,0 +1,13 @@
+/**
+ * Product batch query indexes for observation DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_product_batch_rpc::*;
+
+#[index_zome]
+struct ProductBatch {
+    economic_resources: Local<economic_resource, lot>,
+}
@@ -8,7 +8,11 @@
  * @package hREA
  */
 use hdi::prelude::*;
-pub use hc_zome_rea_proposal_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::{
+    authorization::AuthorOnly,
+    declare_record_modification_validation,
+};
+pub use hc_zome_rea_proposal_storage::{EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -28,3 +32,5 @@ pub fn __num_entry_types() -> u8 {
 pub fn __num_link_types() -> u8 {
     LinkTypes::len()
 }
+
+declare_record_modification_validation!(EntryStorage, AuthorOnly);
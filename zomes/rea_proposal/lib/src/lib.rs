@@ -14,6 +14,7 @@ use hdk_records::{
         delete_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
     },
     metadata::read_revision_metadata_abbreviated,
@@ -32,8 +33,9 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_proposal<S>(entry_def_id: S, proposal: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, proposal)?;
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 pub fn handle_get_proposal(address: ProposalAddress) -> RecordAPIResult<ResponseData>
@@ -48,15 +50,25 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_proposal_revisions(address: ProposalAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_proposal(proposal: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let old_revision = proposal.get_revision_id().to_owned();
     let (meta, base_address, new_entry, _prev_entry): (_, ProposalAddress, EntryData, EntryData) = update_record(&old_revision, proposal)?;
-    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
 }
 
-pub fn handle_delete_proposal(address: ActionHash) -> RecordAPIResult<bool> {
-    delete_record::<EntryStorage>(&address)
+pub fn handle_delete_proposal<S>(entry_def_id: S, address: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &address)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -86,6 +98,7 @@ fn construct_response<'a>(
             publishes: publishes.to_owned(),
             published_to: published_to.to_owned(),
         },
+        indexing_report: None,
     })
 }
 
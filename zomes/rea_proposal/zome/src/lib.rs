@@ -8,10 +8,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_proposal_lib::*;
 use hc_zome_rea_proposal_rpc::*;
 use hc_zome_rea_proposal_storage_consts::*;
+use hc_zome_rea_proposal_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[hdk_extern]
 fn create_proposal(CreateParams { proposal }: CreateParams) -> ExternResult<ResponseData> {
@@ -23,17 +26,30 @@ fn get_proposal(ByAddress { address }: ByAddress<ProposalAddress>) -> ExternResu
     Ok(handle_get_proposal(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_proposal_summary(params: ByAddress<ProposalAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_proposal(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_proposal_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<ProposalAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_proposal_revisions(address, first, after)?)
+}
+
 #[hdk_extern]
 fn update_proposal(UpdateParams { proposal }: UpdateParams) -> ExternResult<ResponseData> {
     Ok(handle_update_proposal(proposal)?)
 }
 
 #[hdk_extern]
-fn delete_proposal(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_proposal(revision_id)?)
+fn delete_proposal(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_proposal(PROPOSAL_ENTRY_TYPE, revision_id)?)
 }
+
+declare_pending_sync_zome_api!();
@@ -14,6 +14,7 @@ use hdk_records::{
         delete_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
     },
     metadata::read_revision_metadata_abbreviated,
 };
@@ -30,6 +31,7 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_proposed_intent<S>(entry_def_id: S, proposed_intent: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_, ProposedIntentAddress, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, proposed_intent.to_owned())?;
 
     // handle link fields
@@ -38,7 +40,7 @@ pub fn handle_create_proposed_intent<S>(entry_def_id: S, proposed_intent: Create
     let r2 = create_index!(proposed_intent.publishes(proposed_intent.publishes.to_owned()), intent.proposed_in(&base_address));
     hdk::prelude::debug!("handle_create_proposed_intent::publishes index {:?}", r2);
 
-    construct_response(&base_address, &meta, &entry_resp)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp))
 }
 
 pub fn handle_get_proposed_intent(address: ProposedIntentAddress) -> RecordAPIResult<ResponseData>
@@ -47,8 +49,15 @@ pub fn handle_get_proposed_intent(address: ProposedIntentAddress) -> RecordAPIRe
     construct_response(&base_address, &meta, &entry)
 }
 
-pub fn handle_delete_proposed_intent(revision_id: &ActionHash) -> RecordAPIResult<bool>
+pub fn handle_get_proposed_intent_revisions(address: ProposedIntentAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
 {
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_delete_proposed_intent<S>(entry_def_id: S, revision_id: &ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
     // Notify indexing zomes in local DNA (& validate).
@@ -57,7 +66,7 @@ pub fn handle_delete_proposed_intent(revision_id: &ActionHash) -> RecordAPIResul
     hdk::prelude::debug!("handle_delete_proposed_intent::published_in index {:?}", r1);
 
     // manage record deletion
-    let res = delete_record::<EntryStorage>(&revision_id);
+    let res = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id);
 
     // Update in associated foreign DNAs as well.
     // :TODO: If we caught errors here, foreign cells can also intervene in record deletion, and cause rollback.
@@ -65,7 +74,7 @@ pub fn handle_delete_proposed_intent(revision_id: &ActionHash) -> RecordAPIResul
     let r2 = update_index!(proposed_intent.publishes.not(&vec![entry.publishes]), intent.proposed_in(&base_address));
     hdk::prelude::debug!("handle_delete_proposed_intent::publishes index {:?}", r2);
 
-    res
+    Ok(DeleteResponse { deleted: res?, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -81,6 +90,7 @@ fn construct_response<'a>(address: &ProposedIntentAddress, meta: &SignedActionHa
             published_in: e.published_in.to_owned(),
             publishes: e.publishes.to_owned(),
         },
+        indexing_report: None,
     })
 }
 
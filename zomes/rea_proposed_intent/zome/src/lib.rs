@@ -8,10 +8,13 @@
 * @package hREA
 */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_proposed_intent_lib::*;
 use hc_zome_rea_proposed_intent_rpc::*;
 use hc_zome_rea_proposed_intent_storage_consts::*;
+use hc_zome_rea_proposed_intent_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[hdk_extern]
 fn create_proposed_intent(CreateParams { proposed_intent }: CreateParams) -> ExternResult<ResponseData> {
@@ -23,7 +26,20 @@ fn get_proposed_intent(ByAddress { address }: ByAddress<ProposedIntentAddress>)
     Ok(handle_get_proposed_intent(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
 #[hdk_extern]
-fn delete_proposed_intent(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_proposed_intent(&revision_id)?)
+fn __internal_get_proposed_intent_summary(params: ByAddress<ProposedIntentAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_proposed_intent(params))
 }
+
+#[hdk_extern]
+fn get_proposed_intent_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<ProposedIntentAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_proposed_intent_revisions(address, first, after)?)
+}
+
+#[hdk_extern]
+fn delete_proposed_intent(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_proposed_intent(PROPOSED_INTENT_ENTRY_TYPE, &revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -14,6 +14,7 @@ use hdk_records::{
         delete_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
     },
     metadata::read_revision_metadata_abbreviated,
 };
@@ -31,6 +32,7 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_proposed_to<S>(entry_def_id: S, proposed_to: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_, ProposedToAddress, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, proposed_to.to_owned())?;
 
     // handle link fields
@@ -39,7 +41,7 @@ pub fn handle_create_proposed_to<S>(entry_def_id: S, proposed_to: CreateRequest)
 
     // :TODO: create index for retrieving all proposals for an agent
 
-    construct_response(&base_address, &meta, &entry_resp)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp))
 }
 
 pub fn handle_get_proposed_to(address: ProposedToAddress) -> RecordAPIResult<ResponseData>
@@ -48,14 +50,22 @@ pub fn handle_get_proposed_to(address: ProposedToAddress) -> RecordAPIResult<Res
     construct_response(&base_address, &meta, &entry)
 }
 
-pub fn handle_delete_proposed_to(revision_id: &ActionHash) -> RecordAPIResult<bool>
+pub fn handle_get_proposed_to_revisions(address: ProposedToAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
 {
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_delete_proposed_to<S>(entry_def_id: S, revision_id: &ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
     let e = update_index!(proposed_to.proposed.not(&vec![entry.proposed]), proposal.published_to(&base_address));
     hdk::prelude::debug!("handle_delete_proposed_to::proposed index {:?}", e);
 
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -68,6 +78,7 @@ fn construct_response<'a>(address: &ProposedToAddress, meta: &SignedActionHashed
             proposed_to: e.proposed_to.to_owned(),
             proposed: e.proposed.to_owned(),
         },
+        indexing_report: None,
     })
 }
 
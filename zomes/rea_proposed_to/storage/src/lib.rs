@@ -14,6 +14,7 @@ use hdk_records::{
 };
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use vf_attributes_hdk::{ProposedToAddress, AgentAddress, ProposalAddress};
 
 use hc_zome_rea_proposed_to_rpc::CreateRequest;
@@ -49,7 +50,9 @@ generate_record_entry!(EntryData, ProposedToAddress, EntryStorage);
 pub enum EntryTypes {
     ProposedTo(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -67,6 +70,14 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
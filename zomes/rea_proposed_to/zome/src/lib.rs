@@ -8,10 +8,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_proposed_to_lib::*;
 use hc_zome_rea_proposed_to_rpc::*;
 use hc_zome_rea_proposed_to_storage_consts::*;
+use hc_zome_rea_proposed_to_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[hdk_extern]
 fn create_proposed_to(CreateParams { proposed_to }: CreateParams) -> ExternResult<ResponseData> {
@@ -23,7 +26,20 @@ fn get_proposed_to(ByAddress { address }: ByAddress<ProposedToAddress>) -> Exter
     Ok(handle_get_proposed_to(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
 #[hdk_extern]
-fn delete_proposed_to(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_proposed_to(&revision_id)?)
+fn __internal_get_proposed_to_summary(params: ByAddress<ProposedToAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_proposed_to(params))
 }
+
+#[hdk_extern]
+fn get_proposed_to_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<ProposedToAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_proposed_to_revisions(address, first, after)?)
+}
+
+#[hdk_extern]
+fn delete_proposed_to(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_proposed_to(PROPOSED_TO_ENTRY_TYPE, &revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
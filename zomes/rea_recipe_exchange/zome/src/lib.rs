@@ -0,0 +1,71 @@
+/**
+ * hREA RecipeExchange zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_recipe_exchange_rpc::*;
+use hc_zome_rea_recipe_exchange_lib::*;
+use hc_zome_rea_recipe_exchange_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub recipe_exchange: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_recipe_exchange(CreateParams { recipe_exchange }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_recipe_exchange(RECIPE_EXCHANGE_ENTRY_TYPE, recipe_exchange)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: RecipeExchangeAddress,
+}
+
+#[hdk_extern]
+fn get_recipe_exchange(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_recipe_exchange(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_recipe_exchange_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_recipe_exchange(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_recipe_exchange_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<RecipeExchangeAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_recipe_exchange_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub recipe_exchange: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_recipe_exchange(UpdateParams { recipe_exchange }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_recipe_exchange(recipe_exchange)?)
+}
+
+#[hdk_extern]
+fn delete_recipe_exchange(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_recipe_exchange(RECIPE_EXCHANGE_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
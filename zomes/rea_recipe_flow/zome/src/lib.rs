@@ -0,0 +1,71 @@
+/**
+ * hREA RecipeFlow zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_recipe_flow_rpc::*;
+use hc_zome_rea_recipe_flow_lib::*;
+use hc_zome_rea_recipe_flow_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub recipe_flow: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_recipe_flow(CreateParams { recipe_flow }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_recipe_flow(RECIPE_FLOW_ENTRY_TYPE, recipe_flow)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: RecipeFlowAddress,
+}
+
+#[hdk_extern]
+fn get_recipe_flow(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_recipe_flow(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_recipe_flow_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_recipe_flow(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_recipe_flow_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<RecipeFlowAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_recipe_flow_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub recipe_flow: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_recipe_flow(UpdateParams { recipe_flow }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_recipe_flow(recipe_flow)?)
+}
+
+#[hdk_extern]
+fn delete_recipe_flow(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_recipe_flow(RECIPE_FLOW_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
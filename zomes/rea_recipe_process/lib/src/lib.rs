@@ -0,0 +1,595 @@
+/**
+ * hREA recipe process zome library API
+ *
+ * Contains helper methods that can be used to manipulate `RecipeProcess`
+ * data structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk::prelude::Serialize;
+use hdk_records::{
+    RecordAPIResult, OtherCellResult, DataIntegrityError, MaybeUndefined, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+    rpc::{call_role_zome_method, call_zome_method},
+};
+use hdk_semantic_indexes_client_lib::*;
+use vf_measurement::{QuantityValue, Unit, Decimal, decimal_scale, multiply, add, convert};
+
+use hc_zome_rea_recipe_process_storage::*;
+use hc_zome_rea_recipe_process_rpc::*;
+use hc_zome_rea_recipe_flow_storage::{
+    EntryData as RecipeFlowData,
+    EntryStorage as RecipeFlowStorage,
+};
+use hc_zome_rea_recipe_resource_storage::{
+    EntryData as RecipeResourceData,
+    EntryStorage as RecipeResourceStorage,
+};
+use hc_zome_rea_plan_rpc::{
+    CreateRequest as PlanCreateRequest,
+    ResponseData as PlanResponseData,
+};
+use hc_zome_rea_process_rpc::{
+    CreateRequest as ProcessCreateRequest,
+    ResponseData as ProcessResponseData,
+};
+use hc_zome_rea_commitment_rpc::{
+    CreateRequest as CommitmentCreateRequest,
+    ResponseData as CommitmentResponseData,
+};
+use hc_zome_rea_unit_rpc::{ResponseData as UnitResponseData, GetUnitRequest, UnitId};
+
+pub use hc_zome_rea_recipe_process_storage::RECIPE_PROCESS_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_process.index_zome)
+}
+
+pub fn handle_create_recipe_process<S>(entry_def_id: S, recipe_process: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    clear_indexing_report();
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, recipe_process.to_owned())?;
+
+    // handle link fields
+    if let CreateRequest { process_conforms_to: MaybeUndefined::Some(process_conforms_to), .. } = &recipe_process {
+        let e = create_index!(recipe_process.process_conforms_to(process_conforms_to), process_specification.recipe_processes(&base_address));
+        hdk::prelude::debug!("handle_create_recipe_process::process_conforms_to index {:?}", e);
+    };
+
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
+}
+
+pub fn handle_get_recipe_process(address: RecipeProcessAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_recipe_process_revisions(address: RecipeProcessAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_recipe_process(recipe_process: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = recipe_process.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, RecipeProcessAddress, EntryData, EntryData) = update_record(&revision_hash, recipe_process)?;
+
+    // handle link fields
+    if new_entry.process_conforms_to != prev_entry.process_conforms_to {
+        let new_value = match &new_entry.process_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.process_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            recipe_process
+                .process_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            process_specification.recipe_processes(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_process::process_conforms_to index {:?}", e);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
+}
+
+pub fn handle_delete_recipe_process<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    if let Some(val) = entry.process_conforms_to {
+        let e = update_index!(recipe_process.process_conforms_to.not(&vec![val]), process_specification.recipe_processes(&base_address));
+        hdk::prelude::debug!("handle_delete_recipe_process::process_conforms_to index {:?}", e);
+    }
+
+    // delete entry last, as it must be present in order for links to be removed
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &RecipeProcessAddress, meta: &SignedActionHashed, e: &EntryData, (
+        recipe_inputs,
+        recipe_outputs,
+    ): (
+        Vec<RecipeFlowAddress>,
+        Vec<RecipeFlowAddress>,
+    ),
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        recipe_process: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            name: e.name.to_owned(),
+            has_duration: e.has_duration.to_owned(),
+            process_classified_as: e.process_classified_as.to_owned(),
+            process_conforms_to: e.process_conforms_to.to_owned(),
+            note: e.note.to_owned(),
+            recipe_inputs: recipe_inputs.to_owned(),
+            recipe_outputs: recipe_outputs.to_owned(),
+        },
+        indexing_report: None,
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_recipe_process_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_process.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_process_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_process.process_specification_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_recipe_resource_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_process.recipe_resource_index_zome
+}
+
+// @see construct_response
+fn get_link_fields(base_address: &RecipeProcessAddress) -> RecordAPIResult<(
+    Vec<RecipeFlowAddress>,
+    Vec<RecipeFlowAddress>,
+)> {
+    Ok((
+        read_index!(recipe_process(base_address).recipe_inputs)?,
+        read_index!(recipe_process(base_address).recipe_outputs)?,
+    ))
+}
+
+//---------------- PLAN FROM RECIPE ----------------
+
+// zome names of the standard hREA record zomes in the cells targeted by plan generation
+const PLAN_ZOME: &str = "plan";
+const PROCESS_ZOME: &str = "process";
+const COMMITMENT_ZOME: &str = "commitment";
+
+#[derive(Debug, Serialize)]
+struct CreatePlanParams {
+    pub plan: PlanCreateRequest,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateProcessParams {
+    pub process: ProcessCreateRequest,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateCommitmentParams {
+    pub commitment: CommitmentCreateRequest,
+}
+
+/// Properties accessor for zome config
+fn read_plan_role(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_process.plan_role
+}
+
+/// Properties accessor for zome config
+fn read_observation_role(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_process.observation_role
+}
+
+/// Properties accessor for zome config
+fn read_planning_role(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_process.planning_role
+}
+
+/// A `Process` to be written for some `RecipeProcess`, along with its `Commitments`
+struct PlannedProcess {
+    recipe_process_address: RecipeProcessAddress,
+    recipe_process: EntryData,
+    has_beginning: DateTime<FixedOffset>,
+    has_end: DateTime<FixedOffset>,
+    commitments: Vec<PlannedCommitment>,
+}
+
+/// A `Commitment` to be written for some `RecipeFlow` of a `PlannedProcess`
+struct PlannedCommitment {
+    flow_address: RecipeFlowAddress,
+    flow: RecipeFlowData,
+    resource: RecipeResourceData,
+    resource_quantity: Option<QuantityValue>,
+    effort_quantity: Option<QuantityValue>,
+    is_input: bool,
+    independent_demand: bool,
+}
+
+/// Accumulates the processes to be written while walking the recipe graph
+struct PlanBuilder {
+    processes: Vec<PlannedProcess>,
+}
+
+impl PlannedProcess {
+    /// Add the commitments planned for another demand upon the same `RecipeProcess`, rescheduling
+    /// the process to complete in time for the earliest of them.
+    ///
+    fn merge(&mut self, commitments: Vec<PlannedCommitment>, due: DateTime<FixedOffset>) -> RecordAPIResult<()>
+    {
+        if due < self.has_end {
+            self.has_end = due;
+            self.has_beginning = process_beginning(&self.recipe_process, due);
+        }
+
+        for commitment in commitments {
+            match self.commitments.iter_mut().find(|c| c.flow_address == commitment.flow_address) {
+                Some(existing) => {
+                    existing.resource_quantity = add_quantities(existing.resource_quantity.take(), commitment.resource_quantity)?;
+                    existing.effort_quantity = add_quantities(existing.effort_quantity.take(), commitment.effort_quantity)?;
+                    existing.independent_demand = existing.independent_demand || commitment.independent_demand;
+                },
+                None => self.commitments.push(commitment),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sum optional quantities, treating absent quantities as unspecified rather than zero.
+///
+fn add_quantities(q1: Option<QuantityValue>, q2: Option<QuantityValue>) -> RecordAPIResult<Option<QuantityValue>>
+{
+    match (q1, q2) {
+        (Some(q1), Some(q2)) => Ok(Some(add(q1, q2)?)),
+        (q1, q2) => Ok(q1.or(q2)),
+    }
+}
+
+/// Determine when a `Process` instantiating some `RecipeProcess` must begin in order to complete by `due`.
+///
+fn process_beginning(recipe_process: &EntryData, due: DateTime<FixedOffset>) -> DateTime<FixedOffset>
+{
+    match &recipe_process.has_duration {
+        Some(duration) => due - chrono::Duration::seconds(duration.as_seconds() as i64),
+        None => due,
+    }
+}
+
+/// Records written while generating a `Plan`, retained so that they can be removed if generation fails
+#[derive(Default)]
+struct WrittenPlan {
+    plan: Option<(PlanAddress, ActionHash)>,
+    processes: Vec<(ProcessAddress, ActionHash)>,
+    commitments: Vec<(CommitmentAddress, ActionHash)>,
+}
+
+/// Instantiate a `Plan` from recipe definitions, sufficient to produce the requested quantity
+/// of the given `RecipeResource` by the due date.
+///
+/// The recipe graph is walked backwards from the requested output: the `RecipeProcess` producing
+/// each resource is instantiated as a `Process` with `Commitments` for all of its recipe inputs and
+/// outputs, quantities being scaled relative to the recipe's output quantity. Inputs which are
+/// themselves produced by other recipe processes are then planned in turn, scheduled to complete by
+/// the time the consuming process begins. Resources not produced by any recipe process are treated
+/// as raw inputs and left unplanned.
+///
+/// Where several processes consume the output of the same recipe process, it is planned once for
+/// their combined demand and scheduled to complete in time for the earliest of them. A recipe process
+/// which (directly or indirectly) consumes its own output is not planned again for that input. Requesting a resource which no recipe process produces is an error.
+///
+/// The output `Commitment` for the requested resource is recorded as the independent demand of the `Plan`.
+///
+/// All recipe records are read and all quantities computed before anything is written. Should writing
+/// any of the generated records fail, those already written are deleted before the error is returned.
+///
+pub fn handle_create_plan_from_recipe(request: PlanFromRecipeRequest) -> RecordAPIResult<PlanFromRecipeResponse>
+{
+    let mut builder = PlanBuilder {
+        processes: vec![],
+    };
+
+    let planned = plan_recipe_resource(&mut builder, &mut vec![], &request.recipe_resource, Some(request.resource_quantity.to_owned()), request.due.to_owned(), true)?;
+    if !planned {
+        return Err(DataIntegrityError::RecipeNotFound(request.recipe_resource.to_string()));
+    }
+
+    let mut written = WrittenPlan::default();
+    match write_plan(&request, builder.processes, &mut written) {
+        Ok(plan) => Ok(PlanFromRecipeResponse {
+            plan,
+            processes: written.processes.into_iter().map(|(address, _)| address).collect(),
+            commitments: written.commitments.into_iter().map(|(address, _)| address).collect(),
+        }),
+        Err(e) => {
+            rollback_plan(written);
+            Err(e)
+        },
+    }
+}
+
+/// Plan the production of some `RecipeResource`, returning `false` if no recipe exists to produce it.
+///
+/// `path` holds the recipe processes whose inputs are being planned on the way to this resource.
+///
+fn plan_recipe_resource(
+    builder: &mut PlanBuilder,
+    path: &mut Vec<RecipeProcessAddress>,
+    recipe_resource: &RecipeResourceAddress,
+    quantity: Option<QuantityValue>,
+    due: DateTime<FixedOffset>,
+    is_independent_demand: bool,
+) -> RecordAPIResult<bool>
+{
+    // locate the recipe process which outputs the resource
+    let flows: Vec<RecipeFlowAddress> = read_index!(recipe_resource(recipe_resource).recipe_flows)?;
+    let mut output_flow = None;
+    for flow_address in flows.iter() {
+        let flow = read_recipe_flow(flow_address)?;
+        if flow.recipe_output_of.is_some() {
+            output_flow = Some((flow_address.to_owned(), flow));
+            break;
+        }
+    }
+    let (output_flow_address, output_flow) = match output_flow {
+        Some(f) => f,
+        None => return Ok(false),
+    };
+    let recipe_process_address = output_flow.recipe_output_of.to_owned().unwrap();
+
+    // guard against cyclic recipes
+    if path.contains(&recipe_process_address) {
+        return Ok(true);
+    }
+
+    // scale the recipe relative to its output, with the requested quantity expressed in the unit of the recipe flow
+    let scale = match (quantity, &output_flow.resource_quantity) {
+        (Some(requested), Some(produced)) if !produced.get_numerical_value().is_zero() => {
+            let requested = match &produced.has_unit {
+                Some(unit) => convert(requested, unit, get_unit_definition)?,
+                None if requested.has_unit.is_none() => requested,
+                None => return Err(DataIntegrityError::MismatchingUnits(requested.get_unit().map(|unit| unit.1), None)),
+            };
+            requested.get_numerical_value()
+                .checked_div(&produced.get_numerical_value(), decimal_scale())
+                .ok_or(DataIntegrityError::QuantityOverflow)?
+        },
+        _ => Decimal::from(1),
+    };
+
+    let mut commitments = vec![];
+    for flow_address in read_index!(recipe_process(&recipe_process_address).recipe_outputs)?.iter() {
+        let flow = read_recipe_flow(flow_address)?;
+        let independent_demand = is_independent_demand && *flow_address == output_flow_address;
+        commitments.push(plan_flow_commitment(flow_address, flow, scale, false, independent_demand)?);
+    }
+
+    let mut inputs = vec![];
+    for flow_address in read_index!(recipe_process(&recipe_process_address).recipe_inputs)?.iter() {
+        let commitment = plan_flow_commitment(flow_address, read_recipe_flow(flow_address)?, scale, true, false)?;
+        inputs.push((commitment.flow.recipe_flow_resource.to_owned(), commitment.resource_quantity.to_owned()));
+        commitments.push(commitment);
+    }
+
+    // accumulate demand upon recipe processes shared with other consumers
+    let beginning = match builder.processes.iter_mut().find(|p| p.recipe_process_address == recipe_process_address) {
+        Some(existing) => {
+            existing.merge(commitments, due)?;
+            existing.has_beginning
+        },
+        None => {
+            let (_meta, _base_address, recipe_process): (_, RecipeProcessAddress, EntryData) = read_record_entry::<EntryData, EntryStorage, _>(recipe_process_address.as_ref())?;
+            let beginning = process_beginning(&recipe_process, due);
+            builder.processes.push(PlannedProcess {
+                recipe_process_address: recipe_process_address.to_owned(),
+                recipe_process,
+                has_beginning: beginning,
+                has_end: due,
+                commitments,
+            });
+            beginning
+        },
+    };
+
+    path.push(recipe_process_address);
+    for (input_resource, input_quantity) in inputs {
+        plan_recipe_resource(builder, path, &input_resource, input_quantity, beginning, false)?;
+    }
+    path.pop();
+
+    Ok(true)
+}
+
+/// Determine the `Commitment` needed to fulfil a `RecipeFlow` within a planned `Process`, scaling its quantities.
+///
+fn plan_flow_commitment(
+    flow_address: &RecipeFlowAddress,
+    flow: RecipeFlowData,
+    scale: Decimal,
+    is_input: bool,
+    independent_demand: bool,
+) -> RecordAPIResult<PlannedCommitment>
+{
+    let (_meta, _base_address, resource): (_, RecipeResourceAddress, RecipeResourceData) = read_record_entry::<RecipeResourceData, RecipeResourceStorage, _>(flow.recipe_flow_resource.as_ref())?;
+    let resource_quantity = flow.resource_quantity.to_owned().map(|q| multiply(q, scale)).transpose()?;
+    let effort_quantity = flow.effort_quantity.to_owned().map(|q| multiply(q, scale)).transpose()?;
+
+    Ok(PlannedCommitment {
+        flow_address: flow_address.to_owned(),
+        flow,
+        resource,
+        resource_quantity,
+        effort_quantity,
+        is_input,
+        independent_demand,
+    })
+}
+
+/// Write the `Plan` and all of its planned `Processes` & `Commitments`, recording each in `written` as it is created.
+///
+fn write_plan(request: &PlanFromRecipeRequest, processes: Vec<PlannedProcess>, written: &mut WrittenPlan) -> RecordAPIResult<PlanAddress>
+{
+    let plan: PlanResponseData = call_role_zome_method(
+        read_plan_role, PLAN_ZOME, "create_plan",
+        CreatePlanParams { plan: PlanCreateRequest {
+            name: request.name.to_owned(),
+            created: MaybeUndefined::Undefined,
+            due: MaybeUndefined::Some(request.due.to_owned()),
+            note: request.note.to_owned(),
+            deletable: MaybeUndefined::Undefined,
+        } },
+    )?;
+    let plan_address = plan.plan.id;
+    written.plan = Some((plan_address.to_owned(), plan.plan.revision_id));
+
+    for planned in processes {
+        let process: ProcessResponseData = call_role_zome_method(
+            read_observation_role, PROCESS_ZOME, "create_process",
+            CreateProcessParams { process: ProcessCreateRequest {
+                name: planned.recipe_process.name.to_owned(),
+                has_beginning: MaybeUndefined::Some(planned.has_beginning),
+                has_end: MaybeUndefined::Some(planned.has_end),
+                before: MaybeUndefined::Undefined,
+                after: MaybeUndefined::Undefined,
+                classified_as: planned.recipe_process.process_classified_as.to_owned().into(),
+                based_on: planned.recipe_process.process_conforms_to.to_owned().into(),
+                planned_within: MaybeUndefined::Some(plan_address.to_owned()),
+                finished: MaybeUndefined::Some(false),
+                in_scope_of: MaybeUndefined::Undefined,
+                note: planned.recipe_process.note.to_owned().into(),
+            } },
+        )?;
+        let process_address = process.process.id;
+        written.processes.push((process_address.to_owned(), process.process.revision_id));
+
+        for commitment in planned.commitments {
+            let due = if commitment.is_input { planned.has_beginning } else { planned.has_end };
+            write_flow_commitment(request, &plan_address, &process_address, commitment, due, written)?;
+        }
+    }
+
+    Ok(plan_address)
+}
+
+/// Write a `Commitment` to fulfil a `RecipeFlow` within a planned `Process`.
+///
+fn write_flow_commitment(
+    request: &PlanFromRecipeRequest,
+    plan_address: &PlanAddress,
+    process_address: &ProcessAddress,
+    planned: PlannedCommitment,
+    due: DateTime<FixedOffset>,
+    written: &mut WrittenPlan,
+) -> RecordAPIResult<()>
+{
+    let (input_of, output_of) = match planned.is_input {
+        true => (MaybeUndefined::Some(process_address.to_owned()), MaybeUndefined::Undefined),
+        false => (MaybeUndefined::Undefined, MaybeUndefined::Some(process_address.to_owned())),
+    };
+
+    let commitment: CommitmentResponseData = call_role_zome_method(
+        read_planning_role, COMMITMENT_ZOME, "create_commitment",
+        CreateCommitmentParams { commitment: CommitmentCreateRequest {
+            action: planned.flow.action.to_owned(),
+            note: planned.flow.note.to_owned().into(),
+            input_of,
+            output_of,
+            provider: request.provider.to_owned(),
+            receiver: request.receiver.to_owned(),
+            resource_inventoried_as: MaybeUndefined::Undefined,
+            resource_classified_as: planned.resource.resource_classified_as.into(),
+            resource_conforms_to: planned.resource.resource_conforms_to.into(),
+            resource_quantity: planned.resource_quantity.into(),
+            effort_quantity: planned.effort_quantity.into(),
+            has_beginning: MaybeUndefined::Undefined,
+            has_end: MaybeUndefined::Undefined,
+            has_point_in_time: MaybeUndefined::Undefined,
+            due: MaybeUndefined::Some(due),
+            at_location: MaybeUndefined::Undefined,
+            agreed_in: MaybeUndefined::Undefined,
+            clause_of: MaybeUndefined::Undefined,
+            planned_within: MaybeUndefined::Some(plan_address.to_owned()),
+            independent_demand_of: if planned.independent_demand { MaybeUndefined::Some(plan_address.to_owned()) } else { MaybeUndefined::Undefined },
+            finished: MaybeUndefined::Some(false),
+            in_scope_of: MaybeUndefined::Undefined,
+        } },
+    )?;
+    written.commitments.push((commitment.commitment.id, commitment.commitment.revision_id));
+
+    Ok(())
+}
+
+/// Delete all records written by a failed plan generation, most dependent first.
+///
+/// Failures are logged rather than returned, so that the error which caused the rollback is reported.
+///
+fn rollback_plan(written: WrittenPlan)
+{
+    for (_, revision_id) in written.commitments.into_iter().rev() {
+        let r: OtherCellResult<DeleteResponse> = call_role_zome_method(read_planning_role, COMMITMENT_ZOME, "delete_commitment", ByRevision { revision_id });
+        hdk::prelude::debug!("handle_create_plan_from_recipe::rollback commitment {:?}", r);
+    }
+    for (_, revision_id) in written.processes.into_iter().rev() {
+        let r: OtherCellResult<DeleteResponse> = call_role_zome_method(read_observation_role, PROCESS_ZOME, "delete_process", ByRevision { revision_id });
+        hdk::prelude::debug!("handle_create_plan_from_recipe::rollback process {:?}", r);
+    }
+    if let Some((_, revision_id)) = written.plan {
+        let r: OtherCellResult<DeleteResponse> = call_role_zome_method(read_plan_role, PLAN_ZOME, "delete_plan", ByRevision { revision_id });
+        hdk::prelude::debug!("handle_create_plan_from_recipe::rollback plan {:?}", r);
+    }
+}
+
+/// Load unit conversion information from the DNA which the unit is defined in
+fn get_unit_definition(unit_id: &UnitId) -> RecordAPIResult<Unit> {
+    let unit_data: OtherCellResult<UnitResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        unit_id,
+        &String::from("read_unit"),
+        GetUnitRequest { id: unit_id.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+
+    match unit_data {
+        Ok(unit_response) => Ok(unit_response.unit.to_unit()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn read_recipe_flow(address: &RecipeFlowAddress) -> RecordAPIResult<RecipeFlowData>
+{
+    let (_meta, _base_address, flow): (_, RecipeFlowAddress, RecipeFlowData) = read_record_entry::<RecipeFlowData, RecipeFlowStorage, _>(address.as_ref())?;
+    Ok(flow)
+}
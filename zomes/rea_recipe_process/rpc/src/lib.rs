@@ -0,0 +1,209 @@
+/**
+ * hREA recipe process zome I/O data structures
+ *
+ * Required by packages wishing to interact with the zome via its standard RPC interface,
+ * and by the zome API handlers accepting these parameters.
+ *
+ * @package hREA
+ */
+use holochain_serialized_bytes::prelude::*;
+
+use serde_maybe_undefined::MaybeUndefined;
+use vf_measurement::QuantityValue;
+pub use vf_attributes_hdk::{
+    IndexingReport, DeleteResponse,
+    RecipeProcessAddress,
+    RecipeResourceAddress,
+    RecipeFlowAddress,
+    ProcessSpecificationAddress,
+    ExternalURL,
+    AgentAddress,
+    PlanAddress,
+    ProcessAddress,
+    CommitmentAddress,
+    DateTime, FixedOffset,
+    ByAction, ActionHash, ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
+};
+
+//---------------- DURATION ----------------
+
+/// Unit of time used to express the duration of a recipe process
+///
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeUnit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Duration {
+    pub numeric_duration: f64,
+    pub unit_type: TimeUnit,
+}
+
+impl Duration {
+    /// Convert to a number of seconds for use in scheduling calculations.
+    ///
+    /// :TODO: months and years are approximated as 30 and 365 days respectively
+    ///
+    pub fn as_seconds(&self) -> f64 {
+        self.numeric_duration * match self.unit_type {
+            TimeUnit::Year => 31_536_000.0,
+            TimeUnit::Month => 2_592_000.0,
+            TimeUnit::Week => 604_800.0,
+            TimeUnit::Day => 86_400.0,
+            TimeUnit::Hour => 3_600.0,
+            TimeUnit::Minute => 60.0,
+            TimeUnit::Second => 1.0,
+        }
+    }
+}
+
+//---------------- EXTERNAL RECORD STRUCTURE ----------------
+
+/// I/O struct to describe the complete record, including all managed link fields
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub id: RecipeProcessAddress,
+    pub revision_id: ActionHash,
+    pub meta: RecordMeta,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_duration: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_classified_as: Option<Vec<ExternalURL>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_conforms_to: Option<ProcessSpecificationAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+
+    // query edges
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recipe_inputs: Vec<RecipeFlowAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recipe_outputs: Vec<RecipeFlowAddress>,
+}
+
+/// I/O struct to describe what is returned outside the gateway.
+/// Responses are usually returned as named attributes in order to leave space
+/// for future additional return values.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseData {
+    pub recipe_process: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
+}
+
+//---------------- CREATE REQUEST ----------------
+
+/// I/O struct to describe the complete input record, including all managed links
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRequest {
+    pub name: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub has_duration: MaybeUndefined<Duration>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub process_classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub process_conforms_to: MaybeUndefined<ProcessSpecificationAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub note: MaybeUndefined<String>,
+}
+
+impl<'a> CreateRequest {
+    // :TODO: accessors for field data
+}
+
+//---------------- UPDATE REQUEST ----------------
+
+/// I/O struct to describe the complete input record, including all managed links
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRequest {
+    pub revision_id: ActionHash,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub name: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub has_duration: MaybeUndefined<Duration>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub process_classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub process_conforms_to: MaybeUndefined<ProcessSpecificationAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub note: MaybeUndefined<String>,
+}
+
+impl<'a> UpdateRequest {
+    pub fn get_revision_id(&self) -> ActionHash {
+        self.revision_id.to_owned().into()
+    }
+
+    // :TODO: accessors for other field data
+}
+
+//---------------- QUERY FILTER REQUEST ----------------
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParams {
+    pub process_conforms_to: Option<ProcessSpecificationAddress>,
+    pub recipe_inputs: Option<RecipeFlowAddress>,
+    pub recipe_outputs: Option<RecipeFlowAddress>,
+}
+
+//---------------- PLAN FROM RECIPE ----------------
+
+/// I/O struct describing the demand for which a `Plan` should be generated from recipes
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanFromRecipeRequest {
+    /// the desired output of the plan
+    pub recipe_resource: RecipeResourceAddress,
+    pub resource_quantity: QuantityValue,
+    pub due: DateTime<FixedOffset>,
+    /// agents to assign as provider & receiver of all generated `Commitments`
+    pub provider: AgentAddress,
+    pub receiver: AgentAddress,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub name: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub note: MaybeUndefined<String>,
+}
+
+/// Identifiers of all records written when generating a `Plan` from recipes
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanFromRecipeResponse {
+    pub plan: PlanAddress,
+    pub processes: Vec<ProcessAddress>,
+    pub commitments: Vec<CommitmentAddress>,
+}
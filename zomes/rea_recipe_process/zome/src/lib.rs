@@ -0,0 +1,81 @@
+/**
+ * hREA RecipeProcess zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_recipe_process_rpc::*;
+use hc_zome_rea_recipe_process_lib::*;
+use hc_zome_rea_recipe_process_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub recipe_process: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_recipe_process(CreateParams { recipe_process }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_recipe_process(RECIPE_PROCESS_ENTRY_TYPE, recipe_process)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: RecipeProcessAddress,
+}
+
+#[hdk_extern]
+fn get_recipe_process(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_recipe_process(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_recipe_process_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_recipe_process(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_recipe_process_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<RecipeProcessAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_recipe_process_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub recipe_process: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_recipe_process(UpdateParams { recipe_process }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_recipe_process(recipe_process)?)
+}
+
+#[hdk_extern]
+fn delete_recipe_process(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_recipe_process(RECIPE_PROCESS_ENTRY_TYPE, revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PlanFromRecipeParams {
+    pub plan: PlanFromRecipeRequest,
+}
+
+#[hdk_extern]
+fn create_plan_from_recipe(PlanFromRecipeParams { plan }: PlanFromRecipeParams) -> ExternResult<PlanFromRecipeResponse> {
+    Ok(handle_create_plan_from_recipe(plan)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -0,0 +1,15 @@
+/**
+ * RecipeProcess query indexes for specification DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_recipe_process_rpc::*;
+
+#[index_zome]
+struct RecipeProcess {
+    process_conforms_to: Local<process_specification, recipe_processes>,
+    recipe_inputs: Local<recipe_flow, recipe_input_of>,
+    recipe_outputs: Local<recipe_flow, recipe_output_of>,
+}
@@ -0,0 +1,138 @@
+/**
+ * hREA recipe resource zome library API
+ *
+ * Contains helper methods that can be used to manipulate `RecipeResource`
+ * data structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, MaybeUndefined, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_recipe_resource_storage::*;
+use hc_zome_rea_recipe_resource_rpc::*;
+
+pub use hc_zome_rea_recipe_resource_storage::RECIPE_RESOURCE_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_resource.index_zome)
+}
+
+pub fn handle_create_recipe_resource<S>(entry_def_id: S, recipe_resource: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    clear_indexing_report();
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, recipe_resource.to_owned())?;
+
+    // handle link fields
+    if let CreateRequest { resource_conforms_to: MaybeUndefined::Some(resource_conforms_to), .. } = &recipe_resource {
+        let e = create_index!(recipe_resource.resource_conforms_to(resource_conforms_to), resource_specification.recipe_resources(&base_address));
+        hdk::prelude::debug!("handle_create_recipe_resource::resource_conforms_to index {:?}", e);
+    };
+
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp))
+}
+
+pub fn handle_get_recipe_resource(address: RecipeResourceAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_recipe_resource_revisions(address: RecipeResourceAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_recipe_resource(recipe_resource: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let revision_hash = recipe_resource.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, RecipeResourceAddress, EntryData, EntryData) = update_record(&revision_hash, recipe_resource)?;
+
+    // handle link fields
+    if new_entry.resource_conforms_to != prev_entry.resource_conforms_to {
+        let new_value = match &new_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            recipe_resource
+                .resource_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            resource_specification.recipe_resources(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_resource::resource_conforms_to index {:?}", e);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
+}
+
+pub fn handle_delete_recipe_resource<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    if let Some(val) = entry.resource_conforms_to {
+        let e = update_index!(recipe_resource.resource_conforms_to.not(&vec![val]), resource_specification.recipe_resources(&base_address));
+        hdk::prelude::debug!("handle_delete_recipe_resource::resource_conforms_to index {:?}", e);
+    }
+
+    // delete entry last, as it must be present in order for links to be removed
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &RecipeResourceAddress, meta: &SignedActionHashed, e: &EntryData,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        recipe_resource: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            name: e.name.to_owned(),
+            unit_of_resource: e.unit_of_resource.to_owned(),
+            unit_of_effort: e.unit_of_effort.to_owned(),
+            resource_classified_as: e.resource_classified_as.to_owned(),
+            resource_conforms_to: e.resource_conforms_to.to_owned(),
+            substitutable: e.substitutable.to_owned(),
+            image: e.image.to_owned(),
+            note: e.note.to_owned(),
+        },
+        indexing_report: None,
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_recipe_resource_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_resource.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_resource.resource_specification_index_zome
+}
@@ -0,0 +1,71 @@
+/**
+ * hREA RecipeResource zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_recipe_resource_rpc::*;
+use hc_zome_rea_recipe_resource_lib::*;
+use hc_zome_rea_recipe_resource_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub recipe_resource: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_recipe_resource(CreateParams { recipe_resource }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_recipe_resource(RECIPE_RESOURCE_ENTRY_TYPE, recipe_resource)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: RecipeResourceAddress,
+}
+
+#[hdk_extern]
+fn get_recipe_resource(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_recipe_resource(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_recipe_resource_summary(params: ReadParams) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_recipe_resource(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_recipe_resource_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<RecipeResourceAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_recipe_resource_revisions(address, first, after)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub recipe_resource: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_recipe_resource(UpdateParams { recipe_resource }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_recipe_resource(recipe_resource)?)
+}
+
+#[hdk_extern]
+fn delete_recipe_resource(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_recipe_resource(RECIPE_RESOURCE_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -0,0 +1,14 @@
+/**
+ * RecipeResource query indexes for specification DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_recipe_resource_rpc::*;
+
+#[index_zome]
+struct RecipeResource {
+    resource_conforms_to: Local<resource_specification, recipe_resources>,
+    recipe_flows: Local<recipe_flow, recipe_flow_resource>,
+}
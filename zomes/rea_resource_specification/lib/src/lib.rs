@@ -12,11 +12,13 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
 };
+use hdk_semantic_indexes_client_lib::{clear_indexing_report, take_indexing_report, with_indexing_report};
 
 use vf_attributes_hdk::{
     EconomicResourceAddress,
@@ -33,9 +35,10 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_resource_specification<S>(entry_def_id: S, resource_specification: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, resource_specification)?;
 
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?))
 }
 
 pub fn handle_get_resource_specification(address: ResourceSpecificationAddress) -> RecordAPIResult<ResponseData>
@@ -50,16 +53,25 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+pub fn handle_get_resource_specification_revisions(address: ResourceSpecificationAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_resource_specification(resource_specification: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let old_revision = resource_specification.get_revision_id();
     let (meta, base_address, new_entry, _prev_entry): (_, ResourceSpecificationAddress, EntryData, EntryData) = update_record(old_revision, resource_specification.to_owned())?;
-    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?))
 }
 
-pub fn handle_delete_resource_specification(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_resource_specification<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
-    delete_record::<EntryStorage>(&revision_id)
+    clear_indexing_report();
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Create response from input DHT primitives
@@ -87,7 +99,8 @@ fn construct_response<'a>(
             default_unit_of_resource: e.default_unit_of_resource.to_owned(),
 
             // conforming_resources: conforming_resources.map(Cow::into_owned),
-        }
+        },
+        indexing_report: None,
     })
 }
 
@@ -9,9 +9,11 @@
 use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAddress, ByAction, RecordMeta, RevisionMeta,
+    IndexingReport, DeleteResponse,
+    ActionHash, ByAddress, ByAction, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
     ResourceSpecificationAddress,
     EconomicResourceAddress,
+    RecipeResourceAddress,
     ExternalURL,
     UnitId,
     ByRevision,
@@ -60,6 +62,8 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub resource_specification: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -118,4 +122,5 @@ impl<'a> UpdateRequest {
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
     pub conforming_resources: Option<EconomicResourceAddress>,
+    pub recipe_resources: Option<RecipeResourceAddress>,
 }
@@ -8,10 +8,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_resource_specification_rpc::*;
 use hc_zome_rea_resource_specification_lib::*;
 use hc_zome_rea_resource_specification_storage_consts::*;
+use hc_zome_rea_resource_specification_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[hdk_extern]
 fn create_resource_specification(CreateParams { resource_specification }: CreateParams) -> ExternResult<ResponseData> {
@@ -23,17 +26,30 @@ fn get_resource_specification(ByAddress { address }: ByAddress<ResourceSpecifica
     Ok(handle_get_resource_specification(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_resource_specification_summary(params: ByAddress<ResourceSpecificationAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_resource_specification(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_resource_specification_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<ResourceSpecificationAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_resource_specification_revisions(address, first, after)?)
+}
+
 #[hdk_extern]
 fn update_resource_specification(UpdateParams { resource_specification }: UpdateParams) -> ExternResult<ResponseData> {
     Ok(handle_update_resource_specification(resource_specification)?)
 }
 
 #[hdk_extern]
-fn delete_resource_specification(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_resource_specification(revision_id)?)
+fn delete_resource_specification(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_resource_specification(ECONOMIC_RESOURCE_SPECIFICATION_ENTRY_TYPE, revision_id)?)
 }
+
+declare_pending_sync_zome_api!();
@@ -11,4 +11,5 @@ use hdk_semantic_indexes_zome_lib::ByAddress; // disambiguate from record query
 #[index_zome]
 struct ResourceSpecification {
     conforming_resources: Remote<economic_resource, conforms_to>,
+    recipe_resources: Local<recipe_resource, resource_conforms_to>,
 }
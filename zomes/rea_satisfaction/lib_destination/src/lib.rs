@@ -14,10 +14,13 @@ use hdk_records::{
     RecordAPIResult,
     records::{
         create_record,
+        read_existing_record,
         read_record_entry,
         read_record_entry_by_action,
-        update_record,
+        read_record_revisions,
+        update_record_once,
         delete_record,
+        is_revision_deleted,
     },
 };
 use hdk_semantic_indexes_client_lib::*;
@@ -35,6 +38,12 @@ fn read_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
 pub fn handle_create_satisfaction<S>(entry_def_id: S, satisfaction: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if let Some((meta, satisfaction_address, entry)) = read_existing_record::<EntryData, EntryStorage, _, _, _>(satisfaction.to_owned())? {
+        return with_indexing_report!(construct_response(&satisfaction_address, &meta, &entry));
+    }
+
     let (meta, satisfaction_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, satisfaction.to_owned())?;
 
     // link entries in the local DNA
@@ -43,7 +52,7 @@ pub fn handle_create_satisfaction<S>(entry_def_id: S, satisfaction: CreateReques
 
     // :TODO: figure out if necessary/desirable to do bidirectional bridging between observation and other planning DNAs
 
-    construct_response(&satisfaction_address, &meta, &entry_resp)
+    with_indexing_report!(construct_response(&satisfaction_address, &meta, &entry_resp))
 }
 
 pub fn handle_get_satisfaction(address: SatisfactionAddress) -> RecordAPIResult<ResponseData>
@@ -58,9 +67,15 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry)
 }
 
+pub fn handle_get_satisfaction_revisions(address: SatisfactionAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
-    let (meta, base_address, new_entry, prev_entry): (_, SatisfactionAddress, EntryData, EntryData) = update_record(&satisfaction.get_revision_id(), satisfaction.to_owned())?;
+    clear_indexing_report();
+    let (meta, base_address, new_entry, prev_entry): (_, SatisfactionAddress, EntryData, EntryData) = update_record_once(&satisfaction.get_revision_id(), satisfaction.to_owned())?;
 
     if new_entry.satisfied_by != prev_entry.satisfied_by {
         let e = update_index!(
@@ -72,11 +87,18 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
         hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (destination) {:?}", e);
     }
 
-    construct_response(&base_address, &meta, &new_entry)
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
 }
 
-pub fn handle_delete_satisfaction(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_satisfaction<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if is_revision_deleted(&revision_id)? {
+        return Ok(DeleteResponse { deleted: true, indexing_report: None });
+    }
+
     // read any referencing indexes
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
@@ -84,7 +106,8 @@ pub fn handle_delete_satisfaction(revision_id: ActionHash) -> RecordAPIResult<bo
     let e = update_index!(satisfaction.satisfied_by.not(&vec![entry.satisfied_by]), economic_event.satisfies(&base_address));
     hdk::prelude::debug!("handle_delete_satisfaction::satisfied_by index (destination) {:?}", e);
 
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 /// Properties accessor for zome config.
@@ -19,10 +19,11 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        read_record_revisions,
         update_record,
         delete_record,
     },
-    rpc::call_zome_method,
+    rpc::{call_zome_method_or_queue, call_local_zome_method},
 };
 use hdk_semantic_indexes_client_lib::*;
 
@@ -31,6 +32,7 @@ use hc_zome_rea_satisfaction_storage::*;
 use hc_zome_rea_satisfaction_rpc::*;
 
 use hc_zome_rea_satisfaction_lib::construct_response;
+use hc_zome_rea_intent_rpc::ResponseData as IntentResponse;
 
 /// properties accessor for zome config
 fn read_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
@@ -40,6 +42,7 @@ fn read_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
 pub fn handle_create_satisfaction<S>(entry_def_id: S, satisfaction: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (meta, satisfaction_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, satisfaction.to_owned())?;
 
     // link entries in the local DNA
@@ -55,9 +58,9 @@ pub fn handle_create_satisfaction<S>(entry_def_id: S, satisfaction: CreateReques
     } else {
       // links to remote event, ping associated foreign DNA & fail if there's an error
       // :TODO: consider the implications of this in loosely coordinated multi-network spaces
-      // we assign a type to the response so that call_zome_method can
+      // we assign a type to the response so that call_zome_method_or_queue can
       // effectively deserialize the response without failing
-      let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+      let result: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
         event_or_commitment,
         &REPLICATE_CREATE_API_METHOD,
         CreateParams { satisfaction: CreateRequest {
@@ -68,12 +71,16 @@ pub fn handle_create_satisfaction<S>(entry_def_id: S, satisfaction: CreateReques
             note: entry_resp.note.to_owned().into(),
             nonce: MaybeUndefined::Some(entry_resp._nonce.to_owned()),
         } },
+        Some(satisfaction_address.to_string()),
         LinkTypes::AvailableCapability
       );
-      hdk::prelude::debug!("handle_create_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_CREATE_API_METHOD, result);
+      hdk::prelude::debug!("handle_create_satisfaction::call_zome_method_or_queue::{:?} {:?}", REPLICATE_CREATE_API_METHOD, result);
     }
 
-    construct_response(&satisfaction_address, &meta, &entry_resp)
+    let r = refresh_intent_finished(&entry_resp.satisfies);
+    hdk::prelude::debug!("handle_create_satisfaction::refresh_intent_finished {:?}", r);
+
+    with_indexing_report!(construct_response(&satisfaction_address, &meta, &entry_resp))
 }
 
 pub fn handle_get_satisfaction(address: SatisfactionAddress) -> RecordAPIResult<ResponseData>
@@ -88,8 +95,14 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry)
 }
 
+pub fn handle_get_satisfaction_revisions(address: SatisfactionAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
 pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let (meta, base_address, new_entry, prev_entry): (_, SatisfactionAddress, EntryData, EntryData) = update_record(&satisfaction.get_revision_id(), satisfaction.to_owned())?;
 
     // update intent indexes in local DNA
@@ -97,7 +110,7 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
         let e = update_index!(
             satisfaction
                 .satisfies(&vec![new_entry.satisfies.to_owned()])
-                .not(&vec![prev_entry.satisfies]),
+                .not(&vec![prev_entry.satisfies.to_owned()]),
             intent.satisfied_by(&base_address)
         );
         hdk::prelude::debug!("handle_update_satisfaction::satisfies index (origin) {:?}", e);
@@ -121,13 +134,14 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
                 hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (origin) {:?}", e);
             } else {
                 // both values were remote and in the same DNA, forward the update
-                let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+                let result: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
                     &prev_entry.satisfied_by,
                     &REPLICATE_UPDATE_API_METHOD,
                     UpdateParams { satisfaction: satisfaction.to_owned() },
+                    Some(base_address.to_string()),
                     LinkTypes::AvailableCapability
                 );
-                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, result);
+                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method_or_queue::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, result);
             }
         } else {
             if is_satisfiedby_local_commitment(&prev_entry.satisfied_by)? {
@@ -136,13 +150,14 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
                 hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (origin) {:?}", e);
             } else {
                 // previous value was remote, handle the remote update as a deletion
-                let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+                let result: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
                     &prev_entry.satisfied_by,
                     &REPLICATE_DELETE_API_METHOD,
                     ByAction { address: satisfaction.get_revision_id().to_owned() },
+                    Some(base_address.to_string()),
                     LinkTypes::AvailableCapability
                 );
-                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_DELETE_API_METHOD, result);
+                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method_or_queue::{:?} {:?}", REPLICATE_DELETE_API_METHOD, result);
             }
 
             if is_satisfiedby_local_commitment(&new_entry.satisfied_by)? {
@@ -151,7 +166,7 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
                 hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (origin) {:?}", e);
             } else {
                 // new value was remote, handle the remote update as a creation
-                let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+                let result: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
                     &new_entry.satisfied_by,
                     &REPLICATE_CREATE_API_METHOD,
                     CreateParams { satisfaction: CreateRequest {
@@ -162,24 +177,35 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
                         note: new_entry.note.to_owned().into(),
                         nonce: MaybeUndefined::Some(new_entry._nonce.to_owned()),
                     } },
+                    Some(base_address.to_string()),
                     LinkTypes::AvailableCapability
                 );
-                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_CREATE_API_METHOD, result);
+                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method_or_queue::{:?} {:?}", REPLICATE_CREATE_API_METHOD, result);
             }
         }
 
         // :TODO: ensure correct number of operations succeeded
     }
 
-    construct_response(&base_address, &meta, &new_entry)
+    // satisfied quantities may have changed on either the old or new intent
+    let r = refresh_intent_finished(&new_entry.satisfies);
+    hdk::prelude::debug!("handle_update_satisfaction::refresh_intent_finished {:?}", r);
+    if new_entry.satisfies != prev_entry.satisfies {
+        let r = refresh_intent_finished(&prev_entry.satisfies);
+        hdk::prelude::debug!("handle_update_satisfaction::refresh_intent_finished (previous) {:?}", r);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
 }
 
-pub fn handle_delete_satisfaction(revision_id: ActionHash) -> RecordAPIResult<bool>
+pub fn handle_delete_satisfaction<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
     // update intent indexes in local DNA
-    let e = update_index!(satisfaction.satisfies.not(&vec![entry.satisfies]), intent.satisfied_by(&base_address));
+    let e = update_index!(satisfaction.satisfies.not(&vec![entry.satisfies.to_owned()]), intent.satisfied_by(&base_address));
     hdk::prelude::debug!("handle_delete_satisfaction::satisfies index (origin) {:?}", e);
 
     // update commitment & event indexes in local or remote DNAs
@@ -190,16 +216,32 @@ pub fn handle_delete_satisfaction(revision_id: ActionHash) -> RecordAPIResult<bo
     } else {
         // links to remote event, ping associated foreign DNA & fail if there's an error
         // :TODO: consider the implications of this in loosely coordinated multi-network spaces
-        let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        let result: OtherCellResult<DeleteResponse> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
             &event_or_commitment,
             &REPLICATE_DELETE_API_METHOD,
             ByAction { address: revision_id.to_owned() },
+            Some(base_address.to_string()),
             LinkTypes::AvailableCapability
         );
-        hdk::prelude::debug!("handle_delete_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_DELETE_API_METHOD, result);
+        hdk::prelude::debug!("handle_delete_satisfaction::call_zome_method_or_queue::{:?} {:?}", REPLICATE_DELETE_API_METHOD, result);
     }
 
-    delete_record::<EntryStorage>(&revision_id)
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+
+    let r = refresh_intent_finished(&entry.satisfies);
+    hdk::prelude::debug!("handle_delete_satisfaction::refresh_intent_finished {:?}", r);
+
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Notify the `Intent` zome that satisfied quantities have changed, so that it
+/// may mark the intent as finished if `auto_finish` is enabled.
+fn refresh_intent_finished(intent: &IntentAddress) -> OtherCellResult<IntentResponse> {
+    call_local_zome_method(
+        read_intent_zome,
+        "_internal_refresh_finished",
+        ByAddress { address: intent.to_owned() },
+    )
 }
 
 fn is_satisfiedby_local_commitment(event_or_commitment: &EventOrCommitmentAddress) -> RecordAPIResult<bool> {
@@ -214,6 +256,11 @@ fn read_satisfaction_index_zome(conf: DnaConfigSlicePlanning) -> Option<String>
     Some(conf.satisfaction.index_zome)
 }
 
+/// Properties accessor for zome config.
+fn read_intent_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    conf.satisfaction.intent_zome
+}
+
 /// Properties accessor for zome config.
 fn read_intent_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
     Some(conf.satisfaction.intent_index_zome)
@@ -9,6 +9,7 @@
 use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     MaybeUndefined,
@@ -51,6 +52,8 @@ pub struct SatisfactionZomeConfigPlanning {
     pub commitment_zome: Option<String>, // :TODO: deprecate this, now we have DnaHash-capable IDs we don't need to query related zome to check relevance
     pub commitment_index_zome: String,
     pub intent_index_zome: String,
+    #[serde(default)]
+    pub intent_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -74,7 +77,9 @@ generate_record_entry!(EntryData, SatisfactionAddress, EntryStorage);
 pub enum EntryTypes {
     Satisfaction(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -92,6 +97,14 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
@@ -8,10 +8,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_satisfaction_lib_origin::*;
 use hc_zome_rea_satisfaction_rpc::*;
 use hc_zome_rea_satisfaction_storage_consts::*;
+use hc_zome_rea_satisfaction_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[hdk_extern]
 fn create_satisfaction(CreateParams { satisfaction }: CreateParams) -> ExternResult<ResponseData> {
@@ -28,17 +31,30 @@ fn get_satisfaction(
     Ok(handle_get_satisfaction(address)?)
 }
 
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_satisfaction_summary(params: ByAddress<SatisfactionAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_satisfaction(params))
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_satisfaction_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<SatisfactionAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_satisfaction_revisions(address, first, after)?)
+}
+
 #[hdk_extern]
 fn update_satisfaction(UpdateParams { satisfaction }: UpdateParams) -> ExternResult<ResponseData> {
     Ok(handle_update_satisfaction(satisfaction)?)
 }
 
 #[hdk_extern]
-fn delete_satisfaction(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_satisfaction(revision_id)?)
+fn delete_satisfaction(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_satisfaction(SATISFACTION_ENTRY_TYPE, revision_id)?)
 }
+
+declare_pending_sync_zome_api!();
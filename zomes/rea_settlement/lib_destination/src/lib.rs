@@ -0,0 +1,121 @@
+/**
+ * hREA settlement zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Settlement` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Contains functionality for the "destination" side of an "indirect remote index" pair
+ * (@see `hdk_records` README).
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult,
+    records::{
+        create_record,
+        read_existing_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record_once,
+        delete_record,
+        is_revision_deleted,
+    },
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_settlement_storage::*;
+use hc_zome_rea_settlement_rpc::*;
+
+use hc_zome_rea_settlement_lib::construct_response;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
+    Some(conf.settlement.index_zome)
+}
+
+pub fn handle_create_settlement<S>(entry_def_id: S, settlement: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if let Some((meta, settlement_address, entry)) = read_existing_record::<EntryData, EntryStorage, _, _, _>(settlement.to_owned())? {
+        return with_indexing_report!(construct_response(&settlement_address, &meta, &entry));
+    }
+
+    let (meta, settlement_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, settlement.to_owned())?;
+
+    // link entries in the local DNA
+    let e = create_index!(settlement.settled_by(settlement.get_settled_by()), economic_event.settles(&settlement_address));
+    hdk::prelude::debug!("handle_create_settlement::settled_by index (destination) {:?}", e);
+
+    // :TODO: figure out if necessary/desirable to do bidirectional bridging between observation and other planning DNAs
+
+    with_indexing_report!(construct_response(&settlement_address, &meta, &entry_resp))
+}
+
+pub fn handle_get_settlement(address: SettlementAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_settlement_revisions(address: SettlementAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_settlement(settlement: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let (meta, base_address, new_entry, prev_entry): (_, SettlementAddress, EntryData, EntryData) = update_record_once(&settlement.get_revision_id(), settlement.to_owned())?;
+
+    if new_entry.settled_by != prev_entry.settled_by {
+        let e = update_index!(
+            settlement
+                .settled_by(&vec![new_entry.settled_by.clone()])
+                .not(&vec![prev_entry.settled_by]),
+            economic_event.settles(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_settlement::settled_by index (destination) {:?}", e);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
+}
+
+pub fn handle_delete_settlement<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    // replication requests may be delivered more than once when replayed from the origin cell's outbox
+    if is_revision_deleted(&revision_id)? {
+        return Ok(DeleteResponse { deleted: true, indexing_report: None });
+    }
+
+    // read any referencing indexes
+    let (_meta, base_address, settlement) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    let e = update_index!(settlement.settled_by.not(&vec![settlement.settled_by]), economic_event.settles(&base_address));
+    hdk::prelude::debug!("handle_delete_settlement::settled_by index (destination) {:?}", e);
+
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Properties accessor for zome config.
+fn read_economic_event_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
+    Some(conf.settlement.economic_event_index_zome)
+}
+
+/// Properties accessor for zome config.
+fn read_settlement_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
+    Some(conf.settlement.index_zome)
+}
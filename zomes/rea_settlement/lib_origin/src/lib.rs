@@ -0,0 +1,155 @@
+/**
+ * hREA settlement zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Settlement` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Contains functionality for the "origin" side of an "indirect remote index" pair
+ * (@see `hdk_records` README).
+
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, OtherCellResult,
+    MaybeUndefined,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        read_record_revisions,
+        update_record,
+        delete_record,
+    },
+    rpc::call_zome_method_or_queue,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_settlement_storage_consts::*;
+use hc_zome_rea_settlement_storage::*;
+use hc_zome_rea_settlement_rpc::*;
+
+use hc_zome_rea_settlement_lib::construct_response;
+
+
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    Some(conf.settlement.index_zome)
+}
+
+pub fn handle_create_settlement<S>(entry_def_id: S, settlement: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let (meta, settlement_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, settlement.to_owned())?;
+
+    // link entries in the local DNA
+    let e = create_index!(settlement.settles(settlement.get_settles()), claim.settled_by(&settlement_address));
+    hdk::prelude::debug!("handle_create_settlement::settles index (origin) {:?}", e);
+
+    // :TODO: report any error
+    // update in the associated foreign DNA as well
+    let pingback: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
+        settlement.get_settled_by(),
+        &REPLICATE_CREATE_API_METHOD,
+        CreateParams { settlement: CreateRequest {
+            settled_by: entry_resp.settled_by.to_owned(),
+            settles: entry_resp.settles.to_owned(),
+            resource_quantity: entry_resp.resource_quantity.to_owned().into(),
+            effort_quantity: entry_resp.effort_quantity.to_owned().into(),
+            note: entry_resp.note.to_owned().into(),
+            nonce: MaybeUndefined::Some(entry_resp._nonce.to_owned()),
+        } },
+        Some(settlement_address.to_string()),
+        LinkTypes::AvailableCapability
+    );
+    hdk::prelude::debug!("handle_create_settlement::call_zome_method_or_queue::{:?} {:?}", REPLICATE_CREATE_API_METHOD, pingback);
+
+    with_indexing_report!(construct_response(&settlement_address, &meta, &entry_resp))
+}
+
+pub fn handle_get_settlement(address: SettlementAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_settlement_revisions(address: SettlementAddress, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    read_record_revisions(&address, after, first)
+}
+
+pub fn handle_update_settlement(settlement: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    clear_indexing_report();
+    let (meta, base_address, new_entry, prev_entry): (_, SettlementAddress, EntryData, EntryData) = update_record(&settlement.get_revision_id(), settlement.to_owned())?;
+
+    // update claim indexes in local DNA
+    if new_entry.settles != prev_entry.settles {
+        let e = update_index!(
+            settlement
+                .settles(&vec![new_entry.settles.clone()])
+                .not(&vec![prev_entry.settles]),
+            claim.settled_by(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_settlement::settles index (origin) {:?}", e);
+    }
+
+    // update settlement records in remote DNA (and by proxy, event indexes in remote DNA)
+    if new_entry.settled_by != prev_entry.settled_by {
+        let pingback: OtherCellResult<ResponseData> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
+            // :TODO: update to intelligently call remote DNAs if new & old target record are not in same network
+            &prev_entry.settled_by,
+            &REPLICATE_UPDATE_API_METHOD,
+            UpdateParams { settlement: settlement.to_owned() },
+            Some(base_address.to_string()),
+            LinkTypes::AvailableCapability
+        );
+        // :TODO: report any error
+        hdk::prelude::debug!("handle_update_settlement::call_zome_method_or_queue::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, pingback);
+    }
+
+    with_indexing_report!(construct_response(&base_address, &meta, &new_entry))
+}
+
+pub fn handle_delete_settlement<S>(entry_def_id: S, revision_id: ActionHash) -> RecordAPIResult<DeleteResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    clear_indexing_report();
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // update claim indexes in local DNA
+    let e = update_index!(settlement.settles.not(&vec![entry.settles]), claim.settled_by(&base_address));
+    hdk::prelude::debug!("handle_delete_settlement::settles index (origin) {:?}", e);
+
+    // update settlement records in remote DNA (and by proxy, event indexes in remote DNA)
+    let pingback: OtherCellResult<DeleteResponse> = call_zome_method_or_queue::<EntryTypes, _, _, _, _, _, _, _>(
+        &entry.settled_by,
+        &REPLICATE_DELETE_API_METHOD,
+        ByAction { address: revision_id.to_owned() },
+        Some(base_address.to_string()),
+        LinkTypes::AvailableCapability
+    );
+    // :TODO: report any error
+    hdk::prelude::debug!("handle_delete_settlement::call_zome_method_or_queue::{:?} {:?}", REPLICATE_DELETE_API_METHOD, pingback);
+
+    let deleted = delete_record::<EntryStorage,_,_,_,_,_>(read_index_zome, &entry_def_id, &revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
+}
+
+/// Properties accessor for zome config.
+fn read_claim_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    Some(conf.settlement.claim_index_zome)
+}
+
+/// Properties accessor for zome config.
+fn read_settlement_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
+    Some(conf.settlement.index_zome)
+}
@@ -0,0 +1,14 @@
+/**
+ * Settlement query indexes for observation DNA
+ *
+ * @package hREA
+ * @since   2026-10-17
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_settlement_rpc::*;
+use hdk_semantic_indexes_zome_lib::ByAddress; // disambiguate from RPC query struct
+
+#[index_zome]
+struct Settlement {
+    settled_by: Local<economic_event, settles>,
+}
@@ -0,0 +1,55 @@
+/**
+ * hREA settlement zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
+
+use hc_zome_rea_settlement_lib_origin::*;
+use hc_zome_rea_settlement_rpc::*;
+use hc_zome_rea_settlement_storage_consts::*;
+use hc_zome_rea_settlement_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
+
+#[hdk_extern]
+fn create_settlement(CreateParams { settlement }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_settlement(SETTLEMENT_ENTRY_TYPE, settlement)?)
+}
+
+#[hdk_extern]
+fn get_settlement(ByAddress { address }: ByAddress<SettlementAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_get_settlement(address)?)
+}
+
+// read API used by index zomes when listing records, @see hdk_records::metadata::with_summary_metadata
+#[hdk_extern]
+fn __internal_get_settlement_summary(params: ByAddress<SettlementAddress>) -> ExternResult<ResponseData> {
+    with_summary_metadata(|| get_settlement(params))
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[hdk_extern]
+fn get_settlement_revisions(RevisionsByAddress { address, first, after }: RevisionsByAddress<SettlementAddress>) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_settlement_revisions(address, first, after)?)
+}
+
+#[hdk_extern]
+fn update_settlement(UpdateParams { settlement }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_settlement(settlement)?)
+}
+
+#[hdk_extern]
+fn delete_settlement(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
+    Ok(handle_delete_settlement(SETTLEMENT_ENTRY_TYPE, revision_id)?)
+}
+
+declare_pending_sync_zome_api!();
@@ -8,10 +8,12 @@
  */
 use hdk::prelude::*;
 use hdk_records::{
-    RecordAPIResult,
+    RecordAPIResult, DataIntegrityError,
+    record_interface::Updateable,
     records_anchored::{
         create_anchored_record,
         read_anchored_record_entry,
+        read_anchored_record_revisions,
         update_anchored_record,
         delete_anchored_record,
     },
@@ -21,6 +23,7 @@ use hdk_records::{
     },
     metadata::read_revision_metadata_abbreviated,
 };
+use hdk_semantic_indexes_client_lib::{clear_indexing_report, take_indexing_report, with_indexing_report};
 
 pub use vf_attributes_hdk::{
     ByAction, ByAddress,
@@ -30,6 +33,7 @@ pub use vf_attributes_hdk::{
 pub use hc_zome_rea_unit_storage_consts::*;
 use hc_zome_rea_unit_storage::*;
 use hc_zome_rea_unit_rpc::*;
+use vf_measurement::{ QuantityValue, Unit, convert, read_base_unit_chain };
 
 
 /// properties accessor for zome config
@@ -40,6 +44,8 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_unit<S>(entry_def_id: S, unit: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    clear_indexing_report();
+    validate_base_unit(&UnitId::new(dna_info()?.hash, unit.symbol.to_owned()), &unit.base_unit.to_owned().to_option())?;
     let (meta, entry_id, entry_resp): (_,UnitId,_) =
       create_anchored_record::<_, _, _, _, _, _, EntryTypes, _, _, _, _>(
         LinkTypes::UnitIdentifier,
@@ -47,7 +53,7 @@ pub fn handle_create_unit<S>(entry_def_id: S, unit: CreateRequest) -> RecordAPIR
         &entry_def_id,
         unit.to_owned()
       )?;
-    construct_response(&entry_id, &meta, &entry_resp)
+    with_indexing_report!(construct_response(&entry_id, &meta, &entry_resp))
 }
 
 pub fn handle_get_unit(id: UnitId) -> RecordAPIResult<ResponseData>
@@ -80,15 +86,61 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     ), &meta, &entry)
 }
 
+pub fn handle_get_unit_revisions(id: UnitId, first: Option<usize>, after: Option<ActionHash>) -> RecordAPIResult<RevisionHistoryPage>
+{
+    let id_str: &String = id.as_ref();
+    read_anchored_record_revisions::<LinkTypes, UnitInternalAddress, _>(
+        LinkTypes::UnitIdentifier,
+        id_str,
+        after, first,
+    )
+}
+
 pub fn handle_update_unit(unit: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    clear_indexing_report();
     let revision_id = unit.get_revision_id().clone();
+    let (_meta, _base_address, prev_entry) = read_record_entry_by_action::<EntryData, EntryStorage, UnitInternalAddress>(&revision_id)?;
+    let next_entry = prev_entry.update_with(unit.to_owned())?;
+    validate_base_unit(&UnitId::new(dna_info()?.hash, next_entry.symbol.to_owned()), &next_entry.base_unit)?;
     let (meta, new_id, new_entry, _prev_entry): (_,UnitId,_,_) = update_anchored_record::<LinkTypes, EntryData, EntryStorage, UnitInternalAddress, _,_,_>(LinkTypes::UnitIdentifier, &revision_id, unit)?;
-    construct_response(&new_id, &meta, &new_entry)
+    with_indexing_report!(construct_response(&new_id, &meta, &new_entry))
+}
+
+/// Express a quantity in another unit, using the conversion parameters stored against
+/// the unit records in this DNA.
+pub fn handle_convert_quantity(quantity: QuantityValue, to_unit: UnitId) -> RecordAPIResult<ConvertResponse>
+{
+    Ok(ConvertResponse {
+        quantity: convert(quantity, &to_unit, read_unit_conversion)?,
+    })
+}
+
+/// Ensure a unit is not defined in terms of itself, either directly or via the base units
+/// of its base unit. Base units must exist in this DNA.
+fn validate_base_unit(id: &UnitId, base_unit: &Option<UnitId>) -> RecordAPIResult<()>
+{
+    let base_unit = match base_unit {
+        Some(base_unit) => base_unit,
+        None => return Ok(()),
+    };
+    let is_cyclic = base_unit == id || read_base_unit_chain(base_unit, &read_unit_conversion)?
+        .iter().any(|unit| &unit.id == id);
+    if is_cyclic {
+        return Err(DataIntegrityError::InvalidUnitConversion(format!("unit '{}' cannot be defined in terms of itself", id.1)));
+    }
+    Ok(())
+}
+
+fn read_unit_conversion(unit_id: &UnitId) -> RecordAPIResult<Unit>
+{
+    Ok(handle_get_unit(unit_id.to_owned())?.unit.to_unit())
 }
 
-pub fn handle_delete_unit(revision_id: ActionHash) -> RecordAPIResult<bool> {
-    delete_anchored_record::<EntryStorage>(&revision_id)
+pub fn handle_delete_unit(revision_id: ActionHash) -> RecordAPIResult<DeleteResponse> {
+    clear_indexing_report();
+    let deleted = delete_anchored_record::<EntryStorage>(&revision_id)?;
+    Ok(DeleteResponse { deleted, indexing_report: take_indexing_report()? })
 }
 
 fn construct_response<'a>(
@@ -101,6 +153,10 @@ fn construct_response<'a>(
             meta: read_revision_metadata_abbreviated(meta)?,
             label: e.label.to_owned(),
             symbol: e.symbol.to_owned(),
-        }
+            base_unit: e.base_unit.to_owned(),
+            conversion_factor: e.conversion_factor.to_owned(),
+            conversion_offset: e.conversion_offset.to_owned(),
+        },
+        indexing_report: None,
     })
 }
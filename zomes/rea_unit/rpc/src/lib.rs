@@ -12,15 +12,17 @@ use hdk_records::{
     MaybeUndefined, RecordAPIResult,
     record_interface::{ UniquelyIdentifiable, UpdateableIdentifier },
 };
+use vf_measurement::{ Decimal, Unit, UnitConversion };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
 // Export external type interface to allow consuming zomes to easily import & define zome API
 pub use vf_attributes_hdk::{
+    IndexingReport, DeleteResponse,
     ActionHash,
     UnitId,
     UnitInternalAddress as UnitAddress,
-    ByRevision, RecordMeta, RevisionMeta,
+    ByRevision, RecordMeta, RevisionMeta, RevisionHistoryPage, RevisionsByAddress,
 };
 
 /// I/O struct to describe the complete record, including all managed link fields
@@ -33,6 +35,12 @@ pub struct Response {
     pub meta: RecordMeta,
     pub label: String,
     pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_unit: Option<UnitId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversion_factor: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversion_offset: Option<Decimal>,
 }
 
 impl<'a> Response {
@@ -40,6 +48,20 @@ impl<'a> Response {
         let s: &String = self.id.as_ref();
         Ok(s.to_owned())
     }
+
+    /// Conversion information for use with `vf_measurement::convert`
+    pub fn to_unit(&'a self) -> Unit {
+        Unit {
+            id: self.id.to_owned(),
+            name: Some(self.label.to_owned()),
+            symbol: Some(self.symbol.to_owned()),
+            conversion: self.base_unit.to_owned().map(|base_unit| UnitConversion {
+                base_unit,
+                factor: self.conversion_factor.unwrap_or_else(|| Decimal::from(1)),
+                offset: self.conversion_offset.unwrap_or_default(),
+            }),
+        }
+    }
 }
 
 /// I/O struct to describe what is returned outside the gateway.
@@ -50,6 +72,8 @@ impl<'a> Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub unit: Response,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_report: Option<IndexingReport>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -61,6 +85,15 @@ pub struct ResponseData {
 pub struct CreateRequest {
     pub label: String,
     pub symbol: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub base_unit: MaybeUndefined<UnitId>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub conversion_factor: MaybeUndefined<Decimal>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub conversion_offset: MaybeUndefined<Decimal>,
 }
 
 impl<'a> CreateRequest {
@@ -85,6 +118,15 @@ pub struct UpdateRequest {
     pub revision_id: ActionHash,
     pub label: MaybeUndefined<String>,
     pub symbol: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub base_unit: MaybeUndefined<UnitId>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub conversion_factor: MaybeUndefined<Decimal>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub conversion_offset: MaybeUndefined<Decimal>,
 }
 
 impl<'a> UpdateRequest {
@@ -103,6 +145,30 @@ impl UpdateableIdentifier for UpdateRequest {
     }
 }
 
+//---------------- READ REQUEST ----------------
+
+/// Input parameters for loading unit definitions, including from other DNAs
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUnitRequest {
+    pub id: UnitId,
+}
+
+//---------------- CONVERSION REQUEST ----------------
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertRequest {
+    pub quantity: vf_measurement::QuantityValue,
+    pub to_unit: UnitId,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertResponse {
+    pub quantity: vf_measurement::QuantityValue,
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
@@ -9,15 +9,18 @@
 use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::outbox::PendingSync;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
+    MaybeUndefined,
     generate_record_entry,
     record_interface::{ Updateable },
 };
 
 use hc_zome_rea_unit_rpc::{ CreateRequest, UpdateRequest };
 
-pub use vf_attributes_hdk::{ UnitInternalAddress };
+pub use vf_attributes_hdk::{ UnitInternalAddress, UnitId };
+use vf_measurement::Decimal;
 
 // :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
 pub use hdk_records::record_interface::Identified;
@@ -41,12 +44,29 @@ pub struct UnitZomeConfig {
 pub struct EntryData {
     pub label: String,
     pub symbol: String,
+    #[serde(default)]
+    pub base_unit: Option<UnitId>,
+    #[serde(default)]
+    pub conversion_factor: Option<Decimal>,
+    #[serde(default)]
+    pub conversion_offset: Option<Decimal>,
 }
 
 impl<'a> EntryData {
     pub fn get_symbol(&'a self) -> String {
         self.symbol.to_owned()
     }
+
+    /// Ensure conversion parameters are usable. A zero factor would make values
+    /// in the base unit impossible to convert back.
+    ///
+    /// :NOTE: references to the base unit are checked by the zome library, where unit IDs are known.
+    fn validate_conversion(self) -> RecordAPIResult<EntryData> {
+        if self.conversion_factor.map_or(false, |factor| factor.is_zero()) {
+            return Err(DataIntegrityError::ZeroConversionFactor(self.symbol));
+        }
+        Ok(self)
+    }
 }
 
 generate_record_entry!(EntryData, UnitInternalAddress, EntryStorage);
@@ -58,7 +78,9 @@ generate_record_entry!(EntryData, UnitInternalAddress, EntryStorage);
 pub enum EntryTypes {
     UnitEntry(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    PendingSync(PendingSync),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -77,6 +99,14 @@ impl TryFrom<AvailableCapability> for EntryTypes {
         Ok(EntryTypes::AvailableCapability(e))
     }
 }
+impl TryFrom<PendingSync> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: PendingSync) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::PendingSync(e))
+    }
+}
 
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
@@ -93,10 +123,13 @@ impl TryFrom<CreateRequest> for EntryData {
     type Error = DataIntegrityError;
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        EntryData {
             label: e.label.into(),
             symbol: e.symbol.into(),
-        })
+            base_unit: e.base_unit.into(),
+            conversion_factor: e.conversion_factor.into(),
+            conversion_offset: e.conversion_offset.into(),
+        }.validate_conversion()
     }
 }
 
@@ -105,9 +138,12 @@ impl TryFrom<CreateRequest> for EntryData {
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        EntryData {
             label:   if !e.label.is_some()   { self.label.to_owned()   } else { e.label.to_owned().unwrap() },
             symbol: if !e.symbol.is_some() { self.symbol.to_owned() } else { e.symbol.to_owned().unwrap() },
-        })
+            base_unit: if e.base_unit == MaybeUndefined::Undefined { self.base_unit.to_owned() } else { e.base_unit.to_owned().into() },
+            conversion_factor: if e.conversion_factor == MaybeUndefined::Undefined { self.conversion_factor.to_owned() } else { e.conversion_factor.to_owned().into() },
+            conversion_offset: if e.conversion_offset == MaybeUndefined::Undefined { self.conversion_offset.to_owned() } else { e.conversion_offset.to_owned().into() },
+        }.validate_conversion()
     }
 }
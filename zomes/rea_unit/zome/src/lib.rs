@@ -8,10 +8,13 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use hdk_records::metadata::with_summary_metadata;
 
 use hc_zome_rea_unit_rpc::*;
 use hc_zome_rea_unit_lib::*;
 use vf_attributes_hdk::UnitInternalAddress;
+use hc_zome_rea_unit_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::declare_pending_sync_zome_api;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -37,7 +40,7 @@ fn get_unit(ById { id }: ById) -> ExternResult<ResponseData> {
 // used by indexing zomes to retrieve indexed record data
 #[hdk_extern]
 fn __internal_get_unit_by_hash(ByAddress { address }: ByAddress<UnitInternalAddress>) -> ExternResult<ResponseData> {
-    Ok(handle_get_unit_by_address(address)?)
+    Ok(with_summary_metadata(|| handle_get_unit_by_address(address))?)
 }
 
 #[hdk_extern]
@@ -45,6 +48,18 @@ fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<Response
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct RevisionsById {
+    id: UnitId,
+    first: Option<usize>,
+    after: Option<ActionHash>,
+}
+
+#[hdk_extern]
+fn get_unit_revisions(RevisionsById { id, first, after }: RevisionsById) -> ExternResult<RevisionHistoryPage> {
+    Ok(handle_get_unit_revisions(id, first, after)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -57,6 +72,13 @@ fn update_unit(UpdateParams { unit }: UpdateParams) -> ExternResult<ResponseData
 }
 
 #[hdk_extern]
-fn delete_unit(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+fn convert_quantity(ConvertRequest { quantity, to_unit }: ConvertRequest) -> ExternResult<ConvertResponse> {
+    Ok(handle_convert_quantity(quantity, to_unit)?)
+}
+
+#[hdk_extern]
+fn delete_unit(ByRevision { revision_id }: ByRevision) -> ExternResult<DeleteResponse> {
     Ok(handle_delete_unit(revision_id)?)
 }
+
+declare_pending_sync_zome_api!();